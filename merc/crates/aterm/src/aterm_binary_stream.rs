@@ -12,6 +12,7 @@ use merc_io::BitStreamReader;
 use merc_io::BitStreamWrite;
 use merc_io::BitStreamWriter;
 use merc_number::bits_for_value;
+use merc_number::encoding_size;
 use merc_utilities::MercError;
 use merc_utilities::debug_trace;
 
@@ -564,9 +565,318 @@ impl<'a, R: Read> ExactSizeIterator for ATermReadIter<'a, R> {
     }
 }
 
+/// A push based decoder for the binary aterm format, see [`BinaryATermReader`]
+/// for the counterpart that reads the whole stream from a reader.
+///
+/// # Details
+///
+/// The bytes of the stream can be fed in arbitrary chunks, for example as they
+/// arrive over a socket. Packets that are not yet complete are retained
+/// internally, and decoding resumes from the start of such a packet when more
+/// bytes are fed. After an error the decoder should no longer be used.
+pub struct BinaryStreamDecoder {
+    /// The bytes that have been fed but are not yet (fully) decoded.
+    buffer: Vec<u8>,
+
+    /// The number of bits of the first byte of the buffer that have already been decoded.
+    bit_offset: usize,
+
+    /// Indicates whether the header of the stream has been decoded.
+    header_read: bool,
+
+    /// Stores the function symbols read so far, and the width needed to encode their indices.
+    function_symbols: Protected<Vec<SymbolRef<'static>>>,
+    function_symbol_index_width: u8,
+
+    /// Stores the terms read so far, and the width needed to encode their indices.
+    terms: Protected<Vec<ATermRef<'static>>>,
+    term_index_width: u8,
+
+    /// Indicates whether the end of stream marker has already been encountered.
+    ended: bool,
+}
+
+/// A single packet of the binary aterm format, decoded without changing the state of the decoder.
+enum Packet {
+    FunctionSymbol(String, usize),
+    IntOutput(usize),
+    Int(usize),
+    Term {
+        symbol_index: usize,
+        arguments: Vec<usize>,
+        output: bool,
+    },
+    EndOfStream,
+}
+
+impl BinaryStreamDecoder {
+    /// Creates a new decoder that expects the header of the stream as its first input.
+    pub fn new() -> Self {
+        // The term with function symbol index 0 indicates the end of the stream
+        let mut function_symbols = Protected::new(Vec::new());
+        let end_of_stream_symbol = Symbol::new(String::new(), 0);
+        function_symbols.write().push(end_of_stream_symbol.copy());
+
+        Self {
+            buffer: Vec::new(),
+            bit_offset: 0,
+            header_read: false,
+            function_symbols,
+            function_symbol_index_width: 1,
+            terms: Protected::new(Vec::new()),
+            term_index_width: 1,
+            ended: false,
+        }
+    }
+
+    /// Feeds the given bytes to the decoder, and returns the output terms that
+    /// have been completed by them in the order of the stream.
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<Vec<ATerm>, MercError> {
+        if self.ended {
+            if bytes.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            return Err(Error::new(ErrorKind::InvalidData, "Attempted to feed() after end of stream").into());
+        }
+
+        self.buffer.extend_from_slice(bytes);
+
+        let mut result = Vec::new();
+        let mut position = self.bit_offset;
+        while !self.ended {
+            let mut cursor = BitCursor::new(&self.buffer, position);
+            let packet = if self.header_read {
+                self.read_packet(&mut cursor).map(Some)
+            } else {
+                Self::read_header(&mut cursor).map(|_| None)
+            };
+
+            let packet = match packet {
+                Ok(packet) => packet,
+                Err(_) if cursor.exhausted => break, // Wait until the remainder of this packet is fed.
+                Err(err) => return Err(err),
+            };
+            position = cursor.position;
+
+            match packet {
+                Some(packet) => {
+                    if let Some(term) = self.apply_packet(packet) {
+                        result.push(term);
+                    }
+                }
+                None => self.header_read = true,
+            }
+        }
+
+        // Remove the bytes that have been decoded entirely.
+        self.buffer.drain(..position / 8);
+        self.bit_offset = position % 8;
+
+        Ok(result)
+    }
+
+    /// Returns true iff the end of stream marker has been decoded.
+    pub fn is_ended(&self) -> bool {
+        self.ended
+    }
+
+    /// Checks the header of the binary aterm format.
+    fn read_header(cursor: &mut BitCursor) -> Result<(), MercError> {
+        if cursor.read_bits(8)? != 0 || cursor.read_bits(16)? != BAF_MAGIC as u64 {
+            return Err(Error::new(ErrorKind::InvalidData, "Missing BAF_MAGIC control sequence").into());
+        }
+
+        let version = cursor.read_bits(16)?;
+        if version != BAF_VERSION as u64 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("BAF version ({version}) incompatible with expected version ({BAF_VERSION})"),
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Reads the next packet from the cursor, only fails when the cursor is
+    /// exhausted or the packet is malformed.
+    fn read_packet(&self, cursor: &mut BitCursor) -> Result<Packet, MercError> {
+        let header = cursor.read_bits(PACKET_BITS)?;
+        match PacketType::from(header as u8) {
+            PacketType::FunctionSymbol => {
+                let name = cursor.read_string()?;
+                let arity = cursor.read_integer()?.try_into()?;
+                Ok(Packet::FunctionSymbol(name, arity))
+            }
+            PacketType::ATermIntOutput => Ok(Packet::IntOutput(cursor.read_integer()?.try_into()?)),
+            packet @ (PacketType::ATerm | PacketType::ATermOutput) => {
+                let symbol_index = cursor.read_bits(self.function_symbol_index_width)? as usize;
+                if symbol_index == 0 {
+                    return Ok(Packet::EndOfStream);
+                }
+
+                let symbols = self.function_symbols.read();
+                let symbol = symbols.get(symbol_index).ok_or(format!(
+                    "Read invalid function symbol index {symbol_index}, length {}",
+                    symbols.len()
+                ))?;
+
+                if is_int_symbol(symbol) {
+                    return Ok(Packet::Int(cursor.read_integer()?.try_into()?));
+                }
+
+                let num_of_terms = self.terms.read().len();
+                let mut arguments = Vec::with_capacity(symbol.arity());
+                for _ in 0..symbol.arity() {
+                    let arg_index = cursor.read_bits(self.term_index_width)? as usize;
+                    if arg_index >= num_of_terms {
+                        return Err(format!("Read invalid aterm index {arg_index}, length {num_of_terms}").into());
+                    }
+
+                    arguments.push(arg_index);
+                }
+
+                Ok(Packet::Term {
+                    symbol_index,
+                    arguments,
+                    output: packet == PacketType::ATermOutput,
+                })
+            }
+        }
+    }
+
+    /// Updates the state of the decoder for the given packet, and returns the
+    /// term if the packet is an output term.
+    fn apply_packet(&mut self, packet: Packet) -> Option<ATerm> {
+        match packet {
+            Packet::FunctionSymbol(name, arity) => {
+                let symbol = Symbol::new(name, arity);
+                debug_trace!("Read symbol {symbol}");
+
+                let mut write_symbols = self.function_symbols.write();
+                let s = write_symbols.protect_symbol(&symbol);
+                write_symbols.push(s);
+                self.function_symbol_index_width = bits_for_value(write_symbols.len());
+                None
+            }
+            Packet::IntOutput(value) => Some(ATermInt::new(value).into()),
+            Packet::Int(value) => {
+                let term = ATermInt::new(value);
+                debug_trace!("Read int term: {term}");
+
+                let mut write_terms = self.terms.write();
+                let t = write_terms.protect(&term);
+                write_terms.push(t);
+                self.term_index_width = bits_for_value(write_terms.len());
+                None
+            }
+            Packet::Term {
+                symbol_index,
+                arguments,
+                output,
+            } => {
+                let symbols = self.function_symbols.read();
+                let mut write_terms = self.terms.write();
+                let term = ATerm::with_iter(
+                    &symbols[symbol_index],
+                    arguments.iter().map(|arg_index| &write_terms[*arg_index]),
+                );
+
+                if output {
+                    debug_trace!("Output term: {term}");
+                    return Some(term);
+                }
+                debug_trace!("Read term: {term}");
+
+                let t = write_terms.protect(&term);
+                write_terms.push(t);
+                self.term_index_width = bits_for_value(write_terms.len());
+                None
+            }
+            Packet::EndOfStream => {
+                debug_trace!("End of stream marker reached");
+                self.ended = true;
+                None
+            }
+        }
+    }
+}
+
+impl Default for BinaryStreamDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads bits from a byte buffer, and remembers whether it ran out of bits
+/// to distinguish incomplete input from malformed input.
+struct BitCursor<'a> {
+    buffer: &'a [u8],
+    position: usize,
+    exhausted: bool,
+}
+
+impl<'a> BitCursor<'a> {
+    /// Creates a cursor that starts at the given bit position of the buffer.
+    fn new(buffer: &'a [u8], position: usize) -> Self {
+        Self {
+            buffer,
+            position,
+            exhausted: false,
+        }
+    }
+}
+
+impl BitStreamRead for BitCursor<'_> {
+    fn read_bits(&mut self, number_of_bits: u8) -> Result<u64, MercError> {
+        assert!(number_of_bits <= 64);
+        if self.position + number_of_bits as usize > self.buffer.len() * 8 {
+            self.exhausted = true;
+            return Err(Error::new(ErrorKind::UnexpectedEof, "Not enough bits in the buffer").into());
+        }
+
+        // The bits are stored with the most significant bit first, as in the BitStreamWriter.
+        let mut value = 0;
+        for _ in 0..number_of_bits {
+            let bit = (self.buffer[self.position / 8] >> (7 - self.position % 8)) & 1;
+            value = (value << 1) | bit as u64;
+            self.position += 1;
+        }
+
+        Ok(value)
+    }
+
+    fn read_string(&mut self) -> Result<String, MercError> {
+        let length = self.read_integer()?;
+        let mut bytes = Vec::new();
+        for _ in 0..length {
+            bytes.push(self.read_bits(8)? as u8);
+        }
+
+        Ok(String::from_utf8(bytes).map_err(|e| Error::new(ErrorKind::InvalidData, e))?)
+    }
+
+    fn read_integer(&mut self) -> Result<u64, MercError> {
+        // The same encoding as `read_u64_variablelength`.
+        let mut value: u64 = 0;
+        for i in 0..encoding_size::<u64>() {
+            let byte = self.read_bits(8)?;
+            value |= (byte & 0b01111111) << (7 * i);
+
+            if byte & 0b10000000 == 0 {
+                break;
+            }
+        }
+
+        Ok(value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use merc_utilities::random_test;
+    use rand::Rng;
 
     use crate::random_term;
 
@@ -628,4 +938,48 @@ mod tests {
             }
         });
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_random_binary_stream_decoder() {
+        random_test(100, |rng| {
+            let input: Vec<_> = (0..20)
+                .map(|_| random_term(rng, &[("f".into(), 2), ("g".into(), 1)], &["a".into(), "b".into()], 1))
+                .collect();
+
+            let mut stream: Vec<u8> = Vec::new();
+
+            let mut output_stream = BinaryATermWriter::new(&mut stream).unwrap();
+            output_stream.write_aterm_iter(input.iter().cloned()).unwrap();
+            ATermWrite::flush(&mut output_stream).expect("Flushing the output to the stream");
+            drop(output_stream); // Explicitly drop to release the mutable borrow
+
+            // Feed the stream in chunks of random sizes, which split the packets at arbitrary positions.
+            let mut decoder = BinaryStreamDecoder::new();
+            let mut result = Vec::new();
+            let mut remaining = &stream[..];
+            while !remaining.is_empty() {
+                let (chunk, rest) = remaining.split_at(rng.random_range(1..=remaining.len().min(7)));
+                result.extend(decoder.feed(chunk).expect("Decoding the chunk must succeed"));
+                remaining = rest;
+            }
+
+            assert!(decoder.is_ended());
+            assert_eq!(result.len(), input.len() + 1);
+            assert_eq!(ATermInt::from(result[0].clone()).value(), input.len());
+            for (term_written, term_read) in input.iter().zip(&result[1..]) {
+                assert_eq!(
+                    term_written, term_read,
+                    "The decoded term must match the term that we have written"
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn test_binary_stream_decoder_malformed() {
+        let mut decoder = BinaryStreamDecoder::new();
+        assert!(decoder.feed(&[0, 0x8b]).unwrap().is_empty());
+        assert!(decoder.feed(&[0xaa, 0x83, 0x08]).is_err());
+    }
 }
@@ -4,8 +4,10 @@ use std::fmt;
 
 use merc_utilities::MercError;
 use merc_utilities::debug_trace;
+use rustc_hash::FxHashMap;
 
 use crate::ATerm;
+use crate::ATermString;
 use crate::Symbol;
 use crate::Term;
 use crate::storage::ThreadTermPool;
@@ -18,6 +20,7 @@ pub struct TermBuilder<I, C> {
     // The stack of terms
     terms: Vec<Option<ATerm>>,
     configs: Vec<Config<I, C>>,
+    strings: StringCache,
 }
 
 /// Applies the given function to every subterm of the given term using the [TermBuilder].
@@ -54,6 +57,21 @@ impl<I: fmt::Debug, C: fmt::Debug> TermBuilder<I, C> {
         TermBuilder {
             terms: vec![],
             configs: vec![],
+            strings: StringCache::default(),
+        }
+    }
+
+    /// Creates a builder that caches up to `capacity` of the [ATermString]s
+    /// created by [ArgStack::string], such that creating the same string again
+    /// does not look it up in the symbol pool.
+    pub fn with_string_cache(capacity: usize) -> TermBuilder<I, C> {
+        TermBuilder {
+            terms: vec![],
+            configs: vec![],
+            strings: StringCache {
+                strings: Some(FxHashMap::default()),
+                capacity,
+            },
         }
     }
 
@@ -103,7 +121,7 @@ impl<I: fmt::Debug, C: fmt::Debug> TermBuilder<I, C> {
                 Config::Apply(input, result) => {
                     // Applies the given function to this input, and obtain a number of symbol and arguments.
                     let top_of_stack = self.configs.len();
-                    let mut args = ArgStack::new(&mut self.terms, &mut self.configs);
+                    let mut args = ArgStack::new(&mut self.terms, &mut self.configs, &mut self.strings);
 
                     match transformer(tp, &mut args, input)? {
                         Yield::Construct(input) => {
@@ -155,15 +173,21 @@ pub enum Yield<C> {
 pub struct ArgStack<'a, I, C> {
     terms: &'a mut Vec<Option<ATerm>>,
     configs: &'a mut Vec<Config<I, C>>,
+    strings: &'a mut StringCache,
     top_of_stack: usize,
 }
 
 impl<'a, I, C> ArgStack<'a, I, C> {
-    fn new(terms: &'a mut Vec<Option<ATerm>>, configs: &'a mut Vec<Config<I, C>>) -> ArgStack<'a, I, C> {
+    fn new(
+        terms: &'a mut Vec<Option<ATerm>>,
+        configs: &'a mut Vec<Config<I, C>>,
+        strings: &'a mut StringCache,
+    ) -> ArgStack<'a, I, C> {
         let top_of_stack = terms.len();
         ArgStack {
             terms,
             configs,
+            strings,
             top_of_stack,
         }
     }
@@ -178,6 +202,44 @@ impl<'a, I, C> ArgStack<'a, I, C> {
         self.configs.push(Config::Apply(input, self.terms.len()));
         self.terms.push(None);
     }
+
+    /// Returns the [ATermString] for the given value, which is taken from the
+    /// string cache of the builder when it is enabled, see [TermBuilder::with_string_cache].
+    pub fn string(&mut self, value: &str) -> ATermString {
+        self.strings.get(value)
+    }
+}
+
+/// A cache of recently created strings of a [TermBuilder].
+#[derive(Default)]
+struct StringCache {
+    /// The cached strings, or None when caching is disabled.
+    strings: Option<FxHashMap<String, ATermString>>,
+
+    /// The maximum number of cached strings, after which the cache is cleared.
+    capacity: usize,
+}
+
+impl StringCache {
+    /// Returns the string for the given value, and caches it when caching is enabled.
+    fn get(&mut self, value: &str) -> ATermString {
+        if let Some(string) = self.strings.as_ref().and_then(|strings| strings.get(value)) {
+            return string.clone();
+        }
+
+        let string = ATermString::new(value);
+
+        if let Some(strings) = &mut self.strings {
+            if strings.len() >= self.capacity {
+                // Evicting all strings is cheap, and the strings that are still used are cached again.
+                strings.clear();
+            }
+
+            strings.insert(value.to_string(), string.clone());
+        }
+
+        string
+    }
 }
 
 impl<I: fmt::Debug, C: fmt::Debug> fmt::Debug for TermBuilder<I, C> {
@@ -206,3 +268,69 @@ impl<I: fmt::Debug, C: fmt::Debug> fmt::Debug for Config<I, C> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::Symb;
+    use crate::storage::THREAD_TERM_POOL;
+
+    /// Rebuilds the given term, where the constants are created by [ArgStack::string].
+    fn rebuild(builder: &mut TermBuilder<ATerm, Symbol>, term: &ATerm) -> ATerm {
+        THREAD_TERM_POOL.with_borrow(|tp| {
+            builder
+                .evaluate(
+                    tp,
+                    term.clone(),
+                    |_tp, args, t| {
+                        if t.get_head_symbol().arity() == 0 {
+                            return Ok(Yield::Term(args.string(t.get_head_symbol().name()).into()));
+                        }
+
+                        for arg in t.arguments() {
+                            args.push(arg.protect());
+                        }
+
+                        Ok(Yield::Construct(t.get_head_symbol().protect()))
+                    },
+                    |tp, symbol, args| Ok(tp.create_term_iter(&symbol, args)),
+                )
+                .unwrap()
+        })
+    }
+
+    /// Returns the number of function symbols that the current thread looked up in the symbol pool.
+    fn symbol_lookups() -> usize {
+        THREAD_TERM_POOL.with_borrow(|tp| tp.symbol_lookups())
+    }
+
+    /// Rebuilds the given term and returns the number of symbol pool lookups that it required.
+    fn rebuild_lookups(builder: &mut TermBuilder<ATerm, Symbol>, term: &ATerm) -> usize {
+        let before = symbol_lookups();
+        assert_eq!(rebuild(builder, term), *term);
+        symbol_lookups() - before
+    }
+
+    #[test]
+    fn test_string_cache() {
+        let term = ATerm::from_string("f(a, g(b, f(a, g(a, b))), f(b, g(a, a)))").unwrap();
+
+        // Every constant is looked up in the symbol pool.
+        let mut uncached = TermBuilder::new();
+        let uncached_lookups = rebuild_lookups(&mut uncached, &term);
+        assert_eq!(uncached_lookups, 8);
+
+        // Only the first occurrence of every constant is looked up.
+        let mut cached = TermBuilder::with_string_cache(16);
+        assert_eq!(rebuild_lookups(&mut cached, &term), 2);
+
+        // The cache is kept between evaluations.
+        assert_eq!(rebuild_lookups(&mut cached, &term), 0);
+
+        // A full cache is cleared, after which the strings are cached again.
+        let mut small = TermBuilder::with_string_cache(1);
+        let small_lookups = rebuild_lookups(&mut small, &term);
+        assert!(small_lookups > 2 && small_lookups < uncached_lookups);
+    }
+}
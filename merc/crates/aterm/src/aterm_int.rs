@@ -11,6 +11,7 @@ use crate::ATerm;
 use crate::ATermArgs;
 use crate::ATermIndex;
 use crate::ATermRef;
+use crate::Castable;
 use crate::Markable;
 use crate::Symb;
 use crate::SymbolRef;
@@ -75,6 +76,8 @@ pub use inner::*;
 mod tests {
     use merc_utilities::test_logger;
 
+    use crate::Symbol;
+
     use super::*;
 
     #[test]
@@ -85,4 +88,43 @@ mod tests {
         assert_eq!(int_term.value(), 42);
         assert!(is_int_term(&int_term));
     }
+
+    #[merc_derive_terms]
+    mod annotated {
+        use super::*;
+
+        /// A term for which the structural equality ignores its second argument.
+        #[merc_term]
+        pub struct AnnotatedTerm {
+            #[merc_ignore_annotation(1)]
+            term: ATerm,
+        }
+    }
+
+    use annotated::AnnotatedTerm;
+
+    #[test]
+    fn test_structural_eq_ignores_annotations() {
+        let _ = test_logger();
+
+        let f = Symbol::new("f", 2);
+        let g = Symbol::new("g", 2);
+        let a = ATerm::constant(&Symbol::new("a", 0));
+        let b = ATerm::constant(&Symbol::new("b", 0));
+
+        let term = |symbol: &Symbol, value: usize, annotation: &ATerm| {
+            AnnotatedTerm::from(ATerm::with_args(symbol, &[ATermInt::new(value).into(), annotation.clone()]).protect())
+        };
+
+        // Terms that only differ in their annotation are structurally equal.
+        let left = term(&f, 1, &a);
+        let right = term(&f, 1, &b);
+        assert_ne!(left, right);
+        assert!(left.structural_eq(&right));
+        assert!(left.copy().structural_eq(&right.copy()));
+
+        // Terms with a different payload or head symbol are not.
+        assert!(!left.structural_eq(&term(&f, 2, &a)));
+        assert!(!left.structural_eq(&term(&g, 1, &a)));
+    }
 }
@@ -85,12 +85,7 @@ impl<T> ATermList<T> {
     where
         T: Into<ATerm>,
     {
-        ATermList {
-            term: THREAD_TERM_POOL.with_borrow(|tp| {
-                ATerm::with_args(tp.list_symbol(), &[item.into().copy(), self.term.copy()]).protect()
-            }),
-            _marker: PhantomData,
-        }
+        self.cons_term(&item.into())
     }
 
     /// Constructs the empty list.
@@ -115,6 +110,61 @@ impl<T> ATermList<T> {
     pub fn iter(&self) -> ATermListIter<T> {
         ATermListIter { current: self.clone() }
     }
+
+    /// Returns the list with its elements in reverse order.
+    pub fn reverse(&self) -> ATermList<T> {
+        let mut result = Self::empty();
+        let mut current = self.clone();
+        while !current.is_empty() {
+            result = result.cons_term(&current.term.arg(0).protect());
+            current = current.tail();
+        }
+        result
+    }
+
+    /// Returns the list without its first `n` elements, which is a sublist
+    /// of this list. Returns the empty list when it has at most `n` elements.
+    pub fn skip(&self, n: usize) -> ATermList<T> {
+        let mut current = self.clone();
+        for _ in 0..n {
+            if current.is_empty() {
+                break;
+            }
+            current = current.tail();
+        }
+        current
+    }
+
+    /// Returns the list of the first `n` elements. The list itself is returned
+    /// when it has at most `n` elements, otherwise the prefix is rebuilt.
+    pub fn take(&self, n: usize) -> ATermList<T> {
+        let mut heads = Vec::new();
+        let mut current = self.clone();
+        while !current.is_empty() {
+            if heads.len() == n {
+                // Only now the list is known to be longer than n.
+                let mut result = Self::empty();
+                for head in heads.iter().rev() {
+                    result = result.cons_term(head);
+                }
+                return result;
+            }
+
+            heads.push(current.term.arg(0).protect());
+            current = current.tail();
+        }
+
+        self.clone()
+    }
+
+    /// Constructs a new list with the given term as the head and the current list as the tail.
+    fn cons_term(&self, head: &ATerm) -> Self {
+        ATermList {
+            term: THREAD_TERM_POOL
+                .with_borrow(|tp| ATerm::with_args(tp.list_symbol(), &[head.copy(), self.term.copy()]).protect()),
+            _marker: PhantomData,
+        }
+    }
 }
 
 impl<'a, 'b, T> Term<'a, 'b> for ATermList<T>
@@ -234,4 +284,25 @@ mod tests {
         assert_eq!(list.tail().tail().head().value(), 3);
         assert!(list.tail().tail().tail().is_empty());
     }
+
+    #[test]
+    fn test_list_reverse_skip_take() {
+        use super::*;
+        use crate::ATermInt;
+
+        let list = ATermList::from_double_iter((1..=4).map(ATermInt::new).collect::<Vec<_>>().into_iter());
+        let values = |list: &ATermList<ATermInt>| list.iter().map(|x| x.value()).collect::<Vec<_>>();
+
+        assert_eq!(values(&list.reverse()), vec![4, 3, 2, 1]);
+        assert_eq!(list.reverse().reverse().copy(), list.copy());
+
+        // Skipping returns the existing sublist.
+        assert_eq!(list.skip(2).copy(), list.tail().tail().copy());
+        assert_eq!(list.skip(0).copy(), list.copy());
+        assert!(list.skip(10).is_empty());
+
+        assert_eq!(values(&list.take(2)), vec![1, 2]);
+        assert_eq!(list.take(4).copy(), list.copy());
+        assert!(list.take(0).is_empty());
+    }
 }
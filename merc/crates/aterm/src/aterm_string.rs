@@ -17,6 +17,7 @@ use crate::ATermRead;
 use crate::ATermRef;
 use crate::ATermStreamable;
 use crate::ATermWrite;
+use crate::Castable;
 use crate::Markable;
 use crate::Symb;
 use crate::Symbol;
@@ -9,6 +9,9 @@ use crate::aterm::ATermRef;
 use crate::storage::GcMutex;
 use crate::storage::Marker;
 
+/// Derives [Markable] for a struct by marking each of its fields.
+pub use merc_macros::Markable;
+
 /// This trait should be used on all objects and containers related to storing unprotected terms, or unprotected symmbols.
 ///
 /// The implementation should mark all contained aterms and symbols that must be kept alive using the provided `Marker`.
@@ -157,6 +160,53 @@ impl<T1: Markable, T2: Markable> Markable for (T1, T2) {
     }
 }
 
+impl<T1: Markable, T2: Markable, T3: Markable> Markable for (T1, T2, T3) {
+    fn mark(&self, marker: &mut Marker) {
+        self.0.mark(marker);
+        self.1.mark(marker);
+        self.2.mark(marker);
+    }
+
+    fn contains_term(&self, term: &ATermRef<'_>) -> bool {
+        self.0.contains_term(term) || self.1.contains_term(term) || self.2.contains_term(term)
+    }
+
+    fn contains_symbol(&self, symbol: &SymbolRef<'_>) -> bool {
+        self.0.contains_symbol(symbol) || self.1.contains_symbol(symbol) || self.2.contains_symbol(symbol)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len() + self.1.len() + self.2.len()
+    }
+}
+
+impl<T1: Markable, T2: Markable, T3: Markable, T4: Markable> Markable for (T1, T2, T3, T4) {
+    fn mark(&self, marker: &mut Marker) {
+        self.0.mark(marker);
+        self.1.mark(marker);
+        self.2.mark(marker);
+        self.3.mark(marker);
+    }
+
+    fn contains_term(&self, term: &ATermRef<'_>) -> bool {
+        self.0.contains_term(term)
+            || self.1.contains_term(term)
+            || self.2.contains_term(term)
+            || self.3.contains_term(term)
+    }
+
+    fn contains_symbol(&self, symbol: &SymbolRef<'_>) -> bool {
+        self.0.contains_symbol(symbol)
+            || self.1.contains_symbol(symbol)
+            || self.2.contains_symbol(symbol)
+            || self.3.contains_symbol(symbol)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len() + self.1.len() + self.2.len() + self.3.len()
+    }
+}
+
 impl Markable for bool {
     fn mark(&self, _marker: &mut Marker) {
         // Nothing to mark
@@ -1,10 +1,14 @@
 #![forbid(unsafe_code)]
 #![allow(clippy::result_large_err)]
 
+use std::io::BufRead;
+
+use pest::error::InputLocation;
 use pest_derive::Parser;
 
 use merc_pest_consume::Error;
 use merc_pest_consume::match_nodes;
+use merc_utilities::MercError;
 
 use crate::ATerm;
 use crate::Symbol;
@@ -55,6 +59,145 @@ impl TermParser {
     }
 }
 
+/// Parses one term per logical unit from the given reader, without reading the
+/// whole input into memory.
+///
+/// # Details
+///
+/// A logical unit consists of consecutive lines, and ends at the first line
+/// on which all opened brackets are closed again. Brackets and `%` inside
+/// quoted strings are ignored. Empty lines and comments starting with `%` are
+/// skipped. Parse errors contain the byte offset in the input at which parsing
+/// failed.
+pub fn parse_terms_from_reader(reader: impl BufRead) -> impl Iterator<Item = Result<ATerm, MercError>> {
+    TermReaderIter {
+        reader,
+        line: String::new(),
+        unit: String::new(),
+        unit_start: 0,
+        offset: 0,
+        depth: 0,
+        in_string: false,
+        finished: false,
+    }
+}
+
+/// The iterator returned by [parse_terms_from_reader].
+struct TermReaderIter<R: BufRead> {
+    reader: R,
+
+    /// The current line and the text of the logical unit that is being read.
+    line: String,
+    unit: String,
+
+    /// The byte offset at which the current unit starts, and the number of bytes read so far.
+    unit_start: usize,
+    offset: usize,
+
+    /// The number of brackets opened in the current unit that have not been closed yet.
+    depth: isize,
+
+    /// Set when the current unit ends inside a quoted string.
+    in_string: bool,
+    finished: bool,
+}
+
+impl<R: BufRead> TermReaderIter<R> {
+    /// Parses the current unit and clears it afterwards.
+    fn parse_unit(&mut self) -> Result<ATerm, MercError> {
+        let result: Result<ATerm, MercError> =
+            <TermParser as merc_pest_consume::Parser>::parse(Rule::TermSpec, &self.unit)
+                .and_then(|mut result| TermParser::TermSpec(result.next().unwrap()))
+                .map_err(|err| {
+                    let position = match err.location {
+                        InputLocation::Pos(position) => position,
+                        InputLocation::Span((start, _)) => start,
+                    };
+
+                    format!(
+                        "Failed to parse term at byte offset {}:\n{err}",
+                        self.unit_start + position
+                    )
+                    .into()
+                });
+
+        self.unit.clear();
+        self.depth = 0;
+        self.in_string = false;
+        result
+    }
+}
+
+impl<R: BufRead> Iterator for TermReaderIter<R> {
+    type Item = Result<ATerm, MercError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => {
+                    // The remaining unit is parsed, which fails when some brackets have not been closed.
+                    self.finished = true;
+                    if !self.unit.is_empty() {
+                        return Some(self.parse_unit());
+                    }
+                }
+                Ok(length) => {
+                    // Only count the brackets before a comment.
+                    let code = count_brackets(&self.line, &mut self.depth, &mut self.in_string);
+                    if self.unit.is_empty() {
+                        if code.trim().is_empty() {
+                            // Skip empty lines and comments between terms.
+                            self.offset += length;
+                            continue;
+                        }
+
+                        self.unit_start = self.offset;
+                    }
+
+                    self.offset += length;
+                    self.unit.push_str(&self.line);
+                    if self.depth <= 0 {
+                        return Some(self.parse_unit());
+                    }
+                }
+                Err(err) => {
+                    self.finished = true;
+                    return Some(Err(err.into()));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Updates the bracket depth for the given line, skipping quoted strings,
+/// and returns the part of the line before a comment.
+fn count_brackets<'a>(line: &'a str, depth: &mut isize, in_string: &mut bool) -> &'a str {
+    let mut escaped = false;
+    for (index, character) in line.char_indices() {
+        if *in_string {
+            match character {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => *in_string = false,
+                _ => {}
+            }
+        } else {
+            match character {
+                '"' => *in_string = true,
+                '%' => return &line[..index],
+                '(' => *depth += 1,
+                ')' => *depth -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    line
+}
+
 #[cfg(test)]
 mod tests {
     use pest::Parser;
@@ -68,4 +211,28 @@ mod tests {
         let result = TermParser::parse(Rule::TermSpec, term).unwrap();
         print!("{}", result);
     }
+
+    #[test]
+    fn test_parse_terms_from_reader() {
+        let input = "f(a,\n  g(b))\n% comment\n\nh\nf(a,,b)\n";
+
+        let mut terms = parse_terms_from_reader(input.as_bytes());
+        assert_eq!(
+            terms.next().unwrap().unwrap(),
+            ATerm::from_string("f(a, g(b))").unwrap()
+        );
+        assert_eq!(terms.next().unwrap().unwrap(), ATerm::from_string("h").unwrap());
+
+        let error = terms.next().unwrap().unwrap_err();
+        assert!(error.to_string().contains("byte offset 30"), "{error}");
+        assert!(terms.next().is_none());
+
+        // The brackets and the comment inside the quoted string do not end or extend the unit.
+        let quoted = "f(\"a(%\\\"\")\ng(b)\n";
+        let mut terms = parse_terms_from_reader(quoted.as_bytes());
+        let error = terms.next().unwrap().unwrap_err();
+        assert!(error.to_string().contains("byte offset 2"), "{error}");
+        assert_eq!(terms.next().unwrap().unwrap(), ATerm::from_string("g(b)").unwrap());
+        assert!(terms.next().is_none());
+    }
 }
@@ -1,4 +1,3 @@
-#[cfg(debug_assertions)]
 use std::cell::RefCell;
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -15,6 +14,7 @@ use crate::Symb;
 use crate::SymbolRef;
 use crate::Term;
 use crate::Transmutable;
+use crate::aterm::ATerm;
 use crate::aterm::ATermRef;
 use crate::storage::GcMutex;
 use crate::storage::GcMutexGuard;
@@ -112,6 +112,65 @@ impl<C> Drop for Protected<C> {
     }
 }
 
+/// A scope that keeps a batch of terms protected from garbage collection
+/// until it is dropped.
+///
+/// # Details
+///
+/// Every term is added to the protection set of the current thread, and is
+/// removed again when the scope is dropped. The returned [ATermRef]s borrow
+/// from the scope, so they cannot outlive the protection. Since the protection
+/// set is thread local the scope itself is not Send.
+pub struct ProtectionScope {
+    terms: RefCell<Vec<ATerm>>,
+
+    // ProtectionScope is not Send because it uses thread-local state for its
+    // protection mechanism.
+    _unsend: PhantomUnsend,
+}
+
+impl ProtectionScope {
+    /// Creates a new scope that protects the given terms.
+    pub fn new<'a, 'b>(terms: &'b [impl Term<'a, 'b>]) -> ProtectionScope {
+        ProtectionScope {
+            terms: RefCell::new(terms.iter().map(|term| term.protect()).collect()),
+            _unsend: Default::default(),
+        }
+    }
+
+    /// Protects the given term for the remainder of this scope.
+    ///
+    /// This takes `&self` such that multiple references obtained from the same
+    /// scope can be used simultaneously.
+    pub fn protect<'a, 'b>(&self, term: &'b impl Term<'a, 'b>) -> ATermRef<'_> {
+        let protected = term.protect();
+
+        // SAFETY: The term is protected until the scope is dropped, which outlives the returned reference.
+        let result = unsafe { ATermRef::from_index(protected.shared()) };
+        self.terms.borrow_mut().push(protected);
+        result
+    }
+
+    /// Returns the number of terms protected by this scope.
+    pub fn len(&self) -> usize {
+        self.terms.borrow().len()
+    }
+
+    /// Returns true iff this scope protects no terms.
+    pub fn is_empty(&self) -> bool {
+        self.terms.borrow().is_empty()
+    }
+}
+
+impl Default for ProtectionScope {
+    fn default() -> Self {
+        ProtectionScope {
+            terms: RefCell::new(Vec::new()),
+            _unsend: Default::default(),
+        }
+    }
+}
+
 pub struct ProtectedWriteGuard<'a, C: Markable> {
     reference: GcMutexGuard<'a, C>,
 
@@ -228,10 +287,35 @@ impl<'a, C: Transmutable> Deref for ProtectedReadGuard<'a, C> {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
     use crate::ATerm;
+    use crate::Symbol;
+    use crate::storage::Marker;
 
     use super::*;
 
+    /// A worklist of unprotected terms for which marking is derived.
+    #[derive(Markable)]
+    struct Worklist {
+        terms: Vec<ATermRef<'static>>,
+        pending: Option<(ATermRef<'static>, ATermRef<'static>)>,
+        done: bool,
+    }
+
+    impl Transmutable for Worklist {
+        type Target<'a> = Worklist;
+
+        fn transmute_lifetime<'a>(&self) -> &'a Self::Target<'a> {
+            unsafe { transmute::<&Self, &'a Worklist>(self) }
+        }
+
+        fn transmute_lifetime_mut<'a>(&mut self) -> &'a mut Self::Target<'a> {
+            unsafe { transmute::<&mut Self, &'a mut Worklist>(self) }
+        }
+    }
+
     #[test]
     fn test_aterm_container() {
         let _ = merc_utilities::test_logger();
@@ -246,4 +330,112 @@ mod tests {
             write.push(t.get());
         }
     }
+
+    #[test]
+    fn test_protection_scope() {
+        let _ = merc_utilities::test_logger();
+
+        let num_of_protected = || THREAD_TERM_POOL.with_borrow(|tp| tp.num_of_protected_terms());
+        let before = num_of_protected();
+
+        {
+            let a = ATerm::from_string("a").unwrap();
+            let scope = ProtectionScope::new(&[a.copy()]);
+
+            let f = ATerm::from_string("f(a, b)").unwrap();
+            let g = ATerm::from_string("g(c)").unwrap();
+            let f_ref = scope.protect(&f);
+            let g_ref = scope.protect(&g);
+            drop(f);
+            drop(g);
+
+            assert_eq!(scope.len(), 3);
+            assert_eq!(f_ref.to_string(), "f(a, b)");
+            assert_eq!(g_ref.to_string(), "g(c)");
+
+            let during = num_of_protected();
+            assert!(during >= before + 3);
+        }
+
+        assert_eq!(
+            num_of_protected(),
+            before,
+            "All terms protected by the scope should be released"
+        );
+    }
+
+    #[test]
+    fn test_derive_markable() {
+        let _ = merc_utilities::test_logger();
+
+        // Count the deleted terms with a head symbol that is only used by this test.
+        let symbol = Symbol::new("derive_markable", 1);
+        let deleted = Arc::new(AtomicUsize::new(0));
+        {
+            let deleted = deleted.clone();
+            THREAD_TERM_POOL.with_borrow(|tp| {
+                tp.term_pool().write().expect("Lock poisoned!").register_deletion_hook(
+                    // The hook protects the symbol itself.
+                    unsafe { transmute::<SymbolRef<'_>, SymbolRef<'static>>(symbol.copy()) },
+                    move |_| {
+                        deleted.fetch_add(1, Ordering::Relaxed);
+                    },
+                )
+            });
+        }
+
+        let collect_garbage = || {
+            THREAD_TERM_POOL.with_borrow(|tp| {
+                tp.term_pool()
+                    .write()
+                    .expect("Lock poisoned!")
+                    .trigger_garbage_collection();
+            })
+        };
+
+        let mut worklist = Protected::new(Worklist {
+            terms: Vec::new(),
+            pending: None,
+            done: false,
+        });
+
+        {
+            let mut write = worklist.write();
+            let terms: Vec<ATerm> = ["a", "b", "c", "d"]
+                .iter()
+                .map(|name| ATerm::from_string(&format!("derive_markable({name})")).unwrap())
+                .collect();
+
+            for term in &terms[0..2] {
+                let term = write.protect(term);
+                write.terms.push(term);
+            }
+            write.pending = Some((write.protect(&terms[2]), write.protect(&terms[3])));
+        }
+
+        // The terms are only referenced by the worklist during garbage collection.
+        collect_garbage();
+        assert_eq!(
+            deleted.load(Ordering::Relaxed),
+            0,
+            "The terms in the worklist should be marked"
+        );
+
+        {
+            let read = worklist.read();
+            assert_eq!(read.len(), 4);
+            assert_eq!(read.terms[1].to_string(), "derive_markable(b)");
+            assert!(read.contains_symbol(&symbol.copy()));
+            assert!(!read.done);
+        }
+
+        {
+            let mut write = worklist.write();
+            write.terms.clear();
+            write.pending = None;
+        }
+
+        collect_garbage();
+        assert_eq!(deleted.load(Ordering::Relaxed), 4);
+    }
 }
@@ -51,6 +51,12 @@ impl ATermStorage {
         self.int_terms.len() + self.terms.len()
     }
 
+    /// Returns the number of bytes used by the stored terms, excluding the overhead of the underlying sets.
+    pub fn bytes_used(&self) -> usize {
+        self.int_terms.len() * std::mem::size_of::<SharedTermFixed<1>>()
+            + self.terms.iter().map(std::mem::size_of_val).sum::<usize>()
+    }
+
     pub fn retain<F>(&self, mut f: F)
     where
         F: FnMut(&StablePointer<SharedTerm>) -> bool,
@@ -3,12 +3,15 @@ use std::collections::HashSet;
 use std::fmt;
 use std::sync::Arc;
 use std::sync::LazyLock;
+use std::sync::Mutex;
 use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::time::Instant;
 
 use log::debug;
 
 use merc_collections::ProtectionSet;
+use merc_io::BytesFormatter;
 use merc_io::LargeFormatter;
 use merc_sharedmutex::GlobalBfSharedMutex;
 use merc_sharedmutex::RecursiveLockReadGuard;
@@ -27,6 +30,7 @@ use crate::storage::ATermStorage;
 use crate::storage::SharedTerm;
 use crate::storage::SharedTermLookup;
 use crate::storage::SymbolPool;
+use crate::storage::THREAD_TERM_POOL;
 
 /// This is the global set of protection sets that are managed by the ThreadTermPool
 pub static GLOBAL_TERM_POOL: LazyLock<GlobalBfSharedMutex<GlobalTermPool>> =
@@ -41,6 +45,59 @@ pub(crate) type GlobalTermPoolGuard<'a> = RecursiveLockReadGuard<'a, GlobalTermP
 /// A type alias for deletion hooks
 type DeletionHook = Box<dyn Fn(&ATermIndex) + Sync + Send>;
 
+/// A type alias for garbage collection callbacks
+pub type GcCallback = Box<dyn Fn(GcEvent) + Send>;
+
+/// The callbacks that are called after every garbage collection, see [register_gc_callback].
+static GC_CALLBACKS: Mutex<Vec<(GcCallbackId, GcCallback)>> = Mutex::new(Vec::new());
+
+/// The identifier of the next registered garbage collection callback.
+static NEXT_GC_CALLBACK_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Identifies a registered garbage collection callback, see [unregister_gc_callback].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GcCallbackId(usize);
+
+/// Describes the result of a single garbage collection of the global term pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GcEvent {
+    /// The number of terms that have been removed.
+    pub terms_collected: usize,
+    /// The number of terms that remain in the pool.
+    pub terms_remaining: usize,
+}
+
+/// Registers a callback that is called after every garbage collection of the
+/// global term pool, for example to flush caches that are keyed by terms.
+///
+/// # Details
+///
+/// The callbacks are called by the thread that performed the garbage
+/// collection, after its exclusive lock on the term pool has been released.
+/// A callback must not register other callbacks or create new terms, since
+/// these can result in a deadlock on the list of callbacks.
+pub fn register_gc_callback(callback: GcCallback) -> GcCallbackId {
+    let id = GcCallbackId(NEXT_GC_CALLBACK_ID.fetch_add(1, Ordering::Relaxed));
+    GC_CALLBACKS.lock().expect("Lock poisoned!").push((id, callback));
+    id
+}
+
+/// Removes the callback with the given identifier, returns false when it was
+/// not registered.
+pub fn unregister_gc_callback(id: GcCallbackId) -> bool {
+    let mut callbacks = GC_CALLBACKS.lock().expect("Lock poisoned!");
+    let length = callbacks.len();
+    callbacks.retain(|(other, _)| *other != id);
+    callbacks.len() != length
+}
+
+/// Calls all registered garbage collection callbacks with the given event.
+pub(crate) fn notify_gc_callbacks(event: GcEvent) {
+    for (_, callback) in GC_CALLBACKS.lock().expect("Lock poisoned!").iter() {
+        callback(event);
+    }
+}
+
 /// The single global (singleton) term pool.
 pub struct GlobalTermPool {
     /// Unique table of all terms with stable pointers for references
@@ -63,6 +120,8 @@ pub struct GlobalTermPool {
 
     /// Indicates whether automatic garbage collection is enabled.
     garbage_collection: bool,
+    /// The number of garbage collection cycles that have been performed.
+    garbage_collection_cycles: usize,
 
     /// Default terms
     int_symbol: SymbolRef<'static>,
@@ -90,6 +149,7 @@ impl GlobalTermPool {
             stack: Vec::new(),
             deletion_hooks: Vec::new(),
             garbage_collection: true,
+            garbage_collection_cycles: 0,
             int_symbol,
             list_symbol,
             empty_list_symbol,
@@ -189,15 +249,16 @@ impl GlobalTermPool {
         }
     }
 
-    /// Triggers garbage collection if necessary and returns an updated counter for the thread local pool.
-    pub(crate) fn trigger_garbage_collection(&mut self) -> usize {
-        self.collect_garbage();
+    /// Triggers garbage collection if necessary and returns an updated counter
+    /// for the thread local pool, and the event if garbage was collected.
+    pub(crate) fn trigger_garbage_collection(&mut self) -> (usize, Option<GcEvent>) {
+        let event = self.collect_garbage();
 
         if AGGRESSIVE_GC {
-            return 1;
+            return (1, event);
         }
 
-        self.len()
+        (self.len(), event)
     }
 
     /// Returns a counter for the unique numeric suffix of the given prefix.
@@ -223,13 +284,15 @@ impl GlobalTermPool {
         self.garbage_collection = enabled;
     }
 
-    /// Collects garbage terms.
-    fn collect_garbage(&mut self) {
+    /// Collects garbage terms, returns `None` when garbage collection is disabled.
+    fn collect_garbage(&mut self) -> Option<GcEvent> {
         if !self.garbage_collection {
             // Garbage collection is disabled.
-            return;
+            return None;
         }
 
+        self.garbage_collection_cycles += 1;
+
         // Clear marking data structures
         self.marked_terms.clear();
         self.marked_symbols.clear();
@@ -322,6 +385,11 @@ impl GlobalTermPool {
             let pool = unsafe { &mut *pool.get() };
             debug!("{}", pool.metrics());
         }
+
+        Some(GcEvent {
+            terms_collected: num_of_terms - self.len(),
+            terms_remaining: self.len(),
+        })
     }
 
     /// Returns the metrics of the term pool, can be formatted and written to output.
@@ -329,6 +397,21 @@ impl GlobalTermPool {
         TermPoolMetrics(self)
     }
 
+    /// Returns the statistics of the term pool, see [pool_statistics].
+    pub fn statistics(&self) -> PoolStatistics {
+        PoolStatistics {
+            num_of_terms: self.terms.len(),
+            num_of_symbols: self.symbol_pool.len(),
+            bytes_used: self.terms.bytes_used(),
+            garbage_collection_cycles: self.garbage_collection_cycles,
+        }
+    }
+
+    /// Returns the name and arity of every symbol in the symbol pool, see [crate::storage::dump_symbol_pool].
+    pub fn symbols(&self) -> Vec<(String, usize)> {
+        self.symbol_pool.dump()
+    }
+
     /// Marks the given term as being reachable.
     ///
     /// # Safety
@@ -373,6 +456,43 @@ impl fmt::Display for TermPoolMetrics<'_> {
     }
 }
 
+/// Returns the statistics of the global term pool, which can be logged to
+/// observe the memory usage over time.
+///
+/// # Details
+///
+/// Prevents garbage collection while the statistics are gathered. Computing the
+/// number of bytes requires a pass over all terms in the pool.
+pub fn pool_statistics() -> PoolStatistics {
+    THREAD_TERM_POOL.with_borrow(|tp| tp.term_pool().read_recursive().expect("Lock poisoned!").statistics())
+}
+
+/// A snapshot of the statistics of the global term pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolStatistics {
+    /// The number of terms that are currently stored.
+    pub num_of_terms: usize,
+    /// The number of function symbols that are currently stored.
+    pub num_of_symbols: usize,
+    /// The number of bytes used by the stored terms.
+    pub bytes_used: usize,
+    /// The number of garbage collection cycles run so far.
+    pub garbage_collection_cycles: usize,
+}
+
+impl fmt::Display for PoolStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "There are {} terms using {}, {} symbols and {} garbage collections",
+            LargeFormatter(self.num_of_terms),
+            BytesFormatter(self.bytes_used),
+            LargeFormatter(self.num_of_symbols),
+            LargeFormatter(self.garbage_collection_cycles)
+        )
+    }
+}
+
 pub struct SharedTermProtection {
     /// Protection set for terms
     pub protection_set: ProtectionSet<ATermIndex>,
@@ -468,7 +588,9 @@ mod tests {
 
     use merc_utilities::random_test;
 
+    use crate::ATerm;
     use crate::random_term;
+    use crate::storage::pool_statistics;
 
     #[test]
     #[cfg_attr(miri, ignore)]
@@ -488,4 +610,15 @@ mod tests {
             }
         });
     }
+
+    #[test]
+    fn test_pool_statistics() {
+        let _term = ATerm::from_string("f(g(a), b)").unwrap();
+
+        // Other tests share the global term pool, so only lower bounds can be checked.
+        let statistics = pool_statistics();
+        assert!(statistics.num_of_terms >= 4);
+        assert!(statistics.num_of_symbols >= 4);
+        assert!(statistics.bytes_used > 0);
+    }
 }
@@ -16,6 +16,7 @@ use merc_unsafety::StablePointerSet;
 use crate::Symb;
 use crate::SymbolIndex;
 use crate::SymbolRef;
+use crate::storage::THREAD_TERM_POOL;
 
 /// Pool for maximal sharing of function symbols, see [crate::SymbolRef]. Ensures that function symbols
 /// with the same name and arity point to the same [SharedSymbol] object.
@@ -80,6 +81,14 @@ impl SymbolPool {
         self.symbols.capacity()
     }
 
+    /// Returns the name and arity of every symbol in the pool.
+    pub fn dump(&self) -> Vec<(String, usize)> {
+        self.symbols
+            .iter()
+            .map(|symbol| (symbol.name().to_string(), symbol.arity()))
+            .collect()
+    }
+
     /// Retain only symbols satisfying the given predicate.
     pub fn retain<F>(&mut self, mut f: F)
     where
@@ -156,6 +165,17 @@ impl SymbolPool {
     }
 }
 
+/// Returns the name and arity of every symbol that is registered in the global
+/// symbol pool, which is useful for debugging.
+///
+/// # Details
+///
+/// Prevents garbage collection while the symbols are copied, which requires a
+/// pass over all symbols in the pool.
+pub fn dump_symbol_pool() -> Vec<(String, usize)> {
+    THREAD_TERM_POOL.with_borrow(|tp| tp.term_pool().read_recursive().expect("Lock poisoned!").symbols())
+}
+
 /// Represents a function symbol with a name and arity.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct SharedSymbol {
@@ -229,8 +249,10 @@ impl Hash for SharedSymbol {
 mod tests {
     use std::sync::atomic::Ordering;
 
+    use crate::Symb;
     use crate::Symbol;
     use crate::storage::THREAD_TERM_POOL;
+    use crate::storage::dump_symbol_pool;
 
     #[test]
     fn test_symbol_sharing() {
@@ -260,4 +282,33 @@ mod tests {
 
         assert_eq!(value.load(Ordering::Relaxed), 131);
     }
+
+    #[test]
+    fn test_symbol_with_arity() {
+        let _ = merc_utilities::test_logger();
+
+        let f = Symbol::new("f", 2);
+        let g = f.with_arity(0);
+
+        assert_eq!(g.name(), "f");
+        assert_eq!(g.arity(), 0);
+        assert_eq!(g.with_arity(2), f);
+    }
+
+    #[test]
+    fn test_dump_symbol_pool() {
+        let _ = merc_utilities::test_logger();
+
+        let _f = Symbol::new("dump_f", 1);
+        let _g = Symbol::new("dump_g", 3);
+        let _c = Symbol::new("dump_f", 0);
+
+        let symbols = dump_symbol_pool();
+        for (name, arity) in [("dump_f", 1), ("dump_g", 3), ("dump_f", 0)] {
+            assert!(
+                symbols.contains(&(name.to_string(), arity)),
+                "Symbol {name}/{arity} is missing from {symbols:?}"
+            );
+        }
+    }
 }
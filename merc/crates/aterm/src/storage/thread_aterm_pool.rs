@@ -30,6 +30,7 @@ use crate::storage::GlobalTermPool;
 use crate::storage::SharedTerm;
 use crate::storage::SharedTermProtection;
 use crate::storage::global_aterm_pool::GLOBAL_TERM_POOL;
+use crate::storage::notify_gc_callbacks;
 
 thread_local! {
     /// Thread-specific term pool that manages protection sets.
@@ -47,6 +48,9 @@ pub struct ThreadTermPool {
     /// A vector of terms that are used to store the arguments of a term for loopup.
     tmp_arguments: RefCell<Vec<ATermRef<'static>>>,
 
+    /// The number of function symbols that were looked up in the symbol pool by this thread.
+    symbol_lookups: Cell<usize>,
+
     /// A local view for the global term pool.
     term_pool: RecursiveLock<GlobalTermPool>,
 
@@ -75,6 +79,7 @@ impl ThreadTermPool {
             protection_set,
             garbage_collection_counter: Cell::new(if AGGRESSIVE_GC { 1 } else { 1000 }),
             tmp_arguments: RefCell::new(Vec::new()),
+            symbol_lookups: Cell::new(0),
             int_symbol,
             empty_list_symbol,
             list_symbol,
@@ -232,6 +237,7 @@ impl ThreadTermPool {
 
     /// Create a function symbol
     pub fn create_symbol(&self, name: impl Into<String> + AsRef<str>, arity: usize) -> Symbol {
+        self.symbol_lookups.set(self.symbol_lookups.get() + 1);
         self.term_pool
             .read_recursive()
             .expect("Lock poisoned!")
@@ -240,6 +246,12 @@ impl ThreadTermPool {
             })
     }
 
+    /// Returns the number of function symbols that were created, or looked up, in
+    /// the symbol pool by this thread.
+    pub fn symbol_lookups(&self) -> usize {
+        self.symbol_lookups.get()
+    }
+
     /// Protect the term by adding its index to the protection set
     pub fn protect(&self, term: &ATermRef<'_>) -> ATerm {
         // Protect the term by adding its index to the protection set
@@ -364,6 +376,12 @@ impl ThreadTermPool {
         guard.automatic_garbage_collection(enabled);
     }
 
+    /// Returns the number of terms in this thread's protection set.
+    #[cfg(test)]
+    pub(crate) fn num_of_protected_terms(&self) -> usize {
+        self.lock_protection_set().protection_set.len()
+    }
+
     /// Returns access to the shared protection set.
     pub(crate) fn get_protection_set(&self) -> &Arc<UnsafeCell<SharedTermProtection>> {
         &self.protection_set
@@ -396,11 +414,17 @@ impl ThreadTermPool {
 
         if value == 0 && !self.term_pool.is_locked() {
             // Trigger garbage collection and acquire a new counter value.
-            value = self
+            let (counter, event) = self
                 .term_pool
                 .write()
                 .expect("Lock poisoned!")
                 .trigger_garbage_collection();
+            value = counter;
+
+            // The callbacks are called after the write lock has been released.
+            if let Some(event) = event {
+                notify_gc_callbacks(event);
+            }
         }
 
         self.garbage_collection_counter.set(value);
@@ -469,8 +493,13 @@ mod tests {
     use crate::Term;
 
     use super::*;
+    use std::sync::Mutex;
     use std::thread;
 
+    use crate::storage::GcEvent;
+    use crate::storage::register_gc_callback;
+    use crate::storage::unregister_gc_callback;
+
     #[test]
     fn test_thread_local_protection() {
         let _ = merc_utilities::test_logger();
@@ -534,4 +563,35 @@ mod tests {
         assert!(t.arg(0).get_head_symbol().name() == "g");
         assert!(t.arg(1).get_head_symbol().name() == "b");
     }
+
+    #[test]
+    fn test_gc_callback() {
+        let _ = merc_utilities::test_logger();
+
+        static EVENT: Mutex<Option<GcEvent>> = Mutex::new(None);
+        let id = register_gc_callback(Box::new(|event| {
+            *EVENT.lock().unwrap() = Some(event);
+        }));
+
+        let term = ATerm::from_string("f(a)").unwrap();
+
+        // Force a garbage collection on the next trigger.
+        THREAD_TERM_POOL.with_borrow(|tp| {
+            tp.garbage_collection_counter.set(1);
+            tp.trigger_garbage_collection();
+        });
+
+        assert!(unregister_gc_callback(id));
+        assert!(
+            !unregister_gc_callback(id),
+            "The callback should only be registered once"
+        );
+
+        let event = EVENT
+            .lock()
+            .unwrap()
+            .expect("Garbage collection should have been triggered");
+        assert!(event.terms_remaining >= 2, "The protected terms remain");
+        drop(term);
+    }
 }
@@ -149,6 +149,11 @@ impl Symbol {
     pub fn new(name: impl Into<String> + AsRef<str>, arity: usize) -> Symbol {
         THREAD_TERM_POOL.with_borrow(|tp| tp.create_symbol(name, arity))
     }
+
+    /// Returns the symbol with the same name as this symbol and the given arity.
+    pub fn with_arity(&self, arity: usize) -> Symbol {
+        Symbol::new(self.name(), arity)
+    }
 }
 
 impl Symbol {
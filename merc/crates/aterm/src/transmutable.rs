@@ -4,8 +4,33 @@ use std::mem::transmute;
 use merc_collections::IndexedSet;
 
 use crate::SymbolRef;
+use crate::Term;
+use crate::aterm::ATerm;
 use crate::aterm::ATermRef;
 
+/// A newtype around an [ATerm] that can only represent terms of a certain
+/// shape, which is implemented by the `merc_derive_terms` macro.
+///
+/// # Safety
+///
+/// The type must have the same layout as [ATerm], i.e., be a
+/// `#[repr(transparent)]` struct around it.
+pub unsafe trait Castable {
+    /// Returns true iff the given term has the shape of this type.
+    const RECOGNIZER: fn(&ATermRef<'_>) -> bool;
+}
+
+/// Interprets the given term as a `T`, or returns None when the term does not
+/// have the shape of `T`, see [Castable].
+pub fn try_cast<T: Castable>(term: &ATerm) -> Option<&T> {
+    if T::RECOGNIZER(&term.copy()) {
+        // Safety: T has the same layout as ATerm by the requirements of Castable.
+        Some(unsafe { &*(term as *const ATerm as *const T) })
+    } else {
+        None
+    }
+}
+
 pub trait Transmutable {
     type Target<'a>
     where
@@ -0,0 +1,37 @@
+use std::hint::black_box;
+
+use criterion::Criterion;
+use criterion::criterion_group;
+use criterion::criterion_main;
+
+use merc_collections::ProtectionIndex;
+use merc_collections::ProtectionSet;
+
+/// The number of objects that are protected in every iteration.
+const NUM_OBJECTS: usize = 1000;
+
+/// Compares protecting a batch of objects one by one with protecting them at once.
+pub fn benchmark_protection_set(c: &mut Criterion) {
+    c.bench_function("protection_set protect/unprotect", |bencher| {
+        let mut set = ProtectionSet::new();
+        bencher.iter(|| {
+            let indices: Vec<ProtectionIndex> = (0..NUM_OBJECTS).map(|object| set.protect(object)).collect();
+            for index in indices {
+                set.unprotect(index);
+            }
+            black_box(&set);
+        });
+    });
+
+    c.bench_function("protection_set protect_all/unprotect_range", |bencher| {
+        let mut set = ProtectionSet::new();
+        bencher.iter(|| {
+            let range = set.protect_all(0..NUM_OBJECTS);
+            set.unprotect_range(range);
+            black_box(&set);
+        });
+    });
+}
+
+criterion_group!(benches, benchmark_protection_set);
+criterion_main!(benches);
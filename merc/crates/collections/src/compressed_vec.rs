@@ -106,7 +106,16 @@ impl<T: CompressedEntry> ByteCompressedVec<T> {
     }
 
     /// Sets the entry at the given index.
+    ///
+    /// When the entry requires more bytes than the current bit width all
+    /// existing entries are widened first. The bit width is never decreased,
+    /// so setting a smaller entry keeps the current width.
     pub fn set(&mut self, index: usize, entry: T) {
+        debug_assert!(
+            index < self.len(),
+            "Index {index} out of bounds for length {}",
+            self.len()
+        );
         self.resize_entries(entry.bytes_required());
 
         let start = index * self.bytes_per_entry;
@@ -129,6 +138,12 @@ impl<T: CompressedEntry> ByteCompressedVec<T> {
         self.len() == 0
     }
 
+    /// Returns the number of bits used to store every entry, which is a
+    /// multiple of eight since the entries are stored in whole bytes.
+    pub fn bit_width(&self) -> usize {
+        self.bytes_per_entry * u8::BITS as usize
+    }
+
     /// Returns metrics about memory usage of this compressed vector
     pub fn metrics(&self) -> CompressedVecMetrics {
         let element_count = self.len();
@@ -535,6 +550,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_random_set_widening() {
+        random_test(100, |rng| {
+            let mut expected: Vec<usize> = (0..50).map(|_| rng.random_range(0..256)).collect();
+            let mut vector = ByteCompressedVec::with_iter(expected.iter().cloned());
+            let width = expected.iter().max().unwrap().bytes_required() * 8;
+            assert_eq!(vector.bit_width(), width);
+
+            // Set a value that requires more bytes than the current width.
+            let index = rng.random_range(0..expected.len());
+            let value = rng.random_range(1 << 16..1 << 24);
+            vector.set(index, value);
+            expected[index] = value;
+            let width = value.bytes_required() * 8;
+            assert!(width > 16);
+            assert_eq!(vector.bit_width(), width);
+
+            // Setting a smaller value keeps the width.
+            let index = rng.random_range(0..expected.len());
+            vector.set(index, 1);
+            expected[index] = 1;
+            assert_eq!(vector.bit_width(), width);
+
+            assert_eq!(vector.iter().collect::<Vec<_>>(), expected);
+        });
+    }
+
     #[test]
     fn test_random_usize_entry() {
         random_test(100, |rng| {
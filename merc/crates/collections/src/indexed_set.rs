@@ -12,6 +12,7 @@ use hashbrown::HashSet;
 use rustc_hash::FxBuildHasher;
 
 use merc_utilities::GenerationCounter;
+use merc_utilities::GenerationError;
 use merc_utilities::GenerationalIndex;
 use merc_utilities::NoHasherBuilder;
 use merc_utilities::cast;
@@ -43,6 +44,14 @@ impl fmt::Display for SetIndex {
 }
 
 /// A set that assigns a unique index to every entry. The returned index can be used to access the inserted entry.
+///
+/// # Details
+///
+/// Removing an element only invalidates the index of that element, the indices
+/// of all other elements remain valid. The index of a removed element is added
+/// to a free list and is reused by a later insertion, so a stale index may
+/// refer to another element afterwards. In debug builds the generation of the
+/// index is used to detect such accesses through [IndexedSet::get].
 pub struct IndexedSet<T, S = FxBuildHasher> {
     /// The table of elements, which can be either filled or empty.
     table: Vec<IndexSetEntry<T>>,
@@ -109,6 +118,19 @@ impl<T, S> IndexedSet<T, S> {
         }
     }
 
+    /// Returns a reference to the element at the given index, or the reason
+    /// that the index does not refer to an element.
+    ///
+    /// Unlike [IndexedSet::get] this does not panic on stale indices in debug
+    /// builds, which makes it useful to diagnose invalid indices.
+    pub fn try_get(&self, index: SetIndex) -> Result<&T, GenerationError> {
+        match self.table.get(self.generation_counter.try_get_index(index.0)?) {
+            Some(IndexSetEntry::Filled(element)) => Ok(element),
+            Some(IndexSetEntry::Empty(_)) => Err(GenerationError::Vacant),
+            None => Err(GenerationError::OutOfBounds),
+        }
+    }
+
     /// Returns the capacity of the set.
     pub fn capacity(&self) -> usize {
         self.table.capacity()
@@ -224,6 +246,7 @@ impl<T: Hash + Eq, S: BuildHasher> IndexedSet<T, S> {
     }
 
     /// Returns the index for the given element, or None if it does not exist.
+    #[doc(alias = "index_of")]
     pub fn index<Q>(&self, key: &Q) -> Option<SetIndex>
     where
         Q: Hash + Equivalent<T>,
@@ -265,8 +288,13 @@ impl<T: Hash + Eq, S: BuildHasher> IndexedSet<T, S> {
         }
     }
 
-    /// Removes the given element from the set.
-    pub fn remove(&mut self, element: &T) -> bool {
+    /// Removes the given element from the set, returns true iff it was present.
+    ///
+    /// The index of the removed element is reused by a later insertion.
+    pub fn remove<Q>(&mut self, element: &Q) -> bool
+    where
+        Q: Hash + Equivalent<T>,
+    {
         let equivalent = IndexValueEquivalent::new(element, &self.hasher, &self.table);
 
         if let Some(entry) = self.index.take(&equivalent) {
@@ -431,6 +459,8 @@ mod tests {
 
     use merc_utilities::random_test;
 
+    use merc_utilities::GenerationError;
+
     use crate::IndexedSet;
     use crate::SetIndex;
 
@@ -494,4 +524,43 @@ mod tests {
             }
         })
     }
+
+    #[test]
+    fn test_indexed_set_reuse_index() {
+        let mut set: IndexedSet<usize> = IndexedSet::default();
+        let (a, _) = set.insert(1);
+        let (b, _) = set.insert(2);
+        let (c, _) = set.insert(3);
+
+        assert!(set.remove(&2));
+        assert!(!set.remove(&2));
+        assert!(!set.contains(&2));
+        assert_eq!(set.index(&2), None);
+
+        // The freed index is reused by the next insertion.
+        let (d, inserted) = set.insert(4);
+        assert!(inserted);
+        assert_eq!(*d, *b);
+
+        // Reinserting the removed element gets a fresh index.
+        let (e, inserted) = set.insert(2);
+        assert!(inserted);
+        assert_eq!(*e, 3);
+
+        // The index of the removed element is vacant, or stale once it has been reused.
+        assert_eq!(set.try_get(d), Ok(&4));
+        if cfg!(debug_assertions) {
+            assert_eq!(set.try_get(b), Err(GenerationError::Stale));
+        }
+        set.remove(&4);
+        assert_eq!(set.try_get(d), Err(GenerationError::Vacant));
+        let (d, _) = set.insert(4);
+
+        // The lookups of all elements are consistent with the returned indices.
+        for (value, index) in [(1, a), (3, c), (4, d), (2, e)] {
+            assert!(set.contains(&value));
+            assert_eq!(set.index(&value), Some(index));
+            assert_eq!(set[index], value);
+        }
+    }
 }
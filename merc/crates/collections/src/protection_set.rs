@@ -3,6 +3,7 @@ use std::fmt;
 use std::hash::Hash;
 use std::ops::Deref;
 use std::ops::Index;
+use std::ops::Range;
 
 use merc_utilities::GenerationCounter;
 use merc_utilities::GenerationalIndex;
@@ -32,6 +33,22 @@ impl fmt::Display for ProtectionIndex {
     }
 }
 
+/// A contiguous range of indices in a [ProtectionSet], as returned by [ProtectionSet::protect_all].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProtectionRange(Range<usize>);
+
+impl ProtectionRange {
+    /// Returns the number of indices in the range.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true iff the range contains no indices.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
 /// A collection that assigns a unique index to every object added to it, and allows
 /// removing objects while reusing their indices later. This is useful for managing
 /// objects that must not be garbage collected, and as such it is called a protection set.
@@ -142,6 +159,76 @@ impl<T> ProtectionSet<T> {
         ProtectionIndex(self.generation_counter.create_index(index))
     }
 
+    /// Adds all given objects to the protection set, and returns the contiguous
+    /// range of their indices.
+    ///
+    /// # Details
+    ///
+    /// The objects are always added at the end, and the free list is not used,
+    /// such that the indices form a contiguous range. This avoids the overhead
+    /// of protecting the objects one by one.
+    pub fn protect_all<I>(&mut self, objects: I) -> ProtectionRange
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let start = self.roots.len();
+        self.roots.extend(objects.into_iter().map(Entry::Filled));
+        let end = self.roots.len();
+
+        let count = end - start;
+        self.number_of_insertions += count as u64;
+        self.size += count;
+
+        for index in start..end {
+            // Only has an effect in debug builds, where it records the generation of the new index.
+            self.generation_counter.create_index(index);
+        }
+
+        ProtectionRange(start..end)
+    }
+
+    /// Returns the indices of the given range, in increasing order.
+    pub fn range_indices(&self, range: &ProtectionRange) -> impl Iterator<Item = ProtectionIndex> + '_ {
+        range
+            .0
+            .clone()
+            .map(|index| ProtectionIndex(self.generation_counter.recall_index(index)))
+    }
+
+    /// Removes the protection of all objects in the given range, which must be
+    /// returned by [ProtectionSet::protect_all] and not unprotected before.
+    ///
+    /// The freed entries are linked into the free list in a single pass, unless
+    /// the range is at the end of the set in which case they are removed.
+    pub fn unprotect_range(&mut self, range: ProtectionRange) {
+        let Range { start, end } = range.0;
+        if start == end {
+            return;
+        }
+
+        debug_assert!(
+            self.roots[start..end]
+                .iter()
+                .all(|entry| matches!(entry, Entry::Filled(_))),
+            "All indices in the range {start}..{end} should point to filled entries"
+        );
+
+        self.size -= end - start;
+
+        if end == self.roots.len() {
+            // The free list only contains free entries, so none of them are in the range.
+            self.roots.truncate(start);
+            return;
+        }
+
+        // Every freed entry points to its successor, and the last one to the previous free list.
+        for index in start..end - 1 {
+            self.roots[index] = Entry::Free(index + 1);
+        }
+        self.roots[end - 1] = Entry::Free(self.free.unwrap_or(end - 1));
+        self.free = Some(start);
+    }
+
     /// Remove protection from the given object. Note that index must be the
     /// index returned by the [ProtectionSet::protect] call.
     pub fn unprotect(&mut self, index: ProtectionIndex) {
@@ -310,4 +397,52 @@ mod tests {
         let idx3 = set.protect(String::from("value3"));
         assert_eq!(set[idx3], "value3");
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_random_protection_set_ranges() {
+        random_test(100, |rng| {
+            let mut bulk = ProtectionSet::<usize>::new();
+            let mut single = ProtectionSet::<usize>::new();
+
+            let values: Vec<usize> = (0..100).map(|_| rng.random_range(0..1000)).collect();
+            let range = bulk.protect_all(values.iter().cloned());
+            let indices: Vec<ProtectionIndex> = values.iter().map(|value| single.protect(*value)).collect();
+
+            // Both ways of protecting result in the same roots.
+            assert_eq!(range.len(), values.len());
+            for (index, (expected, value)) in bulk.range_indices(&range).zip(indices.iter().zip(&values)) {
+                assert_eq!(*index, **expected);
+                assert_eq!(bulk[index], *value);
+            }
+            assert_eq!(bulk.len(), single.len());
+            assert_eq!(bulk.number_of_insertions(), single.number_of_insertions());
+
+            // Unprotect a range and individual roots, the other range is not empty such that the freed entries are kept.
+            let other = bulk.protect_all(0..rng.random_range(1..50));
+            bulk.unprotect_range(range);
+            for index in indices {
+                single.unprotect(index);
+            }
+            assert_eq!(bulk.len(), other.len());
+            assert_eq!(single.len(), 0);
+
+            // The freed entries are reused.
+            let maximum_size = bulk.roots.len();
+            let reused: Vec<ProtectionIndex> = (0..values.len()).map(|value| bulk.protect(value)).collect();
+            assert_eq!(bulk.roots.len(), maximum_size);
+            for (value, index) in reused.iter().enumerate() {
+                assert_eq!(bulk[*index], value);
+            }
+
+            for index in bulk.range_indices(&other) {
+                assert!(bulk.contains_root(index));
+            }
+
+            // A range at the end is removed.
+            bulk.unprotect_range(other);
+            assert_eq!(bulk.roots.len(), values.len());
+            assert_eq!(bulk.len(), values.len());
+        });
+    }
 }
@@ -64,6 +64,11 @@ impl<T: Ord> VecSet<T> {
         self.sorted_array.retain(|e| f(e));
     }
 
+    /// Returns true iff this set is a subset of the other set, see [VecSet::is_subset].
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        self.is_subset(other)
+    }
+
     /// Returns true iff this set is a subset of the other set.
     pub fn is_subset(&self, other: &VecSet<T>) -> bool {
         let mut self_iter = self.sorted_array.iter();
@@ -127,6 +132,34 @@ impl<T: Ord> VecSet<T> {
     }
 }
 
+impl<T: Ord + Clone> VecSet<T> {
+    /// Returns the union of this set and the other set, computed by a single
+    /// merge of both sorted vectors.
+    pub fn union(&self, other: &VecSet<T>) -> VecSet<T> {
+        VecSet {
+            sorted_array: self
+                .sorted_array
+                .iter()
+                .merge_join_by(&other.sorted_array, |a, b| a.cmp(b))
+                .map(|entry| entry.reduce(|a, _| a).clone())
+                .collect(),
+        }
+    }
+
+    /// Returns the intersection of this set and the other set, computed by a
+    /// single merge of both sorted vectors.
+    pub fn intersection(&self, other: &VecSet<T>) -> VecSet<T> {
+        VecSet {
+            sorted_array: self
+                .sorted_array
+                .iter()
+                .merge_join_by(&other.sorted_array, |a, b| a.cmp(b))
+                .filter_map(|entry| entry.both().map(|(a, _)| a.clone()))
+                .collect(),
+        }
+    }
+}
+
 impl<T: Ord> Default for VecSet<T> {
     fn default() -> Self {
         Self::new()
@@ -147,3 +180,56 @@ impl<T: fmt::Debug> fmt::Debug for VecSet<T> {
         write!(f, "{{{:?}}}", self.sorted_array.iter().format(", "))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use rand::Rng;
+
+    use merc_utilities::random_test;
+
+    use super::*;
+
+    #[test]
+    fn test_random_vecset_operations() {
+        random_test(100, |rng| {
+            let lhs: BTreeSet<u32> = (0..rng.random_range(0..20)).map(|_| rng.random_range(0..32)).collect();
+            let rhs: BTreeSet<u32> = (0..rng.random_range(0..20)).map(|_| rng.random_range(0..32)).collect();
+
+            let mut lhs_set = VecSet::new();
+            for element in &lhs {
+                lhs_set.insert(*element);
+            }
+            let mut rhs_set = VecSet::new();
+            for element in &rhs {
+                rhs_set.insert(*element);
+            }
+
+            let union: Vec<u32> = lhs_set.union(&rhs_set).iter().cloned().collect();
+            assert_eq!(union, lhs.union(&rhs).cloned().collect::<Vec<_>>());
+
+            let intersection = lhs_set.intersection(&rhs_set);
+            assert_eq!(
+                intersection.iter().cloned().collect::<Vec<_>>(),
+                lhs.intersection(&rhs).cloned().collect::<Vec<_>>()
+            );
+
+            assert_eq!(lhs_set.is_subset(&rhs_set), lhs.is_subset(&rhs));
+            assert!(intersection.is_subset(&lhs_set));
+            assert!(lhs_set.is_subset(&lhs_set.union(&rhs_set)));
+        })
+    }
+
+    #[test]
+    fn test_vecset_is_subset_of() {
+        let set = vecset![1, 3, 5];
+
+        assert!(VecSet::new().is_subset_of(&set));
+        assert!(vecset![1, 5].is_subset_of(&set));
+        assert!(set.is_subset_of(&set));
+        assert!(!vecset![1, 2].is_subset_of(&set));
+        assert!(!vecset![1, 3, 5, 7].is_subset_of(&set));
+        assert!(!set.is_subset_of(&VecSet::new()));
+    }
+}
@@ -9,6 +9,7 @@ use merc_aterm::ATermArgs;
 use merc_aterm::ATermIndex;
 use merc_aterm::ATermRef;
 use merc_aterm::ATermString;
+use merc_aterm::Castable;
 use merc_aterm::Markable;
 use merc_aterm::Symb;
 use merc_aterm::SymbolRef;
@@ -474,6 +475,7 @@ mod tests {
     use super::*;
 
     use merc_aterm::ATerm;
+    use merc_aterm::try_cast;
 
     #[test]
     fn test_print() {
@@ -518,4 +520,21 @@ mod tests {
         assert_eq!(expression.data_arg(0).data_function_symbol().name(), "s");
         assert_eq!(expression.data_arg(0).data_arg(0).data_function_symbol().name(), "a");
     }
+
+    #[test]
+    fn test_try_cast() {
+        let x: ATerm = DataVariable::new("x").into();
+        let a: ATerm = DataFunctionSymbol::new("a").into();
+
+        // A variable is a data expression, and a data expression can be a variable.
+        let expression = try_cast::<DataExpression>(&x).expect("A variable is a data expression");
+        assert_eq!(*expression, DataExpression::from(x.clone()));
+        let variable = try_cast::<DataVariable>(expression).expect("The expression is a variable");
+        assert_eq!(variable.name(), "x");
+
+        // A function symbol is not a variable, and a sort is not a data expression.
+        assert!(try_cast::<DataExpression>(&a).is_some());
+        assert!(try_cast::<DataVariable>(&a).is_none());
+        assert!(try_cast::<DataExpression>(&SortExpression::unknown_sort()).is_none());
+    }
 }
@@ -1,20 +1,96 @@
 use merc_aterm::ATerm;
 use merc_aterm::ATermRead;
+use merc_aterm::ATermRef;
 use merc_aterm::ATermStreamable;
 use merc_aterm::ATermWrite;
+use merc_aterm::Symb;
 use merc_aterm::Symbol;
+use merc_aterm::Term;
 use merc_utilities::MercError;
 
-/// TODO: Not yet useful, but can be used to read the data specification from a binary stream.
+use crate::DataExpression;
+use crate::DataFunctionSymbol;
+use crate::SortExpression;
+use crate::is_data_function_symbol;
+
+/// A data specification, of which only the constructors and mappings are
+/// stored for now. It can be read from a binary stream as written by the
+/// mCRL2 toolset.
 #[derive(Default)]
-pub struct DataSpecification {}
+pub struct DataSpecification {
+    constructors: Vec<DataFunctionSymbol>,
+    mappings: Vec<DataFunctionSymbol>,
+}
+
+impl DataSpecification {
+    /// Creates a data specification with the given constructors and mappings.
+    pub fn new(constructors: Vec<DataFunctionSymbol>, mappings: Vec<DataFunctionSymbol>) -> Self {
+        Self { constructors, mappings }
+    }
+
+    /// Returns the constructors of the data specification.
+    pub fn constructors(&self) -> &[DataFunctionSymbol] {
+        &self.constructors
+    }
+
+    /// Returns the user defined mappings of the data specification.
+    pub fn mappings(&self) -> &[DataFunctionSymbol] {
+        &self.mappings
+    }
+
+    /// Returns the constructors whose target sort is the given sort.
+    ///
+    /// # Details
+    ///
+    /// For constants this is the sort of the constructor itself, and for a
+    /// constructor `f: D_1 # ... # D_n -> S` it is the codomain `S`.
+    pub fn constructors_of(&self, sort: &SortExpression) -> Vec<DataExpression> {
+        of_sort(&self.constructors, sort)
+    }
+
+    /// Returns the mappings whose target sort is the given sort, see [Self::constructors_of].
+    pub fn mappings_of(&self, sort: &SortExpression) -> Vec<DataExpression> {
+        of_sort(&self.mappings, sort)
+    }
+}
+
+/// Returns the function symbols whose target sort is the given sort.
+fn of_sort(symbols: &[DataFunctionSymbol], sort: &SortExpression) -> Vec<DataExpression> {
+    let sort: ATermRef<'_> = sort.copy().into();
+    symbols
+        .iter()
+        .filter(|symbol| target_sort(symbol.arg(1)) == sort)
+        .map(|symbol| symbol.clone().into())
+        .collect()
+}
+
+/// Returns the codomain of a function sort, or the sort itself otherwise.
+fn target_sort(sort: ATermRef<'_>) -> ATermRef<'_> {
+    let symbol = sort.get_head_symbol();
+    if symbol.name() == "SortArrow" && symbol.arity() == 2 {
+        sort.arg(1)
+    } else {
+        sort
+    }
+}
+
+/// Reads a list of terms, and only keeps the data function symbols.
+fn read_function_symbols<R: ATermRead>(reader: &mut R) -> Result<Vec<DataFunctionSymbol>, MercError> {
+    let terms: Result<Vec<ATerm>, MercError> = reader.read_aterm_iter()?.collect();
+
+    Ok(terms?
+        .into_iter()
+        .filter(|term| is_data_function_symbol(term))
+        .map(DataFunctionSymbol::from)
+        .collect())
+}
 
 impl ATermStreamable for DataSpecification {
     fn write<W: ATermWrite>(&self, writer: &mut W) -> Result<(), MercError> {
         writer.write_aterm_iter((0..0).map(|_| ATerm::constant(&Symbol::new("unimportant", 0))))?;
         writer.write_aterm_iter((0..0).map(|_| ATerm::constant(&Symbol::new("unimportant", 0))))?;
-        writer.write_aterm_iter((0..0).map(|_| ATerm::constant(&Symbol::new("unimportant", 0))))?;
-        writer.write_aterm_iter((0..0).map(|_| ATerm::constant(&Symbol::new("unimportant", 0))))?;
+        writer.write_aterm_iter(self.constructors.iter().map(|symbol| symbol.clone().into()))?;
+        writer.write_aterm_iter(self.mappings.iter().map(|symbol| symbol.clone().into()))?;
         writer.write_aterm_iter((0..0).map(|_| ATerm::constant(&Symbol::new("unimportant", 0))))?;
 
         Ok(())
@@ -26,11 +102,78 @@ impl ATermStreamable for DataSpecification {
     {
         let _sorts: Result<Vec<ATerm>, MercError> = reader.read_aterm_iter()?.collect();
         let _aliases: Result<Vec<ATerm>, MercError> = reader.read_aterm_iter()?.collect();
-        let _constructors: Result<Vec<ATerm>, MercError> = reader.read_aterm_iter()?.collect();
-        let _user_defined_mappings: Result<Vec<ATerm>, MercError> = reader.read_aterm_iter()?.collect();
+        let constructors = read_function_symbols(reader)?;
+        let mappings = read_function_symbols(reader)?;
         let _user_defined_equations: Result<Vec<ATerm>, MercError> = reader.read_aterm_iter()?.collect();
 
-        // Ignore results for now.
-        Ok(DataSpecification {})
+        // The sorts, aliases and equations are ignored for now.
+        Ok(DataSpecification { constructors, mappings })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use merc_aterm::ATermList;
+    use merc_aterm::ATermString;
+
+    use crate::DATA_SYMBOLS;
+
+    use super::*;
+
+    /// Returns a function symbol with the given name and sort.
+    fn function_symbol(name: &str, sort: ATerm) -> DataFunctionSymbol {
+        DATA_SYMBOLS.with_borrow(|ds| {
+            let args: &[ATerm] = &[ATermString::new(name).into(), sort];
+            ATerm::with_args(&*ds.data_function_symbol, args).protect().into()
+        })
+    }
+
+    #[test]
+    fn test_constructors_of_enumerated_sort() {
+        // sort Colour = struct red | green | blue;
+        //      Bit = struct zero | one;
+        // map  next: Colour -> Colour;
+        //      first: Colour;
+        //      to_bit: Colour -> Bit;
+        let colour = SortExpression::new("Colour");
+        let bit = SortExpression::new("Bit");
+        let arrow = |domain: &SortExpression, codomain: &SortExpression| {
+            ATerm::with_args(
+                &Symbol::new("SortArrow", 2),
+                &[
+                    ATerm::from(ATermList::from_double_iter([domain.protect()].into_iter())),
+                    codomain.protect(),
+                ],
+            )
+            .protect()
+        };
+
+        let spec = DataSpecification::new(
+            vec![
+                function_symbol("red", colour.protect()),
+                function_symbol("zero", bit.protect()),
+                function_symbol("green", colour.protect()),
+                function_symbol("one", bit.protect()),
+                function_symbol("blue", colour.protect()),
+            ],
+            vec![
+                function_symbol("next", arrow(&colour, &colour)),
+                function_symbol("first", colour.protect()),
+                function_symbol("to_bit", arrow(&colour, &bit)),
+            ],
+        );
+
+        let names = |expressions: Vec<DataExpression>| {
+            expressions
+                .iter()
+                .map(|expression| expression.data_function_symbol().name().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(spec.constructors_of(&colour)), vec!["red", "green", "blue"]);
+        assert_eq!(names(spec.constructors_of(&bit)), vec!["zero", "one"]);
+        assert_eq!(names(spec.mappings_of(&colour)), vec!["next", "first"]);
+        assert_eq!(names(spec.mappings_of(&bit)), vec!["to_bit"]);
+        assert!(spec.constructors_of(&SortExpression::new("Nat")).is_empty());
     }
 }
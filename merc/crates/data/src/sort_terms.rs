@@ -7,6 +7,7 @@ use merc_aterm::ATerm;
 use merc_aterm::ATermArgs;
 use merc_aterm::ATermIndex;
 use merc_aterm::ATermRef;
+use merc_aterm::Castable;
 use merc_aterm::Markable;
 use merc_aterm::Symb;
 use merc_aterm::SymbolRef;
@@ -15,6 +16,7 @@ use merc_aterm::TermIterator;
 use merc_aterm::Transmutable;
 use merc_aterm::storage::Marker;
 use merc_macros::merc_derive_terms;
+use merc_macros::merc_ignore;
 use merc_macros::merc_term;
 
 use crate::DATA_SYMBOLS;
@@ -33,6 +35,14 @@ mod inner {
     }
 
     impl SortExpression {
+        /// Creates a sort expression that refers to the sort with the given name.
+        #[merc_ignore]
+        pub fn new(name: impl Into<String> + AsRef<str>) -> SortExpression {
+            DATA_SYMBOLS.with_borrow(|ds| SortExpression {
+                term: ATerm::with_args(ds.sort_id_symbol.deref(), &[ATermString::new(name)]).protect(),
+            })
+        }
+
         /// Returns the name of the sort.
         pub fn name(&self) -> &str {
             self.term.arg(0).get_head_symbol().name()
@@ -9,6 +9,7 @@ use bitstream_io::BitWrite;
 use bitstream_io::BitWriter;
 use log::error;
 
+use merc_number::encoded_len;
 use merc_number::read_u64_variablelength;
 use merc_number::write_u64_variablelength;
 use merc_utilities::MercError;
@@ -49,6 +50,7 @@ pub trait BitStreamRead {
 /// Writer for bit-level output operations using an underlying writer.
 pub struct BitStreamWriter<W: Write> {
     writer: BitWriter<W, BigEndian>,
+    bits_written: u64,
 }
 
 impl<W: Write> BitStreamWriter<W> {
@@ -56,8 +58,15 @@ impl<W: Write> BitStreamWriter<W> {
     pub fn new(writer: W) -> Self {
         Self {
             writer: BitWriter::new(writer),
+            bits_written: 0,
         }
     }
+
+    /// Returns the number of bits written so far, excluding the padding that is
+    /// added by [BitStreamWrite::flush] to align the stream to a byte.
+    pub fn bits_written(&self) -> u64 {
+        self.bits_written
+    }
 }
 
 impl<W: Write> Drop for BitStreamWriter<W> {
@@ -72,6 +81,12 @@ impl<W: Write> Drop for BitStreamWriter<W> {
 pub struct BitStreamReader<R: Read> {
     reader: BitReader<R, BigEndian>,
     text_buffer: Vec<u8>,
+
+    /// The bits that have been peeked, but not yet read, stored in the least
+    /// significant bits where the first bit of the stream is the most
+    /// significant one.
+    peeked: u64,
+    number_of_peeked_bits: u8,
 }
 
 impl<R: Read> BitStreamReader<R> {
@@ -80,14 +95,62 @@ impl<R: Read> BitStreamReader<R> {
         Self {
             reader: BitReader::new(reader),
             text_buffer: Vec::with_capacity(128),
+            peeked: 0,
+            number_of_peeked_bits: 0,
         }
     }
+
+    /// Returns the next bits in the least significant bits of a u64, like
+    /// [BitStreamRead::read_bits], but without consuming them. The peeked bits
+    /// are returned again by the subsequent reads.
+    ///
+    /// # Preconditions
+    /// - number_of_bits must be <= 64
+    pub fn peek_bits(&mut self, number_of_bits: u8) -> Result<u64, MercError> {
+        assert!(number_of_bits <= 64);
+
+        if number_of_bits > self.number_of_peeked_bits {
+            // Extend the peeked bits with the missing bits from the stream.
+            let missing = number_of_bits - self.number_of_peeked_bits;
+            let value: u64 = self.reader.read_var(missing as u32)?;
+            self.peeked = shift_left(self.peeked, missing) | value;
+            self.number_of_peeked_bits = number_of_bits;
+        }
+
+        Ok(shift_right(self.peeked, self.number_of_peeked_bits - number_of_bits))
+    }
+
+    /// Reads a single byte, taking the peeked bits into account.
+    fn read_byte(&mut self) -> Result<u8, MercError> {
+        if self.number_of_peeked_bits == 0 {
+            Ok(self.reader.read::<8, u8>()?)
+        } else {
+            Ok(self.read_bits(8)? as u8)
+        }
+    }
+}
+
+/// Returns the value shifted left by the given amount, which is zero when all bits are shifted out.
+fn shift_left(value: u64, amount: u8) -> u64 {
+    value.checked_shl(amount as u32).unwrap_or(0)
+}
+
+/// Returns the value shifted right by the given amount, which is zero when all bits are shifted out.
+fn shift_right(value: u64, amount: u8) -> u64 {
+    value.checked_shr(amount as u32).unwrap_or(0)
+}
+
+/// Returns the value where only the given number of least significant bits are kept.
+fn keep_bits(value: u64, number_of_bits: u8) -> u64 {
+    value & shift_left(1, number_of_bits).wrapping_sub(1)
 }
 
 impl<W: Write> BitStreamWrite for BitStreamWriter<W> {
     fn write_bits(&mut self, value: u64, number_of_bits: u8) -> Result<(), MercError> {
         debug_assert!(number_of_bits <= 64);
-        Ok(self.writer.write_var(number_of_bits as u32, value)?)
+        self.writer.write_var(number_of_bits as u32, value)?;
+        self.bits_written += number_of_bits as u64;
+        Ok(())
     }
 
     fn write_string(&mut self, s: &str) -> Result<(), MercError> {
@@ -95,11 +158,13 @@ impl<W: Write> BitStreamWrite for BitStreamWriter<W> {
         for byte in s.as_bytes() {
             self.writer.write::<8, u64>(*byte as u64)?;
         }
+        self.bits_written += 8 * s.len() as u64;
         Ok(())
     }
 
     fn write_integer(&mut self, value: u64) -> Result<(), MercError> {
         write_u64_variablelength(&mut self.writer, value)?;
+        self.bits_written += 8 * encoded_len(value) as u64;
         Ok(())
     }
 
@@ -112,7 +177,26 @@ impl<W: Write> BitStreamWrite for BitStreamWriter<W> {
 impl<R: Read> BitStreamRead for BitStreamReader<R> {
     fn read_bits(&mut self, number_of_bits: u8) -> Result<u64, MercError> {
         assert!(number_of_bits <= 64);
-        Ok(self.reader.read_var(number_of_bits as u32)?)
+
+        if self.number_of_peeked_bits == 0 {
+            return Ok(self.reader.read_var(number_of_bits as u32)?);
+        }
+
+        if number_of_bits <= self.number_of_peeked_bits {
+            // Take the first bits from the peeked bits.
+            self.number_of_peeked_bits -= number_of_bits;
+            let value = shift_right(self.peeked, self.number_of_peeked_bits);
+            self.peeked = keep_bits(self.peeked, self.number_of_peeked_bits);
+            Ok(value)
+        } else {
+            // Take all peeked bits, followed by the remaining bits from the stream.
+            let remaining = number_of_bits - self.number_of_peeked_bits;
+            let value: u64 = self.reader.read_var(remaining as u32)?;
+            let result = shift_left(self.peeked, remaining) | value;
+            self.peeked = 0;
+            self.number_of_peeked_bits = 0;
+            Ok(result)
+        }
     }
 
     fn read_string(&mut self) -> Result<String, MercError> {
@@ -122,7 +206,7 @@ impl<R: Read> BitStreamRead for BitStreamReader<R> {
             .reserve(length.try_into().expect("String size exceeds usize!"));
 
         for _ in 0..length {
-            let byte = self.reader.read::<8, u8>()?;
+            let byte = self.read_byte()?;
             self.text_buffer.push(byte);
         }
 
@@ -130,7 +214,22 @@ impl<R: Read> BitStreamRead for BitStreamReader<R> {
     }
 
     fn read_integer(&mut self) -> Result<u64, MercError> {
-        read_u64_variablelength(&mut self.reader)
+        if self.number_of_peeked_bits == 0 {
+            return read_u64_variablelength(&mut self.reader);
+        }
+
+        // The same decoding as read_u64_variablelength, but taking the peeked bits into account.
+        let mut value: u64 = 0;
+        for i in 0..u64::BITS.div_ceil(7) {
+            let byte = self.read_byte()?;
+            value |= ((byte & 0b01111111) as u64) << (7 * i);
+
+            if byte & 0b10000000 == 0 {
+                break;
+            }
+        }
+
+        Ok(value)
     }
 }
 
@@ -232,4 +331,78 @@ mod tests {
             }
         });
     }
+
+    #[test]
+    fn test_peek_bits_across_bytes() {
+        let mut buffer = Vec::new();
+        {
+            let mut writer = BitStreamWriter::new(&mut buffer);
+            writer.write_bits(0b101, 3).unwrap();
+            writer.write_bits(0b1100110011, 10).unwrap();
+            writer.write_integer(300).unwrap();
+            writer.write_string("ab").unwrap();
+            assert_eq!(writer.bits_written(), 3 + 10 + 16 + 8 + 16);
+        }
+
+        let mut reader = BitStreamReader::new(&buffer[..]);
+        assert_eq!(reader.peek_bits(2).unwrap(), 0b10);
+        assert_eq!(reader.peek_bits(13).unwrap(), 0b101_1100110011);
+        assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+        assert_eq!(reader.peek_bits(4).unwrap(), 0b1100);
+        assert_eq!(reader.read_bits(10).unwrap(), 0b1100110011);
+
+        // Peek into the encoding of the integer, which must still be read as a whole.
+        assert_eq!(reader.peek_bits(5).unwrap(), 0b10101);
+        assert_eq!(reader.read_integer().unwrap(), 300);
+        assert_eq!(reader.peek_bits(24).unwrap(), 0x026162);
+        assert_eq!(reader.read_string().unwrap(), "ab");
+    }
+
+    #[test]
+    fn test_random_peek_bits() {
+        random_test(100, |rng| {
+            let values: Vec<(u64, u8)> = (0..100)
+                .map(|_| {
+                    let number_of_bits = rng.random_range(1..=64);
+                    let value: u64 = rng.random();
+                    (keep_bits(value, number_of_bits), number_of_bits)
+                })
+                .collect();
+
+            let mut buffer = Vec::new();
+            {
+                let mut writer = BitStreamWriter::new(&mut buffer);
+                for (value, number_of_bits) in &values {
+                    writer.write_bits(*value, *number_of_bits).unwrap();
+                }
+
+                assert_eq!(
+                    writer.bits_written(),
+                    values
+                        .iter()
+                        .map(|(_, number_of_bits)| *number_of_bits as u64)
+                        .sum::<u64>()
+                );
+            }
+
+            let mut reader = BitStreamReader::new(&buffer[..]);
+            for (value, number_of_bits) in &values {
+                // Peek a prefix of the value, possibly multiple times.
+                for _ in 0..rng.random_range(0..3) {
+                    let peek = rng.random_range(0..=*number_of_bits);
+                    assert_eq!(
+                        reader.peek_bits(peek).unwrap(),
+                        shift_right(*value, number_of_bits - peek),
+                        "Failed to peek the bits"
+                    );
+                }
+
+                assert_eq!(
+                    reader.read_bits(*number_of_bits).unwrap(),
+                    *value,
+                    "Failed to read back the bits"
+                );
+            }
+        });
+    }
 }
@@ -0,0 +1,103 @@
+use std::fs::File;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+
+use merc_utilities::MercError;
+
+/// The first two bytes of every gzip compressed stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Opens the given file for reading, where gzip compressed files are
+/// transparently decompressed.
+///
+/// # Details
+///
+/// A file is considered to be compressed when it starts with the gzip magic
+/// bytes, regardless of its extension. Decompression requires the `flate2`
+/// feature, otherwise an error is returned for compressed files.
+pub fn open_decompressed(path: &Path) -> Result<Box<dyn Read>, MercError> {
+    decompress(File::open(path)?)
+}
+
+/// Wraps the given reader in a gzip decoder when its contents start with the
+/// gzip magic bytes, see [open_decompressed].
+pub fn decompress<'a>(reader: impl Read + 'a) -> Result<Box<dyn Read + 'a>, MercError> {
+    let mut reader = BufReader::new(reader);
+    if reader.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        gzip_decoder(reader)
+    } else {
+        Ok(Box::new(reader))
+    }
+}
+
+/// Returns the given path without the `.gz` extension, such that the format of
+/// a compressed file can be inferred from its remaining extension.
+pub fn strip_gzip_extension(path: &Path) -> PathBuf {
+    if path.extension().is_some_and(|extension| extension == "gz") {
+        path.with_extension("")
+    } else {
+        path.to_path_buf()
+    }
+}
+
+#[cfg(feature = "flate2")]
+fn gzip_decoder<'a>(reader: BufReader<impl Read + 'a>) -> Result<Box<dyn Read + 'a>, MercError> {
+    // A gzip file can consist of multiple members, which are concatenated.
+    Ok(Box::new(flate2::bufread::MultiGzDecoder::new(reader)))
+}
+
+#[cfg(not(feature = "flate2"))]
+fn gzip_decoder<'a>(_reader: BufReader<impl Read + 'a>) -> Result<Box<dyn Read + 'a>, MercError> {
+    Err("Reading gzip compressed files requires the 'flate2' feature.".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_gzip_extension() {
+        assert_eq!(
+            strip_gzip_extension(Path::new("dir/model.aut.gz")),
+            Path::new("dir/model.aut")
+        );
+        assert_eq!(
+            strip_gzip_extension(Path::new("dir/model.aut")),
+            Path::new("dir/model.aut")
+        );
+        assert_eq!(strip_gzip_extension(Path::new("model.gz")), Path::new("model"));
+    }
+
+    #[test]
+    fn test_decompress_plain() {
+        let mut contents = String::new();
+        decompress(&b"des (0, 0, 1)"[..])
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(contents, "des (0, 0, 1)");
+    }
+
+    #[cfg(feature = "flate2")]
+    #[test]
+    fn test_decompress_gzip() {
+        use std::io::Write;
+
+        use flate2::Compression;
+        use flate2::write::GzEncoder;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"des (0, 0, 1)").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut contents = String::new();
+        decompress(&compressed[..])
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(contents, "des (0, 0, 1)");
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt;
 
-/// Formats bytes into human-readable format using decimal units (GB, MB, KB, bytes)
+/// Formats bytes into human-readable format using decimal units (GB, MB, KB, bytes), see [ByteFormatter] for binary units.
 pub struct BytesFormatter(pub usize);
 
 impl fmt::Display for BytesFormatter {
@@ -22,13 +22,23 @@ impl fmt::Display for BytesFormatter {
     }
 }
 
+/// Formats the given number with a comma between every group of three digits,
+/// for example `1,234,567`.
 pub struct LargeFormatter<T: ToString>(pub T);
 
+impl LargeFormatter<usize> {
+    /// Returns a formatter that shows the given number with an SI suffix and
+    /// one decimal, for example `1.2M`.
+    pub fn si(value: usize) -> SiFormatter {
+        SiFormatter(value)
+    }
+}
+
 impl<T: ToString> fmt::Display for LargeFormatter<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let num_str = self.0.to_string();
 
-        // Add spaces every three digits from the right
+        // Add commas every three digits from the right
         let len = num_str.len();
         for (i, ch) in num_str.chars().enumerate() {
             if i > 0 && (len - i) % 3 == 0 {
@@ -41,6 +51,51 @@ impl<T: ToString> fmt::Display for LargeFormatter<T> {
     }
 }
 
+/// Formats a number with an SI suffix (k, M, G, T, P) and one decimal, see [LargeFormatter::si].
+pub struct SiFormatter(pub usize);
+
+impl fmt::Display for SiFormatter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match scale(self.0, 1000.0, &["k", "M", "G", "T", "P"]) {
+            Some((value, suffix)) => write!(f, "{value:.1}{suffix}"),
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
+
+/// Formats bytes into human-readable format using binary units (GiB, MiB, KiB, B), for example `3.4 GiB`.
+/// Not to be confused with [BytesFormatter], which uses decimal units.
+pub struct ByteFormatter(pub usize);
+
+impl fmt::Display for ByteFormatter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match scale(self.0, 1024.0, &["KiB", "MiB", "GiB", "TiB", "PiB"]) {
+            Some((value, unit)) => write!(f, "{value:.1} {unit}"),
+            None => write!(f, "{} B", self.0),
+        }
+    }
+}
+
+/// Divides the value by the base until it is smaller than the base, and returns
+/// the result together with the unit, or None when the value is smaller than
+/// the base.
+fn scale(value: usize, base: f64, units: &[&'static str]) -> Option<(f64, &'static str)> {
+    let mut value = value as f64;
+    let mut unit = None;
+
+    for next in units {
+        // Compare the value rounded to one decimal, such that we never show 1000.0k.
+        if (value * 10.0).round() < base * 10.0 {
+            break;
+        }
+
+        value /= base;
+        unit = Some(*next);
+    }
+
+    unit.map(|unit| (value, unit))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -56,4 +111,27 @@ mod tests {
         assert_eq!(format!("{}", LargeFormatter(1234567)), "1,234,567");
         assert_eq!(format!("{}", LargeFormatter(12345678)), "12,345,678");
     }
+
+    #[test]
+    fn test_si_formatter_boundaries() {
+        assert_eq!(format!("{}", LargeFormatter::si(0)), "0");
+        assert_eq!(format!("{}", LargeFormatter::si(999)), "999");
+        assert_eq!(format!("{}", LargeFormatter::si(1000)), "1.0k");
+        assert_eq!(format!("{}", LargeFormatter::si(1023)), "1.0k");
+        assert_eq!(format!("{}", LargeFormatter::si(1024)), "1.0k");
+        assert_eq!(format!("{}", LargeFormatter::si(999_949)), "999.9k");
+        assert_eq!(format!("{}", LargeFormatter::si(999_999)), "1.0M");
+        assert_eq!(format!("{}", LargeFormatter::si(1_234_567)), "1.2M");
+    }
+
+    #[test]
+    fn test_byte_formatter_boundaries() {
+        assert_eq!(format!("{}", ByteFormatter(0)), "0 B");
+        assert_eq!(format!("{}", ByteFormatter(999)), "999 B");
+        assert_eq!(format!("{}", ByteFormatter(1000)), "1000 B");
+        assert_eq!(format!("{}", ByteFormatter(1023)), "1023 B");
+        assert_eq!(format!("{}", ByteFormatter(1024)), "1.0 KiB");
+        assert_eq!(format!("{}", ByteFormatter(1024 * 1024 - 1)), "1.0 MiB");
+        assert_eq!(format!("{}", ByteFormatter(3_650_722_201)), "3.4 GiB");
+    }
 }
@@ -2,12 +2,14 @@
 #![forbid(unsafe_code)]
 
 mod bitstream;
+mod compression;
 mod dumpfiles;
 mod format;
 mod line_iterator;
 mod progress;
 
 pub use bitstream::*;
+pub use compression::*;
 pub use dumpfiles::*;
 pub use format::*;
 pub use line_iterator::*;
@@ -42,3 +42,63 @@ impl<T> TimeProgress<T> {
         }
     }
 }
+
+impl TimeProgress<usize> {
+    /// Create a new time-based progress tracker for a procedure with the given
+    /// total number of steps. The message receives the current step, the total
+    /// and an estimate of the remaining time based on the rate so far.
+    pub fn with_total(
+        total: usize,
+        message: impl Fn(usize, usize, Duration) + 'static,
+        interval_seconds: u64,
+    ) -> TimeProgress<usize> {
+        let start = Instant::now();
+        TimeProgress::new(
+            move |current| message(current, total, estimate_remaining(start.elapsed(), current, total)),
+            interval_seconds,
+        )
+    }
+}
+
+/// Returns the time required to perform the remaining steps at the rate of the
+/// steps performed so far, which is zero when no steps have been performed.
+fn estimate_remaining(elapsed: Duration, current: usize, total: usize) -> Duration {
+    if current == 0 {
+        return Duration::ZERO;
+    }
+
+    let ratio = total.saturating_sub(current) as f64 / current as f64;
+    Duration::try_from_secs_f64(elapsed.as_secs_f64() * ratio).unwrap_or(Duration::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    fn test_estimate_remaining() {
+        assert_eq!(
+            estimate_remaining(Duration::from_secs(10), 25, 100),
+            Duration::from_secs(30)
+        );
+        assert_eq!(estimate_remaining(Duration::from_secs(10), 0, 100), Duration::ZERO);
+        assert_eq!(estimate_remaining(Duration::from_secs(10), 100, 100), Duration::ZERO);
+        assert_eq!(estimate_remaining(Duration::from_secs(10), 200, 100), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_time_progress_with_total() {
+        let reported = Rc::new(Cell::new(None));
+
+        let progress = {
+            let reported = reported.clone();
+            TimeProgress::with_total(10, move |current, total, _eta| reported.set(Some((current, total))), 0)
+        };
+
+        progress.print(4);
+        assert_eq!(reported.get(), Some((4, 10)));
+    }
+}
@@ -11,6 +11,7 @@ use std::cell::RefCell;
 
 use merc_aterm::ATerm;
 use merc_aterm::ATermRead;
+use merc_aterm::ATermWrite;
 use merc_collections::IndexedSet;
 use merc_io::BitStreamRead;
 use merc_io::BitStreamWrite;
@@ -159,6 +160,34 @@ impl<R: BitStreamRead> BinaryLddReader<R> {
     }
 }
 
+impl<W: BitStreamWrite + ATermWrite> ATermWrite for BinaryLddWriter<W> {
+    fn write_aterm(&mut self, term: &ATerm) -> Result<(), MercError> {
+        self.writer.write_aterm(term)
+    }
+
+    fn write_aterm_iter<I>(&mut self, iter: I) -> Result<(), MercError>
+    where
+        I: ExactSizeIterator<Item = ATerm>,
+    {
+        self.writer.write_aterm_iter(iter)
+    }
+
+    fn flush(&mut self) -> Result<(), MercError> {
+        ATermWrite::flush(&mut self.writer)
+    }
+}
+
+impl<W: BitStreamWrite> BitStreamWrite for BinaryLddWriter<W> {
+    delegate::delegate! {
+        to self.writer {
+            fn write_bits(&mut self, value: u64, number_of_bits: u8) -> Result<(), MercError>;
+            fn write_string(&mut self, s: &str) -> Result<(), MercError>;
+            fn write_integer(&mut self, value: u64) -> Result<(), MercError>;
+            fn flush(&mut self) -> Result<(), MercError>;
+        }
+    }
+}
+
 impl<R: BitStreamRead + ATermRead> ATermRead for BinaryLddReader<R> {
     delegate::delegate! {
         to self.reader {
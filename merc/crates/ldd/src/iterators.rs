@@ -1,3 +1,5 @@
+use merc_utilities::MercError;
+
 use crate::Data;
 use crate::Ldd;
 use crate::Storage;
@@ -28,6 +30,27 @@ pub fn iter<'a>(storage: &'a Storage, ldd: &Ldd) -> Iter<'a> {
     }
 }
 
+/// Returns an iterator over all vectors contained in the given LDD, with every
+/// value converted to a `u64`, e.g., to write the vectors as rows of a table.
+///
+/// The vectors of a valid LDD all have the same length, but whenever a vector
+/// has a different length than the first vector an error is yielded for it.
+pub fn elements<'a>(storage: &'a Storage, ldd: &Ldd) -> impl Iterator<Item = Result<Vec<u64>, MercError>> + 'a {
+    let mut arity = None;
+    iter(storage, ldd).map(move |vector| {
+        let expected = *arity.get_or_insert(vector.len());
+        if vector.len() != expected {
+            return Err(format!(
+                "Vector of length {} differs from the length {expected} of the other vectors",
+                vector.len()
+            )
+            .into());
+        }
+
+        Ok(vector.into_iter().map(u64::from).collect())
+    })
+}
+
 // Returns an iterator over all nodes in the given LDD. Visits each node only if the predicate holds.
 pub fn iter_nodes<'a, P>(storage: &'a Storage, ldd: &Ldd, filter: P) -> IterNode<'a, P>
 where
@@ -155,6 +178,7 @@ mod tests {
 
     use merc_utilities::random_test;
 
+    use crate::len;
     use crate::test_utility::from_iter;
     use crate::test_utility::random_vector_set;
 
@@ -178,4 +202,20 @@ mod tests {
             }
         })
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_random_elements() {
+        random_test(100, |rng| {
+            let mut storage = Storage::new();
+
+            let set = random_vector_set(rng, 32, 10, 10);
+            let ldd = from_iter(&mut storage, set.iter());
+            let expected = len(&mut storage, &ldd);
+
+            let vectors: Vec<Vec<u64>> = elements(&storage, &ldd).collect::<Result<_, _>>().unwrap();
+            assert_eq!(vectors.len(), expected);
+            assert!(vectors.iter().all(|vector| vector.len() == 10));
+        })
+    }
 }
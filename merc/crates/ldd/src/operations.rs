@@ -263,6 +263,9 @@ pub fn relational_product(storage: &mut Storage, set: &LddRef, rel: &LddRef, met
 }
 
 /// Returns the largest subset of 'a' that does not contains elements of 'b', i.e., set difference.
+///
+/// The result is memoized in the operation cache of the storage.
+#[doc(alias = "difference")]
 pub fn minus(storage: &mut Storage, a: &LddRef, b: &LddRef) -> Ldd {
     if a == b || a == storage.empty_set() {
         storage.empty_set().clone()
@@ -606,6 +609,42 @@ mod tests {
         });
     }
 
+    // Compare the minus function against the HashSet difference for every vector of a small universe.
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_random_minus_brute_force() {
+        random_test(100, |rng| {
+            let mut storage = Storage::new();
+
+            // All vectors of length three with values 0..4.
+            let universe: Vec<Vec<Value>> = (0..64).map(|i| vec![i / 16, (i / 4) % 4, i % 4]).collect();
+
+            let set_a: HashSet<Vec<Value>> = universe.iter().filter(|_| rng.random_bool(0.5)).cloned().collect();
+            let set_b: HashSet<Vec<Value>> = universe.iter().filter(|_| rng.random_bool(0.5)).cloned().collect();
+
+            let a = from_iter(&mut storage, set_a.iter());
+            let b = from_iter(&mut storage, set_b.iter());
+            let result = minus(&mut storage, &a, &b);
+
+            for vector in &universe {
+                assert_eq!(
+                    element_of(&storage, vector, &result),
+                    set_a.contains(vector) && !set_b.contains(vector),
+                    "The difference does not match for {vector:?}"
+                );
+            }
+
+            let elements: HashSet<Vec<Value>> = iter(&storage, &result).collect();
+            assert_eq!(elements, set_a.sub(&set_b));
+
+            // The difference with itself or the empty set.
+            let empty = storage.empty_set().clone();
+            assert_eq!(minus(&mut storage, &a, &a), empty);
+            assert_eq!(minus(&mut storage, &a, &empty), a);
+            assert_eq!(minus(&mut storage, &empty, &b), empty);
+        });
+    }
+
     // Test the relational product function with read-only inputs.
     #[test]
     #[cfg_attr(miri, ignore)]
@@ -114,6 +114,12 @@ impl Storage {
         }
     }
 
+    /// Returns the number of nodes in the node table, including the nodes that
+    /// are no longer reachable but have not been garbage collected yet.
+    pub fn num_of_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
     /// Provides access to the underlying operation cache.
     pub fn operation_cache(&mut self) -> &mut OperationCache {
         &mut self.cache
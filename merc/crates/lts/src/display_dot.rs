@@ -0,0 +1,127 @@
+#![forbid(unsafe_code)]
+
+use std::fmt;
+
+use crate::LTS;
+
+/// Display implementation to output a labelled transition system in Graphviz
+/// DOT format.
+///
+/// # Details
+///
+/// The initial state is drawn as a double circle and transitions labelled with
+/// a hidden label are drawn as dashed edges. Optionally, the number of states
+/// can be capped by [LtsDot::with_max_states], in which case only the states
+/// (and transitions between them) with an index below the cap are written.
+pub struct LtsDot<'a, L: LTS> {
+    pub lts: &'a L,
+
+    /// The maximum number of states to write, if any.
+    pub max_states: Option<usize>,
+}
+
+impl<'a, L: LTS> LtsDot<'a, L> {
+    /// Creates a new LtsDot Display for the given labelled transition system.
+    pub fn new(lts: &'a L) -> Self {
+        Self { lts, max_states: None }
+    }
+
+    /// Only writes the first `max_states` states of the labelled transition
+    /// system, other states and transitions to them are omitted.
+    pub fn with_max_states(mut self, max_states: usize) -> Self {
+        self.max_states = Some(max_states);
+        self
+    }
+}
+
+impl<L: LTS> fmt::Display for LtsDot<'_, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "digraph lts {{")?;
+
+        let num_of_states = self.lts.num_of_states();
+        let shown_states = self.max_states.map_or(num_of_states, |max| max.min(num_of_states));
+        if shown_states < num_of_states {
+            writeln!(
+                f,
+                "  // Warning: truncated to the first {shown_states} of {num_of_states} states."
+            )?;
+        }
+
+        writeln!(f, "  rankdir=LR;")?;
+        writeln!(f, "  graph [fontname=\"DejaVu Sans\", splines=true];")?;
+        writeln!(f, "  node [fontname=\"DejaVu Sans\", shape=circle];")?;
+        writeln!(
+            f,
+            "  edge [fontname=\"DejaVu Sans\", color=\"#444444\", arrowsize=0.9, penwidth=1.2];"
+        )?;
+
+        let initial = self.lts.initial_state_index();
+        for state_index in self.lts.iter_states().take(shown_states) {
+            if state_index == initial {
+                writeln!(f, "  s{state_index} [label=\"{state_index}\", shape=doublecircle];")?;
+            } else {
+                writeln!(f, "  s{state_index} [label=\"{state_index}\"];")?;
+            }
+        }
+
+        for state_index in self.lts.iter_states().take(shown_states) {
+            for transition in self.lts.outgoing_transitions(state_index) {
+                if transition.to.value() >= shown_states {
+                    continue;
+                }
+
+                let label = escape_label(&self.lts.labels()[transition.label.value()].to_string());
+                if self.lts.is_hidden_label(transition.label) {
+                    writeln!(
+                        f,
+                        "  s{} -> s{} [label=\"{}\", style=dashed];",
+                        state_index, transition.to, label
+                    )?;
+                } else {
+                    writeln!(f, "  s{} -> s{} [label=\"{}\"];", state_index, transition.to, label)?;
+                }
+            }
+        }
+
+        if initial.value() < shown_states {
+            writeln!(f, "  init [shape=point, width=0.05, label=\"\"];")?;
+            writeln!(f, "  init -> s{initial} [arrowsize=0.6];")?;
+        }
+
+        writeln!(f, "}}")
+    }
+}
+
+/// Escapes the characters that have a special meaning inside a quoted DOT string.
+fn escape_label(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::read_aut;
+
+    #[test]
+    fn test_lts_dot() {
+        let lts = read_aut(
+            b"des (0, 3, 3)
+            (0, \"a\", 1)
+            (1, \"i\", 2)
+            (2, \"b\"c\", 0)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+
+        let output = LtsDot::new(&lts).to_string();
+        assert!(output.contains("s0 [label=\"0\", shape=doublecircle];"));
+        assert!(output.contains("s0 -> s1 [label=\"a\"];"));
+        assert!(output.contains("s1 -> s2 [label=\"i\", style=dashed];"));
+        assert!(output.contains("s2 -> s0 [label=\"b\\\"c\"];"));
+
+        let truncated = LtsDot::new(&lts).with_max_states(2).to_string();
+        assert!(truncated.contains("truncated to the first 2 of 3 states"));
+        assert!(!truncated.contains("s2"));
+    }
+}
@@ -1,5 +1,8 @@
 #![forbid(unsafe_code)]
 
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
 use merc_collections::ByteCompressedVec;
 use merc_collections::bytevec;
 
@@ -13,6 +16,9 @@ pub struct IncomingTransitions {
     transition_labels: ByteCompressedVec<LabelIndex>,
     transition_from: ByteCompressedVec<StateIndex>,
     state2incoming: ByteCompressedVec<usize>,
+
+    /// Maps (state, action) to the sources of the incoming transitions with that action, only computed on first use.
+    predecessors: OnceLock<HashMap<(StateIndex, LabelIndex), Vec<StateIndex>>>,
 }
 
 impl IncomingTransitions {
@@ -78,6 +84,7 @@ impl IncomingTransitions {
             transition_labels,
             transition_from,
             state2incoming,
+            predecessors: OnceLock::new(),
         }
     }
 
@@ -96,6 +103,38 @@ impl IncomingTransitions {
             .map(move |i| Transition::new(self.transition_labels.index(i), self.transition_from.index(i)))
             .take_while(|transition| transition.label == 0)
     }
+
+    /// Returns an iterator over the source states of the incoming transitions
+    /// of the given state that are labelled with the given action.
+    ///
+    /// # Details
+    ///
+    /// The first call builds an auxiliary `(state, action) -> sources` map,
+    /// such that its memory is only used when this function is used.
+    pub fn predecessors_by_action(
+        &self,
+        state_index: StateIndex,
+        action: LabelIndex,
+    ) -> impl Iterator<Item = StateIndex> + '_ {
+        let predecessors = self.predecessors.get_or_init(|| {
+            let mut predecessors: HashMap<(StateIndex, LabelIndex), Vec<StateIndex>> = HashMap::new();
+            for state in 0..self.state2incoming.len() - 1 {
+                let state = StateIndex::new(state);
+                for transition in self.incoming_transitions(state) {
+                    predecessors
+                        .entry((state, transition.label))
+                        .or_default()
+                        .push(transition.to);
+                }
+            }
+            predecessors
+        });
+
+        predecessors
+            .get(&(state_index, action))
+            .into_iter()
+            .flat_map(|sources| sources.iter().copied())
+    }
 }
 
 #[cfg(test)]
@@ -141,6 +180,21 @@ mod tests {
                     );
                 }
             }
+
+            // Check that the predecessors by action are exactly the filtered incoming transitions.
+            for state_index in lts.iter_states() {
+                for label_index in 0..lts.num_of_labels() {
+                    let label_index = LabelIndex::new(label_index);
+                    let expected: Vec<StateIndex> = incoming
+                        .incoming_transitions(state_index)
+                        .filter(|transition| transition.label == label_index)
+                        .map(|transition| transition.to)
+                        .collect();
+                    let predecessors: Vec<StateIndex> =
+                        incoming.predecessors_by_action(state_index, label_index).collect();
+                    assert_eq!(predecessors, expected);
+                }
+            }
         });
     }
 }
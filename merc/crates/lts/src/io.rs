@@ -1,9 +1,11 @@
 #![forbid(unsafe_code)]
 
 use std::ffi::OsStr;
-use std::fs::File;
 use std::path::Path;
 
+use merc_io::open_decompressed;
+use merc_io::strip_gzip_extension;
+use merc_utilities::ErrorContext;
 use merc_utilities::MercError;
 use merc_utilities::Timing;
 
@@ -56,20 +58,28 @@ pub enum LtsFormat {
     Lts,
     /// The CADP BCG format (requires 'cadp' feature)
     Bcg,
+    /// The GraphML format, only supported for writing
+    #[cfg_attr(feature = "clap", value(name = "graphml"))]
+    GraphML,
 }
 
-/// Guesses the LTS file format from the file extension.
+/// Guesses the LTS file format from the file extension, ignoring the `.gz`
+/// extension of compressed files.
 pub fn guess_lts_format_from_extension(path: &Path, format: Option<LtsFormat>) -> Option<LtsFormat> {
     if let Some(format) = format {
         return Some(format);
     }
 
+    let path = strip_gzip_extension(path);
+
     if path.extension() == Some(OsStr::new("aut")) {
         Some(LtsFormat::Aut)
     } else if path.extension() == Some(OsStr::new("lts")) {
         Some(LtsFormat::Lts)
     } else if path.extension() == Some(OsStr::new("bcg")) {
         Some(LtsFormat::Bcg)
+    } else if path.extension() == Some(OsStr::new("graphml")) {
+        Some(LtsFormat::GraphML)
     } else {
         None
     }
@@ -139,6 +149,7 @@ impl GenericLts {
 }
 
 /// Reads an explicit labelled transition system from the given path and format.
+/// Files in the textual and binary formats can be gzip compressed, see [open_decompressed].
 pub fn read_explicit_lts(
     path: &Path,
     format: LtsFormat,
@@ -147,16 +158,18 @@ pub fn read_explicit_lts(
 ) -> Result<GenericLts, MercError> {
     let mut time_read = timing.start("read_explicit_lts");
 
+    let context = || format!("Could not read the LTS from \"{}\"", path.display());
     let result = match format {
         LtsFormat::Aut => {
-            let file = File::open(path)?;
-            GenericLts::Aut(read_aut(&file, hidden_labels)?)
+            let file = open_decompressed(path).with_context(context)?;
+            GenericLts::Aut(read_aut(file, hidden_labels).with_context(context)?)
         }
         LtsFormat::Lts => {
-            let file = File::open(path)?;
-            GenericLts::Lts(read_lts(&file, hidden_labels)?)
+            let file = open_decompressed(path).with_context(context)?;
+            GenericLts::Lts(read_lts(file, hidden_labels).with_context(context)?)
         }
-        LtsFormat::Bcg => GenericLts::Bcg(read_bcg(path, hidden_labels)?),
+        LtsFormat::Bcg => GenericLts::Bcg(read_bcg(path, hidden_labels).with_context(context)?),
+        LtsFormat::GraphML => return Err("Reading LTSs in the GraphML format is not supported.".into()),
     };
 
     time_read.finish();
@@ -1,10 +1,12 @@
 #![forbid(unsafe_code)]
 
+use std::io::BufRead;
 use std::io::BufWriter;
 use std::io::Read;
 use std::io::Write;
 
 use log::info;
+use log::warn;
 use merc_io::LargeFormatter;
 use regex::Regex;
 use streaming_iterator::StreamingIterator;
@@ -28,6 +30,9 @@ pub enum IOError {
 
     #[error("Invalid transition {0}")]
     InvalidTransition(String),
+
+    #[error("The header declares {0} transitions, but {1} transitions were read")]
+    InvalidNumberOfTransitions(usize, usize),
 }
 
 /// Loads a labelled transition system in the [Aldebaran
@@ -41,6 +46,11 @@ pub enum IOError {
 /// And one line for every transition either one of these cases:
 ///  `(<from>: Nat, "<label>": Str, <to>: Nat)`
 ///  `(<from>: Nat, <label>: Str, <to>: Nat)`
+///
+/// The counts in the header are only used as a hint to allocate memory, and a
+/// warning is logged when they disagree with the transitions that were read.
+/// When the header is absent the initial state is assumed to be zero. Blank
+/// lines are skipped.
 pub fn read_aut(reader: impl Read, hidden_labels: Vec<String>) -> Result<LabelledTransitionSystem<String>, MercError> {
     info!("Reading LTS in .aut format...");
 
@@ -50,51 +60,208 @@ pub fn read_aut(reader: impl Read, hidden_labels: Vec<String>) -> Result<Labelle
         .get()
         .ok_or(IOError::InvalidHeader("The first line should be the header"))?;
 
-    // Regex for des (<initial>: Nat, <num_of_states>: Nat, <num_of_transitions>: Nat)
-    let header_regex = Regex::new(r#"des\s*\(\s*([0-9]*)\s*,\s*([0-9]*)\s*,\s*([0-9]*)\s*\)\s*"#)
-        .expect("Regex compilation should not fail");
-
-    let (_, [initial_txt, num_of_transitions_txt, num_of_states_txt]) = header_regex
-        .captures(header)
-        .ok_or(IOError::InvalidHeader(
-            "does not match des (<init>, <num_of_transitions>, <num_of_states>)",
-        ))?
-        .extract();
+    // When the header is absent the first line is already a transition, which is read below.
+    let (initial_state, num_of_transitions, num_of_states, has_header) = match read_header(header) {
+        Ok((initial_state, num_of_transitions, num_of_states)) => {
+            (initial_state, num_of_transitions, num_of_states, true)
+        }
+        Err(error) => {
+            if read_transition(header).is_none() {
+                return Err(error);
+            }
 
-    let initial_state = StateIndex::new(initial_txt.parse()?);
-    let num_of_transitions: usize = num_of_transitions_txt.parse()?;
-    let num_of_states: usize = num_of_states_txt.parse()?;
+            warn!("The .aut header is missing, assuming that the initial state is 0");
+            (StateIndex::new(0), 0, 0, false)
+        }
+    };
 
-    let mut builder = LtsBuilder::with_capacity(Vec::new(), hidden_labels, num_of_states, 16, num_of_transitions);
+    let mut builder = LtsBuilder::with_capacity(Vec::new(), hidden_labels, 16, num_of_states, num_of_transitions);
     let progress = TimeProgress::new(
         move |read: usize| {
             info!(
                 "Read {} transitions {}%...",
                 LargeFormatter(read),
-                read * 100 / num_of_transitions
+                read * 100 / num_of_transitions.max(1)
             )
         },
         1,
     );
 
+    if !has_header {
+        add_transition(&mut builder, header)?;
+    }
+
     while let Some(line) = lines.next() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        add_transition(&mut builder, line)?;
+        progress.print(builder.num_of_transitions());
+    }
+
+    if has_header && builder.num_of_transitions() != num_of_transitions {
+        warn!(
+            "{}",
+            IOError::InvalidNumberOfTransitions(num_of_transitions, builder.num_of_transitions())
+        );
+    }
+
+    info!("Finished reading LTS");
+
+    Ok(builder.finish(initial_state))
+}
+
+/// Parses the given line as a transition and adds it to the builder.
+fn add_transition(builder: &mut LtsBuilder<String>, line: &str) -> Result<(), MercError> {
+    let (from_txt, label_txt, to_txt) =
+        read_transition(line).ok_or_else(|| IOError::InvalidTransition(line.to_string()))?;
+
+    // Parse the from and to states, with the given label.
+    let from = StateIndex::new(from_txt.parse()?);
+    let to = StateIndex::new(to_txt.parse()?);
+
+    debug_trace!("Read transition {from} --[{label_txt}]-> {to}");
+
+    builder.add_transition(from, label_txt, to);
+    Ok(())
+}
+
+/// A streaming reader for labelled transition systems in the [Aldebaran
+/// format](https://cadp.inria.fr/man/aldebaran.html), see [read_aut].
+///
+/// # Details
+///
+/// The header is parsed upon construction, after which the transitions are
+/// yielded lazily as `(from, label, to)` tuples, without storing them. Like
+/// [read_aut], a warning is logged when the number of transitions read
+/// disagrees with the header at the end of the input, and a missing header is
+/// tolerated.
+pub struct AutTransitionReader<R: BufRead> {
+    reader: R,
+    line: String,
+
+    /// Set when `line` contains a transition that has not been yielded yet,
+    /// which happens when the header is absent.
+    pending: bool,
+
+    /// Whether the input starts with a header.
+    has_header: bool,
+
+    initial_state: StateIndex,
+    num_of_transitions: usize,
+    num_of_states: usize,
+
+    /// The number of transitions read so far.
+    transitions_read: usize,
+
+    /// Set when the end of the input, or an error, has been encountered.
+    finished: bool,
+}
+
+impl<R: BufRead> AutTransitionReader<R> {
+    /// Creates a new reader, and immediately parses the `des (...)` header.
+    pub fn new(mut reader: R) -> Result<Self, MercError> {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(IOError::InvalidHeader("The first line should be the header").into());
+        }
+
+        // When the header is absent the first line is already a transition.
+        let (initial_state, num_of_transitions, num_of_states, has_header) = match read_header(&line) {
+            Ok((initial_state, num_of_transitions, num_of_states)) => {
+                (initial_state, num_of_transitions, num_of_states, true)
+            }
+            Err(error) => {
+                if read_transition(&line).is_none() {
+                    return Err(error);
+                }
+
+                warn!("The .aut header is missing, assuming that the initial state is 0");
+                (StateIndex::new(0), 0, 0, false)
+            }
+        };
+
+        Ok(Self {
+            reader,
+            line,
+            pending: !has_header,
+            has_header,
+            initial_state,
+            num_of_transitions,
+            num_of_states,
+            transitions_read: 0,
+            finished: false,
+        })
+    }
+
+    /// Returns the initial state declared in the header.
+    pub fn initial_state(&self) -> StateIndex {
+        self.initial_state
+    }
+
+    /// Returns the number of transitions declared in the header.
+    pub fn num_of_transitions(&self) -> usize {
+        self.num_of_transitions
+    }
+
+    /// Returns the number of states declared in the header.
+    pub fn num_of_states(&self) -> usize {
+        self.num_of_states
+    }
+
+    /// Reads the next transition, returns `None` at the end of the input.
+    fn read_next(&mut self) -> Result<Option<(StateIndex, String, StateIndex)>, MercError> {
+        if !self.pending {
+            // Skip blank lines.
+            loop {
+                self.line.clear();
+                if self.reader.read_line(&mut self.line)? == 0 {
+                    if self.has_header && self.transitions_read != self.num_of_transitions {
+                        warn!(
+                            "{}",
+                            IOError::InvalidNumberOfTransitions(self.num_of_transitions, self.transitions_read)
+                        );
+                    }
+
+                    return Ok(None);
+                }
+
+                if !self.line.trim().is_empty() {
+                    break;
+                }
+            }
+        }
+        self.pending = false;
+
+        let line = self.line.trim_end_matches(['\n', '\r']);
         let (from_txt, label_txt, to_txt) =
-            read_transition(line).ok_or_else(|| IOError::InvalidTransition(line.clone()))?;
+            read_transition(line).ok_or_else(|| IOError::InvalidTransition(line.to_string()))?;
 
-        // Parse the from and to states, with the given label.
         let from = StateIndex::new(from_txt.parse()?);
         let to = StateIndex::new(to_txt.parse()?);
 
-        debug_trace!("Read transition {from} --[{label_txt}]-> {to}");
+        self.transitions_read += 1;
+        Ok(Some((from, label_txt.to_string(), to)))
+    }
+}
 
-        builder.add_transition(from, label_txt, to);
+impl<R: BufRead> Iterator for AutTransitionReader<R> {
+    type Item = Result<(StateIndex, String, StateIndex), MercError>;
 
-        progress.print(builder.num_of_transitions());
-    }
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
 
-    info!("Finished reading LTS");
+        let result = self.read_next().transpose();
+        if !matches!(result, Some(Ok(_))) {
+            // Stop after the end of the input or the first error.
+            self.finished = true;
+        }
 
-    Ok(builder.finish(initial_state))
+        result
+    }
 }
 
 /// Write a labelled transition system in plain text in Aldebaran format to the
@@ -144,6 +311,27 @@ pub fn write_aut(writer: &mut impl Write, lts: &impl LTS) -> Result<(), MercErro
     Ok(())
 }
 
+/// Parses the header `des (<initial>: Nat, <num_of_transitions>: Nat,
+/// <num_of_states>: Nat)` and returns these three values.
+fn read_header(header: &str) -> Result<(StateIndex, usize, usize), MercError> {
+    // Regex for des (<initial>: Nat, <num_of_states>: Nat, <num_of_transitions>: Nat)
+    let header_regex = Regex::new(r#"des\s*\(\s*([0-9]*)\s*,\s*([0-9]*)\s*,\s*([0-9]*)\s*\)\s*"#)
+        .expect("Regex compilation should not fail");
+
+    let (_, [initial_txt, num_of_transitions_txt, num_of_states_txt]) = header_regex
+        .captures(header)
+        .ok_or(IOError::InvalidHeader(
+            "does not match des (<init>, <num_of_transitions>, <num_of_states>)",
+        ))?
+        .extract();
+
+    Ok((
+        StateIndex::new(initial_txt.parse()?),
+        num_of_transitions_txt.parse()?,
+        num_of_states_txt.parse()?,
+    ))
+}
+
 /// Dedicated function to parse the following transition formats:
 ///
 /// # Details
@@ -257,6 +445,83 @@ mod tests {
         assert!(lts.num_of_transitions() == lts_original.num_of_transitions());
     }
 
+    #[test]
+    fn test_streaming_aut() {
+        let file = include_str!("../../../examples/lts/abp.aut");
+
+        let reader = AutTransitionReader::new(file.as_bytes()).unwrap();
+        assert_eq!(reader.initial_state().value(), 0);
+        assert_eq!(reader.num_of_transitions(), 92);
+
+        let transitions: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(transitions.len(), 92);
+    }
+
+    #[test]
+    fn test_streaming_aut_wrong_count() {
+        let wrong_count = "des (0,3,3)
+            (0,\"a\",1)
+            (1,\"b\",2)";
+
+        // Like read_aut, a wrong count only results in a warning.
+        let reader = AutTransitionReader::new(wrong_count.as_bytes()).unwrap();
+        let transitions: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(transitions.len(), 2);
+    }
+
+    #[test]
+    fn test_streaming_aut_without_header() {
+        let no_header = "(0,\"a\",1)
+
+            (1,\"b\",2)
+            ";
+
+        let reader = AutTransitionReader::new(no_header.as_bytes()).unwrap();
+        assert_eq!(reader.initial_state().value(), 0);
+
+        let transitions: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(
+            transitions,
+            vec![
+                (StateIndex::new(0), "a".to_string(), StateIndex::new(1)),
+                (StateIndex::new(1), "b".to_string(), StateIndex::new(2))
+            ]
+        );
+        assert_eq!(
+            read_aut(no_header.as_bytes(), vec![]).unwrap().num_of_transitions(),
+            transitions.len()
+        );
+    }
+
+    #[test]
+    fn test_reading_aut_wrong_count() {
+        // The header undercounts both the transitions and the states.
+        let wrong_count = "des (0,1,2)
+            (0,\"a\",1)
+            (1,\"b\",2)
+            (2,\"c\",0)";
+
+        let lts = read_aut(wrong_count.as_bytes(), vec![]).unwrap();
+        assert_eq!(lts.num_of_transitions(), 3);
+        assert_eq!(lts.num_of_states(), 3);
+
+        // The header overcounts, or declares no transitions at all.
+        for header in ["des (0,300,3)", "des (0,0,0)"] {
+            let lts = read_aut(format!("{header}\n(0,\"a\",1)\n(1,\"b\",2)").as_bytes(), vec![]).unwrap();
+            assert_eq!(lts.num_of_transitions(), 2);
+        }
+    }
+
+    #[test]
+    fn test_reading_aut_without_header() {
+        let no_header = "(0,\"a\",1)
+            (1,\"b\",2)";
+
+        let lts = read_aut(no_header.as_bytes(), vec![]).unwrap();
+        assert_eq!(lts.initial_state_index().value(), 0);
+        assert_eq!(lts.num_of_transitions(), 2);
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn test_random_aut_io() {
@@ -5,6 +5,7 @@
 #![allow(non_snake_case)]
 #![allow(unused)]
 
+use std::borrow::Cow;
 use std::path::Path;
 
 use merc_utilities::MercError;
@@ -22,7 +23,11 @@ mod inner {
     }
 
     /// This is a stub implementation used when BCG support is not compiled in.
-    pub fn write_bcg(_lts: &impl LTS, _path: &Path) -> Result<(), MercError> {
+    pub fn write_bcg<L: LTS>(
+        _lts: &L,
+        _path: &Path,
+        _label_namer: impl Fn(&L::Label) -> Cow<'_, str>,
+    ) -> Result<(), MercError> {
         Err("BCG format support not compiled in, see the 'cadp' feature.".into())
     }
 }
@@ -163,11 +168,14 @@ mod inner {
     ///
     /// # Details
     ///
-    /// We require the label to be convertible into a `String`.
-    pub fn write_bcg<L: LTS>(lts: &L, path: &Path) -> Result<(), MercError>
-    where
-        String: From<L::Label>,
-    {
+    /// The `label_namer` determines the string that is written for every
+    /// label. It is applied once per label, which avoids constructing a
+    /// relabelled copy of the LTS for labels that are not strings.
+    pub fn write_bcg<L: LTS>(
+        lts: &L,
+        path: &Path,
+        label_namer: impl Fn(&L::Label) -> Cow<'_, str>,
+    ) -> Result<(), MercError> {
         initialize_bcg()?;
         info!("Writing LTS in BCG format...");
 
@@ -212,7 +220,7 @@ mod inner {
         let labels = lts
             .labels()
             .iter()
-            .map(|label| CString::new::<String>(label.clone().into()))
+            .map(|label| CString::new(label_namer(label).into_owned()))
             .collect::<Result<Vec<_>, _>>()?;
 
         let mut number_of_transitions = 0;
@@ -449,7 +457,7 @@ mod inner {
                 let tmp = temp_dir();
 
                 let file = tmp.join("test_random_bcg_io.bcg");
-                write_bcg(&lts, &file).unwrap();   
+                write_bcg(&lts, &file, |label| label.into()).unwrap();
 
                 let result_lts = read_bcg(&file, Vec::new()).unwrap();
 
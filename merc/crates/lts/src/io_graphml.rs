@@ -0,0 +1,113 @@
+#![forbid(unsafe_code)]
+
+use std::io::BufWriter;
+use std::io::Write;
+
+use html_escape::encode_quoted_attribute;
+use log::info;
+
+use merc_io::LargeFormatter;
+use merc_io::TimeProgress;
+use merc_utilities::MercError;
+
+use crate::LTS;
+
+/// Writes a labelled transition system in the
+/// [GraphML](http://graphml.graphdrawing.org/) format to the given writer.
+///
+/// # Details
+///
+/// Every state is written as a `<node>` and every transition as an `<edge>`
+/// with a `<data>` element carrying the (escaped) action label. The initial
+/// state is marked by the `initial` node attribute.
+///
+/// Note that the writer is buffered internally using a `BufWriter`.
+pub fn write_graphml(writer: &mut impl Write, lts: &impl LTS) -> Result<(), MercError> {
+    info!("Writing LTS in .graphml format...");
+
+    let mut writer = BufWriter::new(writer);
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+    writeln!(
+        writer,
+        r#"  <key id="initial" for="node" attr.name="initial" attr.type="boolean"><default>false</default></key>"#
+    )?;
+    writeln!(
+        writer,
+        r#"  <key id="label" for="edge" attr.name="label" attr.type="string"/>"#
+    )?;
+    writeln!(writer, r#"  <graph id="lts" edgedefault="directed">"#)?;
+
+    for state_index in lts.iter_states() {
+        if state_index == lts.initial_state_index() {
+            writeln!(
+                writer,
+                r#"    <node id="s{state_index}"><data key="initial">true</data></node>"#
+            )?;
+        } else {
+            writeln!(writer, r#"    <node id="s{state_index}"/>"#)?;
+        }
+    }
+
+    let num_of_transitions = lts.num_of_transitions();
+    let progress = TimeProgress::new(
+        move |written: usize| {
+            info!(
+                "Wrote {} transitions {}%...",
+                LargeFormatter(written),
+                written * 100 / num_of_transitions
+            )
+        },
+        1,
+    );
+    let mut transitions_written = 0usize;
+    for state_index in lts.iter_states() {
+        for transition in lts.outgoing_transitions(state_index) {
+            let label = lts.labels()[transition.label.value()].to_string();
+            writeln!(
+                writer,
+                r#"    <edge id="e{}" source="s{}" target="s{}"><data key="label">{}</data></edge>"#,
+                transitions_written,
+                state_index,
+                transition.to,
+                encode_quoted_attribute(&label)
+            )?;
+
+            progress.print(transitions_written);
+            transitions_written += 1;
+        }
+    }
+
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</graphml>")?;
+
+    info!("Finished writing LTS.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::read_aut;
+
+    #[test]
+    fn test_writing_graphml() {
+        let lts = read_aut(
+            b"des (0, 2, 2)
+            (0, \"a&b\", 1)
+            (1, \"<c>'d'\", 0)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+
+        let mut buffer: Vec<u8> = Vec::new();
+        write_graphml(&mut buffer, &lts).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.contains(r#"<node id="s0"><data key="initial">true</data></node>"#));
+        assert!(output.contains(r#"<node id="s1"/>"#));
+        assert!(output.contains(r#"source="s0" target="s1"><data key="label">a&amp;b</data>"#));
+        assert!(output.contains(r#"source="s1" target="s0"><data key="label">&lt;c&gt;&#x27;d&#x27;</data>"#));
+    }
+}
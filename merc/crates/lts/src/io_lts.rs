@@ -1,6 +1,7 @@
 #![forbid(unsafe_code)]
 
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::io::BufReader;
 use std::io::BufWriter;
 use std::io::Read;
@@ -29,6 +30,10 @@ use crate::MultiAction;
 use crate::StateIndex;
 
 /// Loads a labelled transition system from the binary 'lts' format of the mCRL2 toolset.
+///
+/// The actions in `hidden_labels` are removed from every multi-action, see
+/// [MultiAction::hide], where a hidden label of the shape `a|b` hides both `a`
+/// and `b`.
 pub fn read_lts(
     reader: impl Read,
     hidden_labels: Vec<String>,
@@ -46,6 +51,13 @@ pub fn read_lts(
     let _parameters = reader.read_aterm()?;
     let _actions = reader.read_aterm()?;
 
+    // The individual actions that must be hidden.
+    let hidden: HashSet<String> = hidden_labels
+        .iter()
+        .flat_map(|label| label.split('|'))
+        .map(|action| action.trim().to_string())
+        .collect();
+
     // Use a cache to avoid translating the same multi-action multiple times.
     let mut multi_actions: HashMap<ATerm, MultiAction> = HashMap::new();
 
@@ -78,7 +90,7 @@ pub fn read_lts(
                         );
                     } else {
                         // New multi-action found, add it to the builder.
-                        let multi_action = MultiAction::from_mcrl2_aterm(label.clone())?;
+                        let multi_action = MultiAction::from_mcrl2_aterm(label.clone())?.hide(&hidden);
                         multi_actions.insert(label.clone(), multi_action.clone());
                         builder.add_transition(
                             StateIndex::new(from.value()),
@@ -226,6 +238,7 @@ fn probabilistic_transition_mark() -> ATerm {
 mod tests {
     use super::*;
 
+    use itertools::Itertools;
     use merc_utilities::random_test;
 
     use crate::LTS;
@@ -241,6 +254,29 @@ mod tests {
         assert_eq!(*lts.initial_state_index(), 0);
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)] // Tests are too slow under miri.
+    fn test_read_lts_hidden() {
+        let input = include_bytes!("../../../examples/lts/abp.lts").as_ref();
+        let lts = read_lts(input, vec![]).unwrap();
+
+        // Hide all the actions, as composite multi-action labels.
+        let hidden: Vec<String> = lts.labels()[1..]
+            .iter()
+            .map(|label| {
+                label
+                    .to_string()
+                    .split('|')
+                    .map(|action| action.split('(').next().unwrap().to_string())
+                    .join("|")
+            })
+            .collect();
+
+        let hidden_lts = read_lts(input, hidden).unwrap();
+        assert_eq!(hidden_lts.num_of_states(), lts.num_of_states());
+        assert_eq!(hidden_lts.num_of_labels(), 1, "All actions should be hidden");
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn test_random_lts_io() {
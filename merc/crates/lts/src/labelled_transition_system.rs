@@ -9,6 +9,7 @@ use merc_collections::CompressedVecMetrics;
 use merc_collections::bytevec;
 use merc_io::LargeFormatter;
 use merc_utilities::TagIndex;
+use regex::Regex;
 
 use crate::LTS;
 use crate::LabelIndex;
@@ -300,6 +301,46 @@ impl<Label: TransitionLabel> LabelledTransitionSystem<Label> {
         }
     }
 
+    /// Renames the transition labels using regular expressions, where the
+    /// first rule `(pattern, replacement)` whose pattern matches the label is
+    /// applied. The replacement can refer to capture groups, e.g., `$1`.
+    ///
+    /// Labels that become equal are merged, and labels renamed to the tau
+    /// label become hidden. The tau label itself is never renamed.
+    pub fn relabel_regex(&self, rules: &[(Regex, String)]) -> LabelledTransitionSystem<String> {
+        let mut labels = vec![String::tau_label()];
+        let mut labels_index: HashMap<String, LabelIndex> = HashMap::new();
+        labels_index.insert(String::tau_label(), LabelIndex::new(0));
+
+        // Maps every old label index to the new label index.
+        let mapping: Vec<LabelIndex> = self
+            .labels
+            .iter()
+            .enumerate()
+            .map(|(index, label)| {
+                if index == 0 {
+                    return LabelIndex::new(0);
+                }
+
+                let label = label.to_string();
+                let renamed = match rules.iter().find(|(pattern, _)| pattern.is_match(&label)) {
+                    Some((pattern, replacement)) => pattern.replace(&label, replacement.as_str()).into_owned(),
+                    None => label,
+                };
+
+                *labels_index.entry(renamed).or_insert_with_key(|renamed| {
+                    labels.push(renamed.clone());
+                    LabelIndex::new(labels.len() - 1)
+                })
+            })
+            .collect();
+
+        LabelledTransitionSystem::with_successors(self.initial_state, self.num_of_states(), labels, |state_index| {
+            self.outgoing_transitions(state_index)
+                .map(|transition| (mapping[transition.label], transition.to))
+        })
+    }
+
     /// Returns metrics about the LTS.
     pub fn metrics(&self) -> LtsMetrics {
         LtsMetrics {
@@ -434,8 +475,11 @@ pub fn check_equivalent<L: LTS>(lts: &L, lts_read: &L) {
 mod tests {
     use merc_io::DumpFiles;
     use merc_utilities::random_test;
+    use regex::Regex;
 
+    use crate::LTS;
     use crate::random_lts;
+    use crate::read_aut;
     use crate::write_aut;
 
     #[test]
@@ -455,4 +499,35 @@ mod tests {
             files.dump("merged.aut", |f| write_aut(f, &merged)).unwrap();
         })
     }
+
+    #[test]
+    fn test_relabel_regex() {
+        let lts = read_aut(
+            b"des (0, 4, 3)
+            (0, \"send(1)\", 1)
+            (1, \"send(2)\", 2)
+            (2, \"internal\", 0)
+            (2, \"i\", 1)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+
+        let rules = vec![
+            (Regex::new(r"^send\((.*)\)$").unwrap(), "out_$1".to_string()),
+            (Regex::new(r"^send").unwrap(), "unused".to_string()),
+            (Regex::new(r"^internal$").unwrap(), "i".to_string()),
+        ];
+
+        let result = lts.relabel_regex(&rules);
+        assert_eq!(result.num_of_states(), lts.num_of_states());
+        assert_eq!(result.num_of_transitions(), lts.num_of_transitions());
+        assert_eq!(result.labels(), &["i", "out_1", "out_2"]);
+
+        // Both transitions from state 2 are now hidden.
+        assert!(
+            result
+                .outgoing_transitions(crate::StateIndex::new(2))
+                .all(|transition| result.is_hidden_label(transition.label))
+        );
+    }
 }
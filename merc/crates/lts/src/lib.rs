@@ -1,9 +1,11 @@
 #![doc = include_str!("../README.md")]
 
+mod display_dot;
 mod incoming_transitions;
 mod io;
 mod io_aut;
 mod io_bcg;
+mod io_graphml;
 mod io_lts;
 mod labelled_transition_system;
 mod lts;
@@ -12,11 +14,14 @@ mod lts_builder_fast;
 mod multi_action;
 mod product_lts;
 mod random_lts;
+mod shortest_trace;
 
+pub use display_dot::*;
 pub use incoming_transitions::*;
 pub use io::*;
 pub use io_aut::*;
 pub use io_bcg::*;
+pub use io_graphml::*;
 pub use io_lts::*;
 pub use labelled_transition_system::*;
 pub use lts::*;
@@ -25,3 +30,4 @@ pub use lts_builder_fast::*;
 pub use multi_action::*;
 pub use product_lts::*;
 pub use random_lts::*;
+pub use shortest_trace::*;
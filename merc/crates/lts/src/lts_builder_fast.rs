@@ -71,21 +71,73 @@ impl<L: TransitionLabel> LtsBuilderFast<L> {
         L: Borrow<Q>,
         Q: ToOwned<Owned = L> + Eq + Hash,
     {
-        let label_index = if let Some(&index) = self.labels_index.get(label) {
-            index
-        } else {
-            let index = LabelIndex::new(self.labels.len());
-            self.labels_index.insert(label.to_owned(), index);
-            self.labels.push(label.to_owned());
-            index
-        };
-
+        let label_index = self.label_index(label);
         self.transitions.push((from, label_index, to));
 
         // Update the number of states.
         self.num_of_states = self.num_of_states.max(from.value() + 1).max(to.value() + 1);
     }
 
+    /// Splits the builder into `num_of_shards` builders with the same labels,
+    /// where the first shard keeps the transitions added so far. Transitions can
+    /// be added to every shard independently, for example by different threads,
+    /// after which [LtsBuilderFast::combine] assembles the shards again.
+    pub fn shard(self, num_of_shards: usize) -> Vec<LtsBuilderFast<L>> {
+        assert!(num_of_shards > 0, "At least one shard is required");
+
+        let mut shards: Vec<LtsBuilderFast<L>> = (1..num_of_shards)
+            .map(|_| LtsBuilderFast {
+                transitions: Vec::new(),
+                labels_index: self.labels_index.clone(),
+                labels: self.labels.clone(),
+                num_of_states: 0,
+            })
+            .collect();
+
+        shards.insert(0, self);
+        shards
+    }
+
+    /// Combines the given shards, obtained from [LtsBuilderFast::shard], into a
+    /// single builder that contains the transitions of all shards in order.
+    ///
+    /// # Details
+    ///
+    /// Every shard can introduce new labels, so the label indices of the
+    /// transitions of later shards are renumbered to the indices of the first
+    /// shard, where new labels are introduced in the order of the shards.
+    pub fn combine(shards: Vec<LtsBuilderFast<L>>) -> Self {
+        let mut shards = shards.into_iter();
+        let mut result = shards.next().expect("At least one shard is required");
+
+        for shard in shards {
+            // Maps the position of every label in the shard to its index in the result.
+            let label_mapping: Vec<LabelIndex> = shard
+                .labels
+                .iter()
+                .enumerate()
+                .map(|(position, label)| {
+                    if shard.labels_index[label] == LabelIndex::new(position) {
+                        result.label_index(label)
+                    } else {
+                        // Hidden labels are mapped to tau.
+                        LabelIndex::new(0)
+                    }
+                })
+                .collect();
+
+            result.transitions.extend(
+                shard
+                    .transitions
+                    .into_iter()
+                    .map(|(from, label, to)| (from, label_mapping[label], to)),
+            );
+            result.num_of_states = result.num_of_states.max(shard.num_of_states);
+        }
+
+        result
+    }
+
     /// Finalizes the builder and returns the constructed labelled transition system.
     pub fn finish(&mut self, initial_state: StateIndex, remove_duplicates: bool) -> LabelledTransitionSystem<L> {
         if remove_duplicates {
@@ -118,6 +170,22 @@ impl<L: TransitionLabel> LtsBuilderFast<L> {
         }
     }
 
+    /// Returns the index of the given label, which is introduced when it is not yet known.
+    fn label_index<Q>(&mut self, label: &Q) -> LabelIndex
+    where
+        L: Borrow<Q>,
+        Q: ToOwned<Owned = L> + Eq + Hash,
+    {
+        if let Some(&index) = self.labels_index.get(label) {
+            index
+        } else {
+            let index = LabelIndex::new(self.labels.len());
+            self.labels_index.insert(label.to_owned(), index);
+            self.labels.push(label.to_owned());
+            index
+        }
+    }
+
     /// Removes duplicated transitions from the added transitions.
     fn remove_duplicates(&mut self) {
         self.transitions.sort();
@@ -144,11 +212,16 @@ impl<Label: TransitionLabel> fmt::Debug for LtsBuilderFast<Label> {
 mod tests {
     use super::*;
 
+    use std::thread;
+
     use itertools::Itertools;
     use rand::Rng;
+    use rand::seq::SliceRandom;
 
     use merc_utilities::random_test;
 
+    use crate::LTS;
+
     #[test]
     fn test_random_remove_duplicates() {
         random_test(100, |rng| {
@@ -171,4 +244,65 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_random_shard() {
+        random_test(100, |rng| {
+            // The labels d and e are only introduced by the transitions.
+            let labels = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+            let all_labels = ["a", "b", "c", "d", "e"].map(String::from);
+
+            let mut transitions: Vec<(StateIndex, &String, StateIndex)> = (0..rng.random_range(1..20))
+                .map(|_| {
+                    (
+                        StateIndex::new(rng.random_range(0..10)),
+                        &all_labels[rng.random_range(0..all_labels.len())],
+                        StateIndex::new(rng.random_range(0..10)),
+                    )
+                })
+                .collect();
+            transitions.sort();
+
+            let mut builder = LtsBuilderFast::new(labels.clone(), vec!["b".to_string()]);
+            for (from, label, to) in &transitions {
+                builder.add_transition(*from, *label, *to);
+            }
+
+            // Every shard adds the transitions of a disjoint range of source
+            // states, in a random order such that the shards introduce the
+            // labels in a different order than the sequential build.
+            let num_of_shards = rng.random_range(1..4);
+            let shard_transitions: Vec<Vec<_>> = (0..num_of_shards)
+                .map(|index| {
+                    let mut shard_transitions: Vec<_> = transitions
+                        .iter()
+                        .filter(|(from, _, _)| from.value() * num_of_shards / 10 == index)
+                        .collect();
+                    shard_transitions.shuffle(rng);
+                    shard_transitions
+                })
+                .collect();
+
+            let mut shards = LtsBuilderFast::new(labels, vec!["b".to_string()]).shard(num_of_shards);
+            thread::scope(|s| {
+                for (shard, transitions) in shards.iter_mut().zip(&shard_transitions) {
+                    s.spawn(move || {
+                        for (from, label, to) in transitions {
+                            shard.add_transition(*from, *label, *to);
+                        }
+                    });
+                }
+            });
+
+            // The labels can be introduced in a different order, so compare modulo a renaming of the labels.
+            let combined = LtsBuilderFast::combine(shards).finish(StateIndex::new(0), true);
+            let sequential = builder.finish(StateIndex::new(0), true);
+            assert_eq!(
+                combined.labels().iter().sorted().collect::<Vec<_>>(),
+                sequential.labels().iter().sorted().collect::<Vec<_>>()
+            );
+            crate::check_equivalent(&combined, &sequential);
+        });
+    }
 }
@@ -1,5 +1,7 @@
 #![forbid(unsafe_code)]
 
+use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::fmt;
 use std::hash::Hash;
 
@@ -12,6 +14,7 @@ use merc_aterm::ATermIndex;
 use merc_aterm::ATermList;
 use merc_aterm::ATermRef;
 use merc_aterm::ATermString;
+use merc_aterm::Castable;
 use merc_aterm::Markable;
 use merc_aterm::Symb;
 use merc_aterm::SymbolRef;
@@ -19,7 +22,6 @@ use merc_aterm::Term;
 use merc_aterm::TermIterator;
 use merc_aterm::Transmutable;
 use merc_aterm::storage::Marker;
-use merc_collections::VecSet;
 use merc_data::DataExpression;
 use merc_data::DataVariable;
 use merc_data::DataVariableRef;
@@ -31,16 +33,17 @@ use merc_utilities::MercError;
 
 use crate::TransitionLabel;
 
-/// Represents a multi-action, i.e., a set of action labels
+/// Represents a multi-action, i.e., a multiset of action labels
 #[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
 pub struct MultiAction {
-    actions: VecSet<Action>,
+    /// The actions, which are kept sorted such that equal multisets are equal vectors.
+    actions: Vec<Action>,
 }
 
 impl MultiAction {
     /// Parses a multi-action from a string representation, typically found in the Aldebaran format.
     pub fn from_string(input: &str) -> Result<Self, MercError> {
-        let mut actions = VecSet::new();
+        let mut actions = Vec::new();
 
         for part in input.split('|') {
             let part = part.trim();
@@ -61,22 +64,56 @@ impl MultiAction {
                     .split(',')
                     .map(|s| (s.trim().to_string(), undefined_sort.name().to_string()))
                     .collect();
-                actions.insert(Action {
+                actions.push(Action {
                     label: label.to_string(),
                     arguments,
                 });
             } else {
                 let label = part.trim();
-                actions.insert(Action {
+                actions.push(Action {
                     label: label.to_string(),
                     arguments: Vec::new(),
                 });
             }
         }
 
+        actions.sort();
         Ok(MultiAction { actions })
     }
 
+    /// Returns true iff every action occurs at most as often in this
+    /// multi-action as it occurs in the `other` multi-action.
+    pub fn is_submultiset_of(&self, other: &MultiAction) -> bool {
+        // Both actions are sorted, so we can match them in a single pass.
+        let mut other_actions = other.actions.iter();
+        'outer: for action in &self.actions {
+            for other_action in other_actions.by_ref() {
+                match other_action.cmp(action) {
+                    Ordering::Less => continue,
+                    Ordering::Equal => continue 'outer,
+                    Ordering::Greater => return false,
+                }
+            }
+
+            return false;
+        }
+
+        true
+    }
+
+    /// Removes all the actions with a label in `hidden`, every occurrence is
+    /// removed. The result is the tau multi-action when all actions are hidden.
+    pub fn hide(&self, hidden: &HashSet<String>) -> MultiAction {
+        MultiAction {
+            actions: self
+                .actions
+                .iter()
+                .filter(|action| !hidden.contains(&action.label))
+                .cloned()
+                .collect(),
+        }
+    }
+
     /// Converts the MultiAction into its mCRL2 ATerm representation.
     pub fn to_mcrl2_aterm(&self) -> Result<ATerm, MercError> {
         let action_terms: Vec<MCRL2Action> = self
@@ -118,7 +155,7 @@ impl MultiAction {
                 return Err("Timed multi-actions are not supported.".into());
             }
 
-            let mut actions = VecSet::new();
+            let mut actions = Vec::new();
             for action in multi_action.actions() {
                 let arguments = action
                     .arguments()
@@ -126,12 +163,13 @@ impl MultiAction {
                     .map(|arg| (arg.to_string(), arg.data_sort().to_string()))
                     .collect();
 
-                actions.insert(Action {
+                actions.push(Action {
                     label: action.label().name().to_string(),
                     arguments,
                 });
             }
 
+            actions.sort();
             Ok(MultiAction { actions })
         } else {
             Err(format!("Expected TimedMultAction symbol, got {}.", term).into())
@@ -273,7 +311,7 @@ impl TransitionLabel for MultiAction {
     }
 
     fn tau_label() -> Self {
-        MultiAction { actions: VecSet::new() }
+        MultiAction { actions: Vec::new() }
     }
 
     fn matches_label(&self, label: &str) -> bool {
@@ -284,12 +322,12 @@ impl TransitionLabel for MultiAction {
     fn from_index(i: usize) -> Self {
         // For now we only generate single actions, but these could become multiactions as well
         MultiAction {
-            actions: VecSet::singleton(Action::new(
+            actions: vec![Action::new(
                 char::from_digit(i as u32, 36)
                     .expect("Radix is less than 37, so should not panic")
                     .to_string(),
                 Vec::new(),
-            )),
+            )],
         }
     }
 }
@@ -324,9 +362,12 @@ impl fmt::Debug for MultiAction {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use merc_data::SortExpression;
 
     use crate::MultiAction;
+    use crate::TransitionLabel;
 
     #[test]
     fn test_multi_action_parse_string() {
@@ -354,4 +395,33 @@ mod tests {
                 .any(|act| act.label == "c" && act.arguments.is_empty())
         );
     }
+
+    #[test]
+    fn test_multi_action_submultiset() {
+        let a = MultiAction::from_string("a").unwrap();
+        let aa = MultiAction::from_string("a | a").unwrap();
+        let ab = MultiAction::from_string("b | a").unwrap();
+        let aab = MultiAction::from_string("a | b | a").unwrap();
+
+        assert_eq!(aa.actions.len(), 2, "Repeated actions should be kept");
+        assert!(a.is_submultiset_of(&aa));
+        assert!(aa.is_submultiset_of(&aab));
+        assert!(ab.is_submultiset_of(&aab));
+        assert!(!aa.is_submultiset_of(&ab));
+        assert!(!aab.is_submultiset_of(&aa));
+        assert!(MultiAction::tau_label().is_submultiset_of(&a));
+    }
+
+    #[test]
+    fn test_multi_action_hide() {
+        let action = MultiAction::from_string("a | b(1) | a | c").unwrap();
+        let hidden: HashSet<String> = ["a".to_string(), "c".to_string()].into_iter().collect();
+
+        assert_eq!(action.hide(&hidden), MultiAction::from_string("b(1)").unwrap());
+
+        let hidden: HashSet<String> = ["a".to_string(), "b".to_string(), "c".to_string()]
+            .into_iter()
+            .collect();
+        assert!(action.hide(&hidden).is_tau_label());
+    }
 }
@@ -5,6 +5,7 @@ use log::trace;
 use merc_collections::IndexedSet;
 
 use crate::LTS;
+use crate::LabelIndex;
 use crate::LabelledTransitionSystem;
 use crate::LtsBuilderFast;
 use crate::StateIndex;
@@ -129,9 +130,100 @@ pub fn product_lts<L: LTS, R: LTS<Label = L::Label>>(
     lts_builder.finish(StateIndex::new(0), true)
 }
 
+/// Computes the parallel composition of two given LTSs, similar to the `||`
+/// operator of mCRL2 combined with a communication function.
+///
+/// # Details
+///
+/// Every action of either LTS interleaves freely. Furthermore, for every
+/// triple `(a, b, c)` in `sync` the transitions `left -[a]-> left'` and `right
+/// -[b]-> right'` synchronise into `(left, right) -[c]-> (left', right')`. Tau
+/// can never be synchronised, so triples involving tau are ignored. Only the
+/// reachable pairs `(left, right)` are constructed and the initial state of the
+/// result is the pair of both initial states.
+pub fn parallel_compose<L: LTS, R: LTS<Label = L::Label>>(
+    left: &L,
+    right: &R,
+    sync: &[(L::Label, L::Label, L::Label)],
+) -> LabelledTransitionSystem<L::Label> {
+    // Resolve the synchronising labels to label indices of both LTSs, labels that do not occur can never synchronise.
+    let mut synchronisations: Vec<(LabelIndex, LabelIndex, &L::Label)> = Vec::new();
+    for (left_label, right_label, result) in sync {
+        if left_label.is_tau_label() || right_label.is_tau_label() {
+            continue;
+        }
+
+        let left_index = left.labels().iter().position(|label| label == left_label);
+        let right_index = right.labels().iter().position(|label| label == right_label);
+        if let (Some(left_index), Some(right_index)) = (left_index, right_index) {
+            synchronisations.push((LabelIndex::new(left_index), LabelIndex::new(right_index), result));
+        }
+    }
+
+    let mut all_labels: Vec<L::Label> = left.labels().to_vec();
+    all_labels.extend(right.labels().iter().cloned());
+    all_labels.extend(synchronisations.iter().map(|(_, _, result)| (*result).clone()));
+    let mut lts_builder = LtsBuilderFast::new(all_labels, Vec::new());
+
+    let mut discovered_states: IndexedSet<(StateIndex, StateIndex)> = IndexedSet::new();
+    let mut working = vec![(left.initial_state_index(), right.initial_state_index())];
+    let (_, _) = discovered_states.insert((left.initial_state_index(), right.initial_state_index()));
+
+    while let Some((left_state, right_state)) = working.pop() {
+        let (product_index, inserted) = discovered_states.insert((left_state, right_state));
+        debug_assert!(!inserted, "The product state must have already been added");
+
+        let mut add_transition = |label: &L::Label, to: (StateIndex, StateIndex)| {
+            let (to_index, inserted) = discovered_states.insert(to);
+            lts_builder.add_transition(StateIndex::new(*product_index), label, StateIndex::new(*to_index));
+
+            if inserted {
+                trace!("Adding ({}, {})", to.0, to.1);
+                working.push(to);
+            }
+        };
+
+        // (left, right) -[a]-> (left', right) iff left -[a]-> left'.
+        for left_transition in left.outgoing_transitions(left_state) {
+            add_transition(&left.labels()[left_transition.label], (left_transition.to, right_state));
+        }
+
+        // (left, right) -[b]-> (left, right') iff right -[b]-> right'.
+        for right_transition in right.outgoing_transitions(right_state) {
+            add_transition(
+                &right.labels()[right_transition.label],
+                (left_state, right_transition.to),
+            );
+        }
+
+        // (left, right) -[c]-> (left', right') iff left -[a]-> left', right -[b]-> right' and (a, b, c) is synchronised.
+        for (left_label, right_label, result) in &synchronisations {
+            for left_transition in left.outgoing_transitions(left_state) {
+                if left_transition.label != *left_label {
+                    continue;
+                }
+
+                for right_transition in right.outgoing_transitions(right_state) {
+                    if right_transition.label == *right_label {
+                        add_transition(result, (left_transition.to, right_transition.to));
+                    }
+                }
+            }
+        }
+    }
+
+    if lts_builder.num_of_states() == 0 {
+        // Neither LTS has a transition, but an LTS requires at least one state (the initial state).
+        lts_builder.require_num_of_states(1);
+    }
+
+    lts_builder.finish(StateIndex::new(0), true)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::random_lts;
+    use crate::read_aut;
     use crate::write_aut;
 
     use super::*;
@@ -158,4 +250,40 @@ mod tests {
             files.dump("product.aut", |f| write_aut(f, &product)).unwrap();
         });
     }
+
+    #[test]
+    fn test_parallel_compose() {
+        let left = read_aut(
+            b"des (0, 3, 3)
+            (0, \"a\", 1)
+            (1, \"b\", 2)
+            (2, \"i\", 0)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+        let right = read_aut(
+            b"des (0, 1, 2)
+            (0, \"c\", 1)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+
+        let result = parallel_compose(&left, &right, &[("b".to_string(), "c".to_string(), "d".to_string())]);
+
+        // All six pairs are reachable, with 6 interleaved left, 3 interleaved right and 1 synchronised transition.
+        assert_eq!(result.num_of_states(), 6);
+        assert_eq!(result.num_of_transitions(), 10);
+
+        // The initial state is the pair of initial states, and after a the synchronisation d is enabled.
+        let after_a = result
+            .outgoing_transitions(result.initial_state_index())
+            .find(|transition| result.labels()[transition.label] == "a")
+            .unwrap()
+            .to;
+        assert!(
+            result
+                .outgoing_transitions(after_a)
+                .any(|transition| result.labels()[transition.label] == "d")
+        );
+    }
 }
@@ -1,6 +1,8 @@
 #![forbid(unsafe_code)]
 
 use rand::Rng;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 
 use crate::LabelledTransitionSystem;
 use crate::LtsBuilderFast;
@@ -83,12 +85,119 @@ pub fn random_lts_monolithic<L: TransitionLabel>(
     builder.finish(StateIndex::new(0), true)
 }
 
+/// The distribution of the number of outgoing transitions of every state, see
+/// [RandomLtsConfig].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutDegree {
+    /// Uniformly distributed between zero and the given maximum (inclusive).
+    Uniform(usize),
+
+    /// Geometrically distributed with the given mean, which must be finite and positive.
+    Geometric(f64),
+}
+
+impl OutDegree {
+    /// Samples an out degree from this distribution.
+    fn sample(&self, rng: &mut impl Rng) -> usize {
+        match *self {
+            OutDegree::Uniform(max) => rng.random_range(0..=max),
+            OutDegree::Geometric(mean) => {
+                // The number of failures before the first success, with success probability 1 / (mean + 1).
+                let continue_probability = mean / (mean + 1.0);
+                let mut degree = 0;
+                while rng.random_bool(continue_probability) {
+                    degree += 1;
+                }
+
+                degree
+            }
+        }
+    }
+}
+
+/// The parameters of [generate_random_lts].
+#[derive(Clone, Debug)]
+pub struct RandomLtsConfig {
+    /// The number of states of the LTS.
+    pub num_of_states: usize,
+
+    /// The number of visible actions, excluding tau.
+    pub num_of_actions: usize,
+
+    /// The distribution of the number of outgoing transitions per state.
+    pub outdegree: OutDegree,
+
+    /// The probability that a transition is labelled with tau.
+    pub tau_probability: f64,
+
+    /// The seed of the random number generator.
+    pub seed: u64,
+}
+
+impl Default for RandomLtsConfig {
+    fn default() -> Self {
+        Self {
+            num_of_states: 10,
+            num_of_actions: 3,
+            outdegree: OutDegree::Uniform(3),
+            tau_probability: 0.1,
+            seed: 0,
+        }
+    }
+}
+
+/// Generates a monolithic random LTS according to the given configuration,
+/// where the visible actions are named `a0`, `a1`, and so forth.
+///
+/// The same configuration, including the seed, always yields the same LTS.
+pub fn generate_random_lts(config: &RandomLtsConfig) -> LabelledTransitionSystem<String> {
+    assert!(
+        (0.0..=1.0).contains(&config.tau_probability),
+        "The tau probability {} should be between zero and one",
+        config.tau_probability
+    );
+    if let OutDegree::Geometric(mean) = config.outdegree {
+        // An infinite (or NaN) mean would make sampling an out degree never terminate.
+        assert!(
+            mean.is_finite() && mean > 0.0,
+            "The mean out degree {mean} should be finite and positive"
+        );
+    }
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+
+    let mut labels: Vec<String> = vec![String::tau_label()];
+    labels.extend((0..config.num_of_actions).map(|i| format!("a{i}")));
+
+    let num_of_states = config.num_of_states.max(1);
+    let mut builder = LtsBuilderFast::with_capacity(labels.clone(), Vec::new(), num_of_states);
+
+    for state_index in 0..num_of_states {
+        for _ in 0..config.outdegree.sample(&mut rng) {
+            let label = if config.num_of_actions == 0 || rng.random_bool(config.tau_probability) {
+                0
+            } else {
+                rng.random_range(1..=config.num_of_actions)
+            };
+            let to = rng.random_range(0..num_of_states);
+
+            builder.add_transition(StateIndex::new(state_index), &labels[label], StateIndex::new(to));
+        }
+    }
+
+    // Ensure that states without incoming and outgoing transitions exist as well.
+    builder.require_num_of_states(num_of_states);
+    builder.finish(StateIndex::new(0), true)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use test_log::test;
 
+    use crate::LTS;
+
     use merc_utilities::random_test;
 
     #[test]
@@ -98,4 +207,39 @@ mod tests {
             let _lts = random_lts(rng, 10, 3, 3);
         });
     }
+
+    #[test]
+    fn test_generate_random_lts_deterministic() {
+        for outdegree in [OutDegree::Uniform(4), OutDegree::Geometric(2.5)] {
+            let config = RandomLtsConfig {
+                num_of_states: 50,
+                num_of_actions: 4,
+                outdegree,
+                tau_probability: 0.2,
+                seed: 42,
+            };
+
+            let lts = generate_random_lts(&config);
+            assert_eq!(lts.num_of_states(), 50);
+            assert!(
+                lts == generate_random_lts(&config),
+                "The same seed should yield the same LTS"
+            );
+
+            let other = generate_random_lts(&RandomLtsConfig { seed: 43, ..config });
+            assert!(
+                lts != other,
+                "A different seed should (very likely) yield a different LTS"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "should be finite and positive")]
+    fn test_generate_random_lts_infinite_mean() {
+        generate_random_lts(&RandomLtsConfig {
+            outdegree: OutDegree::Geometric(f64::INFINITY),
+            ..RandomLtsConfig::default()
+        });
+    }
 }
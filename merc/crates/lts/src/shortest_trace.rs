@@ -0,0 +1,97 @@
+#![forbid(unsafe_code)]
+
+use std::collections::VecDeque;
+
+use crate::LTS;
+use crate::LabelIndex;
+use crate::StateIndex;
+
+/// Returns a shortest sequence of transitions from the initial state of the
+/// given LTS to the target state, as pairs of the label and the reached state.
+/// Returns None when the target state is not reachable.
+///
+/// # Details
+///
+/// Performs a breadth-first search over the outgoing transitions, and the
+/// trace of the initial state itself is empty.
+pub fn shortest_trace(lts: &impl LTS, target: StateIndex) -> Option<Vec<(LabelIndex, StateIndex)>> {
+    if target.value() >= lts.num_of_states() {
+        return None;
+    }
+
+    // For every discovered state the transition by which it was discovered first.
+    let mut predecessor: Vec<Option<(StateIndex, LabelIndex)>> = vec![None; lts.num_of_states()];
+    let mut discovered = vec![false; lts.num_of_states()];
+
+    let mut queue = VecDeque::new();
+    discovered[lts.initial_state_index()] = true;
+    queue.push_back(lts.initial_state_index());
+
+    while let Some(state_index) = queue.pop_front() {
+        if state_index == target {
+            // Reconstruct the trace backwards from the target.
+            let mut trace = Vec::new();
+            let mut current = target;
+            while let Some((previous, label)) = predecessor[current] {
+                trace.push((label, current));
+                current = previous;
+            }
+
+            trace.reverse();
+            return Some(trace);
+        }
+
+        for transition in lts.outgoing_transitions(state_index) {
+            if !discovered[transition.to] {
+                discovered[transition.to] = true;
+                predecessor[transition.to] = Some((state_index, transition.label));
+                queue.push_back(transition.to);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::read_aut;
+
+    use super::*;
+
+    #[test]
+    fn test_shortest_trace() {
+        let lts = read_aut(
+            b"des (0, 6, 6)
+            (0, \"a\", 1)
+            (1, \"b\", 2)
+            (2, \"c\", 3)
+            (0, \"d\", 4)
+            (4, \"e\", 3)
+            (5, \"f\", 0)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+
+        let labels = |trace: Vec<(LabelIndex, StateIndex)>| {
+            trace
+                .iter()
+                .map(|(label, to)| (lts.labels()[*label].clone(), to.value()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(shortest_trace(&lts, StateIndex::new(0)), Some(vec![]));
+        assert_eq!(
+            shortest_trace(&lts, StateIndex::new(2)).map(labels),
+            Some(vec![("a".to_string(), 1), ("b".to_string(), 2)])
+        );
+        assert_eq!(
+            shortest_trace(&lts, StateIndex::new(3)).map(labels),
+            Some(vec![("d".to_string(), 4), ("e".to_string(), 3)])
+        );
+
+        // State 5 is not reachable, and state 6 does not exist.
+        assert_eq!(shortest_trace(&lts, StateIndex::new(5)), None);
+        assert_eq!(shortest_trace(&lts, StateIndex::new(6)), None);
+    }
+}
@@ -5,7 +5,9 @@ use quote::quote;
 use syn::ItemFn;
 use syn::parse_macro_input;
 
+mod merc_derive_markable;
 mod merc_derive_terms;
+use merc_derive_markable::merc_derive_markable_impl;
 use merc_derive_terms::merc_derive_terms_impl;
 
 /// This proc macro can be used to automatically generate the boilerplate code
@@ -22,7 +24,17 @@ pub fn merc_derive_terms(
     .into()
 }
 
-/// Marks a struct as a term.
+/// Derives `Markable` for a struct by marking each of its fields, which must
+/// all implement `Markable`. Similar to [macro@merc_derive_terms], the names
+/// `Markable`, `Marker`, `ATermRef` and `SymbolRef` must be in scope.
+#[proc_macro_derive(Markable)]
+pub fn merc_derive_markable(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    merc_derive_markable_impl(proc_macro2::TokenStream::from(input)).into()
+}
+
+/// Marks a struct as a term. The optional argument is a recognizer for the
+/// shape of the term, for example `#[merc_term(is_data_expression)]`, which is
+/// required to implement `Castable` for the struct.
 #[proc_macro_attribute]
 pub fn merc_term(_attributes: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     input
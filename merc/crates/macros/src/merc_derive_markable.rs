@@ -0,0 +1,89 @@
+use proc_macro2::TokenStream;
+
+use quote::quote;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Member;
+use syn::parse_quote;
+
+pub(crate) fn merc_derive_markable_impl(input: TokenStream) -> TokenStream {
+    let mut ast: DeriveInput = match syn::parse2(input) {
+        Ok(ast) => ast,
+        Err(error) => return error.to_compile_error(),
+    };
+
+    let Data::Struct(object) = &ast.data else {
+        return syn::Error::new_spanned(&ast.ident, "Markable can only be derived for structs").to_compile_error();
+    };
+
+    // The fields are accessed by name, or by index for tuple structs.
+    let fields: Vec<Member> = object
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(name) => Member::Named(name.clone()),
+            None => Member::Unnamed(index.into()),
+        })
+        .collect();
+
+    // Every type parameter must be markable for its fields to be markable.
+    for parameter in ast.generics.type_params_mut() {
+        parameter.bounds.push(parse_quote!(Markable));
+    }
+
+    let name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = ast.generics.split_for_impl();
+
+    quote!(
+        impl #impl_generics Markable for #name #type_generics #where_clause {
+            fn mark(&self, marker: &mut Marker) {
+                #( Markable::mark(&self.#fields, marker); )*
+            }
+
+            fn contains_term(&self, term: &ATermRef<'_>) -> bool {
+                false #( || Markable::contains_term(&self.#fields, term) )*
+            }
+
+            fn contains_symbol(&self, symbol: &SymbolRef<'_>) -> bool {
+                false #( || Markable::contains_symbol(&self.#fields, symbol) )*
+            }
+
+            fn len(&self) -> usize {
+                0 #( + Markable::len(&self.#fields) )*
+            }
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_derive_markable() {
+        let input = "
+            struct Worklist<T> {
+                terms: Vec<ATerm>,
+                pending: Option<T>,
+            }
+        ";
+
+        let tokens = TokenStream::from_str(input).unwrap();
+        let result = merc_derive_markable_impl(tokens).to_string();
+
+        assert!(result.contains("impl < T : Markable > Markable for Worklist < T >"));
+        assert!(result.contains("Markable :: mark (& self . terms , marker) ;"));
+        assert!(result.contains("Markable :: mark (& self . pending , marker) ;"));
+    }
+
+    #[test]
+    fn test_derive_markable_enum() {
+        let tokens = TokenStream::from_str("enum Choice { Left, Right }").unwrap();
+        let result = merc_derive_markable_impl(tokens).to_string();
+
+        assert!(result.contains("compile_error"));
+    }
+}
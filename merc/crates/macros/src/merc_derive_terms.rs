@@ -20,23 +20,27 @@ pub(crate) fn merc_derive_terms_impl(_attributes: TokenStream, input: TokenStrea
                 Item::Struct(object) => {
                     // If the struct is annotated with term we process it as a term.
                     if let Some(attr) = object.attrs.iter().find(|attr| attr.meta.path().is_ident("merc_term")) {
-                        // The #term(assertion) annotation must contain an assertion
-                        let assertion = match attr.parse_args::<syn::Ident>() {
+                        // The #term(assertion) annotation can contain an assertion, which is also used as the
+                        // recognizer of `Castable`.
+                        let (assertion, recognizer) = match attr.parse_args::<syn::Ident>() {
                             Ok(assertion) => {
                                 let assertion_msg = format!("{assertion}");
-                                quote!(
-                                    debug_assert!(#assertion(&term), "Term {:?} does not satisfy {}", term, #assertion_msg)
+                                (
+                                    quote!(
+                                        debug_assert!(#assertion(&term), "Term {:?} does not satisfy {}", term, #assertion_msg)
+                                    ),
+                                    Some(quote!(|term| #assertion(term))),
                                 )
                             }
-                            Err(_x) => {
-                                quote!()
-                            }
+                            Err(_x) => (quote!(), None),
                         };
 
-                        // Add the expected derive macros to the input struct.
+                        // Add the expected derive macros to the input struct, which must have the layout of
+                        // its term for the implementation of `Castable`.
                         object
                             .attrs
                             .push(parse_quote!(#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]));
+                        object.attrs.push(parse_quote!(#[repr(transparent)]));
 
                         // ALL structs in this module must contain the term.
                         assert!(
@@ -52,11 +56,42 @@ pub(crate) fn merc_derive_terms_impl(_attributes: TokenStream, input: TokenStrea
                             ast.ident
                         );
 
+                        // The #[merc_ignore_annotation(index)] attribute on the term field marks the argument at the
+                        // given index as an annotation. It is removed, since it is not a valid attribute on its own,
+                        // and generates a `structural_eq` that ignores that argument.
+                        let ignored_annotation = object.fields.iter_mut().find_map(|field| {
+                            let position = field
+                                .attrs
+                                .iter()
+                                .position(|attr| attr.meta.path().is_ident("merc_ignore_annotation"))?;
+
+                            Some(
+                                field
+                                    .attrs
+                                    .remove(position)
+                                    .parse_args::<syn::LitInt>()
+                                    .and_then(|index| index.base10_parse::<usize>())
+                                    .expect("merc_ignore_annotation must contain the index of the annotation argument"),
+                            )
+                        });
+
                         let name = format_ident!("{}", object.ident);
 
                         // Simply the generics from the struct.
                         let generics = object.generics.clone();
 
+                        // Without a recognizer the shape of a term cannot be checked, so `Castable` is not
+                        // implemented and `try_cast` cannot be used for the struct.
+                        let castable = recognizer
+                            .map(|recognizer| {
+                                quote!(
+                                    unsafe impl #generics Castable for #name #generics {
+                                        const RECOGNIZER: fn(&ATermRef<'_>) -> bool = #recognizer;
+                                    }
+                                )
+                            })
+                            .unwrap_or_default();
+
                         // Helper to create generics with added lifetimes.
                         fn create_generics_with_lifetimes(
                             base_generics: &syn::Generics,
@@ -93,6 +128,25 @@ pub(crate) fn merc_derive_terms_impl(_attributes: TokenStream, input: TokenStrea
                             generics.to_token_stream()
                         };
 
+                        let structural_eq = if let Some(annotation) = ignored_annotation {
+                            quote!(
+                                /// Returns true iff both terms are equal when the annotation argument is ignored.
+                                pub fn structural_eq(&self, other: &Self) -> bool {
+                                    let left = self.term.copy();
+                                    let right = other.term.copy();
+
+                                    left.get_head_symbol() == right.get_head_symbol()
+                                        && left
+                                            .arguments()
+                                            .zip(right.arguments())
+                                            .enumerate()
+                                            .all(|(index, (left, right))| index == #annotation || left == right)
+                                }
+                            )
+                        } else {
+                            quote!()
+                        };
+
                         // Add a <name>Ref struct that contains the ATermRef<'a> and
                         // the implementation and both protect and borrow. Also add
                         // the conversion from and to an ATerm.
@@ -102,6 +156,8 @@ pub(crate) fn merc_derive_terms_impl(_attributes: TokenStream, input: TokenStrea
                                 pub fn copy #generics_ref(&'a self) -> #name_ref #generics_ref {
                                     self.term.copy().into()
                                 }
+
+                                #structural_eq
                             }
 
                             impl #generics From<ATerm> for #name #generics {
@@ -133,6 +189,8 @@ pub(crate) fn merc_derive_terms_impl(_attributes: TokenStream, input: TokenStrea
                                 }
                             }
 
+                            #castable
+
                             impl #generics Markable for #name #generics{
                                 fn mark(&self, marker: &mut Marker) {
                                     self.term.mark(marker);
@@ -181,6 +239,8 @@ pub(crate) fn merc_derive_terms_impl(_attributes: TokenStream, input: TokenStrea
                                 pub fn protect(&self) -> #name {
                                     self.term.protect().into()
                                 }
+
+                                #structural_eq
                             }
 
                             impl #generics_ref  From<ATermRef<'a>> for #name_ref #generics_ref {
@@ -341,4 +401,44 @@ mod tests {
 
         println!("{result}");
     }
+
+    #[test]
+    fn test_macro_ignore_annotation() {
+        let input = "
+            mod anything {
+
+                #[merc_term]
+                struct Test {
+                    #[merc_ignore_annotation(1)]
+                    term: ATerm,
+                }
+            }
+        ";
+
+        let tokens = TokenStream::from_str(input).unwrap();
+        let result = merc_derive_terms_impl(TokenStream::default(), tokens).to_string();
+
+        assert!(result.contains("structural_eq"));
+        assert!(!result.contains("merc_ignore_annotation"));
+    }
+
+    #[test]
+    fn test_macro_castable() {
+        let input = "
+            mod anything {
+
+                #[merc_term(is_test)]
+                struct Test {
+                    term: ATerm,
+                }
+            }
+        ";
+
+        let tokens = TokenStream::from_str(input).unwrap();
+        let result = merc_derive_terms_impl(TokenStream::default(), tokens).to_string();
+
+        assert!(result.contains("repr (transparent)"));
+        assert!(result.contains("unsafe impl Castable for Test"));
+        assert!(result.contains("| term | is_test (term)"));
+    }
 }
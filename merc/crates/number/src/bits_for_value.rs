@@ -1,4 +1,58 @@
 /// Returns the number of bits needed to represent the given value.
-pub fn bits_for_value(value: usize) -> u8 {
+///
+/// # Panics
+///
+/// Panics when the value is zero.
+pub const fn bits_for_value(value: usize) -> u8 {
     value.ilog2() as u8 + 1
 }
+
+/// Returns the number of bits needed to represent the largest value in the
+/// given slice, which is zero when the slice is empty or only contains zeros.
+///
+/// # Details
+///
+/// The highest bit of the largest value is the highest bit of the bitwise or
+/// of all values, which avoids comparisons in the loop such that it can be
+/// vectorized.
+pub fn bits_for_max(values: &[u64]) -> u32 {
+    let combined = values.iter().fold(0, |combined, value| combined | value);
+    u64::BITS - combined.leading_zeros()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use merc_utilities::random_test;
+
+    use super::*;
+
+    #[test]
+    fn test_bits_for_value() {
+        const BITS: u8 = bits_for_value(255);
+        assert_eq!(BITS, 8);
+        assert_eq!(bits_for_value(1), 1);
+        assert_eq!(bits_for_value(256), 9);
+        assert_eq!(bits_for_value(usize::MAX), usize::BITS as u8);
+    }
+
+    #[test]
+    fn test_random_bits_for_max() {
+        random_test(100, |rng| {
+            let values: Vec<u64> = (0..rng.random_range(1..100))
+                .map(|_| (rng.random::<u64>() >> rng.random_range(0..64)).max(1))
+                .collect();
+
+            let expected = values
+                .iter()
+                .map(|value| bits_for_value(*value as usize) as u32)
+                .max()
+                .unwrap();
+            assert_eq!(bits_for_max(&values), expected);
+        });
+
+        assert_eq!(bits_for_max(&[]), 0);
+        assert_eq!(bits_for_max(&[0, 0]), 0);
+    }
+}
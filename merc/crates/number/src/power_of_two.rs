@@ -43,6 +43,55 @@ where
     value + T::one()
 }
 
+/// Returns the smallest power of two that is larger than or equal to the given
+/// value, or None when it does not fit in the type. Only defined for unsigned
+/// types, since the highest power of two of a signed type is negative.
+///
+/// # Examples
+/// ```
+/// use merc_number::next_power_of_two_checked;
+///
+/// assert_eq!(next_power_of_two_checked(5u64), Some(8));
+/// assert_eq!(next_power_of_two_checked(u64::MAX), None);
+/// ```
+pub fn next_power_of_two_checked<T>(value: T) -> Option<T>
+where
+    T: num::PrimInt + num::Unsigned,
+{
+    if value <= T::one() {
+        return Some(T::one());
+    }
+
+    // The number of bits needed to represent value - 1.
+    let bits = std::mem::size_of::<T>() * 8;
+    let exponent = bits - (value - T::one()).leading_zeros() as usize;
+    if exponent >= bits {
+        None
+    } else {
+        Some(T::one() << exponent)
+    }
+}
+
+/// Returns the exponent of the given value when it is a power of two, and None otherwise.
+///
+/// # Examples
+/// ```
+/// use merc_number::log2_exact;
+///
+/// assert_eq!(log2_exact(8u64), Some(3));
+/// assert_eq!(log2_exact(6u64), None);
+/// ```
+pub fn log2_exact<T>(value: T) -> Option<u32>
+where
+    T: num::PrimInt,
+{
+    if is_power_of_two(value) {
+        Some(value.trailing_zeros())
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,4 +143,35 @@ mod tests {
         assert_eq!(round_up_to_power_of_two(17u64), 32);
         assert_eq!(round_up_to_power_of_two(33usize), 64);
     }
+
+    #[test]
+    fn test_next_power_of_two_checked() {
+        assert_eq!(next_power_of_two_checked(0u64), Some(1));
+        assert_eq!(next_power_of_two_checked(1u64), Some(1));
+        assert_eq!(next_power_of_two_checked(3u64), Some(4));
+
+        // Starts at 2 since the predecessor of 2 is itself a power of two.
+        for exponent in 2..64 {
+            let power = 1u64 << exponent;
+            assert_eq!(next_power_of_two_checked(power), Some(power));
+            assert_eq!(next_power_of_two_checked(power - 1), Some(power));
+        }
+
+        // Everything above the largest power of two overflows.
+        assert_eq!(next_power_of_two_checked((1u64 << 63) + 1), None);
+        assert_eq!(next_power_of_two_checked(u64::MAX), None);
+        assert_eq!(next_power_of_two_checked(u8::MAX), None);
+    }
+
+    #[test]
+    fn test_log2_exact() {
+        for exponent in 0..64 {
+            assert_eq!(log2_exact(1u64 << exponent), Some(exponent));
+        }
+
+        assert_eq!(log2_exact(0u64), None);
+        assert_eq!(log2_exact(3u64), None);
+        assert_eq!(log2_exact(u64::MAX), None);
+        assert!(!is_power_of_two(u64::MAX));
+    }
 }
@@ -54,6 +54,33 @@ pub fn read_u64_variablelength<R: Read, E: Endianness>(stream: &mut BitReader<R,
     Ok(value)
 }
 
+/// Decodes an unsigned variable-length integer from the start of the given
+/// slice, see [read_u64_variablelength]. Returns the value and the number of
+/// bytes that encode it.
+pub fn decode_from_slice(bytes: &[u8]) -> Result<(u64, usize), MercError> {
+    let mut value: u64 = 0;
+    for (i, byte) in bytes.iter().take(encoding_size::<u64>()).enumerate() {
+        value |= ((byte & 0b01111111) as u64) << (7 * i);
+
+        if byte & 0b10000000 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+
+    if bytes.len() >= encoding_size::<u64>() {
+        // The encoding of a u64 never exceeds this size, as in read_u64_variablelength.
+        Ok((value, encoding_size::<u64>()))
+    } else {
+        Err("Unexpected end of a variable-length integer".into())
+    }
+}
+
+/// Returns the number of bytes written by [write_u64_variablelength] for the given value.
+pub const fn encoded_len(value: u64) -> usize {
+    let bits = (u64::BITS - value.leading_zeros()) as usize;
+    if bits == 0 { 1 } else { bits.div_ceil(7) }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,4 +105,56 @@ mod tests {
             assert_eq!(result, value);
         });
     }
+
+    /// Encodes the given value into a vector of bytes.
+    fn encode(value: u64) -> Vec<u8> {
+        let mut stream = Vec::new();
+        {
+            let mut writer = BitWriter::<_, BigEndian>::new(&mut stream);
+            write_u64_variablelength(&mut writer, value).unwrap();
+        }
+        stream
+    }
+
+    #[test]
+    fn test_encoded_len_boundaries() {
+        assert_eq!(encoded_len(0), 1);
+        assert_eq!(encoded_len(u64::MAX), encoding_size::<u64>());
+
+        // Every multiple of seven bits requires an additional byte.
+        for bytes in 1..encoding_size::<u64>() {
+            let largest = (1u64 << (7 * bytes)) - 1;
+            assert_eq!(encoded_len(largest), bytes);
+            assert_eq!(encoded_len(largest + 1), bytes + 1);
+
+            for value in [largest, largest + 1] {
+                let stream = encode(value);
+                assert_eq!(stream.len(), encoded_len(value));
+                assert_eq!(decode_from_slice(&stream).unwrap(), (value, stream.len()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_decode_from_slice() {
+        random_test(1000, |rng| {
+            // Use a random number of bits to cover all encoding lengths.
+            let value = rng.random::<u64>() >> rng.random_range(0..64);
+            let other = rng.random::<u64>();
+
+            let mut stream = encode(value);
+            stream.extend(encode(other));
+
+            let (result, length) = decode_from_slice(&stream).unwrap();
+            assert_eq!(result, value);
+            assert_eq!(length, encoded_len(value));
+            assert_eq!(
+                decode_from_slice(&stream[length..]).unwrap(),
+                (other, encoded_len(other))
+            );
+
+            // Decoding a truncated encoding fails.
+            assert!(decode_from_slice(&stream[..length - 1]).is_err());
+        });
+    }
 }
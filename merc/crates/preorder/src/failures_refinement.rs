@@ -10,9 +10,12 @@
 use log::trace;
 use merc_collections::VecSet;
 use merc_lts::LTS;
+use merc_lts::LabelIndex;
 use merc_lts::StateIndex;
 use merc_reduction::Equivalence;
 use merc_reduction::Partition;
+use merc_reduction::branching_bisim_sigref_preprocessed;
+use merc_reduction::preprocess_branching_with_mapping;
 use merc_reduction::quotient_lts_block;
 use merc_reduction::reduce_lts;
 use merc_reduction::strong_bisim_sigref;
@@ -27,6 +30,10 @@ pub enum ExplorationStrategy {
     DFS,
 }
 
+/// A `(trace, refusal)` pair of the implementation that is not allowed by the
+/// specification, see [failures_refinement_witness].
+pub type FailureWitness<Label> = (Vec<Label>, Vec<Label>);
+
 /// This function checks using algorithms in the paper mentioned above
 /// whether transition system l1 is included in transition system l2, in the
 /// sense of trace inclusions, failures inclusion and divergence failures
@@ -35,13 +42,53 @@ pub fn is_failures_refinement<L: LTS, const COUNTER_EXAMPLE: bool>(
     impl_lts: L,
     spec_lts: L,
     refinement: RefinementType,
-    _strategy: ExplorationStrategy,
+    strategy: ExplorationStrategy,
     preprocess: bool,
     timing: &mut Timing,
 ) -> bool {
+    check_refinement::<L, COUNTER_EXAMPLE>(impl_lts, spec_lts, refinement, strategy, preprocess, timing).is_none()
+}
+
+/// The same as [is_failures_refinement], but returns a `(trace, refusal)` pair
+/// whenever the refinement does not hold, and `None` otherwise.
+///
+/// # Details
+///
+/// The pair is a failure of the implementation: the trace can be performed by
+/// the implementation, after which it reaches a stable state that refuses all
+/// actions in the refusal set. The specification cannot perform the trace, and
+/// therefore has no failure with this trace at all. The refusal set is empty
+/// when the implementation can only diverge after the trace. For weak
+/// refinements the trace and the refusal only contain the visible actions, and
+/// a stable state has no outgoing internal transitions. Otherwise, every state
+/// is stable and the internal actions are treated as any other action.
+pub fn failures_refinement_witness<L: LTS>(
+    impl_lts: L,
+    spec_lts: L,
+    refinement: RefinementType,
+    strategy: ExplorationStrategy,
+    preprocess: bool,
+    timing: &mut Timing,
+) -> Option<FailureWitness<L::Label>> {
+    check_refinement::<L, true>(impl_lts, spec_lts, refinement, strategy, preprocess, timing)
+}
+
+/// Returns `None` iff the implementation refines the specification, and
+/// otherwise a counter example `(trace, refusal)` pair, which are empty unless
+/// `COUNTER_EXAMPLE` is true.
+fn check_refinement<L: LTS, const COUNTER_EXAMPLE: bool>(
+    impl_lts: L,
+    spec_lts: L,
+    refinement: RefinementType,
+    _strategy: ExplorationStrategy,
+    preprocess: bool,
+    timing: &mut Timing,
+) -> Option<FailureWitness<L::Label>> {
     let reduction = match refinement {
         RefinementType::Trace => Equivalence::StrongBisim,
+        RefinementType::WeakTrace => Equivalence::BranchingBisim,
     };
+    let weak = matches!(refinement, RefinementType::WeakTrace);
 
     // For the preprocessing/quotienting step it makes sense to merge both LTSs
     // together in case that some states are equivalent. So we do this in all branches.
@@ -65,6 +112,16 @@ pub fn is_failures_refinement<L: LTS, const COUNTER_EXAMPLE: bool>(
                     // After partitioning the block becomes the state in the reduced_lts.
                     (reduced_lts, StateIndex::new(*initial_spec))
                 }
+                Equivalence::BranchingBisim => {
+                    let (preprocess_lts, mapping) = preprocess_branching_with_mapping(merged_lts);
+                    let partition = branching_bisim_sigref_preprocessed(&preprocess_lts, timing);
+
+                    let initial_spec = partition.block_number(mapping[initial_spec]);
+                    let reduced_lts = quotient_lts_block::<_, true>(&preprocess_lts, &partition);
+
+                    // After partitioning the block becomes the state in the reduced_lts.
+                    (reduced_lts, StateIndex::new(*initial_spec))
+                }
                 _ => unimplemented!(),
             }
         }
@@ -72,47 +129,140 @@ pub fn is_failures_refinement<L: LTS, const COUNTER_EXAMPLE: bool>(
         impl_lts.merge_disjoint(&spec_lts)
     };
 
-    let mut working = vec![(merged_lts.initial_state_index(), VecSet::singleton(initial_spec))];
+    let initial_spec = if weak {
+        tau_closure(&merged_lts, VecSet::singleton(initial_spec))
+    } else {
+        VecSet::singleton(initial_spec)
+    };
+    let mut working = vec![(merged_lts.initial_state_index(), initial_spec, None)];
 
     // The antichain data structure is used for storing explored states. However, as opposed to a discovered set it
     // allows for pruning additional pairs based on the `antichain` property.
     let mut antichain = Antichain::new();
 
-    while let Some((impl_state, spec)) = working.pop() {
+    // The (parent, label) of every explored transition, only used to construct the counter example.
+    let mut trace_tree: Vec<(Option<usize>, LabelIndex)> = Vec::new();
+
+    while let Some((impl_state, spec, node)) = working.pop() {
         trace!("Checking ({:?}, {:?})", impl_state, spec);
         // pop (impl,spec) from working;
 
         for impl_transition in merged_lts.outgoing_transitions(impl_state) {
-            // spec' := {s' | exists s in spec. s-e->s'};
-            let mut spec_prime = VecSet::new();
-            for s in &spec {
-                for spec_transition in merged_lts.outgoing_transitions(*s) {
-                    if impl_transition.label == spec_transition.label {
-                        spec_prime.insert(spec_transition.to);
+            let spec_prime = if weak && merged_lts.is_hidden_label(impl_transition.label) {
+                // spec' := spec, since the specification is closed under tau.
+                spec.clone()
+            } else {
+                // spec' := {s' | exists s in spec. s-e->s'};
+                let mut spec_prime = VecSet::new();
+                for s in &spec {
+                    for spec_transition in merged_lts.outgoing_transitions(*s) {
+                        if impl_transition.label == spec_transition.label {
+                            spec_prime.insert(spec_transition.to);
+                        }
                     }
                 }
-            }
+
+                if weak {
+                    tau_closure(&merged_lts, spec_prime)
+                } else {
+                    spec_prime
+                }
+            };
 
             trace!("spec' = {:?}", spec_prime);
             if spec_prime.is_empty() {
                 // if spec' = {} then
-                return false; //    return false;
+                if !COUNTER_EXAMPLE {
+                    return Some((Vec::new(), Vec::new())); //    return false;
+                }
+
+                // Reconstruct the trace that leads to the implementation state, followed by the violating action.
+                let mut labels = vec![impl_transition.label];
+                let mut current = node;
+                while let Some(index) = current {
+                    let (parent, label) = trace_tree[index];
+                    labels.push(label);
+                    current = parent;
+                }
+                labels.reverse();
+
+                // The refusal set of a stable state that the implementation reaches after the trace.
+                let refusal: Vec<LabelIndex> = stable_state(&merged_lts, impl_transition.to, weak)
+                    .map(|stable| {
+                        (0..merged_lts.num_of_labels())
+                            .map(LabelIndex::new)
+                            .filter(|label| {
+                                (!weak || !merged_lts.is_hidden_label(*label))
+                                    && merged_lts.outgoing_transitions(stable).all(|t| t.label != *label)
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                let to_labels = |labels: Vec<LabelIndex>| -> Vec<L::Label> {
+                    labels
+                        .into_iter()
+                        .filter(|label| !weak || !merged_lts.is_hidden_label(*label))
+                        .map(|label| merged_lts.labels()[label].clone())
+                        .collect()
+                };
+
+                return Some((to_labels(labels), to_labels(refusal)));
             }
 
             if antichain.insert(impl_transition.to, spec_prime.clone()) {
                 // if antichain_insert(impl,spec') then
-                working.push((impl_transition.to, spec_prime));
+                let node = if COUNTER_EXAMPLE {
+                    trace_tree.push((node, impl_transition.label));
+                    Some(trace_tree.len() - 1)
+                } else {
+                    None
+                };
+
+                working.push((impl_transition.to, spec_prime, node));
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns a stable state, without outgoing hidden transitions, that is
+/// reachable by zero or more tau transitions from the given state, or `None` if
+/// there is none. Whenever `weak` is false every state is considered stable.
+fn stable_state(lts: &impl LTS, state_index: StateIndex, weak: bool) -> Option<StateIndex> {
+    if !weak {
+        return Some(state_index);
+    }
+
+    tau_closure(lts, VecSet::singleton(state_index))
+        .iter()
+        .copied()
+        .find(|state| lts.outgoing_transitions(*state).all(|t| !lts.is_hidden_label(t.label)))
+}
+
+/// Returns the set of states reachable by zero or more tau transitions from
+/// the given states. Terminates in the presence of tau-loops, since every state
+/// is explored at most once.
+fn tau_closure(lts: &impl LTS, mut states: VecSet<StateIndex>) -> VecSet<StateIndex> {
+    let mut stack: Vec<StateIndex> = states.iter().copied().collect();
+
+    while let Some(state_index) = stack.pop() {
+        for transition in lts.outgoing_transitions(state_index) {
+            if lts.is_hidden_label(transition.label) && states.insert(transition.to) {
+                stack.push(transition.to);
             }
         }
     }
 
-    true
+    states
 }
 
 #[cfg(test)]
 mod tests {
     use merc_io::DumpFiles;
     use merc_lts::random_lts;
+    use merc_lts::read_aut;
     use merc_lts::write_aut;
     use merc_reduction::Equivalence;
     use merc_reduction::reduce_lts;
@@ -121,6 +271,7 @@ mod tests {
 
     use crate::ExplorationStrategy;
     use crate::RefinementType;
+    use crate::failures_refinement_witness;
     use crate::is_failures_refinement;
 
     #[test]
@@ -150,4 +301,138 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Tests are too slow under miri.
+    fn test_random_weak_trace_refinement() {
+        random_test(100, |rng| {
+            let mut files = DumpFiles::new("test_random_weak_trace_refinement");
+
+            let spec_lts = random_lts(rng, 10, 20, 5);
+
+            let mut timing = Timing::default();
+            let impl_lts = reduce_lts(spec_lts.clone(), Equivalence::BranchingBisim, &mut timing);
+
+            files.dump("spec.aut", |w| write_aut(w, &spec_lts)).unwrap();
+            files.dump("impl.aut", |w| write_aut(w, &impl_lts)).unwrap();
+
+            for preprocess in [false, true] {
+                assert!(
+                    is_failures_refinement::<_, false>(
+                        impl_lts.clone(),
+                        spec_lts.clone(),
+                        RefinementType::WeakTrace,
+                        ExplorationStrategy::BFS,
+                        preprocess,
+                        &mut timing
+                    ),
+                    "Branching bisimulation implies weak trace refinement."
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn test_weak_trace_refinement_tau_loops() {
+        // The implementation has a tau-loop, and the specification a tau-loop before the b.
+        let impl_lts = read_aut(
+            b"des (0, 4, 3)
+            (0, \"i\", 1)
+            (1, \"i\", 0)
+            (1, \"a\", 2)
+            (2, \"b\", 2)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+
+        let spec_lts = read_aut(
+            b"des (0, 4, 3)
+            (0, \"a\", 1)
+            (1, \"i\", 2)
+            (2, \"i\", 1)
+            (2, \"b\", 1)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+
+        let mut timing = Timing::default();
+        let refines = |impl_lts, spec_lts, refinement, timing: &mut Timing| {
+            is_failures_refinement::<_, false>(impl_lts, spec_lts, refinement, ExplorationStrategy::BFS, false, timing)
+        };
+
+        assert!(refines(
+            impl_lts.clone(),
+            spec_lts.clone(),
+            RefinementType::WeakTrace,
+            &mut timing
+        ));
+        assert!(refines(
+            spec_lts.clone(),
+            impl_lts.clone(),
+            RefinementType::WeakTrace,
+            &mut timing
+        ));
+        assert!(!refines(
+            impl_lts.clone(),
+            spec_lts.clone(),
+            RefinementType::Trace,
+            &mut timing
+        ));
+
+        // The initial tau step of the implementation cannot be performed by the specification, after which b is refused.
+        assert_eq!(
+            failures_refinement_witness(
+                impl_lts,
+                spec_lts,
+                RefinementType::Trace,
+                ExplorationStrategy::BFS,
+                false,
+                &mut timing
+            ),
+            Some((vec!["i".to_string()], vec!["b".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_failures_refinement_witness() {
+        let impl_lts = read_aut(
+            b"des (0, 6, 5)
+            (0, \"a\", 1)
+            (1, \"i\", 2)
+            (2, \"b\", 3)
+            (2, \"c\", 3)
+            (3, \"i\", 4)
+            (4, \"b\", 4)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+
+        let spec_lts = read_aut(
+            b"des (0, 2, 3)
+            (0, \"a\", 1)
+            (1, \"b\", 2)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+
+        let mut timing = Timing::default();
+        let witness = failures_refinement_witness(
+            impl_lts,
+            spec_lts,
+            RefinementType::WeakTrace,
+            ExplorationStrategy::BFS,
+            false,
+            &mut timing,
+        );
+
+        // The specification cannot perform a.c, after which the implementation
+        // silently moves to a stable state that refuses a and c.
+        assert_eq!(
+            witness,
+            Some((
+                vec!["a".to_string(), "c".to_string()],
+                vec!["a".to_string(), "c".to_string()]
+            ))
+        );
+    }
 }
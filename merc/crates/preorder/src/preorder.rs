@@ -2,12 +2,16 @@ use merc_lts::LTS;
 use merc_utilities::Timing;
 
 use crate::ExplorationStrategy;
+use crate::FailureWitness;
+use crate::failures_refinement_witness;
 use crate::is_failures_refinement;
 
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum RefinementType {
     Trace,
+    /// Trace refinement where the internal (tau) actions are abstracted from.
+    WeakTrace,
 }
 
 pub fn refines<L: LTS>(impl_lts: L, spec_lts: L, preorder: RefinementType, timing: &mut Timing) -> bool {
@@ -20,5 +24,25 @@ pub fn refines<L: LTS>(impl_lts: L, spec_lts: L, preorder: RefinementType, timin
             false,
             timing,
         ),
+        RefinementType::WeakTrace => is_failures_refinement::<L, false>(
+            impl_lts,
+            spec_lts,
+            RefinementType::WeakTrace,
+            ExplorationStrategy::BFS,
+            false,
+            timing,
+        ),
     }
 }
+
+/// The same as [refines], but returns a `(trace, refusal)` pair of the
+/// implementation that is not allowed by the specification when the refinement
+/// does not hold, and `None` otherwise. See [failures_refinement_witness].
+pub fn refines_with_witness<L: LTS>(
+    impl_lts: L,
+    spec_lts: L,
+    preorder: RefinementType,
+    timing: &mut Timing,
+) -> Option<FailureWitness<L::Label>> {
+    failures_refinement_witness(impl_lts, spec_lts, preorder, ExplorationStrategy::BFS, false, timing)
+}
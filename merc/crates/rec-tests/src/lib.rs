@@ -1,5 +1,5 @@
-//! This crate offers parsing for REC files and test cases for the rewrite
-//! engines.
+//! This crate offers parsing for REC and TRS files and test cases for the
+//! rewrite engines.
 //!
 //! The Rewrite Engine Competition (REC) is a
 //! [competition](https://doi.org/10.1007/978-3-030-17502-3_6) for benchmarking
@@ -11,7 +11,10 @@
 #![forbid(unsafe_code)]
 
 mod parse_rec;
+mod parse_trs;
 mod syntax;
 
 pub use parse_rec::load_rec_from_file;
 pub use parse_rec::load_rec_from_strings;
+pub use parse_trs::load_trs_from_file;
+pub use parse_trs::load_trs_from_string;
@@ -1,4 +1,4 @@
-use std::fs;
+use std::io::Read;
 use std::path::PathBuf;
 use std::str::FromStr;
 
@@ -7,6 +7,7 @@ use pest_derive::Parser;
 
 use merc_aterm::ATerm;
 use merc_aterm::storage::THREAD_TERM_POOL;
+use merc_io::open_decompressed;
 use merc_pest_consume::Error;
 use merc_pest_consume::Node;
 use merc_pest_consume::match_nodes;
@@ -40,9 +41,10 @@ struct RecSpecResult {
     eval_terms: Vec<ATerm>,
 }
 
-/// Load a REC specification from a specified file.
+/// Load a REC specification from a specified file, which can be gzip compressed.
 pub fn load_rec_from_file(file: PathBuf) -> Result<(RewriteSpecificationSyntax, Vec<ATerm>), MercError> {
-    let contents = fs::read_to_string(file.clone())?;
+    let mut contents = String::new();
+    open_decompressed(&file)?.read_to_string(&mut contents)?;
     parse_rec(&contents, Some(file))
 }
 
@@ -89,7 +91,8 @@ fn parse_rec(contents: &str, path: Option<PathBuf>) -> Result<(RewriteSpecificat
             let include_path = p.parent().unwrap();
             let file_name = PathBuf::from_str(&(file.to_lowercase() + ".rec")).unwrap();
             let load_file = include_path.join(file_name);
-            let contents = fs::read_to_string(load_file)?;
+            let mut contents = String::new();
+            open_decompressed(&load_file)?.read_to_string(&mut contents)?;
             let (include_spec, include_terms) = parse_rec(&contents, path.clone())?;
 
             // Add rewrite rules and terms to the result.
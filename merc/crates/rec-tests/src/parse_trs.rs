@@ -0,0 +1,252 @@
+use std::io::Read;
+use std::path::PathBuf;
+
+use pest::Parser;
+use pest_derive::Parser;
+
+use merc_aterm::ATerm;
+use merc_aterm::Symb;
+use merc_aterm::Term;
+use merc_aterm::storage::THREAD_TERM_POOL;
+use merc_io::open_decompressed;
+use merc_pest_consume::Error;
+use merc_pest_consume::Node;
+use merc_pest_consume::match_nodes;
+use merc_sabre::RewriteSpecification;
+use merc_utilities::MercError;
+
+use crate::syntax::ConditionSyntax;
+use crate::syntax::RewriteRuleSyntax;
+use crate::syntax::RewriteSpecificationSyntax;
+
+#[derive(Parser)]
+#[grammar = "trs_grammar.pest"]
+pub struct TrsParser;
+
+type ParseResult<T> = Result<T, Error<Rule>>;
+type ParseNode<'i> = Node<'i, Rule, ()>;
+
+/// Load a rewrite specification from a file in the TRS format, which can be gzip compressed.
+pub fn load_trs_from_file(file: PathBuf) -> Result<RewriteSpecification, MercError> {
+    let mut contents = String::new();
+    open_decompressed(&file)?.read_to_string(&mut contents)?;
+    load_trs_from_string(&contents)
+}
+
+/// Load a rewrite specification in the TRS format from the given string.
+///
+/// # Details
+///
+/// Only the `VAR`, `RULES` and `COMMENT` sections are supported, and the
+/// latter is ignored. Conditions of the shape `eqeq(lhs, rhs) -> true` and
+/// `!eq(lhs, rhs) -> true`, as written by the `TrsFormatter` of the rewrite
+/// tool, are read back as an equality and inequality of `lhs` and `rhs`
+/// respectively. Any other condition `lhs -> rhs` or `lhs == rhs` requires the
+/// normal forms of `lhs` and `rhs` to be equal.
+pub fn load_trs_from_string(contents: &str) -> Result<RewriteSpecification, MercError> {
+    let mut parse_result = TrsParser::parse(Rule::trs_spec, contents)?;
+    let root = parse_result.next().ok_or("Could not parse TRS specification")?;
+    let parse_node = ParseNode::new(root);
+
+    let spec = TrsParser::trs_spec(parse_node)?;
+    Ok(spec.to_rewrite_spec())
+}
+
+/// Converts the condition `lhs -> rhs` into a condition of the rewrite rule.
+fn to_condition(lhs: ATerm, rhs: ATerm) -> ConditionSyntax {
+    let is_true = rhs.get_head_symbol().name() == "true" && rhs.get_head_symbol().arity() == 0;
+
+    let symbol = lhs.get_head_symbol();
+    if is_true && symbol.arity() == 2 {
+        // The TrsFormatter replaces every '=' by 'eq' in the output.
+        match symbol.name() {
+            "eqeq" => {
+                return ConditionSyntax {
+                    lhs: lhs.arg(0).protect(),
+                    rhs: lhs.arg(1).protect(),
+                    equality: true,
+                };
+            }
+            "!eq" => {
+                return ConditionSyntax {
+                    lhs: lhs.arg(0).protect(),
+                    rhs: lhs.arg(1).protect(),
+                    equality: false,
+                };
+            }
+            _ => {}
+        }
+    }
+
+    ConditionSyntax {
+        lhs,
+        rhs,
+        equality: true,
+    }
+}
+
+#[merc_pest_consume::parser]
+impl TrsParser {
+    /// Parse a TRS specification, the sections can occur in any order.
+    fn trs_spec(spec: ParseNode) -> ParseResult<RewriteSpecificationSyntax> {
+        let mut rewrite_spec = RewriteSpecificationSyntax::default();
+
+        for child in spec.into_children() {
+            match child.as_rule() {
+                Rule::var => {
+                    for variable in TrsParser::var(child)? {
+                        if !rewrite_spec.variables.contains(&variable) {
+                            rewrite_spec.variables.push(variable);
+                        }
+                    }
+                }
+                Rule::rules => {
+                    rewrite_spec.rewrite_rules.append(&mut TrsParser::rules(child)?);
+                }
+                Rule::EOI => {
+                    // End of input
+                    break;
+                }
+                _ => {
+                    return Err(child.error(format!("Unexpected rule {:?} in a TRS specification", child.as_rule())));
+                }
+            }
+        }
+
+        Ok(rewrite_spec)
+    }
+
+    /// Extracts the variable names from the VAR section.
+    fn var(var: ParseNode) -> ParseResult<Vec<String>> {
+        match_nodes!(var.into_children();
+            [identifier(variables)..] => {
+                Ok(variables.collect())
+            }
+        )
+    }
+
+    /// Extracts the rewrite rules from the RULES section.
+    fn rules(rules: ParseNode) -> ParseResult<Vec<RewriteRuleSyntax>> {
+        match_nodes!(rules.into_children();
+            [rewrite_rule(rule_nodes)..] => {
+                Ok(rule_nodes.collect())
+            }
+        )
+    }
+
+    /// Parse a rewrite rule
+    fn rewrite_rule(rule: ParseNode) -> ParseResult<RewriteRuleSyntax> {
+        match_nodes!(rule.into_children();
+            [term(lhs), term(rhs), condition(conditions)..] => {
+                Ok(RewriteRuleSyntax {
+                    lhs,
+                    rhs,
+                    conditions: conditions.collect(),
+                })
+            }
+        )
+    }
+
+    /// Parse a single rewrite rule
+    fn single_rewrite_rule(rule: ParseNode) -> ParseResult<RewriteRuleSyntax> {
+        match_nodes!(rule.into_children();
+            [rewrite_rule(rule), EOI(_)] => {
+                Ok(rule)
+            },
+        )
+    }
+
+    /// Parse a condition in a rewrite rule
+    fn condition(condition: ParseNode) -> ParseResult<ConditionSyntax> {
+        match_nodes!(condition.into_children();
+            [term(lhs), term(rhs)] => {
+                Ok(to_condition(lhs, rhs))
+            }
+        )
+    }
+
+    /// Parse a term
+    fn term(term: ParseNode) -> ParseResult<ATerm> {
+        match_nodes!(term.into_children();
+            [identifier(head_symbol), args(arguments)] => {
+                THREAD_TERM_POOL.with_borrow(|tp| {
+                    let symbol = tp.create_symbol(&head_symbol, arguments.len());
+                    if arguments.is_empty() {
+                        Ok(tp.create_constant(&symbol))
+                    } else {
+                        Ok(tp.create_term_iter(&symbol, arguments))
+                    }
+                })
+            },
+            [identifier(head_symbol)] => {
+                THREAD_TERM_POOL.with_borrow(|tp| {
+                    let symbol = tp.create_symbol(&head_symbol, 0);
+                    Ok(tp.create_constant(&symbol))
+                })
+            }
+        )
+    }
+
+    /// Parse arguments of a term
+    fn args(args: ParseNode) -> ParseResult<Vec<ATerm>> {
+        match_nodes!(args.into_children();
+            [term(term_args)..] => {
+                Ok(term_args.collect())
+            }
+        )
+    }
+
+    /// Parse an identifier
+    fn identifier(id: ParseNode) -> ParseResult<String> {
+        Ok(id.as_str().to_string())
+    }
+
+    /// Ignored rules
+    fn EOI(_eof: ParseNode) -> ParseResult<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_trs_parsing() {
+        assert!(TrsParser::parse(Rule::single_rewrite_rule, "f(x) -> g(x)").is_ok());
+        assert!(TrsParser::parse(Rule::single_rewrite_rule, "f(x) -> g(x) | eqeq(x,a) -> true").is_ok());
+        assert!(TrsParser::parse(Rule::single_rewrite_rule, "f(x) -> g(x) | x == a, b -> c").is_ok());
+        assert!(TrsParser::parse(Rule::single_rewrite_rule, "f(x) ->").is_err());
+        assert!(TrsParser::parse(Rule::trs_spec, "(VAR x) (COMMENT a (nested) comment) (RULES f(x) -> x)").is_ok());
+    }
+
+    #[test]
+    fn test_parsing_trs_conditions() {
+        let expected = RewriteRuleSyntax {
+            lhs: ATerm::from_string("f(x,b)").unwrap(),
+            rhs: ATerm::from_string("g(x)").unwrap(),
+            conditions: vec![
+                ConditionSyntax {
+                    lhs: ATerm::from_string("x").unwrap(),
+                    rhs: ATerm::from_string("a").unwrap(),
+                    equality: true,
+                },
+                ConditionSyntax {
+                    lhs: ATerm::from_string("b").unwrap(),
+                    rhs: ATerm::from_string("c").unwrap(),
+                    equality: false,
+                },
+            ],
+        };
+
+        let mut parse_result = TrsParser::parse(
+            Rule::single_rewrite_rule,
+            "f(x,b) -> g(x) | eqeq(x,a) -> true | !eq(b,c) -> true",
+        )
+        .unwrap();
+        let node = ParseNode::new(parse_result.next().unwrap());
+        let actual = TrsParser::single_rewrite_rule(node).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+}
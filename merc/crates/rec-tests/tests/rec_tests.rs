@@ -30,6 +30,7 @@ fn rec_test(rec_files: Vec<&str>, expected_result: &str) {
     // Test Sabre rewriter
     let mut sa = SabreRewriter::new(&spec);
     let mut inner = InnermostRewriter::new(&spec);
+    let mut cached = InnermostRewriter::with_cache(&spec, 1024);
 
     let mut expected = expected_result.split('\n');
 
@@ -44,6 +45,13 @@ fn rec_test(rec_files: Vec<&str>, expected_result: &str) {
             "The inner rewrite result doesn't match the expected result",
         );
 
+        let result = cached.rewrite(term);
+        assert_eq!(
+            result,
+            expected_result.clone(),
+            "The cached inner rewrite result doesn't match the expected result",
+        );
+
         let result = sa.rewrite(term);
         assert_eq!(
             result, expected_result,
@@ -173,3 +181,177 @@ fn test_rec_specification_largestack(rec_files: Vec<&str>, expected_result: &str
 // #[test_case(vec![include_str!("../../../examples/REC/rec/omul8.rec")], include_str!("snapshot/result_omul8.txt") ; "omul8")]
 // #[test_case(vec![include_str!("../../../examples/REC/rec/omul32.rec")], include_str!("snapshot/result_omul32.txt") ; "omul32")]
 // #[test_case(vec![include_str!("../../../examples/REC/rec/intnat.rec")], include_str!("snapshot/result_intnat.txt") ; "intnat")]
+
+/// A specification that does not have a normal form.
+const LOOP_SPEC: &str = "REC-SPEC Loop
+SORTS
+  Nat
+CONS
+  d0 : -> Nat
+  s : Nat -> Nat
+OPNS
+  loop : Nat -> Nat
+VARS
+  N : Nat
+RULES
+  loop(N) -> loop(s(N))
+EVAL
+  loop(d0)
+END-SPEC";
+
+#[test]
+fn test_sabre_rewrite_bounded() {
+    test_logger();
+
+    let (syntax_spec, syntax_terms) = load_rec_from_strings(&[LOOP_SPEC]).unwrap();
+    let spec = syntax_spec.to_rewrite_spec();
+    let term = to_untyped_data_expression(syntax_terms[0].clone(), None);
+
+    // The specification does not terminate, so the limit must be reached.
+    let mut sa = SabreRewriter::new(&spec);
+    let (result, steps, reached_normal_form) = sa.rewrite_bounded(&term, 10);
+    assert_eq!(steps, 10);
+    assert!(!reached_normal_form);
+
+    let expected = to_untyped_data_expression(
+        ATerm::from_string("loop(s(s(s(s(s(s(s(s(s(s(d0)))))))))))").unwrap(),
+        None,
+    );
+    assert_eq!(result, expected);
+
+    // A terminating specification reaches the same normal form as rewrite.
+    let (syntax_spec, syntax_terms) = load_rec_from_strings(&[
+        include_str!("../../../examples/REC/rec/fibonacci05.rec"),
+        include_str!("../../../examples/REC/rec/fibonacci.rec"),
+    ])
+    .unwrap();
+    let spec = syntax_spec.to_rewrite_spec();
+    let mut sa = SabreRewriter::new(&spec);
+
+    for term in &syntax_terms {
+        let term = to_untyped_data_expression(term.clone(), None);
+
+        let (result, _, reached_normal_form) = sa.rewrite_bounded(&term, usize::MAX);
+        assert!(reached_normal_form);
+        assert_eq!(result, sa.rewrite(&term));
+
+        let (_, steps, reached_normal_form) = sa.rewrite_bounded(&term, 3);
+        assert_eq!(steps, 3);
+        assert!(!reached_normal_form);
+    }
+}
+
+#[test]
+fn test_rewrite_traced() {
+    test_logger();
+
+    let (syntax_spec, syntax_terms) = load_rec_from_strings(&[
+        include_str!("../../../examples/REC/rec/fibonacci05.rec"),
+        include_str!("../../../examples/REC/rec/fibonacci.rec"),
+    ])
+    .unwrap();
+    let spec = syntax_spec.to_rewrite_spec();
+
+    let term = to_untyped_data_expression(ATerm::from_string("plus(s(d0), s(d0))").unwrap(), None);
+    let expected_redexes: Vec<DataExpression> = ["plus(s(d0), s(d0))", "plus(d0, s(d0))"]
+        .iter()
+        .map(|t| to_untyped_data_expression(ATerm::from_string(t).unwrap(), None))
+        .collect();
+
+    let engines: Vec<Box<dyn RewriteEngine>> = vec![
+        Box::new(SabreRewriter::new(&spec)),
+        Box::new(InnermostRewriter::new(&spec)),
+        Box::new(NaiveRewriter::new(&spec)),
+    ];
+
+    for mut engine in engines {
+        let mut redexes = Vec::new();
+        let result = engine.rewrite_traced(&term, &mut |rule, redex| {
+            assert!(
+                spec.rewrite_rules().contains(rule),
+                "The applied rule must be part of the specification"
+            );
+            redexes.push(redex.clone());
+        });
+
+        assert_eq!(result, engine.rewrite(&term));
+        assert_eq!(redexes, expected_redexes);
+    }
+
+    // Traced rewriting results in the same normal form.
+    let mut sa = SabreRewriter::new(&spec);
+    for term in &syntax_terms {
+        let term = to_untyped_data_expression(term.clone(), None);
+        let mut steps = 0;
+        let result = sa.rewrite_traced(&term, &mut |_, _| steps += 1);
+
+        assert_eq!(result, sa.rewrite(&term));
+        assert!(steps > 0);
+    }
+}
+
+/// A specification with a rule that is guarded by two conditions.
+const GUARDED_SPEC: &str = "REC-SPEC Guarded
+SORTS
+  Bool Nat
+CONS
+  T : -> Bool
+  F : -> Bool
+  d0 : -> Nat
+  s : Nat -> Nat
+OPNS
+  iszero : Nat -> Bool
+  f : Nat Nat -> Nat
+VARS
+  N M : Nat
+RULES
+  iszero(d0) -> T
+  iszero(s(N)) -> F
+  f(N, M) -> d0 if iszero(N) = T and-if iszero(M) = T
+EVAL
+  f(d0, d0)
+  f(d0, s(d0))
+  f(s(d0), d0)
+END-SPEC";
+
+#[test]
+fn test_rec_multiple_conditions() {
+    // The rule may only be applied when both conditions hold.
+    rec_test(vec![GUARDED_SPEC], "d0\nf(d0,s(d0))\nf(s(d0),d0)");
+
+    let (syntax_spec, syntax_terms) = load_rec_from_strings(&[GUARDED_SPEC]).unwrap();
+    let spec = syntax_spec.to_rewrite_spec();
+    assert!(spec.rewrite_rules().iter().any(|rule| rule.conditions.len() == 2));
+
+    let mut naive = NaiveRewriter::new(&spec);
+    for (term, expected) in syntax_terms.iter().zip(["d0", "f(d0,s(d0))", "f(s(d0),d0)"]) {
+        let term = to_untyped_data_expression(term.clone(), None);
+        let expected = to_untyped_data_expression(ATerm::from_string(expected).unwrap(), None);
+        assert_eq!(naive.rewrite(&term), expected);
+    }
+}
+
+#[test]
+fn test_innermost_cache() {
+    test_logger();
+
+    let (syntax_spec, syntax_terms) = load_rec_from_strings(&[
+        include_str!("../../../examples/REC/rec/fibonacci05.rec"),
+        include_str!("../../../examples/REC/rec/fibonacci.rec"),
+    ])
+    .unwrap();
+    let spec = syntax_spec.to_rewrite_spec();
+
+    // A small capacity also exercises the eviction of entries.
+    let mut inner = InnermostRewriter::new(&spec);
+    let mut cached = InnermostRewriter::with_cache(&spec, 8);
+
+    for term in &syntax_terms {
+        let term = to_untyped_data_expression(term.clone(), None);
+        assert_eq!(cached.rewrite(&term), inner.rewrite(&term));
+    }
+
+    let cache = cached.cache().unwrap();
+    assert!(cache.len() <= 8);
+    assert!(cache.hits() > 0, "The shared subterms should result in cache hits");
+}
@@ -1,16 +1,26 @@
 #![forbid(unsafe_code)]
 
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+
 use merc_lts::LTS;
+use merc_lts::LabelIndex;
+use merc_lts::StateIndex;
+use merc_utilities::MercError;
 use merc_utilities::Timing;
+use rustc_hash::FxHashMap;
 
 use crate::Equivalence;
 use crate::Partition;
-use crate::branching_bisim_sigref;
-use crate::branching_bisim_sigref_naive;
+use crate::branching_bisim_sigref_naive_preprocessed;
+use crate::branching_bisim_sigref_preprocessed;
+use crate::divbranching_bisim_sigref_naive_preprocessed;
+use crate::preprocess_branching_with_mapping;
+use crate::preprocess_divbranching_with_mapping;
 use crate::strong_bisim_sigref;
 use crate::strong_bisim_sigref_naive;
-use crate::weak_bisim_sigref_naive;
-use crate::weak_bisimulation;
+use crate::weak_bisim_sigref_naive_preprocessed;
+use crate::weak_bisimulation_preprocessed;
 
 // Compare two LTSs for equivalence using the given algorithm.
 pub fn compare_lts<L: LTS>(equivalence: Equivalence, left: L, right: L, timing: &mut Timing) -> bool {
@@ -19,16 +29,49 @@ pub fn compare_lts<L: LTS>(equivalence: Equivalence, left: L, right: L, timing:
     drop(right); // No longer needed.
     time_merge.finish();
 
+    compare_merged(equivalence, merged, rhs_initial, timing)
+}
+
+/// Compares two LTSs for equivalence, see [compare_lts], and returns `None`
+/// when they are equivalent and a witness trace otherwise.
+///
+/// # Details
+///
+/// The witness is only a trace, and not a witness of the branching structure
+/// such as a Hennessy-Milner formula. When the LTSs have different traces the
+/// witness is a shortest sequence of actions that one of the LTSs can perform,
+/// but the other cannot. For the weak and branching equivalences the hidden
+/// actions are abstracted from. When the LTSs are not equivalent but have the
+/// same (weak) traces, for example `a.b + a.c` and `a.(b + c)`, they only
+/// differ in their branching structure and the witness is the empty trace.
+pub fn compare_lts_with_witness<L: LTS + Clone>(
+    equivalence: Equivalence,
+    left: L,
+    right: L,
+    timing: &mut Timing,
+) -> Result<Option<Vec<L::Label>>, MercError> {
+    let mut time_merge = timing.start("merge lts");
+    let (merged, rhs_initial) = left.merge_disjoint(&right);
+    drop(right); // No longer needed.
+    time_merge.finish();
+
+    if compare_merged(equivalence, merged.clone(), rhs_initial, timing) {
+        return Ok(None);
+    }
+
+    let weak = !matches!(equivalence, Equivalence::StrongBisim | Equivalence::StrongBisimNaive);
+
+    let mut time_witness = timing.start("witness");
+    let trace = distinguishing_trace(&merged, merged.initial_state_index(), rhs_initial, weak);
+    time_witness.finish();
+
+    Ok(Some(trace.unwrap_or_default()))
+}
+
+/// Returns true iff the `rhs_initial` state is equivalent to the initial state of the merged LTS.
+fn compare_merged<L: LTS>(equivalence: Equivalence, merged: L, rhs_initial: StateIndex, timing: &mut Timing) -> bool {
     // Reduce the merged LTS modulo the given equivalence and return the partition
     match equivalence {
-        Equivalence::WeakBisim => {
-            let (lts, partition) = weak_bisimulation(merged, timing);
-            partition.block_number(lts.initial_state_index()) == partition.block_number(rhs_initial)
-        }
-        Equivalence::WeakBisimSigref => {
-            let (lts, partition) = weak_bisim_sigref_naive(merged, timing);
-            partition.block_number(lts.initial_state_index()) == partition.block_number(rhs_initial)
-        }
         Equivalence::StrongBisim => {
             let (lts, partition) = strong_bisim_sigref(merged, timing);
             partition.block_number(lts.initial_state_index()) == partition.block_number(rhs_initial)
@@ -37,13 +80,288 @@ pub fn compare_lts<L: LTS>(equivalence: Equivalence, left: L, right: L, timing:
             let (lts, partition) = strong_bisim_sigref_naive(merged, timing);
             partition.block_number(lts.initial_state_index()) == partition.block_number(rhs_initial)
         }
-        Equivalence::BranchingBisim => {
-            let (lts, partition) = branching_bisim_sigref(merged, timing);
-            partition.block_number(lts.initial_state_index()) == partition.block_number(rhs_initial)
+        Equivalence::DivBranchingBisim => {
+            let mut time_pre = timing.start("preprocess");
+            let (lts, mapping, divergent) = preprocess_divbranching_with_mapping(merged);
+            time_pre.finish();
+
+            let partition = divbranching_bisim_sigref_naive_preprocessed(&lts, &divergent, timing);
+            partition.block_number(lts.initial_state_index()) == partition.block_number(mapping[rhs_initial])
         }
-        Equivalence::BranchingBisimNaive => {
-            let (lts, partition) = branching_bisim_sigref_naive(merged, timing);
-            partition.block_number(lts.initial_state_index()) == partition.block_number(rhs_initial)
+        _ => {
+            // The preprocessing renumbers the states, so the right initial state must be mapped as well.
+            let mut time_pre = timing.start("preprocess");
+            let (lts, mapping) = preprocess_branching_with_mapping(merged);
+            time_pre.finish();
+
+            let rhs_initial = mapping[rhs_initial];
+            match equivalence {
+                Equivalence::WeakBisim => {
+                    let partition = weak_bisimulation_preprocessed(&lts, timing);
+                    partition.block_number(lts.initial_state_index()) == partition.block_number(rhs_initial)
+                }
+                Equivalence::WeakBisimSigref => {
+                    let partition = weak_bisim_sigref_naive_preprocessed(&lts, timing);
+                    partition.block_number(lts.initial_state_index()) == partition.block_number(rhs_initial)
+                }
+                Equivalence::BranchingBisim => {
+                    let partition = branching_bisim_sigref_preprocessed(&lts, timing);
+                    partition.block_number(lts.initial_state_index()) == partition.block_number(rhs_initial)
+                }
+                Equivalence::BranchingBisimNaive => {
+                    let partition = branching_bisim_sigref_naive_preprocessed(&lts, timing);
+                    partition.block_number(lts.initial_state_index()) == partition.block_number(rhs_initial)
+                }
+                Equivalence::StrongBisim | Equivalence::StrongBisimNaive => {
+                    unreachable!("Strong bisimulation does not require preprocessing")
+                }
+                Equivalence::DivBranchingBisim => {
+                    unreachable!("Divergence-preserving branching bisimulation requires its own preprocessing")
+                }
+            }
+        }
+    }
+}
+
+/// Returns a shortest sequence of actions that can be performed from exactly
+/// one of the given states, or `None` if both states have the same traces.
+/// Whenever `weak` is true the hidden actions are abstracted from.
+///
+/// This explores the pairs of reachable state sets of both states, similar to a
+/// subset construction, and can therefore take exponential time.
+fn distinguishing_trace<L: LTS>(lts: &L, left: StateIndex, right: StateIndex, weak: bool) -> Option<Vec<L::Label>> {
+    // Closes the given set of states under hidden transitions, when weak is true.
+    let closure = |mut states: Vec<StateIndex>| -> Vec<StateIndex> {
+        if weak {
+            let mut stack = states.clone();
+            while let Some(state_index) = stack.pop() {
+                for transition in lts.outgoing_transitions(state_index) {
+                    if lts.is_hidden_label(transition.label) && !states.contains(&transition.to) {
+                        states.push(transition.to);
+                        stack.push(transition.to);
+                    }
+                }
+            }
+        }
+
+        states.sort();
+        states.dedup();
+        states
+    };
+
+    // Every explored pair of state sets, with the pair and action it was reached by.
+    type StatePair = (Vec<StateIndex>, Vec<StateIndex>);
+    let mut pairs: Vec<(StatePair, Option<(usize, LabelIndex)>)> = Vec::new();
+    let mut visited: FxHashMap<StatePair, usize> = FxHashMap::default();
+    let mut queue = VecDeque::new();
+
+    let initial = (closure(vec![left]), closure(vec![right]));
+    visited.insert(initial.clone(), 0);
+    pairs.push((initial, None));
+    queue.push_back(0);
+
+    // Reconstructs the trace that leads to the given pair, followed by the given label.
+    let trace = |pairs: &Vec<(StatePair, Option<(usize, LabelIndex)>)>, mut index: usize, label: LabelIndex| {
+        let mut trace = vec![lts.labels()[label].clone()];
+        while let Some((parent, label)) = pairs[index].1 {
+            trace.push(lts.labels()[label].clone());
+            index = parent;
         }
+
+        trace.reverse();
+        trace
+    };
+
+    while let Some(index) = queue.pop_front() {
+        // Compute the successors of both sets for every action.
+        let mut successors: BTreeMap<LabelIndex, StatePair> = BTreeMap::new();
+        let ((left_states, right_states), _) = &pairs[index];
+        for (states, is_left) in [(left_states, true), (right_states, false)] {
+            for state_index in states {
+                for transition in lts.outgoing_transitions(*state_index) {
+                    if weak && lts.is_hidden_label(transition.label) {
+                        continue;
+                    }
+
+                    let (left_successors, right_successors) = successors.entry(transition.label).or_default();
+                    if is_left {
+                        left_successors.push(transition.to);
+                    } else {
+                        right_successors.push(transition.to);
+                    }
+                }
+            }
+        }
+
+        for (label, (left_successors, right_successors)) in successors {
+            if left_successors.is_empty() || right_successors.is_empty() {
+                // Only one of the sets can perform this action.
+                return Some(trace(&pairs, index, label));
+            }
+
+            let pair = (closure(left_successors), closure(right_successors));
+            if !visited.contains_key(&pair) {
+                visited.insert(pair.clone(), pairs.len());
+                queue.push_back(pairs.len());
+                pairs.push((pair, Some((index, label))));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use merc_lts::read_aut;
+    use test_log::test;
+
+    use crate::reduce_lts;
+
+    use super::*;
+
+    #[test]
+    fn test_branching_differs_from_weak_bisim() {
+        // a.(tau.b + c) + a.b is weakly bisimilar to a.(tau.b + c), but not
+        // branching bisimilar since the a-step to b cannot be matched by an
+        // a-step to a branching bisimilar state.
+        let left = || {
+            read_aut(
+                b"des (0, 6, 5)
+                (0, \"a\", 1)
+                (1, \"i\", 3)
+                (3, \"b\", 4)
+                (1, \"c\", 4)
+                (0, \"a\", 2)
+                (2, \"b\", 4)" as &[u8],
+                vec![],
+            )
+            .unwrap()
+        };
+
+        let right = || {
+            read_aut(
+                b"des (0, 4, 4)
+                (0, \"a\", 1)
+                (1, \"i\", 2)
+                (2, \"b\", 3)
+                (1, \"c\", 3)" as &[u8],
+                vec![],
+            )
+            .unwrap()
+        };
+
+        let mut timing = Timing::new();
+        for equivalence in [Equivalence::WeakBisim, Equivalence::WeakBisimSigref] {
+            assert!(compare_lts(equivalence, left(), right(), &mut timing));
+        }
+
+        for equivalence in [
+            Equivalence::BranchingBisim,
+            Equivalence::BranchingBisimNaive,
+            Equivalence::StrongBisim,
+        ] {
+            assert!(!compare_lts(equivalence, left(), right(), &mut timing));
+        }
+    }
+
+    #[test]
+    fn test_divbranching_differs_from_branching_bisim() {
+        // a.(tau-loop + b) is branching bisimilar to a.b, but not divergence-preserving
+        // branching bisimilar since only the former can diverge after the a-step.
+        let left = || {
+            read_aut(
+                b"des (0, 3, 3)
+                (0, \"a\", 1)
+                (1, \"i\", 1)
+                (1, \"b\", 2)" as &[u8],
+                vec![],
+            )
+            .unwrap()
+        };
+
+        let right = || {
+            read_aut(
+                b"des (0, 2, 3)
+                (0, \"a\", 1)
+                (1, \"b\", 2)" as &[u8],
+                vec![],
+            )
+            .unwrap()
+        };
+
+        let mut timing = Timing::new();
+        assert!(compare_lts(Equivalence::BranchingBisim, left(), right(), &mut timing));
+        assert!(!compare_lts(
+            Equivalence::DivBranchingBisim,
+            left(),
+            right(),
+            &mut timing
+        ));
+        assert!(compare_lts(Equivalence::DivBranchingBisim, left(), left(), &mut timing));
+
+        // The reduction keeps the tau-loop of the divergent state.
+        let reduced = reduce_lts(left(), Equivalence::DivBranchingBisim, &mut timing);
+        assert_eq!(reduced.num_of_states(), 3);
+        assert_eq!(reduced.num_of_transitions(), 3);
+        assert!(compare_lts(
+            Equivalence::DivBranchingBisim,
+            left(),
+            reduced,
+            &mut timing
+        ));
+    }
+
+    #[test]
+    fn test_compare_lts_with_witness() {
+        // a.b + a.c is not bisimilar to a.(b + c), but no trace distinguishes them.
+        let left = read_aut(
+            b"des (0, 4, 4)
+            (0, \"a\", 1)
+            (0, \"a\", 2)
+            (1, \"b\", 3)
+            (2, \"c\", 3)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+
+        let right = read_aut(
+            b"des (0, 3, 3)
+            (0, \"a\", 1)
+            (1, \"b\", 2)
+            (1, \"c\", 2)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+
+        let mut timing = Timing::new();
+        assert_eq!(
+            compare_lts_with_witness(Equivalence::StrongBisim, left, right.clone(), &mut timing).unwrap(),
+            Some(vec![]),
+            "The LTSs are not equivalent, but have the same traces"
+        );
+
+        // The trace a.i can only be performed by the other LTS, and weakly a.c only by the right LTS.
+        let other = read_aut(
+            b"des (0, 3, 3)
+            (0, \"a\", 1)
+            (1, \"i\", 2)
+            (2, \"b\", 1)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+
+        assert_eq!(
+            compare_lts_with_witness(Equivalence::StrongBisim, right.clone(), other.clone(), &mut timing).unwrap(),
+            Some(vec!["a".to_string(), "i".to_string()])
+        );
+        assert_eq!(
+            compare_lts_with_witness(Equivalence::BranchingBisim, right.clone(), other, &mut timing).unwrap(),
+            Some(vec!["a".to_string(), "c".to_string()])
+        );
+        assert_eq!(
+            compare_lts_with_witness(Equivalence::StrongBisim, right.clone(), right, &mut timing).unwrap(),
+            None
+        );
     }
 }
@@ -0,0 +1,79 @@
+#![forbid(unsafe_code)]
+
+use merc_lts::LTS;
+use merc_lts::StateIndex;
+
+use crate::Partition;
+use crate::tau_scc_decomposition;
+
+/// The states of a labelled transition system found by [detect].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Detection {
+    /// The states without outgoing transitions.
+    pub deadlocks: Vec<StateIndex>,
+
+    /// The states that lie on a cycle of tau transitions.
+    pub divergent: Vec<StateIndex>,
+}
+
+/// Detects the deadlock states and the states on a divergent tau-cycle of the
+/// given LTS. Both are returned in increasing order of the state index.
+///
+/// # Details
+///
+/// A state is divergent whenever its strongly connected tau component contains
+/// at least two states, or it has a tau self-loop.
+pub fn detect(lts: &impl LTS) -> Detection {
+    let deadlocks = lts
+        .iter_states()
+        .filter(|state_index| lts.outgoing_transitions(*state_index).next().is_none())
+        .collect();
+
+    // Count the number of states in every tau component.
+    let partition = tau_scc_decomposition(lts);
+    let mut component_size = vec![0usize; partition.num_of_blocks()];
+    for state_index in lts.iter_states() {
+        component_size[partition.block_number(state_index)] += 1;
+    }
+
+    let divergent = lts
+        .iter_states()
+        .filter(|state_index| {
+            component_size[partition.block_number(*state_index)] > 1
+                || lts
+                    .outgoing_transitions(*state_index)
+                    .any(|transition| lts.is_hidden_label(transition.label) && transition.to == *state_index)
+        })
+        .collect();
+
+    Detection { deadlocks, divergent }
+}
+
+#[cfg(test)]
+mod tests {
+    use merc_lts::read_aut;
+
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        let lts = read_aut(
+            b"des (0, 6, 5)
+            (0, \"a\", 1)
+            (1, \"i\", 2)
+            (2, \"i\", 1)
+            (2, \"b\", 3)
+            (3, \"i\", 3)
+            (0, \"c\", 4)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+
+        let result = detect(&lts);
+        assert_eq!(result.deadlocks, vec![StateIndex::new(4)]);
+        assert_eq!(
+            result.divergent,
+            vec![StateIndex::new(1), StateIndex::new(2), StateIndex::new(3)]
+        );
+    }
+}
@@ -2,6 +2,7 @@
 
 mod block_partition;
 mod compare;
+mod detect;
 mod indexed_partition;
 mod quotient;
 mod reduce;
@@ -10,10 +11,12 @@ mod signature_refinement;
 mod signatures;
 mod simple_block_partition;
 mod sort_topological;
+mod tau_confluence;
 mod weak_bisimulation;
 
 pub use block_partition::*;
 pub use compare::*;
+pub use detect::*;
 pub use indexed_partition::*;
 pub use quotient::*;
 pub use reduce::*;
@@ -22,4 +25,5 @@ pub use signature_refinement::*;
 pub use signatures::*;
 pub use simple_block_partition::*;
 pub use sort_topological::*;
+pub use tau_confluence::*;
 pub use weak_bisimulation::*;
@@ -4,6 +4,7 @@ use merc_lts::LTS;
 use merc_lts::LabelledTransitionSystem;
 use merc_lts::LtsBuilderFast;
 use merc_lts::StateIndex;
+use merc_lts::TransitionLabel;
 use merc_utilities::TagIndex;
 
 use crate::BlockPartition;
@@ -35,6 +36,40 @@ pub trait Partition {
     }
 }
 
+/// Returns a new LTS based on the given partition, see [quotient_lts_naive]
+/// with eliminated tau-loops, where a tau-loop is kept for every block that
+/// contains a `divergent` state. This preserves the divergences for
+/// divergence-preserving branching bisimulation.
+pub fn quotient_lts_divergent<L: LTS>(
+    lts: &L,
+    partition: &impl Partition,
+    divergent: &[bool],
+) -> LabelledTransitionSystem<L::Label> {
+    let mut builder = LtsBuilderFast::with_capacity(lts.labels().into(), Vec::new(), partition.num_of_blocks());
+
+    for state_index in lts.iter_states() {
+        let block = StateIndex::new(partition.block_number(state_index).value());
+
+        for transition in lts.outgoing_transitions(state_index) {
+            let to_block = StateIndex::new(partition.block_number(transition.to).value());
+
+            if !(lts.is_hidden_label(transition.label) && block == to_block) {
+                builder.add_transition(block, &lts.labels()[transition.label], to_block);
+            }
+        }
+
+        if divergent[state_index] {
+            builder.add_transition(block, &L::Label::tau_label(), block);
+        }
+    }
+
+    builder.require_num_of_states(partition.num_of_blocks());
+    builder.finish(
+        StateIndex::new(partition.block_number(lts.initial_state_index()).value()),
+        true,
+    )
+}
+
 /// Returns a new LTS based on the given partition.
 ///
 /// The naive version will add the transitions of all states in the block to the quotient LTS.
@@ -78,6 +113,38 @@ pub fn quotient_lts_naive<L: LTS>(
     )
 }
 
+/// The same as [quotient_lts_naive], but also returns a representative state
+/// for every block, indexed by the block number, see [block_representatives].
+pub fn quotient_lts_naive_with_representatives<L: LTS>(
+    lts: &L,
+    partition: &impl Partition,
+    eliminate_tau_loops: bool,
+) -> (LabelledTransitionSystem<L::Label>, Vec<StateIndex>) {
+    (
+        quotient_lts_naive(lts, partition, eliminate_tau_loops),
+        block_representatives(lts, partition),
+    )
+}
+
+/// Returns a representative state for every block of the given partition,
+/// indexed by the block number. The representative is the state with the
+/// smallest index in the block.
+pub fn block_representatives(lts: &impl LTS, partition: &impl Partition) -> Vec<StateIndex> {
+    let mut representatives: Vec<Option<StateIndex>> = vec![None; partition.num_of_blocks()];
+
+    for state_index in lts.iter_states() {
+        let block = partition.block_number(state_index);
+        if representatives[block].is_none() {
+            representatives[block] = Some(state_index);
+        }
+    }
+
+    representatives
+        .into_iter()
+        .map(|state| state.expect("Blocks in the partition should not be empty"))
+        .collect()
+}
+
 /// Optimised implementation for block partitions.
 ///
 /// Chooses a single state in the block as representative. If BRANCHING then the chosen state is a bottom state.
@@ -85,7 +152,17 @@ pub fn quotient_lts_block<L: LTS, const BRANCHING: bool>(
     lts: &L,
     partition: &BlockPartition,
 ) -> LabelledTransitionSystem<L::Label> {
+    quotient_lts_block_with_representatives::<L, BRANCHING>(lts, partition).0
+}
+
+/// The same as [quotient_lts_block], but also returns the representative state
+/// whose transitions were taken for every block, indexed by the block number.
+pub fn quotient_lts_block_with_representatives<L: LTS, const BRANCHING: bool>(
+    lts: &L,
+    partition: &BlockPartition,
+) -> (LabelledTransitionSystem<L::Label>, Vec<StateIndex>) {
     let mut builder = LtsBuilderFast::new(lts.labels().into(), Vec::new());
+    let mut representatives = Vec::with_capacity(partition.num_of_blocks());
 
     for block in (0..partition.num_of_blocks()).map(BlockIndex::new) {
         // Pick any state in the block
@@ -128,11 +205,63 @@ pub fn quotient_lts_block<L: LTS, const BRANCHING: bool>(
                 StateIndex::new(*partition.block_number(transition.to)),
             );
         }
+
+        representatives.push(candidate);
     }
 
     builder.require_num_of_states(partition.num_of_blocks());
-    builder.finish(
-        StateIndex::new(partition.block_number(lts.initial_state_index()).value()),
-        true,
+    (
+        builder.finish(
+            StateIndex::new(partition.block_number(lts.initial_state_index()).value()),
+            true,
+        ),
+        representatives,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use merc_lts::random_lts;
+    use merc_utilities::Timing;
+    use merc_utilities::random_test;
+
+    use crate::branching_bisim_sigref;
+    use crate::strong_bisim_sigref;
+
+    use super::*;
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_random_quotient_representatives() {
+        random_test(100, |rng| {
+            let lts = random_lts(rng, 10, 3, 3);
+            let mut timing = Timing::new();
+
+            let (lts, partition) = strong_bisim_sigref(lts, &mut timing);
+            let (quotient, representatives) = quotient_lts_naive_with_representatives(&lts, &partition, false);
+            assert_eq!(representatives.len(), quotient.num_of_states());
+            for (block, representative) in representatives.iter().enumerate() {
+                assert_eq!(partition.block_number(*representative), block);
+
+                // The representative is the smallest state in its block.
+                assert!(
+                    lts.iter_states()
+                        .take(representative.value())
+                        .all(|state_index| partition.block_number(state_index) != block)
+                );
+            }
+
+            let (lts, partition) = branching_bisim_sigref(lts, &mut timing);
+            let (quotient, representatives) = quotient_lts_block_with_representatives::<_, true>(&lts, &partition);
+            assert_eq!(representatives.len(), quotient.num_of_states());
+            for (block, representative) in representatives.iter().enumerate() {
+                assert_eq!(partition.block_number(*representative), block);
+
+                // The representative is a bottom state of its block.
+                assert!(!lts.outgoing_transitions(*representative).any(|transition| {
+                    lts.is_hidden_label(transition.label) && partition.block_number(transition.to) == block
+                }));
+            }
+        });
+    }
+}
@@ -2,16 +2,29 @@
 
 use merc_lts::LTS;
 use merc_lts::LabelledTransitionSystem;
+use merc_lts::StateIndex;
 use merc_utilities::Timing;
 
+use crate::Partition;
 use crate::branching_bisim_sigref;
 use crate::branching_bisim_sigref_naive;
+use crate::branching_bisim_sigref_naive_preprocessed;
+use crate::branching_bisim_sigref_preprocessed;
+use crate::divbranching_bisim_sigref_naive;
+use crate::divbranching_bisim_sigref_naive_preprocessed;
+use crate::preprocess_branching_with_mapping;
+use crate::preprocess_divbranching_with_mapping;
 use crate::quotient_lts_block;
+use crate::quotient_lts_divergent;
 use crate::quotient_lts_naive;
+use crate::quotient_lts_naive_with_representatives;
 use crate::strong_bisim_sigref;
 use crate::strong_bisim_sigref_naive;
+use crate::strong_bisim_sigref_with_labels;
 use crate::weak_bisim_sigref_naive;
+use crate::weak_bisim_sigref_naive_preprocessed;
 use crate::weak_bisimulation;
+use crate::weak_bisimulation_preprocessed;
 
 #[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
@@ -24,6 +37,8 @@ pub enum Equivalence {
     StrongBisimNaive,
     BranchingBisim,
     BranchingBisimNaive,
+    /// Divergence-preserving branching bisimulation.
+    DivBranchingBisim,
 }
 
 /// Reduces the given LTS modulo the given equivalence using signature refinement
@@ -59,8 +74,198 @@ pub fn reduce_lts<L: LTS>(lts: L, equivalence: Equivalence, timing: &mut Timing)
             let quotient_time = timing.start("quotient");
             (quotient_lts_naive(&lts, &partition, true), quotient_time)
         }
+        Equivalence::DivBranchingBisim => {
+            let (lts, partition, divergent) = divbranching_bisim_sigref_naive(lts, timing);
+            let quotient_time = timing.start("quotient");
+            (quotient_lts_divergent(&lts, &partition, &divergent), quotient_time)
+        }
     };
 
     timer.finish();
     result
 }
+
+/// Reduces the given LTS modulo the given equivalence, see [reduce_lts], and
+/// additionally returns a mapping indexed by the states of the given LTS. This
+/// mapping yields the block of every state, which is also the corresponding
+/// state in the reduced LTS.
+pub fn reduce_lts_with_mapping<L: LTS>(
+    lts: L,
+    equivalence: Equivalence,
+    timing: &mut Timing,
+) -> (LabelledTransitionSystem<L::Label>, Vec<usize>) {
+    match equivalence {
+        Equivalence::StrongBisim => {
+            let (lts, partition) = strong_bisim_sigref(lts, timing);
+            let mut quotient_time = timing.start("quotient");
+            let result = quotient_lts_block::<_, false>(&lts, &partition);
+            let mapping = block_mapping(lts.iter_states(), &partition);
+            quotient_time.finish();
+            (result, mapping)
+        }
+        Equivalence::StrongBisimNaive => {
+            let (lts, partition) = strong_bisim_sigref_naive(lts, timing);
+            let mut quotient_time = timing.start("quotient");
+            let result = quotient_lts_naive(&lts, &partition, false);
+            let mapping = block_mapping(lts.iter_states(), &partition);
+            quotient_time.finish();
+            (result, mapping)
+        }
+        Equivalence::DivBranchingBisim => {
+            // The divergences are removed by the branching preprocessing, so they are preserved separately.
+            let mut time_pre = timing.start("preprocess");
+            let (lts, preprocess_mapping, divergent) = preprocess_divbranching_with_mapping(lts);
+            time_pre.finish();
+
+            let partition = divbranching_bisim_sigref_naive_preprocessed(&lts, &divergent, timing);
+            let mut quotient_time = timing.start("quotient");
+            let result = quotient_lts_divergent(&lts, &partition, &divergent);
+            let mapping = block_mapping(preprocess_mapping.into_iter(), &partition);
+            quotient_time.finish();
+            (result, mapping)
+        }
+        _ => {
+            // The other equivalences reduce the preprocessed LTS, so the mapping must be composed.
+            let mut time_pre = timing.start("preprocess");
+            let (lts, preprocess_mapping) = preprocess_branching_with_mapping(lts);
+            time_pre.finish();
+
+            let (result, mut quotient_time, mapping) = match equivalence {
+                Equivalence::WeakBisim => {
+                    let partition = weak_bisimulation_preprocessed(&lts, timing);
+                    let quotient_time = timing.start("quotient");
+                    (
+                        quotient_lts_naive(&lts, &partition, true),
+                        quotient_time,
+                        block_mapping(preprocess_mapping.into_iter(), &partition),
+                    )
+                }
+                Equivalence::WeakBisimSigref => {
+                    let partition = weak_bisim_sigref_naive_preprocessed(&lts, timing);
+                    let quotient_time = timing.start("quotient");
+                    (
+                        quotient_lts_naive(&lts, &partition, true),
+                        quotient_time,
+                        block_mapping(preprocess_mapping.into_iter(), &partition),
+                    )
+                }
+                Equivalence::BranchingBisim => {
+                    let partition = branching_bisim_sigref_preprocessed(&lts, timing);
+                    let quotient_time = timing.start("quotient");
+                    (
+                        quotient_lts_block::<_, true>(&lts, &partition),
+                        quotient_time,
+                        block_mapping(preprocess_mapping.into_iter(), &partition),
+                    )
+                }
+                Equivalence::BranchingBisimNaive => {
+                    let partition = branching_bisim_sigref_naive_preprocessed(&lts, timing);
+                    let quotient_time = timing.start("quotient");
+                    (
+                        quotient_lts_naive(&lts, &partition, true),
+                        quotient_time,
+                        block_mapping(preprocess_mapping.into_iter(), &partition),
+                    )
+                }
+                Equivalence::StrongBisim | Equivalence::StrongBisimNaive => {
+                    unreachable!("Strong bisimulation does not require preprocessing")
+                }
+                Equivalence::DivBranchingBisim => {
+                    unreachable!("Divergence-preserving branching bisimulation requires its own preprocessing")
+                }
+            };
+
+            quotient_time.finish();
+            (result, mapping)
+        }
+    }
+}
+
+/// Returns the block number for each of the given states.
+fn block_mapping(states: impl Iterator<Item = StateIndex>, partition: &impl Partition) -> Vec<usize> {
+    states
+        .map(|state_index| partition.block_number(state_index).value())
+        .collect()
+}
+
+/// Reduces the given LTS modulo strong bisimulation, where two states can only
+/// be related when they have the same label in `state_labels`.
+///
+/// Returns the reduced LTS, in which state `i` corresponds to block `i`, and a
+/// representative (original) state for every block. The latter can be used to
+/// map the state labels onto the reduced LTS.
+pub fn reduce_lts_with_labels<L: LTS>(
+    lts: L,
+    state_labels: &[u32],
+    timing: &mut Timing,
+) -> (LabelledTransitionSystem<L::Label>, Vec<StateIndex>) {
+    let (lts, partition) = strong_bisim_sigref_with_labels(lts, state_labels, timing);
+
+    let mut quotient_time = timing.start("quotient");
+    let (result, representatives) = quotient_lts_naive_with_representatives(&lts, &partition, false);
+    quotient_time.finish();
+
+    (result, representatives)
+}
+
+#[cfg(test)]
+mod tests {
+    use merc_lts::random_lts;
+    use merc_utilities::random_test;
+    use test_log::test;
+
+    use super::*;
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_random_reduce_lts_with_mapping() {
+        random_test(100, |rng| {
+            let lts = random_lts(rng, 10, 3, 3);
+            let mut timing = Timing::new();
+
+            // Strong bisimulation preserves every transition under the mapping.
+            let (reduced, mapping) = reduce_lts_with_mapping(lts.clone(), Equivalence::StrongBisim, &mut timing);
+            assert_eq!(mapping.len(), lts.num_of_states());
+            assert_eq!(
+                mapping[lts.initial_state_index()],
+                reduced.initial_state_index().value()
+            );
+
+            for state_index in lts.iter_states() {
+                for transition in lts.outgoing_transitions(state_index) {
+                    let label = &lts.labels()[transition.label];
+                    assert!(
+                        reduced
+                            .outgoing_transitions(StateIndex::new(mapping[state_index]))
+                            .any(|reduced_transition| reduced_transition.to == mapping[transition.to]
+                                && reduced.labels()[reduced_transition.label] == *label),
+                        "Transition {state_index} -[{label}]-> {} is not preserved by the mapping",
+                        transition.to
+                    );
+                }
+            }
+
+            // The mapping must agree with the reduced LTS for all equivalences.
+            for equivalence in [
+                Equivalence::StrongBisimNaive,
+                Equivalence::BranchingBisim,
+                Equivalence::BranchingBisimNaive,
+                Equivalence::DivBranchingBisim,
+                Equivalence::WeakBisim,
+                Equivalence::WeakBisimSigref,
+            ] {
+                let (reduced, mapping) = reduce_lts_with_mapping(lts.clone(), equivalence, &mut timing);
+                assert_eq!(mapping.len(), lts.num_of_states());
+                assert_eq!(
+                    mapping[lts.initial_state_index()],
+                    reduced.initial_state_index().value()
+                );
+                assert!(mapping.iter().all(|block| *block < reduced.num_of_states()));
+                assert_eq!(
+                    reduced.num_of_states(),
+                    reduce_lts(lts.clone(), equivalence, &mut timing).num_of_states()
+                );
+            }
+        });
+    }
+}
@@ -5,6 +5,8 @@ use log::trace;
 use merc_io::LargeFormatter;
 use merc_lts::LTS;
 use merc_lts::LabelIndex;
+use merc_lts::LabelledTransitionSystem;
+use merc_lts::LtsBuilderFast;
 use merc_lts::StateIndex;
 
 use crate::BlockIndex;
@@ -59,6 +61,48 @@ where
     partition
 }
 
+/// Computes the condensation of the given LTS, which is the acyclic graph in
+/// which every strongly connected component (over all transitions) is a single
+/// state. Returns the component of every state together with the condensation.
+///
+/// # Details
+///
+/// The transitions of the condensation are the transitions between different
+/// components, where duplicate transitions are removed. The initial state is
+/// the component of the initial state.
+pub fn condensation<L: LTS>(lts: &L) -> (Vec<usize>, LabelledTransitionSystem<L::Label>) {
+    let partition = scc_decomposition(lts, &|_, _, _| true);
+
+    let mut builder = LtsBuilderFast::with_capacity(lts.labels().into(), Vec::new(), lts.num_of_transitions());
+    for state_index in lts.iter_states() {
+        let block = partition.block_number(state_index);
+
+        for transition in lts.outgoing_transitions(state_index) {
+            let to_block = partition.block_number(transition.to);
+            if block != to_block {
+                builder.add_transition(
+                    StateIndex::new(block.value()),
+                    &lts.labels()[transition.label],
+                    StateIndex::new(to_block.value()),
+                );
+            }
+        }
+    }
+
+    builder.require_num_of_states(partition.num_of_blocks());
+    let condensation = builder.finish(
+        StateIndex::new(partition.block_number(lts.initial_state_index()).value()),
+        true,
+    );
+
+    let components = lts
+        .iter_states()
+        .map(|state_index| partition.block_number(state_index).value())
+        .collect();
+
+    (components, condensation)
+}
+
 #[derive(Clone, Debug)]
 struct StateInfo {
     /// A unique index for every state.
@@ -274,4 +318,27 @@ mod tests {
 
         let _ = tau_scc_decomposition(&lts);
     }
+
+    #[test]
+    fn test_condensation() {
+        // The components {0, 1} and {2, 3} are connected by the transition from 1 to 2.
+        let transitions = [(0, 0, 1), (1, 1, 0), (1, 0, 2), (2, 1, 3), (3, 0, 2)]
+            .map(|(from, label, to)| (StateIndex::new(from), LabelIndex::new(label), StateIndex::new(to)));
+
+        let lts = LabelledTransitionSystem::new(
+            StateIndex::new(0),
+            None,
+            || transitions.iter().cloned(),
+            vec!["tau".to_string(), "a".to_string()],
+        );
+
+        let (components, condensation) = condensation(&lts);
+        assert_eq!(components[0], components[1]);
+        assert_eq!(components[2], components[3]);
+        assert_ne!(components[0], components[2]);
+
+        assert_eq!(condensation.num_of_states(), 2);
+        assert_eq!(condensation.num_of_transitions(), 1);
+        assert_eq!(*condensation.initial_state_index(), components[0]);
+    }
 }
@@ -1,9 +1,11 @@
 use std::mem::swap;
+use std::thread;
 
 use bumpalo::Bump;
 use log::debug;
 use log::info;
 use log::trace;
+use merc_collections::IndexedSet;
 use merc_io::TimeProgress;
 use merc_lts::IncomingTransitions;
 use merc_lts::LTS;
@@ -13,6 +15,7 @@ use merc_lts::StateIndex;
 use rustc_hash::FxHashMap;
 use rustc_hash::FxHashSet;
 
+use merc_sharedmutex::BfSharedMutex;
 use merc_utilities::Timing;
 
 use crate::BlockIndex;
@@ -25,8 +28,10 @@ use crate::SignatureBuilder;
 use crate::branching_bisim_signature;
 use crate::branching_bisim_signature_inductive;
 use crate::branching_bisim_signature_sorted;
+use crate::divbranching_bisim_signature_sorted;
 use crate::is_tau_hat;
 use crate::preprocess_branching;
+use crate::preprocess_divbranching_with_mapping;
 use crate::strong_bisim_signature;
 use crate::weak_bisim_signature_sorted;
 use crate::weak_bisim_signature_sorted_taus;
@@ -54,7 +59,73 @@ pub fn strong_bisim_sigref<L: LTS>(lts: L, timing: &mut Timing) -> (L, BlockPart
 /// Computes a strong bisimulation partitioning using signature refinement
 pub fn strong_bisim_sigref_naive<L: LTS>(lts: L, timing: &mut Timing) -> (L, IndexedPartition) {
     let mut time = timing.start("reduction");
-    let partition = signature_refinement_naive::<_, _, false>(&lts, |state_index, partition, _, builder| {
+    let partition = signature_refinement_naive::<_, _, false>(
+        &lts,
+        IndexedPartition::new(lts.num_of_states()),
+        |state_index, partition, _, builder| {
+            strong_bisim_signature(state_index, &lts, partition, builder);
+        },
+    );
+
+    time.finish();
+    (lts, partition)
+}
+
+/// Computes a strong bisimulation partitioning using signature refinement,
+/// where the initial partition is given by the `state_labels`. As such, two
+/// states can only be related when they have the same initial label.
+pub fn strong_bisim_sigref_with_labels<L: LTS>(
+    lts: L,
+    state_labels: &[u32],
+    timing: &mut Timing,
+) -> (L, IndexedPartition) {
+    assert_eq!(
+        state_labels.len(),
+        lts.num_of_states(),
+        "Every state should have exactly one label"
+    );
+
+    // Number the labels densely to obtain the initial partition.
+    let mut label_to_block: FxHashMap<u32, BlockIndex> = FxHashMap::default();
+    let mut initial_partition = IndexedPartition::new(lts.num_of_states());
+    for state_index in lts.iter_states() {
+        let next_block = BlockIndex::new(label_to_block.len());
+        let block = *label_to_block.entry(state_labels[state_index]).or_insert(next_block);
+        initial_partition.set_block(state_index, block);
+    }
+
+    // A label that is not used by any transition, used to include the current block in the signature.
+    let current_block_label = LabelIndex::new(lts.num_of_labels());
+
+    let mut time = timing.start("reduction");
+    let partition =
+        signature_refinement_naive::<_, _, false>(&lts, initial_partition, |state_index, partition, _, builder| {
+            strong_bisim_signature(state_index, &lts, partition, builder);
+
+            // Only split blocks, since the initial partition is not trivial. Remains sorted since the label is the largest.
+            builder.push((current_block_label, partition.block_number(state_index)));
+        });
+
+    time.finish();
+    (lts, partition)
+}
+
+/// Computes a strong bisimulation partitioning using signature refinement,
+/// where the signatures are computed by `num_of_threads` threads in parallel.
+///
+/// The resulting partition is identical to the one of
+/// [strong_bisim_sigref_naive], to which it falls back for a single thread.
+pub fn strong_bisim_sigref_parallel<L: LTS + Sync>(
+    lts: L,
+    num_of_threads: usize,
+    timing: &mut Timing,
+) -> (L, IndexedPartition) {
+    if num_of_threads <= 1 {
+        return strong_bisim_sigref_naive(lts, timing);
+    }
+
+    let mut time = timing.start("reduction");
+    let partition = signature_refinement_parallel(&lts, num_of_threads, |state_index, partition, builder| {
         strong_bisim_signature(state_index, &lts, partition, builder);
     });
 
@@ -69,25 +140,35 @@ pub fn branching_bisim_sigref<L: LTS>(
 ) -> (LabelledTransitionSystem<L::Label>, BlockPartition) {
     let mut timepre = timing.start("preprocess");
     let preprocessed_lts = preprocess_branching(lts);
-    let incoming = IncomingTransitions::new(&preprocessed_lts);
     timepre.finish();
 
+    let partition = branching_bisim_sigref_preprocessed(&preprocessed_lts, timing);
+    (preprocessed_lts, partition)
+}
+
+/// The same as [branching_bisim_sigref], but for an LTS that has already been
+/// preprocessed by [preprocess_branching].
+pub fn branching_bisim_sigref_preprocessed<L: LTS>(preprocessed_lts: &L, timing: &mut Timing) -> BlockPartition {
+    let mut time_incoming = timing.start("incoming transitions");
+    let incoming = IncomingTransitions::new(preprocessed_lts);
+    time_incoming.finish();
+
     let mut time = timing.start("reduction");
     let mut expected_builder = SignatureBuilder::default();
     let mut visited = FxHashSet::default();
     let mut stack = Vec::new();
 
     let partition = signature_refinement::<_, _, true>(
-        &preprocessed_lts,
+        preprocessed_lts,
         &incoming,
         |state_index, partition, state_to_key, builder| {
-            branching_bisim_signature_inductive(state_index, &preprocessed_lts, partition, state_to_key, builder);
+            branching_bisim_signature_inductive(state_index, preprocessed_lts, partition, state_to_key, builder);
 
             // Compute the expected signature, only used in debugging.
             if cfg!(debug_assertions) {
                 branching_bisim_signature(
                     state_index,
-                    &preprocessed_lts,
+                    preprocessed_lts,
                     partition,
                     &mut expected_builder,
                     &mut visited,
@@ -106,13 +187,13 @@ pub fn branching_bisim_sigref<L: LTS>(
         |signature, key_to_signature| {
             // Inductive signatures.
             for (label, key) in signature.iter().rev() {
-                if is_tau_hat(*label, &preprocessed_lts)
+                if is_tau_hat(*label, preprocessed_lts)
                     && key_to_signature[*key].is_subset_of(signature, (*label, *key))
                 {
                     return Some(*key);
                 }
 
-                if !is_tau_hat(*label, &preprocessed_lts) {
+                if !is_tau_hat(*label, preprocessed_lts) {
                     return None;
                 }
             }
@@ -123,8 +204,7 @@ pub fn branching_bisim_sigref<L: LTS>(
 
     time.finish();
 
-    // Combine the SCC partition with the branching bisimulation partition.
-    (preprocessed_lts, partition)
+    partition
 }
 
 /// Computes a branching bisimulation partitioning using signature refinement without dirty blocks.
@@ -136,21 +216,32 @@ pub fn branching_bisim_sigref_naive<L: LTS>(
     let preprocessed_lts = preprocess_branching(lts);
     timepre.finish();
 
+    let partition = branching_bisim_sigref_naive_preprocessed(&preprocessed_lts, timing);
+    (preprocessed_lts, partition)
+}
+
+/// The same as [branching_bisim_sigref_naive], but for an LTS that has already been
+/// preprocessed by [preprocess_branching].
+pub fn branching_bisim_sigref_naive_preprocessed<L: LTS>(
+    preprocessed_lts: &L,
+    timing: &mut Timing,
+) -> IndexedPartition {
     let mut time = timing.start("reduction");
     let mut expected_builder = SignatureBuilder::default();
     let mut visited = FxHashSet::default();
     let mut stack = Vec::new();
 
     let partition = signature_refinement_naive::<_, _, false>(
-        &preprocessed_lts,
+        preprocessed_lts,
+        IndexedPartition::new(preprocessed_lts.num_of_states()),
         |state_index, partition, state_to_signature, builder| {
-            branching_bisim_signature_sorted(state_index, &preprocessed_lts, partition, state_to_signature, builder);
+            branching_bisim_signature_sorted(state_index, preprocessed_lts, partition, state_to_signature, builder);
 
             // Compute the expected signature, only used in debugging.
             if cfg!(debug_assertions) {
                 branching_bisim_signature(
                     state_index,
-                    &preprocessed_lts,
+                    preprocessed_lts,
                     partition,
                     &mut expected_builder,
                     &mut visited,
@@ -169,7 +260,58 @@ pub fn branching_bisim_sigref_naive<L: LTS>(
     );
     time.finish();
 
-    (preprocessed_lts, partition)
+    partition
+}
+
+/// Computes a divergence-preserving branching bisimulation partitioning using
+/// signature refinement without dirty blocks. Also returns whether every state
+/// of the preprocessed LTS is divergent, see [preprocess_divbranching_with_mapping].
+///
+/// # Details
+///
+/// The preprocessing removes the tau-cycles, after which a state can diverge
+/// within its block iff it is divergent itself, or it has an inert
+/// tau-transition to a state that can diverge within the block. This is
+/// included in the branching bisimulation signature, such that the resulting
+/// partition only differs from branching bisimulation on divergent states.
+pub fn divbranching_bisim_sigref_naive<L: LTS>(
+    lts: L,
+    timing: &mut Timing,
+) -> (LabelledTransitionSystem<L::Label>, IndexedPartition, Vec<bool>) {
+    let mut timepre = timing.start("preprocess");
+    let (preprocessed_lts, _, divergent) = preprocess_divbranching_with_mapping(lts);
+    timepre.finish();
+
+    let partition = divbranching_bisim_sigref_naive_preprocessed(&preprocessed_lts, &divergent, timing);
+    (preprocessed_lts, partition, divergent)
+}
+
+/// The same as [divbranching_bisim_sigref_naive], but for an LTS that has
+/// already been preprocessed by [preprocess_divbranching_with_mapping].
+pub fn divbranching_bisim_sigref_naive_preprocessed<L: LTS>(
+    preprocessed_lts: &L,
+    divergent: &[bool],
+    timing: &mut Timing,
+) -> IndexedPartition {
+    let mut time = timing.start("reduction");
+
+    let partition = signature_refinement_naive::<_, _, false>(
+        preprocessed_lts,
+        IndexedPartition::new(preprocessed_lts.num_of_states()),
+        |state_index, partition, state_to_signature, builder| {
+            divbranching_bisim_signature_sorted(
+                state_index,
+                preprocessed_lts,
+                partition,
+                state_to_signature,
+                divergent,
+                builder,
+            )
+        },
+    );
+    time.finish();
+
+    partition
 }
 
 /// Computes a branching bisimulation partitioning using signature refinement without dirty blocks.
@@ -181,17 +323,25 @@ pub fn weak_bisim_sigref_naive<L: LTS>(
     let preprocessed_lts = preprocess_branching(lts);
     timepre.finish();
 
+    let partition = weak_bisim_sigref_naive_preprocessed(&preprocessed_lts, timing);
+    (preprocessed_lts, partition)
+}
+
+/// The same as [weak_bisim_sigref_naive], but for an LTS that has already been
+/// preprocessed by [preprocess_branching].
+pub fn weak_bisim_sigref_naive_preprocessed<L: LTS>(preprocessed_lts: &L, timing: &mut Timing) -> IndexedPartition {
     let mut time = timing.start("reduction");
 
     let partition = signature_refinement_naive::<_, _, true>(
-        &preprocessed_lts,
+        preprocessed_lts,
+        IndexedPartition::new(preprocessed_lts.num_of_states()),
         |state_index, partition, state_to_signature, builder| {
-            weak_bisim_signature_sorted(state_index, &preprocessed_lts, partition, state_to_signature, builder)
+            weak_bisim_signature_sorted(state_index, preprocessed_lts, partition, state_to_signature, builder)
         },
     );
     time.finish();
 
-    (preprocessed_lts, partition)
+    partition
 }
 
 /// General signature refinement algorithm that accepts an arbitrary signature
@@ -348,7 +498,15 @@ where
 /// The signature function is called for each state and should fill the
 /// signature builder with the signature of the state. It consists of the
 /// current partition, the signatures per state for the next partition.
-fn signature_refinement_naive<F, L: LTS, const WEAK: bool>(lts: &L, mut signature: F) -> IndexedPartition
+///
+/// The refinement starts from the given `initial_partition`, whenever this is
+/// not the trivial partition the signature must include the current block to
+/// ensure that the result is a refinement of it.
+fn signature_refinement_naive<F, L: LTS, const WEAK: bool>(
+    lts: &L,
+    initial_partition: IndexedPartition,
+    mut signature: F,
+) -> IndexedPartition
 where
     F: FnMut(StateIndex, &IndexedPartition, &Vec<Signature<'_>>, &mut SignatureBuilder),
 {
@@ -361,7 +519,7 @@ where
 
     // Assigns the signature to each state.
     let mut partition = IndexedPartition::new(lts.num_of_states());
-    let mut next_partition = IndexedPartition::new(lts.num_of_states());
+    let mut next_partition = initial_partition;
     let mut state_to_signature: Vec<Signature<'_>> = Vec::new();
     state_to_signature.resize_with(lts.num_of_states(), Signature::default);
 
@@ -463,6 +621,104 @@ where
     partition
 }
 
+/// Signature refinement algorithm where the signatures of the states are
+/// computed in parallel, see [signature_refinement_naive].
+///
+/// # Details
+///
+/// Every thread computes the signatures of a consecutive chunk of states and
+/// inserts them into a shared [IndexedSet]. Afterwards, the indices of this set
+/// are renumbered in the order of the states, which ensures that the block
+/// numbers are independent of the scheduling of the threads.
+fn signature_refinement_parallel<F, L: LTS + Sync>(lts: &L, num_of_threads: usize, signature: F) -> IndexedPartition
+where
+    F: Fn(StateIndex, &IndexedPartition, &mut SignatureBuilder) + Sync,
+{
+    let num_of_states = lts.num_of_states();
+    let chunk_size = num_of_states.div_ceil(num_of_threads).max(1);
+
+    let mut partition = IndexedPartition::new(num_of_states);
+    let mut next_partition = IndexedPartition::new(num_of_states);
+
+    // The index of the signature of every state in the shared set.
+    let mut state_to_key: Vec<usize> = vec![0; num_of_states];
+
+    // Refine partitions until stable.
+    let mut old_count = 1;
+    let mut count = 0;
+    let mut iteration = 0;
+
+    let progress = TimeProgress::new(
+        |(iteration, blocks)| {
+            debug!("Iteration {iteration}, found {blocks} blocks...",);
+        },
+        5,
+    );
+
+    while old_count != count {
+        old_count = count;
+        progress.print((iteration, old_count));
+        swap(&mut partition, &mut next_partition);
+
+        let signatures: BfSharedMutex<IndexedSet<SignatureBuilder>> = BfSharedMutex::new(IndexedSet::new());
+
+        thread::scope(|scope| {
+            for (chunk_index, keys) in state_to_key.chunks_mut(chunk_size).enumerate() {
+                let signatures = signatures.clone();
+                let partition = &partition;
+                let signature = &signature;
+
+                scope.spawn(move || {
+                    let mut builder = SignatureBuilder::default();
+
+                    for (offset, key) in keys.iter_mut().enumerate() {
+                        let state_index = StateIndex::new(chunk_index * chunk_size + offset);
+                        signature(state_index, partition, &mut builder);
+
+                        // Most signatures already exist, so first try to find it with shared access.
+                        let existing = signatures
+                            .read()
+                            .expect("Failed to lock the signatures")
+                            .index(&builder);
+                        *key = match existing {
+                            Some(index) => *index,
+                            None => {
+                                *signatures
+                                    .write()
+                                    .expect("Failed to lock the signatures")
+                                    .insert(builder.clone())
+                                    .0
+                            }
+                        };
+                    }
+                });
+            }
+        });
+
+        // Renumber the signatures in the order of the states, as the sequential algorithm would.
+        let mut key_to_block: Vec<Option<BlockIndex>> = vec![None; signatures.read().expect("Failed to lock").len()];
+        count = 0;
+        for state_index in lts.iter_states() {
+            let block = *key_to_block[state_to_key[state_index]].get_or_insert_with(|| {
+                count += 1;
+                BlockIndex::new(count - 1)
+            });
+
+            next_partition.set_block(state_index, block);
+        }
+
+        iteration += 1;
+
+        debug_assert!(
+            iteration <= lts.num_of_states().max(2),
+            "There can never be more splits than number of states, but at least two iterations for stability"
+        );
+    }
+
+    trace!("Refinement partition {partition}");
+    partition
+}
+
 /// Returns true iff the given partition is a strong bisimulation partition
 pub fn is_valid_refinement<F, P>(lts: &impl LTS, partition: &P, mut compute_signature: F) -> bool
 where
@@ -519,6 +775,7 @@ where
 mod tests {
     use super::*;
 
+    use rand::Rng;
     use test_log::test;
 
     use merc_lts::random_lts;
@@ -583,6 +840,22 @@ mod tests {
         });
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_random_strong_bisim_sigref_parallel() {
+        random_test(100, |rng| {
+            let lts = random_lts(rng, 10, 3, 3);
+            let mut timing = Timing::new();
+
+            let (_result_lts, result_partition) = strong_bisim_sigref_parallel(lts.clone(), 4, &mut timing);
+            let (_expected_lts, expected_partition) = strong_bisim_sigref_naive(lts, &mut timing);
+
+            // The block numbers should be identical, regardless of the thread scheduling.
+            assert_eq!(result_partition.num_of_blocks(), expected_partition.num_of_blocks());
+            assert!(result_partition.iter().eq(expected_partition.iter()));
+        });
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)] // Miri is too slow
     fn test_random_branching_bisim_sigref() {
@@ -598,6 +871,38 @@ mod tests {
         });
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_random_strong_bisim_sigref_with_labels() {
+        random_test(100, |rng| {
+            let lts = random_lts(rng, 10, 3, 3);
+            let mut timing = Timing::new();
+
+            // A single label should result in the strong bisimulation partition.
+            let (_, expected_partition) = strong_bisim_sigref_naive(lts.clone(), &mut timing);
+            let (_, result_partition) =
+                strong_bisim_sigref_with_labels(lts.clone(), &vec![0; lts.num_of_states()], &mut timing);
+            assert!(equal_partitions(&result_partition, &expected_partition));
+
+            // Related states must have the same label, and be strongly bisimilar.
+            let state_labels: Vec<u32> = lts.iter_states().map(|_| rng.random_range(0..3)).collect();
+            let (_, labelled_partition) = strong_bisim_sigref_with_labels(lts.clone(), &state_labels, &mut timing);
+            for state_index in lts.iter_states() {
+                for other_state_index in lts.iter_states() {
+                    if labelled_partition.block_number(state_index)
+                        == labelled_partition.block_number(other_state_index)
+                    {
+                        assert_eq!(state_labels[state_index], state_labels[other_state_index]);
+                        assert_eq!(
+                            expected_partition.block_number(state_index),
+                            expected_partition.block_number(other_state_index)
+                        );
+                    }
+                }
+            }
+        });
+    }
+
     /// Checks that the branching bisimulation partition is a refinement of the strong bisimulation partition.
     fn is_refinement(lts: &impl LTS, strong_partition: &impl Partition, branching_partition: &impl Partition) {
         for state_index in lts.iter_states() {
@@ -628,6 +933,25 @@ mod tests {
         });
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_random_divbranching_bisim_sigref_naive() {
+        random_test(100, |rng| {
+            let lts = random_lts(rng, 10, 3, 3);
+            let mut timing = Timing::new();
+
+            let (preprocessed_lts, divbranching_partition, divergent) =
+                divbranching_bisim_sigref_naive(lts, &mut timing);
+            let branching_partition = branching_bisim_sigref_naive_preprocessed(&preprocessed_lts, &mut timing);
+            is_refinement(&preprocessed_lts, &divbranching_partition, &branching_partition);
+
+            // Without divergent states both equivalences coincide.
+            if !divergent.contains(&true) {
+                assert!(equal_partitions(&divbranching_partition, &branching_partition));
+            }
+        });
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)] // Miri is too slow
     fn test_random_weak_bisim_sigref_naive() {
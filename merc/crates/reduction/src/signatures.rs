@@ -205,6 +205,28 @@ pub fn branching_bisim_signature_sorted(
     builder.dedup();
 }
 
+/// The same as [branching_bisim_signature_sorted], but additionally includes
+/// whether the state can diverge within its own block. The `divergent` states
+/// are the states on a tau-cycle before preprocessing, see
+/// [preprocess_divbranching_with_mapping].
+pub fn divbranching_bisim_signature_sorted(
+    state_index: StateIndex,
+    lts: &impl LTS,
+    partition: &impl Partition,
+    state_to_signature: &[Signature],
+    divergent: &[bool],
+    builder: &mut SignatureBuilder,
+) {
+    branching_bisim_signature_sorted(state_index, lts, partition, state_to_signature, builder);
+
+    // The divergence of a state is inherited through the signatures of the inert tau-transitions.
+    let divergence = (tau_hat(lts), BlockIndex::new(0));
+    if divergent[state_index] && builder.last() != Some(&divergence) {
+        // Remains sorted since the tau_hat label is the largest.
+        builder.push(divergence);
+    }
+}
+
 /// The inductive version of [branching_bisim_signature_sorted]. Assumes that
 /// the input LTS has no tau-cycles, and is topologically sorted.
 pub fn branching_bisim_signature_inductive(
@@ -299,6 +321,12 @@ pub fn weak_bisim_signature_sorted_taus(
 /// Perform the preprocessing necessary for branching bisimulation with the
 /// sorted signature see [branching_bisim_signature_sorted].
 pub fn preprocess_branching<L: LTS>(lts: L) -> LabelledTransitionSystem<L::Label> {
+    preprocess_branching_with_mapping(lts).0
+}
+
+/// The same as [preprocess_branching], but also returns the corresponding
+/// state in the preprocessed LTS for every state of the given LTS.
+pub fn preprocess_branching_with_mapping<L: LTS>(lts: L) -> (LabelledTransitionSystem<L::Label>, Vec<StateIndex>) {
     let scc_partition = tau_scc_decomposition(&lts);
     let tau_loop_free_lts = quotient_lts_naive(&lts, &scc_partition, true);
     drop(lts);
@@ -311,5 +339,42 @@ pub fn preprocess_branching<L: LTS>(lts: L) -> LabelledTransitionSystem<L::Label
     )
     .expect("After quotienting, the LTS should not contain cycles");
 
-    LabelledTransitionSystem::new_from_permutation(tau_loop_free_lts, |i| topological_permutation[i])
+    // Every state is first mapped to its strongly connected component, which is then permuted.
+    let mapping = scc_partition
+        .iter()
+        .map(|block| topological_permutation[block.value()])
+        .collect();
+
+    (
+        LabelledTransitionSystem::new_from_permutation(tau_loop_free_lts, |i| topological_permutation[i]),
+        mapping,
+    )
+}
+
+/// The same as [preprocess_branching_with_mapping], but also returns for every
+/// state of the preprocessed LTS whether it is divergent, i.e., whether its
+/// strongly connected tau component contains a tau-cycle.
+pub fn preprocess_divbranching_with_mapping<L: LTS>(
+    lts: L,
+) -> (LabelledTransitionSystem<L::Label>, Vec<StateIndex>, Vec<bool>) {
+    // The preprocessing removes the tau-cycles, so the divergent states are determined beforehand.
+    let scc_partition = tau_scc_decomposition(&lts);
+    let divergent_states: Vec<StateIndex> = lts
+        .iter_states()
+        .filter(|state_index| {
+            lts.outgoing_transitions(*state_index).any(|transition| {
+                lts.is_hidden_label(transition.label)
+                    && scc_partition.block_number(*state_index) == scc_partition.block_number(transition.to)
+            })
+        })
+        .collect();
+
+    let (preprocessed_lts, mapping) = preprocess_branching_with_mapping(lts);
+
+    let mut divergent = vec![false; preprocessed_lts.num_of_states()];
+    for state_index in divergent_states {
+        divergent[mapping[state_index]] = true;
+    }
+
+    (preprocessed_lts, mapping, divergent)
 }
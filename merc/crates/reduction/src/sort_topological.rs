@@ -5,15 +5,15 @@ use log::trace;
 use merc_lts::LTS;
 use merc_lts::LabelIndex;
 use merc_lts::StateIndex;
-use merc_utilities::MercError;
 use merc_utilities::is_valid_permutation;
 
 /// Returns a topological ordering of the states of the given LTS.
 ///
-/// An error is returned if the LTS contains a cycle.
+/// If the LTS contains a cycle then the states on one such cycle are returned
+/// as error, in the order in which they are connected.
 ///     - filter: Only transitions satisfying the filter are considered part of the graph.
 ///     - reverse: If true, the topological ordering is reversed, i.e. successors before the incoming state.
-pub fn sort_topological<F, L>(lts: &L, filter: F, reverse: bool) -> Result<Vec<StateIndex>, MercError>
+pub fn sort_topological<F, L>(lts: &L, filter: F, reverse: bool) -> Result<Vec<StateIndex>, Vec<StateIndex>>
 where
     F: Fn(LabelIndex, StateIndex) -> bool,
     L: LTS,
@@ -24,21 +24,21 @@ where
     let mut visited = vec![false; lts.num_of_states()];
     let mut depth_stack = Vec::new();
     let mut marks = vec![None; lts.num_of_states()];
+    let mut parent = vec![None; lts.num_of_states()];
 
     for state_index in lts.iter_states() {
-        if marks[state_index].is_none()
-            && !sort_topological_visit(
+        if marks[state_index].is_none() {
+            sort_topological_visit(
                 lts,
                 &filter,
                 state_index,
                 &mut depth_stack,
                 &mut marks,
+                &mut parent,
                 &mut visited,
                 &mut stack,
             )
-        {
-            trace!("There is a cycle from state {state_index} on path {stack:?}");
-            return Err("Labelled transition system contains a cycle".into());
+            .inspect_err(|cycle| trace!("There is a cycle {cycle:?} reachable from state {state_index}"))?;
         }
     }
 
@@ -70,16 +70,19 @@ enum Mark {
 
 /// Visits the given state in a depth first search.
 ///
-/// Returns false if a cycle is detected.
+/// The `parent` of a state is the state from which it was visited, which is
+/// used to return the states on the cycle when a cycle is detected.
+#[allow(clippy::too_many_arguments)]
 fn sort_topological_visit<F>(
     lts: &impl LTS,
     filter: &F,
     state_index: StateIndex,
     depth_stack: &mut Vec<StateIndex>,
     marks: &mut [Option<Mark>],
+    parent: &mut [Option<StateIndex>],
     visited: &mut [bool],
     stack: &mut Vec<StateIndex>,
-) -> bool
+) -> Result<(), Vec<StateIndex>>
 where
     F: Fn(LabelIndex, StateIndex) -> bool,
 {
@@ -97,9 +100,20 @@ where
                 {
                     // If it was marked temporary, then a cycle is detected.
                     if marks[transition.to] == Some(Mark::Temporary) {
-                        return false;
+                        // The temporary states are on the current path, so follow the parents back to the target.
+                        let mut cycle = vec![state];
+                        let mut current = state;
+                        while current != transition.to {
+                            current = parent[current].expect("The target of the cycle is an ancestor");
+                            cycle.push(current);
+                        }
+
+                        cycle.reverse();
+                        return Err(cycle);
                     }
                     if marks[transition.to].is_none() {
+                        // The last push of a state is popped first, so this is the state it is visited from.
+                        parent[transition.to] = Some(state);
                         depth_stack.push(transition.to);
                     }
                 }
@@ -113,7 +127,7 @@ where
         }
     }
 
-    true
+    Ok(())
 }
 
 /// Returns true if the given permutation is a topological ordering of the states of the given LTS.
@@ -170,6 +184,45 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_sort_topological_cycle() {
+        // The states 1, 2 and 3 form a cycle that is reachable from state 0.
+        let transitions = [(0, 0, 1), (1, 0, 2), (2, 0, 3), (3, 0, 1), (3, 0, 4)]
+            .map(|(from, label, to)| (StateIndex::new(from), LabelIndex::new(label), StateIndex::new(to)));
+
+        let lts = LabelledTransitionSystem::new(
+            StateIndex::new(0),
+            None,
+            || transitions.iter().cloned(),
+            vec!["a".to_string()],
+        );
+
+        let mut cycle = sort_topological(&lts, |_, _| true, false).unwrap_err();
+        cycle.sort();
+        assert_eq!(cycle, vec![StateIndex::new(1), StateIndex::new(2), StateIndex::new(3)]);
+
+        // Without the transitions to state 1 the states can be sorted.
+        assert!(sort_topological(&lts, |_, to| to != StateIndex::new(1), false).is_ok());
+    }
+
+    #[test]
+    fn test_random_sort_topological_cycle() {
+        random_test(100, |rng| {
+            let lts = random_lts(rng, 10, 3, 2);
+            if let Err(cycle) = sort_topological(&lts, |_, _| true, false) {
+                // Every state on the cycle has a transition to the next state on the cycle.
+                for (index, state_index) in cycle.iter().enumerate() {
+                    let next = cycle[(index + 1) % cycle.len()];
+                    assert!(
+                        lts.outgoing_transitions(*state_index)
+                            .any(|transition| transition.to == next),
+                        "The states {cycle:?} do not form a cycle"
+                    );
+                }
+            }
+        });
+    }
+
     #[test]
     fn test_random_reorder_states() {
         random_test(100, |rng| {
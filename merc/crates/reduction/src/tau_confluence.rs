@@ -0,0 +1,207 @@
+#![forbid(unsafe_code)]
+
+use log::debug;
+use rustc_hash::FxHashSet;
+
+use merc_lts::LTS;
+use merc_lts::LabelledTransitionSystem;
+use merc_lts::LtsBuilderFast;
+use merc_lts::StateIndex;
+
+use crate::preprocess_branching;
+
+/// Reduces the given LTS by giving priority to confluent tau-transitions, and
+/// collapsing the source of such a transition into its target. The result is
+/// branching bisimilar, and thus also weakly bisimilar and trace equivalent,
+/// to the given LTS.
+///
+/// # Details
+///
+/// The hidden labels of the LTS are treated as tau, as in [crate::reduce_lts].
+/// First the tau-cycles are removed, see [preprocess_branching]. A set `T` of
+/// tau-transitions is confluent whenever for every `s -tau-> t` in `T` and
+/// every other transition `s -a-> u` there is a transition `t -a-> v` with
+/// `u = v` or `u -tau-> v` in `T`, or `a` is tau and `u -tau-> t` in `T`. The greatest such
+/// set is computed as a fixed point, after which every state with a confluent
+/// tau-transition is represented by the state reached by following these
+/// transitions, and only the transitions of the representatives are kept.
+pub fn tau_confluence_reduce<L: LTS>(lts: L) -> LabelledTransitionSystem<L::Label> {
+    let lts = preprocess_branching(lts);
+
+    // Start with all tau-transitions and remove the ones that violate confluence until a fixed point is reached.
+    let mut confluent: FxHashSet<(StateIndex, StateIndex)> = FxHashSet::default();
+    for state_index in lts.iter_states() {
+        for transition in lts.outgoing_transitions(state_index) {
+            if lts.is_hidden_label(transition.label) && transition.to != state_index {
+                confluent.insert((state_index, transition.to));
+            }
+        }
+    }
+
+    loop {
+        let violating: Vec<(StateIndex, StateIndex)> = confluent
+            .iter()
+            .filter(|(from, to)| !is_confluent(&lts, &confluent, *from, *to))
+            .copied()
+            .collect();
+
+        if violating.is_empty() {
+            break;
+        }
+
+        for transition in violating {
+            confluent.remove(&transition);
+        }
+    }
+
+    debug!("Found {} confluent tau-transitions", confluent.len());
+
+    // Every state follows its first confluent tau-transition, which terminates since there are no tau-cycles.
+    let mut representative: Vec<Option<StateIndex>> = vec![None; lts.num_of_states()];
+    for state_index in lts.iter_states() {
+        let mut path = Vec::new();
+        let mut current = state_index;
+
+        let result = loop {
+            if let Some(result) = representative[current] {
+                break result;
+            }
+
+            match lts
+                .outgoing_transitions(current)
+                .find(|transition| confluent.contains(&(current, transition.to)))
+            {
+                Some(transition) => {
+                    path.push(current);
+                    current = transition.to;
+                }
+                None => {
+                    representative[current] = Some(current);
+                    break current;
+                }
+            }
+        };
+
+        for state_index in path {
+            representative[state_index] = Some(result);
+        }
+    }
+
+    // Number the representatives consecutively, these are the states of the reduced LTS.
+    let mut state_number = vec![StateIndex::new(0); lts.num_of_states()];
+    let mut num_of_states = 0;
+    for state_index in lts.iter_states() {
+        if representative[state_index] == Some(state_index) {
+            state_number[state_index] = StateIndex::new(num_of_states);
+            num_of_states += 1;
+        }
+    }
+
+    let number =
+        |state_index: StateIndex| state_number[representative[state_index].expect("Every state has a representative")];
+
+    let mut builder = LtsBuilderFast::with_capacity(lts.labels().into(), Vec::new(), lts.num_of_transitions());
+    for state_index in lts.iter_states() {
+        if representative[state_index] == Some(state_index) {
+            for transition in lts.outgoing_transitions(state_index) {
+                builder.add_transition(
+                    number(state_index),
+                    &lts.labels()[transition.label],
+                    number(transition.to),
+                );
+            }
+        }
+    }
+
+    builder.require_num_of_states(num_of_states);
+    builder.finish(number(lts.initial_state_index()), true)
+}
+
+/// Returns true iff the tau-transition `from -tau-> to` satisfies the
+/// confluence condition with respect to the given set of confluent
+/// tau-transitions, see [tau_confluence_reduce].
+fn is_confluent(
+    lts: &impl LTS,
+    confluent: &FxHashSet<(StateIndex, StateIndex)>,
+    from: StateIndex,
+    to: StateIndex,
+) -> bool {
+    lts.outgoing_transitions(from).all(|other| {
+        if lts.is_hidden_label(other.label) && (other.to == to || confluent.contains(&(other.to, to))) {
+            return true;
+        }
+
+        lts.outgoing_transitions(to).any(|closing| {
+            closing.label == other.label && (closing.to == other.to || confluent.contains(&(other.to, closing.to)))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use merc_lts::random_lts;
+    use merc_lts::read_aut;
+    use merc_utilities::Timing;
+    use merc_utilities::random_test;
+    use test_log::test;
+
+    use crate::Equivalence;
+    use crate::compare_lts;
+
+    use super::*;
+
+    #[test]
+    fn test_tau_confluence_diamond() {
+        // The tau-transitions 0 -> 1 and 2 -> 3 form a confluent diamond with the a-transitions.
+        let lts = read_aut(
+            b"des (0, 5, 5)
+            (0, \"i\", 1)
+            (0, \"a\", 2)
+            (1, \"a\", 3)
+            (2, \"i\", 3)
+            (3, \"b\", 4)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+
+        let reduced = tau_confluence_reduce(lts.clone());
+        assert_eq!(reduced.num_of_states(), 3);
+        assert_eq!(reduced.num_of_transitions(), 2);
+
+        let mut timing = Timing::new();
+        assert!(compare_lts(Equivalence::WeakBisim, lts, reduced, &mut timing));
+    }
+
+    #[test]
+    fn test_tau_confluence_choice() {
+        // The tau-transition resolves a choice, so it is not confluent.
+        let lts = read_aut(
+            b"des (0, 2, 3)
+            (0, \"i\", 1)
+            (0, \"a\", 2)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+
+        let reduced = tau_confluence_reduce(lts.clone());
+        assert_eq!(reduced.num_of_states(), 3);
+        assert_eq!(reduced.num_of_transitions(), 2);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_random_tau_confluence() {
+        random_test(100, |rng| {
+            let lts = random_lts(rng, 10, 3, 3);
+            let reduced = tau_confluence_reduce(lts.clone());
+
+            assert!(reduced.num_of_states() <= lts.num_of_states());
+
+            let mut timing = Timing::new();
+            assert!(
+                compare_lts(Equivalence::BranchingBisim, lts, reduced, &mut timing),
+                "The reduced LTS should be branching bisimilar"
+            );
+        });
+    }
+}
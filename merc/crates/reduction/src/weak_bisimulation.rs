@@ -14,11 +14,14 @@ use merc_lts::IncomingTransitions;
 use merc_lts::LTS;
 use merc_lts::LabelIndex;
 use merc_lts::LabelledTransitionSystem;
+use merc_lts::LtsBuilderFast;
 use merc_utilities::Timing;
 
 use crate::BlockIndex;
+use crate::Equivalence;
 use crate::SimpleBlockPartition;
 use crate::preprocess_branching;
+use crate::reduce_lts;
 
 /// Type alias because we use bitvec for marking states
 type BitArray = BitVec<u64, Lsb0>;
@@ -32,13 +35,43 @@ pub fn weak_bisimulation<L: LTS>(
     let tau_loop_free_lts = preprocess_branching(lts);
     time_pre.finish();
 
+    let blocks = weak_bisimulation_preprocessed(&tau_loop_free_lts, timing);
+    (tau_loop_free_lts, blocks)
+}
+
+/// Reduces the given LTS modulo weak bisimulation, where the actions in
+/// `hidden` are treated as tau for this reduction only. The labels of the
+/// other actions are kept as is in the reduced LTS.
+pub fn reduce_weak_with_hidden<L: LTS>(
+    lts: &L,
+    hidden: &[String],
+    timing: &mut Timing,
+) -> LabelledTransitionSystem<L::Label> {
+    let mut time_hide = timing.start("hide");
+    let mut builder = LtsBuilderFast::with_capacity(lts.labels().into(), hidden.to_vec(), lts.num_of_transitions());
+    for state_index in lts.iter_states() {
+        for transition in lts.outgoing_transitions(state_index) {
+            builder.add_transition(state_index, &lts.labels()[transition.label], transition.to);
+        }
+    }
+
+    builder.require_num_of_states(lts.num_of_states());
+    let hidden_lts = builder.finish(lts.initial_state_index(), false);
+    time_hide.finish();
+
+    reduce_lts(hidden_lts, Equivalence::WeakBisim, timing)
+}
+
+/// The same as [weak_bisimulation], but for an LTS that has already been
+/// preprocessed by [preprocess_branching].
+pub fn weak_bisimulation_preprocessed<L: LTS>(tau_loop_free_lts: &L, timing: &mut Timing) -> SimpleBlockPartition {
     let mut time_reduction = timing.start("reduction");
     let mut blocks = SimpleBlockPartition::new(tau_loop_free_lts.num_of_states());
 
     let mut act_mark = bitvec![u64, Lsb0; 0; tau_loop_free_lts.num_of_states()];
     let mut tau_mark = bitvec![u64, Lsb0; 0; tau_loop_free_lts.num_of_states()];
 
-    let incoming = IncomingTransitions::new(&tau_loop_free_lts);
+    let incoming = IncomingTransitions::new(tau_loop_free_lts);
 
     let progress = TimeProgress::new(
         |num_of_blocks: usize| {
@@ -69,7 +102,7 @@ pub fn weak_bisimulation<L: LTS>(
                 compute_weak_act(
                     &mut act_mark,
                     &mut tau_mark,
-                    &tau_loop_free_lts,
+                    tau_loop_free_lts,
                     &blocks,
                     &incoming,
                     block_index,
@@ -92,7 +125,7 @@ pub fn weak_bisimulation<L: LTS>(
     }
 
     time_reduction.finish();
-    (tau_loop_free_lts, blocks)
+    blocks
 }
 
 /// Sets s.act_mark to true iff exists t: S. s =\not{a}=> t
@@ -147,6 +180,7 @@ mod tests {
     use merc_io::DumpFiles;
     use merc_lts::LTS;
     use merc_lts::random_lts;
+    use merc_lts::read_aut;
     use merc_lts::write_aut;
     use merc_utilities::Timing;
     use merc_utilities::random_test;
@@ -155,6 +189,8 @@ mod tests {
     use crate::compare_lts;
     use crate::reduce_lts;
 
+    use super::*;
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn test_weak_bisimulation() {
@@ -177,4 +213,47 @@ mod tests {
             assert!(compare_lts(Equivalence::StrongBisim, result, expected, &mut timing));
         })
     }
+
+    #[test]
+    fn test_reduce_weak_with_hidden() {
+        let lts = read_aut(
+            b"des (0, 4, 4)
+            (0, \"a\", 1)
+            (1, \"b\", 2)
+            (2, \"c\", 3)
+            (0, \"c\", 3)" as &[u8],
+            vec![],
+        )
+        .unwrap();
+
+        // Returns the labels that occur on the transitions of the given LTS.
+        let actions = |lts: &LabelledTransitionSystem<String>| {
+            let mut actions: Vec<String> = lts
+                .iter_states()
+                .flat_map(|state_index| lts.outgoing_transitions(state_index))
+                .map(|transition| lts.labels()[transition.label].clone())
+                .collect();
+            actions.sort();
+            actions.dedup();
+            actions
+        };
+
+        let mut timing = Timing::new();
+        let hide_a = reduce_weak_with_hidden(&lts, &["a".to_string()], &mut timing);
+        let hide_b = reduce_weak_with_hidden(&lts, &["b".to_string()], &mut timing);
+        let hide_none = reduce_weak_with_hidden(&lts, &[], &mut timing);
+
+        // The input is not changed, and only the hidden actions are renamed.
+        assert_eq!(actions(&lts), vec!["a", "b", "c"]);
+        assert_eq!(actions(&hide_a), vec!["b", "c", "i"]);
+        assert_eq!(actions(&hide_none), vec!["a", "b", "c"]);
+
+        // Hiding b makes the states before and after it equivalent, which removes the tau-transition.
+        assert_eq!(actions(&hide_b), vec!["a", "c"]);
+
+        assert_eq!(hide_none.num_of_states(), 4);
+        assert_eq!(hide_a.num_of_states(), 4);
+        assert_eq!(hide_b.num_of_states(), 3);
+        assert!(!compare_lts(Equivalence::WeakBisim, hide_a, hide_b, &mut timing));
+    }
 }
@@ -6,7 +6,11 @@ use criterion::Criterion;
 use criterion::criterion_group;
 use criterion::criterion_main;
 
+use merc_data::DataExpression;
+use merc_data::to_untyped_data_expression;
 use merc_rec_tests::load_rec_from_strings;
+use merc_sabre::InnermostRewriter;
+use merc_sabre::RewriteEngine;
 use merc_sabre::SetAutomaton;
 
 pub fn criterion_benchmark_set_automaton(c: &mut Criterion) {
@@ -29,5 +33,58 @@ pub fn criterion_benchmark_set_automaton(c: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, criterion_benchmark_set_automaton,);
+pub fn criterion_benchmark_innermost_cache(c: &mut Criterion) {
+    let (name, rec_files) = (
+        "fibonacci18",
+        [
+            include_str!("../../../../examples/REC/rec/fibonacci18.rec"),
+            include_str!("../../../../examples/REC/rec/fibonacci.rec"),
+        ],
+    );
+    let (syntax_spec, syntax_terms) = load_rec_from_strings(&rec_files).unwrap();
+    let spec = syntax_spec.to_rewrite_spec();
+    let terms: Vec<DataExpression> = syntax_terms
+        .iter()
+        .map(|t| to_untyped_data_expression(t.clone(), None))
+        .collect();
+
+    c.bench_function(&format!("innermost {}", name), |bencher| {
+        bencher.iter(|| {
+            let mut rewriter = InnermostRewriter::new(&spec);
+            for term in &terms {
+                let _ = black_box(rewriter.rewrite(term));
+            }
+        });
+    });
+
+    c.bench_function(&format!("innermost cached {}", name), |bencher| {
+        bencher.iter(|| {
+            let mut rewriter = InnermostRewriter::with_cache(&spec, 1 << 16);
+            for term in &terms {
+                let _ = black_box(rewriter.rewrite(term));
+            }
+        });
+    });
+
+    // Report the hit rate of the cache for a single run.
+    let mut rewriter = InnermostRewriter::with_cache(&spec, 1 << 16);
+    for term in &terms {
+        let _ = rewriter.rewrite(term);
+    }
+
+    let cache = rewriter.cache().expect("The cache is enabled");
+    println!(
+        "innermost cached {}: {} hits, {} misses, hit rate {:.2}%",
+        name,
+        cache.hits(),
+        cache.misses(),
+        cache.hit_rate() * 100.0
+    );
+}
+
+criterion_group!(
+    benches,
+    criterion_benchmark_set_automaton,
+    criterion_benchmark_innermost_cache,
+);
 criterion_main!(benches);
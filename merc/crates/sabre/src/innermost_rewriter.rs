@@ -10,6 +10,7 @@ use merc_data::DataExpressionRef;
 
 use crate::RewriteEngine;
 use crate::RewriteSpecification;
+use crate::RewriteTrace;
 use crate::RewritingStatistics;
 use crate::Rule;
 use crate::matching::conditions::EMACondition;
@@ -22,25 +23,18 @@ use crate::set_automaton::SetAutomaton;
 use crate::utilities::Config;
 use crate::utilities::DataPositionIndexed;
 use crate::utilities::InnermostStack;
+use crate::utilities::NormalFormCache;
 use crate::utilities::TermStack;
 use crate::utilities::TermStackBuilder;
 use merc_utilities::debug_trace;
 
 impl RewriteEngine for InnermostRewriter {
     fn rewrite(&mut self, t: &DataExpression) -> DataExpression {
-        let mut stats = RewritingStatistics::default();
-
-        debug_trace!("input: {}", t);
-
-        let result = THREAD_TERM_POOL.with_borrow(|tp| {
-            InnermostRewriter::rewrite_aux(tp, &mut self.stack, &mut self.builder, &mut stats, &self.apma, t)
-        });
+        self.rewrite_impl(t, &mut None)
+    }
 
-        info!(
-            "{} rewrites, {} single steps and {} symbol comparisons",
-            stats.recursions, stats.rewrite_steps, stats.symbol_comparisons
-        );
-        result
+    fn rewrite_traced(&mut self, t: &DataExpression, trace: &mut dyn FnMut(&Rule, &DataExpression)) -> DataExpression {
+        self.rewrite_impl(t, &mut Some(trace))
     }
 }
 
@@ -56,6 +50,46 @@ impl InnermostRewriter {
         }
     }
 
+    /// Creates a new InnermostRewriter that caches at most `capacity` normal
+    /// forms of the subterms that it has rewritten, see [NormalFormCache].
+    ///
+    /// Note that the rules applied to obtain a cached normal form are not
+    /// reported again by [RewriteEngine::rewrite_traced].
+    pub fn with_cache(spec: &RewriteSpecification, capacity: usize) -> InnermostRewriter {
+        let mut result = InnermostRewriter::new(spec);
+        result.stack.cache = Some(NormalFormCache::new(capacity));
+        result
+    }
+
+    /// Returns the normal form cache, if it is enabled.
+    pub fn cache(&self) -> Option<&NormalFormCache> {
+        self.stack.cache.as_ref()
+    }
+
+    /// Rewrites the term 't' and reports the applied rules to the optional trace.
+    fn rewrite_impl(&mut self, t: &DataExpression, trace: &mut RewriteTrace<'_>) -> DataExpression {
+        let mut stats = RewritingStatistics::default();
+
+        debug_trace!("input: {}", t);
+
+        let result = THREAD_TERM_POOL.with_borrow(|tp| {
+            InnermostRewriter::rewrite_aux(tp, &mut self.stack, &mut self.builder, &mut stats, trace, &self.apma, t)
+        });
+
+        info!(
+            "{} rewrites, {} single steps and {} symbol comparisons",
+            stats.recursions, stats.rewrite_steps, stats.symbol_comparisons
+        );
+        if let Some(cache) = &self.stack.cache {
+            info!(
+                "Normal form cache contains {} terms, hit rate {:.2}%",
+                cache.len(),
+                cache.hit_rate() * 100.0
+            );
+        }
+        result
+    }
+
     /// Function to rewrite a term 't'. The elements of the automaton 'states'
     /// and 'tp' are passed as separate parameters to satisfy the borrow
     /// checker.
@@ -75,6 +109,7 @@ impl InnermostRewriter {
         stack: &mut InnermostStack,
         builder: &mut TermStackBuilder,
         stats: &mut RewritingStatistics,
+        trace: &mut RewriteTrace<'_>,
         automaton: &SetAutomaton<AnnouncementInnermost>,
         input_term: &DataExpression,
     ) -> DataExpression {
@@ -100,27 +135,51 @@ impl InnermostRewriter {
                         let mut write_terms = stack.terms.write();
                         let term = write_terms.pop().unwrap().unwrap();
 
-                        let symbol = term.data_function_symbol();
-                        let arguments = term.data_arguments();
+                        if let Some(normal_form) = stack.cache.as_mut().and_then(|cache| cache.get(&term)) {
+                            // The normal form of this term was computed before.
+                            write_terms[result] = Some(write_terms.protect(&normal_form).into());
+                        } else {
+                            if stack.cache.is_some() {
+                                // Store the normal form in the cache after it has been computed.
+                                let term = write_configs.protect(&term);
+                                write_configs.push(Config::Store(term.into(), result));
+                            }
 
-                        // For all the argument we reserve space on the stack.
-                        let top_of_stack = write_terms.len();
-                        for _ in 0..arguments.len() {
-                            write_terms.push(Default::default());
-                        }
+                            let symbol = term.data_function_symbol();
+                            let arguments = term.data_arguments();
 
-                        let symbol = write_configs.protect(&symbol);
-                        InnermostStack::add_result(&mut write_configs, symbol.into(), arguments.len(), result);
-                        for (offset, arg) in arguments.into_iter().enumerate() {
-                            InnermostStack::add_rewrite(
-                                &mut write_configs,
-                                &mut write_terms,
-                                arg,
-                                top_of_stack + offset,
-                            );
+                            // For all the argument we reserve space on the stack.
+                            let top_of_stack = write_terms.len();
+                            for _ in 0..arguments.len() {
+                                write_terms.push(Default::default());
+                            }
+
+                            let symbol = write_configs.protect(&symbol);
+                            InnermostStack::add_result(&mut write_configs, symbol.into(), arguments.len(), result);
+                            for (offset, arg) in arguments.into_iter().enumerate() {
+                                InnermostStack::add_rewrite(
+                                    &mut write_configs,
+                                    &mut write_terms,
+                                    arg,
+                                    top_of_stack + offset,
+                                );
+                            }
                         }
                         drop(write_configs);
                     }
+                    Config::Store(term, index) => {
+                        let normal_form = stack.terms.read()[index]
+                            .as_ref()
+                            .expect("The normal form should have been computed")
+                            .protect();
+
+                        stack
+                            .cache
+                            .as_mut()
+                            .expect("Store is only used when the cache is enabled")
+                            .insert(term.protect(), normal_form);
+                        drop(write_configs);
+                    }
                     Config::Construct(symbol, arity, index) => {
                         // Take the last arity arguments.
                         let mut write_terms = stack.terms.write();
@@ -139,31 +198,60 @@ impl InnermostRewriter {
                         drop(write_terms);
                         drop(write_configs);
 
-                        match InnermostRewriter::find_match(tp, stack, builder, stats, automaton, &term.copy()) {
-                            Some((_announcement, annotation)) => {
-                                debug_trace!(
-                                    "rewrite {} => {} using rule {}",
-                                    term,
-                                    annotation.rhs_stack.evaluate(&term),
-                                    _announcement.rule
-                                );
+                        let cached = stack
+                            .cache
+                            .as_mut()
+                            .and_then(|cache| cache.get(&term.copy()).map(|normal_form| normal_form.protect()));
 
-                                // Reacquire the write access and add the matching RHSStack.
-                                let mut write_terms = stack.terms.write();
-                                let mut write_configs = stack.configs.write();
-                                InnermostStack::integrate(
-                                    &mut write_configs,
-                                    &mut write_terms,
-                                    &annotation.rhs_stack,
-                                    &term.copy(),
-                                    index,
-                                );
-                                stats.rewrite_steps += 1;
-                            }
-                            None => {
-                                // Add the term on the stack.
-                                let mut write_terms = stack.terms.write();
-                                write_terms[index] = Some(write_terms.protect(&term).into());
+                        if let Some(normal_form) = cached {
+                            // The normal form of this term was computed before.
+                            let mut write_terms = stack.terms.write();
+                            write_terms[index] = Some(write_terms.protect(&normal_form).into());
+                        } else {
+                            match InnermostRewriter::find_match(
+                                tp,
+                                stack,
+                                builder,
+                                stats,
+                                trace,
+                                automaton,
+                                &term.copy(),
+                            ) {
+                                Some((announcement, annotation)) => {
+                                    if let Some(trace) = trace {
+                                        trace(&announcement.rule, &term);
+                                    }
+
+                                    debug_trace!(
+                                        "rewrite {} => {} using rule {}",
+                                        term,
+                                        annotation.rhs_stack.evaluate(&term),
+                                        announcement.rule
+                                    );
+
+                                    // Reacquire the write access and add the matching RHSStack.
+                                    let mut write_terms = stack.terms.write();
+                                    let mut write_configs = stack.configs.write();
+                                    if stack.cache.is_some() {
+                                        // Store the normal form in the cache after the right hand side has been rewritten.
+                                        let term = write_configs.protect(&term);
+                                        write_configs.push(Config::Store(term.into(), index));
+                                    }
+
+                                    InnermostStack::integrate(
+                                        &mut write_configs,
+                                        &mut write_terms,
+                                        &annotation.rhs_stack,
+                                        &term.copy(),
+                                        index,
+                                    );
+                                    stats.rewrite_steps += 1;
+                                }
+                                None => {
+                                    // Add the term on the stack.
+                                    let mut write_terms = stack.terms.write();
+                                    write_terms[index] = Some(write_terms.protect(&term).into());
+                                }
                             }
                         }
                     }
@@ -191,6 +279,7 @@ impl InnermostRewriter {
                                         Config::Construct(_, _, result) => index == *result,
                                         Config::Rewrite(result) => index == *result,
                                         Config::Term(_, result) => index == *result,
+                                        Config::Store(_, _) => false,
                                         Config::Return() => true,
                                     }
                                 }),
@@ -209,6 +298,7 @@ impl InnermostRewriter {
         stack: &mut InnermostStack,
         builder: &mut TermStackBuilder,
         stats: &mut RewritingStatistics,
+        trace: &mut RewriteTrace<'_>,
         automaton: &'a SetAutomaton<AnnouncementInnermost>,
         t: &DataExpressionRef<'_>,
     ) -> Option<(&'a MatchAnnouncement, &'a AnnouncementInnermost)> {
@@ -226,7 +316,9 @@ impl InnermostRewriter {
             if let Some(transition) = automaton.transitions().get(&(state_index, symbol.operation_id())) {
                 for (announcement, annotation) in &transition.announcements {
                     if check_equivalence_classes(t, &annotation.equivalence_classes)
-                        && InnermostRewriter::check_conditions(tp, stack, builder, stats, automaton, annotation, t)
+                        && InnermostRewriter::check_conditions(
+                            tp, stack, builder, stats, trace, automaton, annotation, t,
+                        )
                     {
                         // We found a matching pattern
                         return Some((announcement, annotation));
@@ -247,11 +339,13 @@ impl InnermostRewriter {
     }
 
     /// Checks whether the condition holds for given match announcement.
+    #[allow(clippy::too_many_arguments)]
     fn check_conditions(
         tp: &ThreadTermPool,
         stack: &mut InnermostStack,
         builder: &mut TermStackBuilder,
         stats: &mut RewritingStatistics,
+        trace: &mut RewriteTrace<'_>,
         automaton: &SetAutomaton<AnnouncementInnermost>,
         announcement: &AnnouncementInnermost,
         t: &DataExpressionRef<'_>,
@@ -260,8 +354,8 @@ impl InnermostRewriter {
             let rhs: DataExpression = c.rhs_term_stack.evaluate_with(t, builder);
             let lhs: DataExpression = c.lhs_term_stack.evaluate_with(t, builder);
 
-            let rhs_normal = InnermostRewriter::rewrite_aux(tp, stack, builder, stats, automaton, &rhs);
-            let lhs_normal = InnermostRewriter::rewrite_aux(tp, stack, builder, stats, automaton, &lhs);
+            let rhs_normal = InnermostRewriter::rewrite_aux(tp, stack, builder, stats, trace, automaton, &rhs);
+            let lhs_normal = InnermostRewriter::rewrite_aux(tp, stack, builder, stats, trace, automaton, &lhs);
 
             if lhs_normal != rhs_normal && c.equality || lhs_normal == rhs_normal && !c.equality {
                 return false;
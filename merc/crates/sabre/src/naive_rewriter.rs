@@ -10,7 +10,10 @@ use crate::AnnouncementInnermost;
 use crate::MatchAnnouncement;
 use crate::RewriteEngine;
 use crate::RewriteSpecification;
+use crate::RewriteTrace;
 use crate::RewritingStatistics;
+use crate::Rule;
+use crate::matching::nonlinear::check_equivalence_classes;
 use crate::set_automaton::SetAutomaton;
 use crate::utilities::DataPositionIndexed;
 
@@ -22,15 +25,11 @@ pub struct NaiveRewriter {
 
 impl RewriteEngine for NaiveRewriter {
     fn rewrite(&mut self, t: &DataExpression) -> DataExpression {
-        let mut stats = RewritingStatistics::default();
-
-        let result = NaiveRewriter::rewrite_aux(&self.apma, t.copy(), &mut stats);
+        self.rewrite_impl(t, &mut None)
+    }
 
-        info!(
-            "{} rewrites, {} single steps and {} symbol comparisons",
-            stats.recursions, stats.rewrite_steps, stats.symbol_comparisons
-        );
-        result
+    fn rewrite_traced(&mut self, t: &DataExpression, trace: &mut dyn FnMut(&Rule, &DataExpression)) -> DataExpression {
+        self.rewrite_impl(t, &mut Some(trace))
     }
 }
 
@@ -41,11 +40,25 @@ impl NaiveRewriter {
         }
     }
 
+    /// Rewrites the term 't' and reports the applied rules to the optional trace.
+    fn rewrite_impl(&mut self, t: &DataExpression, trace: &mut RewriteTrace<'_>) -> DataExpression {
+        let mut stats = RewritingStatistics::default();
+
+        let result = NaiveRewriter::rewrite_aux(&self.apma, t.copy(), trace, &mut stats);
+
+        info!(
+            "{} rewrites, {} single steps and {} symbol comparisons",
+            stats.recursions, stats.rewrite_steps, stats.symbol_comparisons
+        );
+        result
+    }
+
     /// Function to rewrite a term 't'. The elements of the automaton 'states' and 'tp' are passed
     /// as separate parameters to satisfy the borrow checker.
     fn rewrite_aux(
         automaton: &SetAutomaton<AnnouncementInnermost>,
         t: DataExpressionRef<'_>,
+        trace: &mut RewriteTrace<'_>,
         stats: &mut RewritingStatistics,
     ) -> DataExpression {
         let symbol = t.data_function_symbol();
@@ -53,7 +66,7 @@ impl NaiveRewriter {
         // Recursively call rewrite_aux on all the subterms.
         let mut arguments = vec![];
         for t in t.data_arguments() {
-            arguments.push(NaiveRewriter::rewrite_aux(automaton, t, stats));
+            arguments.push(NaiveRewriter::rewrite_aux(automaton, t, trace, stats));
         }
 
         let nf: DataExpression = if arguments.is_empty() {
@@ -62,12 +75,16 @@ impl NaiveRewriter {
             DataApplication::with_args(&symbol, &arguments).into()
         };
 
-        match NaiveRewriter::find_match(automaton, &nf, stats) {
+        match NaiveRewriter::find_match(automaton, &nf, trace, stats) {
             None => nf,
-            Some((_announcement, ema)) => {
+            Some((announcement, ema)) => {
+                if let Some(trace) = trace {
+                    trace(&announcement.rule, &nf);
+                }
+
                 let result = ema.rhs_stack.evaluate(&nf);
-                debug_trace!("rewrote {} to {} using rule {}", nf, result, _announcement.rule);
-                NaiveRewriter::rewrite_aux(automaton, result.copy(), stats)
+                debug_trace!("rewrote {} to {} using rule {}", nf, result, announcement.rule);
+                NaiveRewriter::rewrite_aux(automaton, result.copy(), trace, stats)
             }
         }
     }
@@ -76,6 +93,7 @@ impl NaiveRewriter {
     fn find_match<'a>(
         automaton: &'a SetAutomaton<AnnouncementInnermost>,
         t: &DataExpression,
+        trace: &mut RewriteTrace<'_>,
         stats: &mut RewritingStatistics,
     ) -> Option<(&'a MatchAnnouncement, &'a AnnouncementInnermost)> {
         // Start at the initial state
@@ -90,31 +108,10 @@ impl NaiveRewriter {
             // Get the transition for the label and check if there is a pattern match
             if let Some(transition) = automaton.transitions().get(&(state_index, symbol.operation_id())) {
                 for (announcement, ema) in &transition.announcements {
-                    let mut conditions_hold = true;
-
-                    // Check conditions if there are any
-                    if !ema.conditions.is_empty() {
-                        conditions_hold = NaiveRewriter::check_conditions(automaton, &t.copy(), ema, stats);
-                    }
-
-                    // Check equivalence of subterms for non-linear patterns
-                    'ec_check: for ec in &ema.equivalence_classes {
-                        if ec.positions.len() > 1 {
-                            let mut iter_pos = ec.positions.iter();
-                            let first_pos = iter_pos.next().unwrap();
-                            let first_term = t.get_data_position(first_pos);
-
-                            for other_pos in iter_pos {
-                                let other_term = t.get_data_position(other_pos);
-                                if first_term != other_term {
-                                    conditions_hold = false;
-                                    break 'ec_check;
-                                }
-                            }
-                        }
-                    }
-
-                    if conditions_hold {
+                    // Check equivalence of subterms for non-linear patterns before the (more expensive) conditions
+                    if check_equivalence_classes(&t.copy(), &ema.equivalence_classes)
+                        && NaiveRewriter::check_conditions(automaton, &t.copy(), ema, trace, stats)
+                    {
                         // We found a matching pattern
                         return Some((announcement, ema));
                     }
@@ -135,19 +132,21 @@ impl NaiveRewriter {
     }
 
     /// Given a term with head symbol 't_head' and subterms 't_subterms' and an EnhancedMatchAnnouncement,
-    /// check if the conditions hold.
+    /// check if the conditions hold. The conditions are checked in order and the first condition that
+    /// does not hold stops the evaluation.
     fn check_conditions(
         automaton: &SetAutomaton<AnnouncementInnermost>,
         t: &DataExpressionRef<'_>,
         ema: &AnnouncementInnermost,
+        trace: &mut RewriteTrace<'_>,
         stats: &mut RewritingStatistics,
     ) -> bool {
         for c in &ema.conditions {
             let rhs = c.lhs_term_stack.evaluate(t);
             let lhs = c.rhs_term_stack.evaluate(t);
 
-            let rhs_normal = NaiveRewriter::rewrite_aux(automaton, rhs.copy(), stats);
-            let lhs_normal = NaiveRewriter::rewrite_aux(automaton, lhs.copy(), stats);
+            let rhs_normal = NaiveRewriter::rewrite_aux(automaton, rhs.copy(), trace, stats);
+            let lhs_normal = NaiveRewriter::rewrite_aux(automaton, lhs.copy(), trace, stats);
 
             let holds = (lhs_normal == rhs_normal && c.equality) || (lhs_normal != rhs_normal && !c.equality);
             if !holds {
@@ -36,7 +36,9 @@ pub struct Condition {
 /// A rewrite rule.
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Rule {
-    /// A conjunction of clauses
+    /// A conjunction of clauses, the rule is only applied when all of them
+    /// hold. They are evaluated in order, and evaluation stops at the first
+    /// condition that does not hold.
     pub conditions: Vec<Condition>,
     pub lhs: DataExpression,
     pub rhs: DataExpression,
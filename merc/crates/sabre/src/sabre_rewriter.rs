@@ -9,6 +9,7 @@ use merc_data::DataExpressionRef;
 use merc_utilities::debug_trace;
 
 use crate::RewriteSpecification;
+use crate::Rule;
 use crate::matching::nonlinear::check_equivalence_classes;
 use crate::set_automaton::MatchAnnouncement;
 use crate::set_automaton::SetAutomaton;
@@ -22,8 +23,19 @@ use crate::utilities::SideInfoType;
 pub trait RewriteEngine {
     /// Rewrites the given term into normal form.
     fn rewrite(&mut self, term: &DataExpression) -> DataExpression;
+
+    /// Rewrites the given term into normal form, and calls `trace` with the
+    /// applied rule and the redex every time that a rewrite rule is applied.
+    fn rewrite_traced(
+        &mut self,
+        term: &DataExpression,
+        trace: &mut dyn FnMut(&Rule, &DataExpression),
+    ) -> DataExpression;
 }
 
+/// The optional callback that is passed along during rewriting, see [RewriteEngine::rewrite_traced].
+pub(crate) type RewriteTrace<'a> = Option<&'a mut dyn FnMut(&Rule, &DataExpression)>;
+
 #[derive(Default)]
 pub struct RewritingStatistics {
     /// Count the number of rewrite rules applied
@@ -43,6 +55,32 @@ impl RewriteEngine for SabreRewriter {
     fn rewrite(&mut self, term: &DataExpression) -> DataExpression {
         self.stack_based_normalise(term)
     }
+
+    fn rewrite_traced(
+        &mut self,
+        term: &DataExpression,
+        trace: &mut dyn FnMut(&Rule, &DataExpression),
+    ) -> DataExpression {
+        let mut stats = RewritingStatistics::default();
+
+        let (result, _) = THREAD_TERM_POOL.with_borrow(|tp| {
+            SabreRewriter::stack_based_normalise_aux(
+                tp,
+                &self.automaton,
+                term,
+                usize::MAX,
+                &mut Some(trace),
+                &mut stats,
+            )
+        });
+
+        info!(
+            "{} rewrites, {} single steps and {} symbol comparisons",
+            stats.recursions, stats.rewrite_steps, stats.symbol_comparisons
+        );
+
+        result
+    }
 }
 
 impl SabreRewriter {
@@ -56,8 +94,9 @@ impl SabreRewriter {
     pub fn stack_based_normalise(&mut self, t: &DataExpression) -> DataExpression {
         let mut stats = RewritingStatistics::default();
 
-        let result = THREAD_TERM_POOL
-            .with_borrow(|tp| SabreRewriter::stack_based_normalise_aux(tp, &self.automaton, t, &mut stats));
+        let (result, _) = THREAD_TERM_POOL.with_borrow(|tp| {
+            SabreRewriter::stack_based_normalise_aux(tp, &self.automaton, t, usize::MAX, &mut None, &mut stats)
+        });
 
         info!(
             "{} rewrites, {} single steps and {} symbol comparisons",
@@ -67,15 +106,42 @@ impl SabreRewriter {
         result
     }
 
+    /// Rewrites the given term, but applies at most `max_steps` rewrite rules.
+    ///
+    /// Returns the term obtained after the last applied rewrite step, the
+    /// number of steps that were taken and whether this term is a normal form.
+    /// Note that the rewrite steps needed to evaluate the conditions of
+    /// conditional rules also count towards the limit.
+    pub fn rewrite_bounded(&mut self, term: &DataExpression, max_steps: usize) -> (DataExpression, usize, bool) {
+        let mut stats = RewritingStatistics::default();
+
+        let (result, reached_normal_form) = THREAD_TERM_POOL.with_borrow(|tp| {
+            SabreRewriter::stack_based_normalise_aux(tp, &self.automaton, term, max_steps, &mut None, &mut stats)
+        });
+
+        info!(
+            "{} rewrites, {} single steps and {} symbol comparisons, normal form reached: {}",
+            stats.recursions, stats.rewrite_steps, stats.symbol_comparisons, reached_normal_form
+        );
+
+        (result, stats.rewrite_steps, reached_normal_form)
+    }
+
     /// The _aux function splits the [TermPool] pool and the [SetAutomaton] to make borrow checker happy.
     /// We can now mutate the term pool and read the state and transition information at the same time
+    ///
+    /// Stops before applying a rewrite rule once `step_limit` rewrite steps have been taken, the returned
+    /// boolean indicates whether the resulting term is a normal form.
     fn stack_based_normalise_aux(
         tp: &ThreadTermPool,
         automaton: &SetAutomaton<AnnouncementSabre>,
         t: &DataExpression,
+        step_limit: usize,
+        trace: &mut RewriteTrace<'_>,
         stats: &mut RewritingStatistics,
-    ) -> DataExpression {
+    ) -> (DataExpression, bool) {
         stats.recursions += 1;
+        let mut reached_normal_form = true;
 
         // We explore the configuration tree depth first using a ConfigurationStack
         let mut cs = ConfigurationStack::new(0, t);
@@ -119,6 +185,11 @@ impl SabreRewriter {
                                             });
                                         } else {
                                             // For a rewrite rule that is not duplicating or has a condition we just apply it straight away
+                                            if stats.rewrite_steps >= step_limit {
+                                                reached_normal_form = false;
+                                                break 'outer;
+                                            }
+
                                             drop(read_terms);
                                             SabreRewriter::apply_rewrite_rule(
                                                 tp,
@@ -127,6 +198,7 @@ impl SabreRewriter {
                                                 annotation,
                                                 leaf_index,
                                                 &mut cs,
+                                                trace,
                                                 stats,
                                             );
                                             break 'skip_point;
@@ -175,6 +247,11 @@ impl SabreRewriter {
                                     cs.grow(leaf_index, sb);
                                 }
                                 SideInfoType::DelayedRewriteRule(announcement, annotation) => {
+                                    if stats.rewrite_steps >= step_limit {
+                                        reached_normal_form = false;
+                                        break 'outer;
+                                    }
+
                                     drop(read_terms);
                                     // apply the delayed rewrite rule
                                     SabreRewriter::apply_rewrite_rule(
@@ -184,21 +261,37 @@ impl SabreRewriter {
                                         annotation,
                                         leaf_index,
                                         &mut cs,
+                                        trace,
                                         stats,
                                     );
                                 }
                                 SideInfoType::EquivalenceAndConditionCheck(announcement, annotation) => {
                                     // Apply the delayed rewrite rule if the conditions hold
-                                    if check_equivalence_classes(leaf_term, &annotation.equivalence_classes)
-                                        && SabreRewriter::conditions_hold(
-                                            tp,
-                                            automaton,
-                                            announcement,
-                                            annotation,
-                                            leaf_term,
-                                            stats,
-                                        )
-                                    {
+                                    if !check_equivalence_classes(leaf_term, &annotation.equivalence_classes) {
+                                        continue;
+                                    }
+
+                                    let Some(conditions_hold) = SabreRewriter::conditions_hold(
+                                        tp,
+                                        automaton,
+                                        announcement,
+                                        annotation,
+                                        leaf_term,
+                                        step_limit,
+                                        trace,
+                                        stats,
+                                    ) else {
+                                        // The step limit was reached while evaluating the conditions.
+                                        reached_normal_form = false;
+                                        break 'outer;
+                                    };
+
+                                    if conditions_hold {
+                                        if stats.rewrite_steps >= step_limit {
+                                            reached_normal_form = false;
+                                            break 'outer;
+                                        }
+
                                         drop(read_terms);
                                         SabreRewriter::apply_rewrite_rule(
                                             tp,
@@ -207,6 +300,7 @@ impl SabreRewriter {
                                             annotation,
                                             leaf_index,
                                             &mut cs,
+                                            trace,
                                             stats,
                                         );
                                     }
@@ -221,10 +315,11 @@ impl SabreRewriter {
             }
         }
 
-        cs.compute_final_term(tp)
+        (cs.compute_final_term(tp), reached_normal_form)
     }
 
     /// Apply a rewrite rule and prune back
+    #[allow(clippy::too_many_arguments)]
     fn apply_rewrite_rule(
         tp: &ThreadTermPool,
         automaton: &SetAutomaton<AnnouncementSabre>,
@@ -232,17 +327,21 @@ impl SabreRewriter {
         annotation: &AnnouncementSabre,
         leaf_index: usize,
         cs: &mut ConfigurationStack<'_>,
+        trace: &mut RewriteTrace<'_>,
         stats: &mut RewritingStatistics,
     ) {
         stats.rewrite_steps += 1;
 
         let read_terms = cs.terms.read();
         let leaf_subterm: &DataExpressionRef<'_> = &read_terms[leaf_index];
+        let redex = leaf_subterm.get_data_position(&announcement.position);
+
+        if let Some(trace) = trace {
+            trace(&announcement.rule, &redex.protect());
+        }
 
         // Computes the new subterm of the configuration
-        let new_subterm = annotation
-            .rhs_term_stack
-            .evaluate(&leaf_subterm.get_data_position(&announcement.position));
+        let new_subterm = annotation.rhs_term_stack.evaluate(&redex);
 
         debug_trace!(
             "rewrote {} to {} using rule {}",
@@ -258,14 +357,19 @@ impl SabreRewriter {
     }
 
     /// Checks conditions and subterm equality of non-linear patterns.
+    ///
+    /// Returns `None` when the step limit was reached before the conditions could be decided.
+    #[allow(clippy::too_many_arguments)]
     fn conditions_hold(
         tp: &ThreadTermPool,
         automaton: &SetAutomaton<AnnouncementSabre>,
         announcement: &MatchAnnouncement,
         annotation: &AnnouncementSabre,
         subterm: &DataExpressionRef<'_>,
+        step_limit: usize,
+        trace: &mut RewriteTrace<'_>,
         stats: &mut RewritingStatistics,
-    ) -> bool {
+    ) -> Option<bool> {
         for c in &annotation.conditions {
             let subterm = subterm.get_data_position(&announcement.position);
 
@@ -274,16 +378,22 @@ impl SabreRewriter {
 
             // Equality => lhs == rhs.
             if !c.equality || lhs != rhs {
-                let rhs_normal = SabreRewriter::stack_based_normalise_aux(tp, automaton, &rhs, stats);
-                let lhs_normal = SabreRewriter::stack_based_normalise_aux(tp, automaton, &lhs, stats);
+                let (rhs_normal, rhs_done) =
+                    SabreRewriter::stack_based_normalise_aux(tp, automaton, &rhs, step_limit, trace, stats);
+                let (lhs_normal, lhs_done) =
+                    SabreRewriter::stack_based_normalise_aux(tp, automaton, &lhs, step_limit, trace, stats);
+
+                if !rhs_done || !lhs_done {
+                    return None;
+                }
 
                 // If lhs != rhs && !equality OR equality && lhs == rhs.
                 if (!c.equality && lhs_normal == rhs_normal) || (c.equality && lhs_normal != rhs_normal) {
-                    return false;
+                    return Some(false);
                 }
             }
         }
 
-        true
+        Some(true)
     }
 }
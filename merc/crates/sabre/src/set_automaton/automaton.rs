@@ -87,6 +87,28 @@ impl<M> SetAutomaton<M> {
     /// announcement. This is used to accomondate different types of annotations
     /// for the different rewrite engines.
     pub fn new(spec: &RewriteSpecification, annotate: impl Fn(&Rule) -> M, apma: bool) -> SetAutomaton<M> {
+        SetAutomaton::construct(spec, annotate, apma, log_enabled!(log::Level::Debug))
+    }
+
+    /// Creates a new SetAutomaton similar to [SetAutomaton::new], but the
+    /// match goals of every state are kept such that they can be inspected, for
+    /// example using [SetAutomaton::to_dot]. Otherwise they are only kept when
+    /// debug logging is enabled.
+    pub fn new_with_match_goals(
+        spec: &RewriteSpecification,
+        annotate: impl Fn(&Rule) -> M,
+        apma: bool,
+    ) -> SetAutomaton<M> {
+        SetAutomaton::construct(spec, annotate, apma, true)
+    }
+
+    /// The construction shared by [SetAutomaton::new] and [SetAutomaton::new_with_match_goals].
+    fn construct(
+        spec: &RewriteSpecification,
+        annotate: impl Fn(&Rule) -> M,
+        apma: bool,
+        keep_match_goals: bool,
+    ) -> SetAutomaton<M> {
         let start = Instant::now();
 
         // States are labelled s0, s1, s2, etcetera. state_counter keeps track of count.
@@ -229,7 +251,7 @@ impl<M> SetAutomaton<M> {
         }
 
         // Clear the match goals since they are only for debugging purposes.
-        if !log_enabled!(log::Level::Debug) {
+        if !keep_match_goals {
             for state in &mut states {
                 state.match_goals.clear();
             }
@@ -268,6 +290,16 @@ impl<M> SetAutomaton<M> {
         &self.transitions
     }
 
+    /// Renders the automaton in the GraphViz .dot format, including the
+    /// transitions back to the initial state and to the final state.
+    ///
+    /// Every state is labelled by the position that it inspects and its match
+    /// goals, which are only available when the automaton was created by
+    /// [SetAutomaton::new_with_match_goals].
+    pub fn to_dot(&self) -> String {
+        self.to_dot_graph(true, true).to_string()
+    }
+
     /// Provides a formatter for the .dot file format
     pub fn to_dot_graph(&self, show_backtransitions: bool, show_final: bool) -> DotFormatter<'_, M> {
         DotFormatter {
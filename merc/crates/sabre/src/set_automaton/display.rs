@@ -123,3 +123,40 @@ impl<M> fmt::Display for DotFormatter<'_, M> {
         writeln!(f, "}}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::RewriteSpecification;
+    use crate::test_utility::create_rewrite_rule;
+
+    use super::*;
+
+    #[test]
+    fn test_set_automaton_to_dot() {
+        let spec = RewriteSpecification::new(vec![
+            create_rewrite_rule("f(x, b)", "x", &["x"]).unwrap(),
+            create_rewrite_rule("f(g(x), y)", "y", &["x", "y"]).unwrap(),
+        ]);
+
+        let automaton = SetAutomaton::new_with_match_goals(&spec, |_| (), false);
+        let dot = automaton.to_dot();
+
+        assert!(automaton.num_of_states() > 1);
+        assert!(automaton.num_of_transitions() > 0);
+
+        assert!(
+            !automaton.states()[0].match_goals().is_empty(),
+            "The match goals should be kept"
+        );
+
+        for (index, state) in automaton.states().iter().enumerate() {
+            assert!(dot.contains(&format!("s{index}[shape=record")));
+
+            for goal in state.match_goals() {
+                for obligation in &goal.obligations {
+                    assert!(dot.contains(&format!("@{}", obligation.position)));
+                }
+            }
+        }
+    }
+}
@@ -1,10 +1,20 @@
 #![forbid(unsafe_code)]
 
+use ahash::AHashMap;
 use ahash::AHashSet;
 use merc_aterm::ATerm;
+use merc_data::DataApplication;
+use merc_data::DataExpression;
+use merc_data::DataExpressionRef;
+use merc_data::DataFunctionSymbol;
+use merc_data::DataVariable;
+use merc_data::is_data_variable;
 use merc_data::to_untyped_data_expression;
 use merc_utilities::MercError;
+use rand::Rng;
+use rand::prelude::IteratorRandom;
 
+use crate::RewriteSpecification;
 use crate::Rule;
 
 /// Create a rewrite rule lhs -> rhs with the given names being variables.
@@ -22,3 +32,304 @@ pub fn create_rewrite_rule(lhs: &str, rhs: &str, variables: &[&str]) -> Result<R
         rhs: to_untyped_data_expression(rhs, Some(&vars)),
     })
 }
+
+/// The many-sorted signature of a rewrite specification, where sorts are
+/// represented by indices.
+///
+/// # Details
+///
+/// Rewrite specifications are untyped, so the sorts are inferred from the
+/// rewrite rules. Every function symbol (of a given arity) has a result sort
+/// and a sort for each argument. The sort of an argument position is the
+/// result sort of every term that occurs at that position, both sides of a
+/// rule and of a condition have the same sort, and the occurrences of a
+/// variable within one rule have the same sort.
+pub struct Signature {
+    /// The function symbols with their argument sorts and result sort.
+    symbols: Vec<(DataFunctionSymbol, Vec<usize>, usize)>,
+
+    /// Maps a function symbol and its arity to its index in `symbols`.
+    indices: AHashMap<(DataFunctionSymbol, usize), usize>,
+
+    /// The number of sorts.
+    number_of_sorts: usize,
+}
+
+impl Signature {
+    /// Infers the signature of the function symbols that occur in the given specification.
+    pub fn from_spec(spec: &RewriteSpecification) -> Signature {
+        let mut inference = SortInference::default();
+
+        for rule in spec.rewrite_rules() {
+            let mut variables = AHashMap::new();
+
+            let lhs = inference.infer(&rule.lhs.copy(), &mut variables);
+            let rhs = inference.infer(&rule.rhs.copy(), &mut variables);
+            inference.union(lhs, rhs);
+
+            for condition in &rule.conditions {
+                let lhs = inference.infer(&condition.lhs.copy(), &mut variables);
+                let rhs = inference.infer(&condition.rhs.copy(), &mut variables);
+                inference.union(lhs, rhs);
+            }
+        }
+
+        // Number the resulting equivalence classes consecutively.
+        let mut sorts = AHashMap::new();
+        let mut canonical = |inference: &mut SortInference, class: usize| {
+            let representative = inference.find(class);
+            let next = sorts.len();
+            *sorts.entry(representative).or_insert(next)
+        };
+
+        let mut symbols = Vec::new();
+        for (symbol, arguments, result) in inference.symbols.clone() {
+            let arguments = arguments
+                .iter()
+                .map(|argument| canonical(&mut inference, *argument))
+                .collect();
+            let result = canonical(&mut inference, result);
+            symbols.push((symbol, arguments, result));
+        }
+
+        Signature {
+            symbols,
+            indices: inference.indices,
+            number_of_sorts: sorts.len(),
+        }
+    }
+
+    /// Returns the function symbols with their argument sorts and result sort.
+    pub fn symbols(&self) -> &[(DataFunctionSymbol, Vec<usize>, usize)] {
+        &self.symbols
+    }
+
+    /// Returns the sort of the given ground term, or None when it is not
+    /// well-sorted with respect to this signature.
+    pub fn sort_of(&self, term: &DataExpressionRef<'_>) -> Option<usize> {
+        if is_data_variable(term) {
+            return None;
+        }
+
+        let arguments: Vec<DataExpressionRef<'_>> = term.data_arguments().collect();
+        let index = self
+            .indices
+            .get(&(term.data_function_symbol().protect(), arguments.len()))?;
+        let (_, argument_sorts, result) = &self.symbols[*index];
+
+        for (argument, sort) in arguments.iter().zip(argument_sorts) {
+            if self.sort_of(argument)? != *sort {
+                return None;
+            }
+        }
+
+        Some(*result)
+    }
+}
+
+/// Generates a random ground term over the signature of the given
+/// specification, see [Signature], with at most `max_depth` nested function
+/// applications. Returns None when no such term exists.
+pub fn random_well_sorted_term(
+    spec: &RewriteSpecification,
+    max_depth: usize,
+    rng: &mut impl Rng,
+) -> Option<DataExpression> {
+    let signature = Signature::from_spec(spec);
+
+    // For every depth d, whether the sort has a ground term with at most d nested applications.
+    let mut inhabited = vec![vec![false; signature.number_of_sorts]];
+    for (_, arguments, result) in &signature.symbols {
+        if arguments.is_empty() {
+            inhabited[0][*result] = true;
+        }
+    }
+
+    for depth in 1..=max_depth {
+        let mut next = inhabited[depth - 1].clone();
+        for (_, arguments, result) in &signature.symbols {
+            if arguments.iter().all(|sort| inhabited[depth - 1][*sort]) {
+                next[*result] = true;
+            }
+        }
+        inhabited.push(next);
+    }
+
+    let index = applicable_symbols(&signature, &inhabited, None, max_depth).choose(rng)?;
+    Some(generate_term(&signature, &inhabited, index, max_depth, rng))
+}
+
+/// Returns the indices of the function symbols that can be applied at the
+/// given depth, and have the given result sort when it is provided.
+fn applicable_symbols<'a>(
+    signature: &'a Signature,
+    inhabited: &'a [Vec<bool>],
+    sort: Option<usize>,
+    depth: usize,
+) -> impl Iterator<Item = usize> + 'a {
+    signature
+        .symbols
+        .iter()
+        .enumerate()
+        .filter(move |(_, (_, arguments, result))| {
+            sort.is_none_or(|sort| sort == *result)
+                && (arguments.is_empty() || (depth > 0 && arguments.iter().all(|sort| inhabited[depth - 1][*sort])))
+        })
+        .map(|(index, _)| index)
+}
+
+/// Generates a random term with the function symbol at the given index as head symbol.
+fn generate_term(
+    signature: &Signature,
+    inhabited: &[Vec<bool>],
+    index: usize,
+    depth: usize,
+    rng: &mut impl Rng,
+) -> DataExpression {
+    let (symbol, arguments, _) = &signature.symbols[index];
+    if arguments.is_empty() {
+        return symbol.clone().into();
+    }
+
+    let arguments: Vec<DataExpression> = arguments
+        .iter()
+        .map(|sort| {
+            let index = applicable_symbols(signature, inhabited, Some(*sort), depth - 1)
+                .choose(rng)
+                .expect("The sort of every argument is inhabited");
+            generate_term(signature, inhabited, index, depth - 1, rng)
+        })
+        .collect();
+
+    DataApplication::with_args(symbol, &arguments).into()
+}
+
+/// Infers the sorts of a signature by merging equivalence classes of sorts.
+#[derive(Default)]
+struct SortInference {
+    /// The parent of every class, where the representatives are their own parent.
+    parent: Vec<usize>,
+
+    /// The function symbols with the classes of their arguments and result.
+    symbols: Vec<(DataFunctionSymbol, Vec<usize>, usize)>,
+
+    /// Maps a function symbol and its arity to its index in `symbols`.
+    indices: AHashMap<(DataFunctionSymbol, usize), usize>,
+}
+
+impl SortInference {
+    /// Returns a fresh class.
+    fn fresh(&mut self) -> usize {
+        self.parent.push(self.parent.len());
+        self.parent.len() - 1
+    }
+
+    /// Returns the representative of the given class.
+    fn find(&mut self, class: usize) -> usize {
+        let mut representative = class;
+        while self.parent[representative] != representative {
+            representative = self.parent[representative];
+        }
+
+        // Path compression.
+        let mut class = class;
+        while self.parent[class] != representative {
+            let next = self.parent[class];
+            self.parent[class] = representative;
+            class = next;
+        }
+
+        representative
+    }
+
+    /// Merges the two given classes.
+    fn union(&mut self, left: usize, right: usize) {
+        let left = self.find(left);
+        let right = self.find(right);
+        self.parent[left] = right;
+    }
+
+    /// Returns the class of the given term, and merges the classes of its
+    /// arguments with the argument classes of its head symbol.
+    fn infer(&mut self, term: &DataExpressionRef<'_>, variables: &mut AHashMap<DataVariable, usize>) -> usize {
+        if is_data_variable(term) {
+            let variable = DataVariable::from(term.protect());
+            if let Some(class) = variables.get(&variable) {
+                return *class;
+            }
+
+            let class = self.fresh();
+            variables.insert(variable, class);
+            return class;
+        }
+
+        let arguments: Vec<DataExpressionRef<'_>> = term.data_arguments().collect();
+        let key = (term.data_function_symbol().protect(), arguments.len());
+        let index = match self.indices.get(&key) {
+            Some(index) => *index,
+            None => {
+                let argument_classes = (0..arguments.len()).map(|_| self.fresh()).collect();
+                let result = self.fresh();
+                self.symbols.push((key.0.clone(), argument_classes, result));
+                self.indices.insert(key, self.symbols.len() - 1);
+                self.symbols.len() - 1
+            }
+        };
+
+        for (position, argument) in arguments.iter().enumerate() {
+            let class = self.infer(argument, variables);
+            self.union(class, self.symbols[index].1[position]);
+        }
+
+        self.symbols[index].2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use merc_utilities::random_test;
+
+    use super::*;
+
+    /// Returns the number of nested function applications of the given term.
+    fn depth(term: &DataExpressionRef<'_>) -> usize {
+        term.data_arguments()
+            .map(|argument| depth(&argument) + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_random_well_sorted_term() {
+        let spec = RewriteSpecification::new(vec![
+            create_rewrite_rule("plus(x, zero)", "x", &["x"]).unwrap(),
+            create_rewrite_rule("plus(x, s(y))", "s(plus(x, y))", &["x", "y"]).unwrap(),
+            create_rewrite_rule("less(zero, s(x))", "true", &["x"]).unwrap(),
+            create_rewrite_rule("less(x, zero)", "false", &["x"]).unwrap(),
+            create_rewrite_rule("less(s(x), s(y))", "less(x, y)", &["x", "y"]).unwrap(),
+            create_rewrite_rule("and(true, b)", "b", &["b"]).unwrap(),
+            create_rewrite_rule("and(false, b)", "false", &["b"]).unwrap(),
+        ]);
+
+        let signature = Signature::from_spec(&spec);
+        let sort_of = |text: &str| signature.sort_of(&DataExpression::from_string(text).unwrap().copy());
+
+        // The natural numbers and booleans have been inferred as different sorts.
+        assert_eq!(sort_of("plus(zero, s(zero))"), sort_of("zero"));
+        assert_eq!(sort_of("and(less(zero, zero), true)"), sort_of("false"));
+        assert_ne!(sort_of("zero"), sort_of("true"));
+        assert_eq!(sort_of("and(zero, true)"), None);
+        assert_eq!(sort_of("s(zero, zero)"), None);
+
+        random_test(100, |rng| {
+            let max_depth = rng.random_range(0..5);
+            let term = random_well_sorted_term(&spec, max_depth, rng).expect("The specification has constants");
+
+            assert!(
+                signature.sort_of(&term.copy()).is_some(),
+                "The term {term} is not well-sorted"
+            );
+            assert!(depth(&term.copy()) <= max_depth, "The term {term} is too deep");
+        });
+    }
+}
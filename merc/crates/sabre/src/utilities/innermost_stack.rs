@@ -15,6 +15,7 @@ use itertools::Itertools;
 use crate::utilities::DataPositionIndexed;
 
 use super::Config;
+use super::NormalFormCache;
 use super::TermStack;
 
 /// This stack is used to avoid recursion and also to keep track of terms in
@@ -24,6 +25,9 @@ use super::TermStack;
 pub struct InnermostStack {
     pub configs: Protected<Vec<Config<'static>>>,
     pub terms: Protected<Vec<Option<DataExpressionRef<'static>>>>,
+
+    /// An optional cache of previously computed normal forms.
+    pub cache: Option<NormalFormCache>,
 }
 
 impl InnermostStack {
@@ -58,7 +62,7 @@ impl InnermostStack {
                     let term = write_configs.protect(term);
                     write_configs.push(Config::Term(term.into(), *index));
                 }
-                Config::Rewrite(_) => {
+                Config::Rewrite(_) | Config::Store(_, _) => {
                     unreachable!("This case should not happen");
                 }
                 Config::Return() => {
@@ -2,6 +2,7 @@ mod configuration_stack;
 mod data_position;
 mod data_substitution;
 mod innermost_stack;
+mod normal_form_cache;
 mod position;
 mod substitution;
 mod term_stack;
@@ -10,6 +11,7 @@ pub use configuration_stack::*;
 pub use data_position::*;
 pub use data_substitution::*;
 pub use innermost_stack::*;
+pub use normal_form_cache::*;
 pub use position::*;
 pub use substitution::*;
 pub use term_stack::*;
@@ -0,0 +1,215 @@
+#![forbid(unsafe_code)]
+
+use std::collections::HashMap;
+
+use merc_aterm::Term;
+use merc_data::DataExpression;
+use merc_data::DataExpressionRef;
+use merc_utilities::NoHasherBuilder;
+
+/// A bounded cache that maps terms to their normal forms, evicting the least
+/// recently used entry when it is full.
+///
+/// # Details
+///
+/// Terms are maximally shared, so the cache is keyed by the identity of the
+/// term. The key is the address of the term multiplied by an odd constant,
+/// which is a bijection and spreads the addresses evenly over the hash
+/// buckets. For this reason the index uses the [NoHasherBuilder]. The cache
+/// keeps both the term and its normal form protected, which guarantees that
+/// the address of a cached term cannot be reused.
+pub struct NormalFormCache {
+    /// Maps the key of a term to its entry.
+    index: HashMap<u64, usize, NoHasherBuilder>,
+
+    /// The entries, which form a doubly linked list from the most recently used
+    /// (head) to the least recently used (tail) entry.
+    entries: Vec<CacheEntry>,
+    head: Option<usize>,
+    tail: Option<usize>,
+
+    capacity: usize,
+    hits: usize,
+    misses: usize,
+}
+
+struct CacheEntry {
+    term: DataExpression,
+    normal_form: DataExpression,
+    previous: Option<usize>,
+    next: Option<usize>,
+}
+
+impl NormalFormCache {
+    /// Creates a new cache that stores at most `capacity` normal forms.
+    pub fn new(capacity: usize) -> NormalFormCache {
+        assert!(capacity > 0, "The capacity of the cache must be positive");
+
+        NormalFormCache {
+            index: HashMap::with_capacity_and_hasher(capacity, NoHasherBuilder),
+            entries: Vec::with_capacity(capacity),
+            head: None,
+            tail: None,
+            capacity,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the normal form of the given term, if it is cached, and marks
+    /// it as the most recently used entry.
+    pub fn get(&mut self, term: &DataExpressionRef<'_>) -> Option<DataExpressionRef<'_>> {
+        if let Some(&entry) = self.index.get(&Self::key(term)) {
+            self.hits += 1;
+            self.move_to_front(entry);
+            Some(self.entries[entry].normal_form.copy())
+        } else {
+            self.misses += 1;
+            None
+        }
+    }
+
+    /// Stores the normal form of the given term, evicting the least recently
+    /// used entry when the cache is full.
+    pub fn insert(&mut self, term: DataExpression, normal_form: DataExpression) {
+        let key = Self::key(&term.copy());
+        if let Some(&entry) = self.index.get(&key) {
+            // The normal form of a term is unique, so only update its use.
+            self.move_to_front(entry);
+            return;
+        }
+
+        let entry = if self.entries.len() < self.capacity {
+            self.entries.push(CacheEntry {
+                term,
+                normal_form,
+                previous: None,
+                next: None,
+            });
+            self.entries.len() - 1
+        } else {
+            // Reuse the least recently used entry.
+            let entry = self.tail.expect("A full cache has a least recently used entry");
+            self.unlink(entry);
+            self.index.remove(&Self::key(&self.entries[entry].term.copy()));
+
+            self.entries[entry].term = term;
+            self.entries[entry].normal_form = normal_form;
+            entry
+        };
+
+        self.index.insert(key, entry);
+        self.push_front(entry);
+    }
+
+    /// Returns the number of cached normal forms.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true iff no normal forms are cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the maximum number of cached normal forms.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of lookups that found a normal form.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Returns the number of lookups that did not find a normal form.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Returns the fraction of lookups that found a normal form.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+
+    /// Returns the key of the given term.
+    fn key(term: &DataExpressionRef<'_>) -> u64 {
+        (term.index() as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+    }
+
+    /// Marks the given entry as the most recently used one.
+    fn move_to_front(&mut self, entry: usize) {
+        if self.head != Some(entry) {
+            self.unlink(entry);
+            self.push_front(entry);
+        }
+    }
+
+    /// Removes the given entry from the linked list.
+    fn unlink(&mut self, entry: usize) {
+        let CacheEntry { previous, next, .. } = self.entries[entry];
+
+        match previous {
+            Some(previous) => self.entries[previous].next = next,
+            None => self.head = next,
+        }
+
+        match next {
+            Some(next) => self.entries[next].previous = previous,
+            None => self.tail = previous,
+        }
+    }
+
+    /// Inserts the given (unlinked) entry at the front of the linked list.
+    fn push_front(&mut self, entry: usize) {
+        self.entries[entry].previous = None;
+        self.entries[entry].next = self.head;
+
+        if let Some(head) = self.head {
+            self.entries[head].previous = Some(entry);
+        }
+        self.head = Some(entry);
+
+        if self.tail.is_none() {
+            self.tail = Some(entry);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use merc_aterm::ATerm;
+    use merc_data::to_untyped_data_expression;
+
+    use super::*;
+
+    fn expression(text: &str) -> DataExpression {
+        to_untyped_data_expression(ATerm::from_string(text).unwrap(), None)
+    }
+
+    #[test]
+    fn test_normal_form_cache_eviction() {
+        let mut cache = NormalFormCache::new(2);
+
+        let (a, b, c) = (expression("f(a)"), expression("f(b)"), expression("f(c)"));
+        cache.insert(a.clone(), expression("a"));
+        cache.insert(b.clone(), expression("b"));
+
+        // Using a makes b the least recently used entry.
+        assert_eq!(cache.get(&a.copy()), Some(expression("a").copy()));
+        cache.insert(c.clone(), expression("c"));
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&b.copy()), None);
+        assert_eq!(cache.get(&a.copy()), Some(expression("a").copy()));
+        assert_eq!(cache.get(&c.copy()), Some(expression("c").copy()));
+
+        assert_eq!(cache.hits(), 3);
+        assert_eq!(cache.misses(), 1);
+    }
+}
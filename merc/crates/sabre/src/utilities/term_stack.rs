@@ -51,35 +51,42 @@ pub enum Config<'a> {
     Construct(DataFunctionSymbolRef<'a>, usize, usize),
     /// A concrete term to be placed at the current position in the stack.
     Term(DataExpressionRef<'a>, usize),
+    /// Stores the term at the given index as the normal form of the given term in the normal form cache.
+    Store(DataExpressionRef<'a>, usize),
     /// Yields the given index as returned term.
     Return(),
 }
 
 impl Markable for Config<'_> {
     fn mark(&self, marker: &mut Marker<'_>) {
-        if let Config::Construct(t, _, _) = self {
-            t.mark(marker);
+        match self {
+            Config::Construct(t, _, _) => t.mark(marker),
+            Config::Store(t, _) => t.mark(marker),
+            _ => {}
         }
     }
 
     fn contains_term(&self, term: &ATermRef<'_>) -> bool {
-        if let Config::Construct(t, _, _) = self {
-            t.contains_term(term)
-        } else {
-            false
+        match self {
+            Config::Construct(t, _, _) => t.contains_term(term),
+            Config::Store(t, _) => t.contains_term(term),
+            _ => false,
         }
     }
 
     fn contains_symbol(&self, symbol: &SymbolRef<'_>) -> bool {
-        if let Config::Construct(t, _, _) = self {
-            t.contains_symbol(symbol)
-        } else {
-            false
+        match self {
+            Config::Construct(t, _, _) => t.contains_symbol(symbol),
+            Config::Store(t, _) => t.contains_symbol(symbol),
+            _ => false,
         }
     }
 
     fn len(&self) -> usize {
-        if let Config::Construct(_, _, _) = self { 1 } else { 0 }
+        match self {
+            Config::Construct(_, _, _) | Config::Store(_, _) => 1,
+            _ => 0,
+        }
     }
 }
 
@@ -105,6 +112,9 @@ impl fmt::Display for Config<'_> {
             Config::Term(term, result) => {
                 write!(f, "Term({term}, {result})")
             }
+            Config::Store(term, result) => {
+                write!(f, "Store({term}, {result})")
+            }
             Config::Return() => write!(f, "Return()"),
         }
     }
@@ -204,7 +214,7 @@ impl TermStack {
                         let t = write_terms.protect(&term);
                         write_terms[index] = Some(t.into());
                     }
-                    Config::Rewrite(_) => {
+                    Config::Rewrite(_) | Config::Store(_, _) => {
                         unreachable!("This case should not happen");
                     }
                     Config::Return() => {
@@ -260,6 +270,9 @@ impl Clone for TermStack {
                 Config::Term(t, y) => {
                     write.push(Config::Term(t.copy(), *y));
                 }
+                Config::Store(t, y) => {
+                    write.push(Config::Store(t.copy(), *y));
+                }
                 Config::Return() => write.push(Config::Return()),
             }
         }
@@ -1,12 +1,7 @@
-use std::hint::black_box;
-use std::sync::Arc;
-use std::sync::Barrier;
-use std::sync::atomic::AtomicBool;
-use std::thread::{self};
-
 use criterion::Criterion;
-use rand::distr::Bernoulli;
-use rand::distr::Distribution;
+
+use merc_sharedmutex::ContentionBench;
+use merc_sharedmutex::ContentionConfig;
 
 /// The number of iterations to run for each benchmark.
 pub const NUM_ITERATIONS: usize = 100000;
@@ -28,74 +23,25 @@ pub fn benchmark<T, R, W>(
     read_ratio: u32,
 ) where
     T: Clone + Send + 'static,
-    R: FnOnce(&T) + Send + Copy + 'static,
-    W: FnOnce(&T) + Send + Copy + 'static,
+    R: Fn(&T) + Send + Copy + 'static,
+    W: Fn(&T) + Send + Copy + 'static,
 {
     // Share threads to avoid overhead.
-    let mut threads = vec![];
-
-    #[derive(Clone)]
-    struct ThreadInfo<T> {
-        busy: Arc<AtomicBool>,
-        begin_barrier: Arc<Barrier>,
-        end_barrier: Arc<Barrier>,
-        dist: Bernoulli,
-        shared: T,
-    }
-
-    let info = ThreadInfo {
-        busy: Arc::new(AtomicBool::new(true)),
-        begin_barrier: Arc::new(Barrier::new(num_threads + 1)),
-        end_barrier: Arc::new(Barrier::new(num_threads + 1)),
-        dist: Bernoulli::from_ratio(1, read_ratio).unwrap(),
+    let bench = ContentionBench::new(
         shared,
-    };
-
-    for _ in 0..num_threads {
-        let info = info.clone();
-        threads.push(thread::spawn(move || {
-            let mut rng = rand::rng();
-
-            loop {
-                info.begin_barrier.wait();
-
-                if !info.busy.load(std::sync::atomic::Ordering::SeqCst) {
-                    // Quit the thread.
-                    break;
-                }
-
-                // We execute it a fixed number of times.
-                for _ in 0..num_iterations {
-                    if info.dist.sample(&mut rng) {
-                        write(&info.shared);
-                        black_box(());
-                    } else {
-                        read(&info.shared);
-                        black_box(());
-                    }
-                }
-
-                info.end_barrier.wait();
-            }
-        }));
-    }
+        read,
+        write,
+        ContentionConfig {
+            num_threads,
+            num_iterations,
+            read_ratio,
+        },
+    );
 
     c.bench_function(
         format!("{name} {num_threads} {num_iterations} {read_ratio}").as_str(),
         |bencher| {
-            bencher.iter(|| {
-                info.begin_barrier.wait();
-
-                info.end_barrier.wait();
-            });
+            bencher.iter(|| bench.run());
         },
     );
-
-    // Tell the threads to quit and wait for them to join.
-    info.busy.store(false, std::sync::atomic::Ordering::SeqCst);
-    info.begin_barrier.wait();
-
-    for thread in threads {
-        thread.join().unwrap();
-    }
 }
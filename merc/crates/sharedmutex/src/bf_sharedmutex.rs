@@ -252,6 +252,64 @@ impl<T> BfSharedMutex<T> {
         })
     }
 
+    /// Attempts to acquire write access to the underlying object without blocking.
+    ///
+    /// # Details
+    ///
+    /// Returns `None` when another instance holds the write lock, or when
+    /// another instance holds a read lock. In the latter case the forbidden
+    /// flags are reset before returning, such that the waiting readers can
+    /// continue. Also returns `None` when the mutex is poisoned.
+    pub fn try_write(&self) -> Option<BfSharedMutexWriteGuard<'_, T>> {
+        debug_assert!(
+            !self.control.busy.load(Ordering::SeqCst),
+            "Can only exclusive lock outside of a shared lock, use try_upgrade instead"
+        );
+
+        self.try_forbid_others()
+    }
+
+    /// Attempts to upgrade the read lock that this instance holds to a write
+    /// lock without blocking, returns `None` when another instance holds a
+    /// lock or waits for one. On success the read lock is released, so the
+    /// read guard must be forgotten.
+    pub(crate) fn try_upgrade(&self) -> Option<BfSharedMutexWriteGuard<'_, T>> {
+        debug_assert!(
+            self.control.busy.load(Ordering::SeqCst),
+            "Can only upgrade inside of a shared lock"
+        );
+
+        let guard = self.try_forbid_others()?;
+
+        // All other instances are forbidden, so we can leave our busy section.
+        self.control.busy.store(false, Ordering::SeqCst);
+        Some(guard)
+    }
+
+    /// Sets the forbidden flags of all instances and returns the write guard
+    /// when no other instance is busy, without blocking.
+    fn try_forbid_others(&self) -> Option<BfSharedMutexWriteGuard<'_, T>> {
+        let other = self.shared.other.try_lock().ok()?;
+
+        // Make all instances wait due to forbidden access.
+        for control in other.iter().flatten() {
+            control.forbidden.store(true, Ordering::SeqCst);
+        }
+
+        // If any of the other instances is busy then a reader holds the lock.
+        let busy = other.iter().enumerate().any(|(index, option)| {
+            index != self.index && option.as_ref().is_some_and(|c| c.busy.load(Ordering::SeqCst))
+        });
+
+        // Creating the guard ensures that the forbidden flags are reset on failure.
+        let guard = BfSharedMutexWriteGuard {
+            mutex: self,
+            guard: other,
+        };
+
+        if busy { None } else { Some(guard) }
+    }
+
     /// Check if the shared mutex is locked shared, meaning no other thread has a read lock.
     pub fn is_locked(&self) -> bool {
         self.control.busy.load(Ordering::Relaxed)
@@ -326,6 +384,8 @@ mod tests {
     use crate::bf_sharedmutex::BfSharedMutex;
     use rand::prelude::*;
     use std::hint::black_box;
+    use std::sync::Barrier;
+    use std::thread;
 
     use merc_utilities::random_test_threads;
     use merc_utilities::test_threads;
@@ -378,4 +438,46 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_shared_mutex_try_write() {
+        let mut shared_number = BfSharedMutex::new(5);
+        let acquired = Barrier::new(2);
+        let release = Barrier::new(2);
+
+        for exclusive in [false, true] {
+            thread::scope(|s| {
+                // Spawn a thread that holds the lock until it is released.
+                let holder = shared_number.clone();
+                let (acquired, release) = (&acquired, &release);
+                s.spawn(move || {
+                    if exclusive {
+                        let _guard = holder.write().unwrap();
+                        acquired.wait();
+                        release.wait();
+                    } else {
+                        let _guard = holder.read().unwrap();
+                        acquired.wait();
+                        release.wait();
+                    }
+                });
+
+                acquired.wait();
+                thread::scope(|s| {
+                    for _ in 0..4 {
+                        let number = shared_number.clone();
+                        s.spawn(move || assert!(number.try_write().is_none()));
+                    }
+                });
+                assert!(shared_number.try_write().is_none());
+                release.wait();
+            });
+
+            // The holder has released the lock, so the attempt succeeds.
+            *shared_number.try_write().unwrap() += 1;
+        }
+
+        assert_eq!(*shared_number.get_mut(), 7);
+    }
 }
@@ -0,0 +1,173 @@
+//! Authors: Maurice Laveaux, Flip van Spaendonck and Jan Friso Groote
+
+use std::hint::black_box;
+use std::sync::Arc;
+use std::sync::Barrier;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::time::Instant;
+
+use rand::distr::Bernoulli;
+use rand::distr::Distribution;
+
+/// The configuration of a contention benchmark.
+#[derive(Clone, Copy, Debug)]
+pub struct ContentionConfig {
+    /// The number of threads that access the shared object concurrently.
+    pub num_threads: usize,
+
+    /// The number of operations that every thread performs per sample.
+    pub num_iterations: usize,
+
+    /// The (average) number of read operations per write operation, must be positive.
+    pub read_ratio: u32,
+}
+
+/// A contention benchmark for a readers-writer lock, where a number of threads
+/// perform a random mix of read and write operations on a shared object.
+///
+/// # Details
+///
+/// The threads are spawned once and shared between all samples to avoid the
+/// overhead of spawning threads, and they are joined when the benchmark is
+/// dropped. Every thread obtains its own clone of the shared object, as is
+/// required for the [crate::BfSharedMutex].
+pub struct ContentionBench {
+    control: Arc<BenchControl>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+/// The state shared between the benchmark and its threads.
+struct BenchControl {
+    busy: AtomicBool,
+    begin_barrier: Barrier,
+    end_barrier: Barrier,
+}
+
+impl ContentionBench {
+    /// Spawns the threads of the benchmark, where `read` and `write` perform
+    /// a single read and write operation on the shared object respectively.
+    pub fn new<T, R, W>(shared: T, read: R, write: W, config: ContentionConfig) -> Self
+    where
+        T: Clone + Send + 'static,
+        R: Fn(&T) + Send + Copy + 'static,
+        W: Fn(&T) + Send + Copy + 'static,
+    {
+        let control = Arc::new(BenchControl {
+            busy: AtomicBool::new(true),
+            begin_barrier: Barrier::new(config.num_threads + 1),
+            end_barrier: Barrier::new(config.num_threads + 1),
+        });
+        let dist = Bernoulli::from_ratio(1, config.read_ratio).expect("The read ratio must be positive");
+
+        let threads = (0..config.num_threads)
+            .map(|_| {
+                let control = control.clone();
+                let shared = shared.clone();
+                thread::spawn(move || {
+                    let mut rng = rand::rng();
+
+                    loop {
+                        control.begin_barrier.wait();
+
+                        if !control.busy.load(Ordering::SeqCst) {
+                            // Quit the thread.
+                            break;
+                        }
+
+                        // We execute it a fixed number of times.
+                        for _ in 0..config.num_iterations {
+                            if dist.sample(&mut rng) {
+                                write(&shared);
+                                black_box(());
+                            } else {
+                                read(&shared);
+                                black_box(());
+                            }
+                        }
+
+                        control.end_barrier.wait();
+                    }
+                })
+            })
+            .collect();
+
+        ContentionBench { control, threads }
+    }
+
+    /// Lets all threads perform their operations once, and returns the time
+    /// until the last thread has finished.
+    pub fn run(&self) -> Duration {
+        let start = Instant::now();
+        self.control.begin_barrier.wait();
+        self.control.end_barrier.wait();
+        start.elapsed()
+    }
+}
+
+impl Drop for ContentionBench {
+    fn drop(&mut self) {
+        // Tell the threads to quit and wait for them to join.
+        self.control.busy.store(false, Ordering::SeqCst);
+        self.control.begin_barrier.wait();
+
+        for thread in self.threads.drain(..) {
+            thread.join().expect("A benchmark thread panicked");
+        }
+    }
+}
+
+/// Runs a contention benchmark, see [ContentionBench], and returns the time
+/// taken by each of the given number of samples.
+pub fn contention_bench<T, R, W>(
+    shared: T,
+    read: R,
+    write: W,
+    config: ContentionConfig,
+    num_samples: usize,
+) -> Vec<Duration>
+where
+    T: Clone + Send + 'static,
+    R: Fn(&T) + Send + Copy + 'static,
+    W: Fn(&T) + Send + Copy + 'static,
+{
+    let bench = ContentionBench::new(shared, read, write, config);
+    (0..num_samples).map(|_| bench.run()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BfSharedMutex;
+
+    use super::*;
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_contention_bench() {
+        let shared = BfSharedMutex::new(0);
+        let config = ContentionConfig {
+            num_threads: 4,
+            num_iterations: 100,
+            read_ratio: 1,
+        };
+
+        // With a read ratio of one every operation is a write.
+        let samples = contention_bench(
+            shared.clone(),
+            |shared| {
+                let _guard = shared.read().unwrap();
+            },
+            |shared| {
+                *shared.write().unwrap() += 1;
+            },
+            config,
+            3,
+        );
+
+        assert_eq!(samples.len(), 3);
+        assert_eq!(*shared.read().unwrap(), 3 * 4 * 100);
+    }
+}
@@ -2,8 +2,12 @@
 
 mod bf_sharedmutex;
 mod bf_vec;
+#[cfg(feature = "bench")]
+mod contention_bench;
 mod recursive_lock;
 
 pub use bf_sharedmutex::*;
 pub use bf_vec::*;
+#[cfg(feature = "bench")]
+pub use contention_bench::*;
 pub use recursive_lock::*;
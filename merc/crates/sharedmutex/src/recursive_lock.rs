@@ -118,6 +118,35 @@ impl<T> Deref for RecursiveLockReadGuard<'_, T> {
     }
 }
 
+impl<'a, T> RecursiveLockReadGuard<'a, T> {
+    /// Upgrades the read lock to a write lock without releasing it in between.
+    ///
+    /// # Details
+    ///
+    /// The upgrade only succeeds when this guard is the only (recursive) read
+    /// guard of the current thread and no other thread holds or waits for a
+    /// lock. Otherwise the upgrade would deadlock, or invalidate the other read
+    /// guards of this thread, and the read guard is returned unchanged.
+    pub fn upgrade(self) -> Result<RecursiveLockWriteGuard<'a, T>, Self> {
+        if self.mutex.recursive_depth.get() != 1 {
+            // Other read guards of this thread are still alive.
+            return Err(self);
+        }
+
+        match self.mutex.inner.try_upgrade() {
+            Some(guard) => {
+                let mutex = self.mutex;
+
+                // The read lock has been released by the upgrade, and the write guard keeps the recursive depth at one.
+                mem::forget(self);
+                mutex.write_calls.set(mutex.write_calls.get() + 1);
+                Ok(RecursiveLockWriteGuard { mutex, guard })
+            }
+            None => Err(self),
+        }
+    }
+}
+
 impl<T> Drop for RecursiveLockReadGuard<'_, T> {
     fn drop(&mut self) {
         self.mutex.recursive_depth.set(self.mutex.recursive_depth.get() - 1);
@@ -163,6 +192,9 @@ impl<T> Drop for RecursiveLockWriteGuard<'_, T> {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Barrier;
+    use std::thread;
+
     use super::*;
 
     #[test]
@@ -298,4 +330,59 @@ mod tests {
             assert_eq!(lock.read_recursive_call_count(), 3);
         }
     }
+
+    #[test]
+    fn test_upgrade() {
+        let lock = RecursiveLock::new(42);
+
+        // Upgrading is not possible while another recursive read guard is alive.
+        let guard1 = lock.read_recursive().unwrap();
+        let guard2 = lock.read_recursive().unwrap();
+        let guard2 = guard2.upgrade().err().expect("Upgrade with nested guards should fail");
+        drop(guard2);
+
+        let mut write = guard1.upgrade().ok().expect("Upgrade of the only guard should succeed");
+        *write = 43;
+        assert_eq!(lock.write_call_count(), 1);
+        drop(write);
+
+        assert_eq!(lock.recursive_depth.get(), 0);
+        assert!(!lock.is_locked());
+        assert!(!lock.is_locked_exclusive());
+        assert_eq!(*lock.read_recursive().unwrap(), 43);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_upgrade_other_reader() {
+        let mutex = BfSharedMutex::new(42);
+        let acquired = Barrier::new(2);
+        let release = Barrier::new(2);
+
+        thread::scope(|s| {
+            let other = RecursiveLock::from_mutex(mutex.clone());
+            let (acquired, release) = (&acquired, &release);
+            s.spawn(move || {
+                let _guard = other.read_recursive().unwrap();
+                acquired.wait();
+                release.wait();
+            });
+
+            let lock = RecursiveLock::from_mutex(mutex.clone());
+            let guard = lock.read_recursive().unwrap();
+            acquired.wait();
+
+            // The other thread holds a read lock, so upgrading would deadlock.
+            let guard = guard.upgrade().err().expect("Upgrade with another reader should fail");
+            assert_eq!(*guard, 42);
+            release.wait();
+        });
+
+        // The other thread has released its read lock.
+        let lock = RecursiveLock::from_mutex(mutex);
+        let mut write = lock.read_recursive().unwrap().upgrade().ok().unwrap();
+        *write += 1;
+        drop(write);
+        assert_eq!(*lock.read().unwrap(), 43);
+    }
 }
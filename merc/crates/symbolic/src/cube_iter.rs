@@ -1,9 +1,11 @@
 //! Iterator over cubes in a BDD.
 
+use std::borrow::Cow;
 use std::marker::PhantomData;
 
 use merc_utilities::MercError;
 use oxidd::BooleanFunction;
+use oxidd::BooleanFunctionQuant;
 use oxidd::bdd::BDDFunction;
 use oxidd::util::OptBool;
 
@@ -45,9 +47,9 @@ impl Iterator for CubeIter<'_> {
 /// considering don't care values. For the universe BDD, the [CubeIter] yields only
 /// one cube with all don't cares, while this iterator yields all possible cubes.
 pub struct CubeIterAll<'a> {
-    bdd: &'a BDDFunction,
+    bdd: Cow<'a, BDDFunction>,
     // The variables used in the BDD.
-    variables: &'a Vec<BDDFunction>,
+    variables: Cow<'a, [BDDFunction]>,
     // The last cube generated.
     cube: Vec<OptBool>,
     // Whether to stop the iteration.
@@ -56,15 +58,43 @@ pub struct CubeIterAll<'a> {
 
 impl<'a> CubeIterAll<'a> {
     /// Creates a new cube iterator that iterates over the single cube
-    pub fn new(variables: &'a Vec<BDDFunction>, bdd: &'a BDDFunction) -> CubeIterAll<'a> {
+    pub fn new(variables: &'a [BDDFunction], bdd: &'a BDDFunction) -> CubeIterAll<'a> {
         let cube = Vec::from_iter((0..variables.len()).map(|_| OptBool::False));
         Self {
-            bdd,
+            bdd: Cow::Borrowed(bdd),
             cube,
-            variables,
+            variables: Cow::Borrowed(variables),
             done: false,
         }
     }
+
+    /// Creates a new cube iterator that only iterates over the assignments to
+    /// the variables in `project_onto`, in that order.
+    ///
+    /// # Details
+    ///
+    /// The variables in `all_variables` that do not occur in `project_onto` are
+    /// existentially quantified from the given BDD first. Therefore, an
+    /// assignment is yielded whenever it can be extended to a satisfying
+    /// assignment of the BDD, and the yielded BDD is the quantified BDD
+    /// restricted to the assignment.
+    pub fn new_projected(
+        all_variables: &[BDDFunction],
+        project_onto: &[BDDFunction],
+        bdd: &BDDFunction,
+    ) -> Result<CubeIterAll<'static>, MercError> {
+        let mut quantified = bdd.clone();
+        for variable in all_variables.iter().filter(|variable| !project_onto.contains(variable)) {
+            quantified = quantified.exists(variable)?;
+        }
+
+        Ok(CubeIterAll {
+            bdd: Cow::Owned(quantified),
+            cube: vec![OptBool::False; project_onto.len()],
+            variables: Cow::Owned(project_onto.to_vec()),
+            done: false,
+        })
+    }
 }
 
 impl Iterator for CubeIterAll<'_> {
@@ -76,7 +106,7 @@ impl Iterator for CubeIterAll<'_> {
         }
 
         loop {
-            let mut tmp = self.bdd.clone();
+            let mut tmp = self.bdd.as_ref().clone();
             for (index, value) in self.cube.iter().enumerate() {
                 if *value == OptBool::True {
                     tmp = match tmp.and(&self.variables[index]) {
@@ -108,6 +138,9 @@ impl Iterator for CubeIterAll<'_> {
                 // The next iteration overflows, we are done
                 self.done = !increment(&mut self.cube);
                 return Some(Ok((result, tmp)));
+            } else if self.cube.is_empty() {
+                // Without variables there is no next cube to try.
+                return None;
             }
         }
     }
@@ -181,6 +214,30 @@ mod tests {
         })
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)] // Oxidd does not work with miri
+    fn test_random_cube_iter_projected() {
+        random_test(100, |rng| {
+            let manager_ref = oxidd::bdd::new_manager(2048, 1024, 1);
+            let set = random_bitvectors(rng, 5, 10);
+
+            let variables = create_variables(&manager_ref, 5).unwrap();
+            let bdd = from_iter(&manager_ref, &variables, set.iter()).unwrap();
+
+            // Project onto the first, third and last variable.
+            let projected: Vec<BDDFunction> = [0, 2, 4].iter().map(|i| variables[*i].clone()).collect();
+            let expected: HashSet<Vec<OptBool>> = set.iter().map(|v| vec![v[0], v[2], v[4]]).collect();
+
+            let result: Result<Vec<(Vec<OptBool>, BDDFunction)>, MercError> =
+                CubeIterAll::new_projected(&variables, &projected, &bdd)
+                    .unwrap()
+                    .collect();
+            let cubes: HashSet<Vec<OptBool>> = result.unwrap().into_iter().map(|(bits, _)| bits).collect();
+
+            assert_eq!(cubes, expected);
+        })
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)] // Oxidd does not work with miri
     fn test_random_cube_iter() {
@@ -1,26 +1,38 @@
 use std::io::Read;
 
 use log::info;
+use merc_data::DataSpecification;
+use merc_data::DataVariable;
 use merc_ldd::Ldd;
 use merc_ldd::Storage;
 use merc_ldd::SylvanReader;
 use merc_ldd::Value;
 use merc_ldd::compute_meta;
+use merc_ldd::height;
 use merc_ldd::read_u32;
 use merc_utilities::MercError;
 
+use crate::ReachabilityStats;
+use crate::SummandGroup;
 use crate::SymbolicLTS;
+use crate::SymbolicLts;
 use crate::TransitionGroup;
+use crate::reachable_states_bfs;
 
 /// Returns the (initial state, transitions) read from the file in Sylvan's format.
+///
+/// # Details
+///
+/// The state vectors are ordered as in the file, and the read and write
+/// projections of every transition group refer to indices in this vector. An
+/// error is returned whenever the file does not satisfy
+/// [SylvanLts::validate_ordering].
 pub fn read_sylvan(storage: &mut Storage, stream: &mut impl Read) -> Result<SylvanLts, MercError> {
     info!("Reading symbolic LTS in Sylvan format...");
     let mut reader = SylvanReader::new();
 
-    let _vector_length = read_u32(stream)?;
-    //println!("Length of vector {}", vector_length);
-
-    let _unused = read_u32(stream)?; // This is called 'k' in Sylvan's ldd2bdd.c, but unused.
+    let vector_length = read_u32(stream)?;
+    let bits_per_value = read_u32(stream)?; // This is called 'k' in Sylvan's ldd2bdd.c, but unused.
     let initial_state = reader.read_ldd(storage, stream)?;
     let num_transitions: usize = read_u32(stream)? as usize;
     let mut groups: Vec<SylvanTransitionGroup> = Vec::new();
@@ -28,17 +40,25 @@ pub fn read_sylvan(storage: &mut Storage, stream: &mut impl Read) -> Result<Sylv
     // Read all the transition groups.
     for _ in 0..num_transitions {
         let (read_proj, write_proj) = read_projection(stream)?;
-        groups.push(SylvanTransitionGroup::new(
+        let mut group = SylvanTransitionGroup::new(
             storage.empty_set().clone(),
             compute_meta(storage, &read_proj, &write_proj),
-        ));
+        );
+        group.read_proj = read_proj;
+        group.write_proj = write_proj;
+        groups.push(group);
     }
 
     for transition in groups.iter_mut().take(num_transitions) {
         transition.relation = reader.read_ldd(storage, stream)?;
     }
 
-    Ok(SylvanLts::new(storage.empty_set().clone(), initial_state, groups))
+    let mut lts = SylvanLts::new(storage.empty_set().clone(), initial_state, groups);
+    lts.vector_length = Some(vector_length as usize);
+    lts.bits_per_value = Some(bits_per_value);
+    lts.validate_ordering(storage)?;
+
+    Ok(lts)
 }
 
 /// Reads the read and write projections from the given stream.
@@ -70,6 +90,12 @@ pub struct SylvanLts {
     transition_groups: Vec<SylvanTransitionGroup>, // (relation, meta)
 
     empty_set: Ldd,
+
+    /// The length of the state vectors declared in the file.
+    vector_length: Option<usize>,
+
+    /// The number of bits per value declared in the file.
+    bits_per_value: Option<u32>,
 }
 
 impl SylvanLts {
@@ -79,8 +105,114 @@ impl SylvanLts {
             initial_state,
             transition_groups,
             empty_set,
+            vector_length: None,
+            bits_per_value: None,
         }
     }
+
+    /// Returns the transition groups in the order of the file.
+    pub fn transition_groups(&self) -> &[SylvanTransitionGroup] {
+        &self.transition_groups
+    }
+
+    /// Returns the length of the state vectors declared in the file, if known.
+    pub fn vector_length(&self) -> Option<usize> {
+        self.vector_length
+    }
+
+    /// Returns the number of bits per value declared in the file, if known.
+    pub fn bits_per_value(&self) -> Option<u32> {
+        self.bits_per_value
+    }
+
+    /// Converts this LTS into a [SymbolicLts], which can be written in the
+    /// mCRL2 `.sym` format.
+    ///
+    /// # Details
+    ///
+    /// The Sylvan format does not store the process parameters, so these are
+    /// named `x0`, `x1`, and so forth. The values in the state vectors are not
+    /// associated with data values. The set of states is computed by a
+    /// breadth-first exploration from the initial state.
+    pub fn to_symbolic_lts(&self, storage: &mut Storage) -> Result<SymbolicLts, MercError> {
+        let vector_length = self.vector_length.ok_or("The length of the state vectors is unknown")?;
+        let parameters: Vec<DataVariable> = (0..vector_length)
+            .map(|index| DataVariable::new(format!("x{index}").as_str()))
+            .collect();
+
+        let mut summand_groups = Vec::with_capacity(self.transition_groups.len());
+        for group in &self.transition_groups {
+            let select = |projection: &[Value]| -> Vec<DataVariable> {
+                projection
+                    .iter()
+                    .map(|index| parameters[*index as usize].clone())
+                    .collect()
+            };
+
+            summand_groups.push(SummandGroup::new(
+                storage,
+                &parameters,
+                select(&group.read_proj),
+                select(&group.write_proj),
+                group.relation.clone(),
+            )?);
+        }
+
+        let states = reachable_states_bfs(storage, self, &mut ReachabilityStats::default());
+
+        Ok(SymbolicLts::new(
+            DataSpecification::default(),
+            parameters,
+            vec![Vec::new(); vector_length],
+            Vec::new(),
+            states,
+            self.initial_state.clone(),
+            summand_groups,
+        ))
+    }
+
+    /// Checks that the transition groups are consistent with the declared
+    /// length of the state vectors.
+    ///
+    /// # Details
+    ///
+    /// The read and write projections of every group must be strictly
+    /// increasing and only refer to indices below the vector length, and the
+    /// initial state must be a vector of that length. Does nothing when the
+    /// vector length is unknown.
+    pub fn validate_ordering(&self, storage: &Storage) -> Result<(), MercError> {
+        let Some(vector_length) = self.vector_length else {
+            return Ok(());
+        };
+
+        if self.initial_state != *storage.empty_set() && height(storage, &self.initial_state) != vector_length {
+            return Err(format!(
+                "The initial state has length {}, but the declared vector length is {vector_length}",
+                height(storage, &self.initial_state)
+            )
+            .into());
+        }
+
+        for (index, group) in self.transition_groups.iter().enumerate() {
+            for (kind, projection) in [("read", &group.read_proj), ("write", &group.write_proj)] {
+                if let Some(variable) = projection.iter().find(|variable| **variable as usize >= vector_length) {
+                    return Err(format!(
+                        "Transition group {index} {kind}s variable {variable}, but the declared vector length is {vector_length}"
+                    )
+                    .into());
+                }
+
+                if !projection.windows(2).all(|pair| pair[0] < pair[1]) {
+                    return Err(format!(
+                        "The {kind} variables {projection:?} of transition group {index} are not strictly increasing"
+                    )
+                    .into());
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl SymbolicLTS for SylvanLts {
@@ -101,12 +233,33 @@ impl SymbolicLTS for SylvanLts {
 pub struct SylvanTransitionGroup {
     relation: Ldd,
     meta: Ldd,
+
+    /// The indices of the variables read by this group.
+    read_proj: Vec<Value>,
+
+    /// The indices of the variables written by this group.
+    write_proj: Vec<Value>,
 }
 
 impl SylvanTransitionGroup {
     /// Creates a new Sylvan transition group.
     pub fn new(relation: Ldd, meta: Ldd) -> Self {
-        Self { relation, meta }
+        Self {
+            relation,
+            meta,
+            read_proj: Vec::new(),
+            write_proj: Vec::new(),
+        }
+    }
+
+    /// Returns the indices of the variables read by this group, as stored in the file.
+    pub fn read_projection(&self) -> &[Value] {
+        &self.read_proj
+    }
+
+    /// Returns the indices of the variables written by this group, as stored in the file.
+    pub fn write_projection(&self) -> &[Value] {
+        &self.write_proj
     }
 }
 
@@ -132,7 +285,11 @@ mod test {
         let mut storage = Storage::new();
         let bytes = include_bytes!("../../../examples/ldd/anderson.4.ldd");
         let lts = read_sylvan(&mut storage, &mut &bytes[..]).expect("Loading should work correctly");
-        reachability(&mut storage, &lts).expect("Reachability should work correctly");
+        assert_eq!(lts.vector_length(), Some(height(&storage, lts.initial_state())));
+        let (_, stats) = reachability(&mut storage, &lts).expect("Reachability should work correctly");
+
+        assert!(stats.iterations > 0);
+        assert!(stats.peak_nodes >= storage.num_of_nodes());
     }
 
     #[test]
@@ -143,4 +300,25 @@ mod test {
         let lts = read_sylvan(&mut storage, &mut &bytes[..]).expect("Loading should work correctly");
         reachability(&mut storage, &lts).expect("Reachability should work correctly");
     }
+
+    #[test]
+    fn test_validate_ordering() {
+        let mut storage = Storage::new();
+        let initial_state = merc_ldd::singleton(&mut storage, &[0, 0, 0]);
+
+        let mut group = SylvanTransitionGroup::new(storage.empty_set().clone(), storage.empty_vector().clone());
+        group.read_proj = vec![0, 3];
+
+        let mut lts = SylvanLts::new(storage.empty_set().clone(), initial_state, vec![group]);
+        assert!(lts.validate_ordering(&storage).is_ok(), "The vector length is unknown");
+
+        lts.vector_length = Some(3);
+        assert!(lts.validate_ordering(&storage).is_err());
+
+        lts.transition_groups[0].read_proj = vec![2, 0];
+        assert!(lts.validate_ordering(&storage).is_err());
+
+        lts.transition_groups[0].read_proj = vec![0, 2];
+        assert!(lts.validate_ordering(&storage).is_ok());
+    }
 }
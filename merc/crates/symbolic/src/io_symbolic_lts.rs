@@ -1,20 +1,26 @@
 use std::io::Read;
+use std::io::Write;
 
 use log::info;
 use merc_aterm::ATerm;
 use merc_aterm::ATermList;
 use merc_aterm::ATermRead;
 use merc_aterm::ATermStreamable;
+use merc_aterm::ATermWrite;
 use merc_aterm::BinaryATermReader;
+use merc_aterm::BinaryATermWriter;
 use merc_aterm::Symbol;
 use merc_data::DataSpecification;
 use merc_data::DataVariable;
 use merc_io::BitStreamRead;
+use merc_io::BitStreamWrite;
 use merc_ldd::BinaryLddReader;
+use merc_ldd::BinaryLddWriter;
 use merc_ldd::Storage;
 use merc_utilities::MercError;
 
 use crate::SummandGroup;
+use crate::SymbolicLTS;
 use crate::SymbolicLts;
 
 /// Reads a symbolic LTS from a binary stream in the mCRL2 `.sym` format.
@@ -65,18 +71,22 @@ pub fn read_symbolic_lts<R: Read>(storage: &mut Storage, reader: R) -> Result<Sy
     let states = stream.read_ldd(storage)?;
 
     // Read the values for the process parameters.
+    let mut parameter_values = Vec::with_capacity(process_parameters.len());
     for _parameter in &process_parameters {
         let num_of_entries = stream.read_integer()?;
 
+        let mut values = Vec::with_capacity(num_of_entries as usize);
         for _ in 0..num_of_entries {
-            let _value = stream.read_aterm()?;
+            values.push(stream.read_aterm()?.ok_or("Unexpected end of stream")?);
         }
+        parameter_values.push(values);
     }
 
     // Read the action labels.
     let num_of_action_labels = stream.read_integer()?;
+    let mut action_labels = Vec::with_capacity(num_of_action_labels as usize);
     for _ in 0..num_of_action_labels {
-        let _action_label = stream.read_aterm()?;
+        action_labels.push(stream.read_aterm()?.ok_or("Unexpected end of stream")?);
     }
 
     // Read the summand groups.
@@ -107,7 +117,63 @@ pub fn read_symbolic_lts<R: Read>(storage: &mut Storage, reader: R) -> Result<Sy
         )?);
     }
 
-    Ok(SymbolicLts::new(data_spec, states, initial_state, summand_groups))
+    Ok(SymbolicLts::new(
+        data_spec,
+        process_parameters,
+        parameter_values,
+        action_labels,
+        states,
+        initial_state,
+        summand_groups,
+    ))
+}
+
+/// Writes a symbolic LTS to a binary stream in the mCRL2 `.sym` format, see
+/// [read_symbolic_lts] for the layout of the stream.
+pub fn write_symbolic_lts<W: Write>(storage: &mut Storage, writer: W, lts: &SymbolicLts) -> Result<(), MercError> {
+    info!("Writing symbolic LTS in the mCRL2 symbolic format...");
+
+    let aterm_stream = BinaryATermWriter::new(writer)?;
+    let mut stream = BinaryLddWriter::new(aterm_stream, storage)?;
+
+    stream.write_aterm(&symbolic_labelled_transition_system_mark())?;
+    lts.data_specification().write(&mut stream)?;
+    stream.write_aterm(&ATermList::from_double_iter(lts.process_parameters().iter().cloned()).into())?;
+
+    stream.write_ldd(lts.initial_state(), storage)?;
+    stream.write_ldd(lts.states(), storage)?;
+
+    // Write the values for the process parameters.
+    for values in lts.parameter_values() {
+        stream.write_integer(values.len() as u64)?;
+        for value in values {
+            stream.write_aterm(value)?;
+        }
+    }
+
+    // Write the action labels.
+    stream.write_integer(lts.action_labels().len() as u64)?;
+    for action_label in lts.action_labels() {
+        stream.write_aterm(action_label)?;
+    }
+
+    // Write the summand groups.
+    stream.write_integer(lts.summand_groups().len() as u64)?;
+    for group in lts.summand_groups() {
+        stream.write_integer(group.read_parameters().len() as u64)?;
+        for parameter in group.read_parameters() {
+            stream.write_aterm(parameter)?;
+        }
+
+        stream.write_integer(group.write_parameters().len() as u64)?;
+        for parameter in group.write_parameters() {
+            stream.write_aterm(parameter)?;
+        }
+
+        stream.write_ldd(group.relation(), storage)?;
+    }
+
+    ATermWrite::flush(&mut stream)
 }
 
 /// Returns the ATerm mark for symbolic labelled transition systems.
@@ -117,8 +183,12 @@ fn symbolic_labelled_transition_system_mark() -> ATerm {
 
 #[cfg(test)]
 mod tests {
+    use merc_ldd::len;
     use merc_utilities::test_logger;
 
+    use crate::reachability;
+    use crate::read_sylvan;
+
     use super::*;
 
     #[test]
@@ -130,4 +200,23 @@ mod tests {
         let mut storage = Storage::new();
         let _lts = read_symbolic_lts(&mut storage, &input[..]).unwrap();
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_write_symbolic_lts_round_trip() {
+        let mut storage = Storage::new();
+        let bytes = include_bytes!("../../../examples/ldd/anderson.4.ldd");
+        let sylvan = read_sylvan(&mut storage, &mut &bytes[..]).unwrap();
+        let (expected, _) = reachability(&mut storage, &sylvan).unwrap();
+
+        let lts = sylvan.to_symbolic_lts(&mut storage).unwrap();
+        let mut output = Vec::new();
+        write_symbolic_lts(&mut storage, &mut output, &lts).unwrap();
+
+        let result = read_symbolic_lts(&mut storage, &output[..]).unwrap();
+        assert_eq!(result.process_parameters(), lts.process_parameters());
+        assert_eq!(result.initial_state(), sylvan.initial_state());
+        assert_eq!(len(&mut storage, result.states()), expected);
+        assert_eq!(reachability(&mut storage, &result).unwrap().0, expected);
+    }
 }
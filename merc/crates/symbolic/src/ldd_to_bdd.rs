@@ -7,18 +7,89 @@ use merc_ldd::DataRef;
 use merc_ldd::LddRef;
 use merc_ldd::Storage;
 use merc_ldd::height;
+use merc_ldd::iterators::iter;
 use merc_utilities::MercError;
 
+/// Determines how the bits that encode the levels of an LDD are ordered in the
+/// BDD.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Interleaving {
+    /// The bits of every level are placed at consecutive BDD variables.
+    #[default]
+    Grouped,
+
+    /// The levels are paired as source and target, e.g., for a transition
+    /// relation, and the bits of both levels in a pair are alternated. A
+    /// remaining last level is placed at consecutive BDD variables.
+    Interleaved,
+}
+
+/// The parameters of [ldd_to_bdd_with_options].
+#[derive(Clone, Debug)]
+pub struct LddToBddOptions {
+    /// The order of the bits in the BDD.
+    pub interleaving: Interleaving,
+
+    /// The number of bits used to encode the values at every level.
+    pub bits: Vec<u32>,
+
+    /// The first BDD variable that is used for the encoding.
+    pub first_variable: u32,
+}
+
+impl LddToBddOptions {
+    /// Returns the options with grouped bits, where the number of bits at
+    /// every level is sufficient to encode the highest value in the given LDD.
+    pub fn from_ldd(storage: &mut Storage, ldd: &LddRef<'_>) -> Self {
+        Self {
+            interleaving: Interleaving::Grouped,
+            bits: compute_bits(&compute_highest(storage, ldd)),
+            first_variable: 0,
+        }
+    }
+
+    /// Returns the number of BDD variables used by the encoding.
+    pub fn num_of_variables(&self) -> u32 {
+        self.bits.iter().sum()
+    }
+
+    /// Returns for every level the BDD variable of every bit, where the least
+    /// significant bit comes first. The most significant bit of a level is
+    /// always placed before its less significant bits.
+    fn variables(&self) -> Vec<Vec<u32>> {
+        let mut result: Vec<Vec<u32>> = self.bits.iter().map(|bits| vec![0; *bits as usize]).collect();
+        let mut next = self.first_variable;
+
+        let group_size = match self.interleaving {
+            Interleaving::Grouped => 1,
+            Interleaving::Interleaved => 2,
+        };
+
+        for group in (0..self.bits.len()).collect::<Vec<_>>().chunks(group_size) {
+            let width = group.iter().map(|level| self.bits[*level]).max().unwrap_or(0);
+            for position in 0..width {
+                for level in group {
+                    // Align the most significant bits of the levels in the group.
+                    let bits = self.bits[*level];
+                    if position < bits {
+                        result[*level][(bits - position - 1) as usize] = next;
+                        next += 1;
+                    }
+                }
+            }
+        }
+
+        result
+    }
+}
+
 pub fn ldd_to_bdd_simple(
     storage: &mut Storage,
     manager_ref: &BDDManagerRef,
     ldd: &LddRef<'_>,
 ) -> Result<BDDFunction, MercError> {
-    let highest = compute_highest(storage, ldd);
-    let bits = compute_bits(&highest);
-    let bits_dd = merc_ldd::singleton(storage, &bits);
-
-    ldd_to_bdd(storage, manager_ref, ldd, &bits_dd, 0)
+    let options = LddToBddOptions::from_ldd(storage, ldd);
+    ldd_to_bdd_with_options(storage, manager_ref, ldd, &options)
 }
 
 /// Converts an LDD representing a set of vectors into a BDD representing the
@@ -38,6 +109,42 @@ pub fn ldd_to_bdd(
     ldd: &LddRef<'_>,
     bits: &LddRef<'_>,
     first_variable: u32,
+) -> Result<BDDFunction, MercError> {
+    let bits = storage.protect(bits);
+    let options = LddToBddOptions {
+        interleaving: Interleaving::Grouped,
+        bits: iter(storage, &bits)
+            .next()
+            .ok_or("The bits should be a singleton LDD")?,
+        first_variable,
+    };
+
+    ldd_to_bdd_with_options(storage, manager_ref, ldd, &options)
+}
+
+/// Converts an LDD representing a set of vectors into a BDD representing the
+/// same set, where the values at every level are encoded by the number of bits
+/// and in the order given by the options.
+///
+/// The variables `first_variable` up to `first_variable +
+/// options.num_of_variables()` *must* already exist in the given BDD manager.
+pub fn ldd_to_bdd_with_options(
+    storage: &mut Storage,
+    manager_ref: &BDDManagerRef,
+    ldd: &LddRef<'_>,
+    options: &LddToBddOptions,
+) -> Result<BDDFunction, MercError> {
+    ldd_to_bdd_rec(storage, manager_ref, ldd, &options.variables(), 0)
+}
+
+/// Helper function for [ldd_to_bdd_with_options], where `variables` contains
+/// the BDD variables of every bit at every level.
+fn ldd_to_bdd_rec(
+    storage: &mut Storage,
+    manager_ref: &BDDManagerRef,
+    ldd: &LddRef<'_>,
+    variables: &[Vec<u32>],
+    depth: usize,
 ) -> Result<BDDFunction, MercError> {
     // Base cases
     if **storage.empty_set() == *ldd {
@@ -49,24 +156,21 @@ pub fn ldd_to_bdd(
 
     // TODO: Implement caching
     let DataRef(value, down, right) = storage.get_ref(ldd);
-    let DataRef(bits_value, bits_down, _bits_right) = storage.get_ref(bits); // Is singleton so right is ignored.
 
-    let right = ldd_to_bdd(storage, manager_ref, &right, bits, first_variable)?;
-    let mut down = ldd_to_bdd(storage, manager_ref, &down, &bits_down, first_variable + bits_value)?;
+    let right = ldd_to_bdd_rec(storage, manager_ref, &right, variables, depth)?;
+    let mut down = ldd_to_bdd_rec(storage, manager_ref, &down, variables, depth + 1)?;
 
     // Encode current value
-    for i in 0..bits_value {
-        // encode with high bit first
-        let bit = bits_value - i - 1;
+    for (i, variable) in variables[depth].iter().enumerate() {
         if value & (1 << i) != 0 {
             // bit is 1
             down = manager_ref.with_manager_shared(|manager| {
-                BDDFunction::var(manager, first_variable + bit)?.ite(&BDDFunction::f(manager), &down)
+                BDDFunction::var(manager, *variable)?.ite(&BDDFunction::f(manager), &down)
             })?;
         } else {
             // bit is 0
             down = manager_ref.with_manager_shared(|manager| {
-                BDDFunction::var(manager, first_variable + bit)?.ite(&down, &BDDFunction::f(manager))
+                BDDFunction::var(manager, *variable)?.ite(&down, &BDDFunction::f(manager))
             })?;
         }
     }
@@ -106,6 +210,7 @@ mod tests {
     use merc_ldd::random_vector_set;
     use merc_ldd::singleton;
     use merc_utilities::random_test;
+    use oxidd::Function;
 
     use crate::create_variables;
 
@@ -175,4 +280,40 @@ mod tests {
             let _bdd = ldd_to_bdd(&mut storage, &manager_ref, &ldd, &bits_dd, 0).unwrap();
         });
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Oxidd does not work with miri
+    fn test_ldd_to_bdd_interleaving() {
+        // The identity relation on three bit values, as pairs of source and target levels.
+        let relation: Vec<Vec<u32>> = (0..8).map(|value| vec![value, value]).collect();
+
+        let mut storage = Storage::new();
+        let ldd = from_iter(&mut storage, relation.iter());
+
+        let grouped = LddToBddOptions::from_ldd(&mut storage, &ldd);
+        let interleaved = LddToBddOptions {
+            interleaving: Interleaving::Interleaved,
+            ..grouped.clone()
+        };
+
+        let manager_ref = oxidd::bdd::new_manager(2048, 1024, 1);
+        let _variables = create_variables(&manager_ref, grouped.num_of_variables()).unwrap();
+
+        let grouped_bdd = ldd_to_bdd_with_options(&mut storage, &manager_ref, &ldd, &grouped).unwrap();
+        let interleaved_bdd = ldd_to_bdd_with_options(&mut storage, &manager_ref, &ldd, &interleaved).unwrap();
+
+        // Both encodings contain exactly the vectors of the relation.
+        for source in 0..8 {
+            for target in 0..8 {
+                let vector = singleton(&mut storage, &[source, target]);
+                for (options, bdd) in [(&grouped, &grouped_bdd), (&interleaved, &interleaved_bdd)] {
+                    let cube = ldd_to_bdd_with_options(&mut storage, &manager_ref, &vector, options).unwrap();
+                    assert_eq!(cube.and(bdd).unwrap().satisfiable(), source == target);
+                }
+            }
+        }
+
+        // Equality of interleaved bits requires fewer nodes.
+        assert!(interleaved_bdd.node_count() < grouped_bdd.node_count());
+    }
 }
@@ -1,5 +1,9 @@
+use std::cmp::Reverse;
+
+use log::debug;
 use log::info;
 use merc_io::TimeProgress;
+use merc_ldd::Data;
 use merc_ldd::Ldd;
 use merc_ldd::Storage;
 use merc_ldd::len;
@@ -28,11 +32,82 @@ pub trait TransitionGroup {
     fn meta(&self) -> &Ldd;
 }
 
-/// Performs reachability analysis using the given initial state and transitions read from a Sylvan file.
-pub fn reachability(storage: &mut Storage, lts: &impl SymbolicLTS) -> Result<usize, MercError> {
+/// Statistics gathered during [reachability].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReachabilityStats {
+    /// The number of fixpoint iterations.
+    pub iterations: usize,
+
+    /// The largest number of nodes in the LDD storage observed after any iteration.
+    pub peak_nodes: usize,
+}
+
+/// The order in which the transition groups are applied during reachability.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum ReachabilityStrategy {
+    /// Applies all transition groups to the frontier in every iteration.
+    Bfs,
+    /// Saturates the states with the transition groups in a bottom-up order.
+    Saturation,
+}
+
+/// Performs reachability analysis using the given initial state and transitions
+/// read from a Sylvan file. Returns the number of reachable states together with
+/// statistics of the exploration.
+pub fn reachability(storage: &mut Storage, lts: &impl SymbolicLTS) -> Result<(usize, ReachabilityStats), MercError> {
+    let mut stats = ReachabilityStats {
+        iterations: 0,
+        peak_nodes: storage.num_of_nodes(),
+    };
+
+    let states = reachable_states_bfs(storage, lts, &mut stats);
+    debug!(
+        "Reachability took {} iterations with at most {} LDD nodes",
+        stats.iterations, stats.peak_nodes
+    );
+
+    Ok((len(storage, &states), stats))
+}
+
+/// Performs reachability analysis in the same way as [reachability], but
+/// applies the transition groups in a saturation order.
+///
+/// # Details
+///
+/// The transition groups are sorted on the first level that they depend on,
+/// from the bottom level to the top level. Afterwards, for every prefix of
+/// this order, the states are extended with the transition groups in the
+/// prefix until no new states are found. Every group is applied until it
+/// reaches a fixpoint before the next group is considered, such that the
+/// groups that only affect the lower levels are saturated first. Every
+/// application of a transition group counts as an iteration.
+pub fn reachability_saturation(
+    storage: &mut Storage,
+    lts: &impl SymbolicLTS,
+) -> Result<(usize, ReachabilityStats), MercError> {
+    let mut stats = ReachabilityStats {
+        iterations: 0,
+        peak_nodes: storage.num_of_nodes(),
+    };
+
+    let states = reachable_states_saturation(storage, lts, &mut stats);
+    debug!(
+        "Saturation took {} iterations with at most {} LDD nodes",
+        stats.iterations, stats.peak_nodes
+    );
+
+    Ok((len(storage, &states), stats))
+}
+
+/// Returns the reachable states using a breadth-first exploration.
+pub(crate) fn reachable_states_bfs(
+    storage: &mut Storage,
+    lts: &impl SymbolicLTS,
+    stats: &mut ReachabilityStats,
+) -> Ldd {
     let mut todo = lts.initial_state().clone();
     let mut states = lts.initial_state().clone(); // The state space.
-    let mut iteration = 0;
 
     let progress = TimeProgress::new(
         |iteration: usize| {
@@ -50,9 +125,130 @@ pub fn reachability(storage: &mut Storage, lts: &impl SymbolicLTS) -> Result<usi
 
         todo = minus(storage, &todo1, &states);
         states = union(storage, &states, &todo);
-        progress.print(iteration);
-        iteration += 1;
+        progress.print(stats.iterations);
+
+        stats.iterations += 1;
+        stats.peak_nodes = stats.peak_nodes.max(storage.num_of_nodes());
     }
 
-    Ok(len(storage, &states))
+    states
+}
+
+/// Returns the reachable states using saturation, see [reachability_saturation].
+fn reachable_states_saturation(storage: &mut Storage, lts: &impl SymbolicLTS, stats: &mut ReachabilityStats) -> Ldd {
+    let mut groups: Vec<_> = lts.transition_groups().iter().collect();
+    groups.sort_by_cached_key(|group| Reverse(top_level(storage, group.meta())));
+
+    let progress = TimeProgress::new(
+        |(saturated, total): (usize, usize)| {
+            info!("Saturated {saturated} out of {total} transition groups");
+        },
+        1,
+    );
+
+    let mut states = lts.initial_state().clone();
+    for saturated in 1..=groups.len() {
+        loop {
+            let previous = states.clone();
+
+            for group in &groups[..saturated] {
+                // Apply the group to the newly found states until no new states are found.
+                let mut todo = states.clone();
+                while todo != *storage.empty_set() {
+                    let result = relational_product(storage, &todo, group.relation(), group.meta());
+                    todo = minus(storage, &result, &states);
+                    states = union(storage, &states, &todo);
+
+                    stats.iterations += 1;
+                    stats.peak_nodes = stats.peak_nodes.max(storage.num_of_nodes());
+                }
+            }
+
+            if states == previous {
+                break;
+            }
+        }
+
+        progress.print((saturated, groups.len()));
+    }
+
+    states
+}
+
+/// Returns the first level of the given meta information that is read or
+/// written, or the number of levels when there is no such level.
+fn top_level(storage: &Storage, meta: &Ldd) -> usize {
+    let mut level = 0;
+    let mut current = meta.clone();
+    while current != *storage.empty_vector() {
+        let Data(value, down, _) = storage.get(&current);
+        if value != 0 {
+            break;
+        }
+
+        level += 1;
+        current = down;
+    }
+
+    level
+}
+
+#[cfg(test)]
+mod tests {
+    use merc_ldd::compute_meta;
+    use merc_ldd::from_iter;
+    use merc_ldd::random_sorted_vector;
+    use merc_ldd::random_vector;
+    use merc_ldd::random_vector_set;
+    use merc_ldd::singleton;
+    use merc_utilities::random_test;
+
+    use crate::SylvanLts;
+    use crate::SylvanTransitionGroup;
+    use crate::read_sylvan;
+
+    use super::*;
+
+    /// Checks that both strategies compute the same set of reachable states.
+    fn check_strategies(storage: &mut Storage, lts: &impl SymbolicLTS) {
+        let bfs = reachable_states_bfs(storage, lts, &mut ReachabilityStats::default());
+        let saturation = reachable_states_saturation(storage, lts, &mut ReachabilityStats::default());
+        assert!(
+            bfs == saturation,
+            "Saturation should find the same states as breadth-first search"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_random_reachability_saturation() {
+        random_test(100, |rng| {
+            let mut storage = Storage::new();
+
+            let initial_state = singleton(&mut storage, &random_vector(rng, 6, 4));
+            let mut groups = Vec::new();
+            for _ in 0..3 {
+                let read_proj = random_sorted_vector(rng, 2, 5);
+                let write_proj = random_sorted_vector(rng, 2, 5);
+
+                // Every vector of the relation interleaves the read and written values.
+                let relation = random_vector_set(rng, 16, read_proj.len() + write_proj.len(), 4);
+                let relation = from_iter(&mut storage, relation.iter());
+                let meta = compute_meta(&mut storage, &read_proj, &write_proj);
+                groups.push(SylvanTransitionGroup::new(relation, meta));
+            }
+
+            let lts = SylvanLts::new(storage.empty_set().clone(), initial_state, groups);
+            check_strategies(&mut storage, &lts);
+        })
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_anderson_4_reachability_saturation() {
+        let mut storage = Storage::new();
+        let bytes = include_bytes!("../../../examples/ldd/anderson.4.ldd");
+        let lts = read_sylvan(&mut storage, &mut &bytes[..]).expect("Loading should work correctly");
+        check_strategies(&mut storage, &lts);
+    }
 }
@@ -1,5 +1,6 @@
 use std::fmt;
 
+use merc_aterm::ATerm;
 use merc_data::DataSpecification;
 use merc_data::DataVariable;
 use merc_ldd::Ldd;
@@ -15,6 +16,16 @@ use crate::TransitionGroup;
 pub struct SymbolicLts {
     data_specification: DataSpecification,
 
+    /// The process parameters, which determine the levels of the state vectors.
+    process_parameters: Vec<DataVariable>,
+
+    /// For every process parameter the data values that are encoded by the
+    /// values in the state vectors.
+    parameter_values: Vec<Vec<ATerm>>,
+
+    /// The action labels, as stored in the `.sym` format.
+    action_labels: Vec<ATerm>,
+
     states: Ldd,
 
     /// A singleton LDD representing the initial state.
@@ -27,12 +38,24 @@ impl SymbolicLts {
     /// Creates a new symbolic LTS.
     pub fn new(
         data_specification: DataSpecification,
+        process_parameters: Vec<DataVariable>,
+        parameter_values: Vec<Vec<ATerm>>,
+        action_labels: Vec<ATerm>,
         states: Ldd,
         initial_state: Ldd,
         summand_groups: Vec<SummandGroup>,
     ) -> Self {
+        debug_assert_eq!(
+            process_parameters.len(),
+            parameter_values.len(),
+            "Every process parameter should have a table of values"
+        );
+
         Self {
             data_specification,
+            process_parameters,
+            parameter_values,
+            action_labels,
             states,
             initial_state,
             summand_groups,
@@ -43,6 +66,26 @@ impl SymbolicLts {
     pub fn data_specification(&self) -> &DataSpecification {
         &self.data_specification
     }
+
+    /// Returns the process parameters of the LTS.
+    pub fn process_parameters(&self) -> &[DataVariable] {
+        &self.process_parameters
+    }
+
+    /// Returns for every process parameter the data values encoded in the state vectors.
+    pub fn parameter_values(&self) -> &[Vec<ATerm>] {
+        &self.parameter_values
+    }
+
+    /// Returns the action labels of the LTS.
+    pub fn action_labels(&self) -> &[ATerm] {
+        &self.action_labels
+    }
+
+    /// Returns the summand groups of the LTS.
+    pub fn summand_groups(&self) -> &[SummandGroup] {
+        &self.summand_groups
+    }
 }
 
 impl SymbolicLTS for SymbolicLts {
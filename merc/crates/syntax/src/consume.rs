@@ -239,17 +239,20 @@ impl Mcrl2Parser {
     }
 
     pub(crate) fn PropVarInst(inst: ParseNode) -> ParseResult<PropVarInst> {
+        let span = inst.as_span();
         match_nodes!(inst.into_children();
             [Id(identifier)] => {
                 Ok(PropVarInst {
                     identifier,
                     arguments: Vec::new(),
+                    span: span.into(),
                 })
             },
             [Id(identifier), DataExprList(arguments)] => {
                 Ok(PropVarInst {
                     identifier,
                     arguments,
+                    span: span.into(),
                 })
             }
         )
@@ -802,7 +805,8 @@ impl Mcrl2Parser {
     }
 
     pub(crate) fn Number(input: ParseNode) -> ParseResult<DataExpr> {
-        Ok(DataExpr::Number(input.as_str().into()))
+        // The rule is not atomic, so it can include trailing whitespace.
+        Ok(DataExpr::Number(input.as_str().trim_end().into()))
     }
 
     fn VarDecl(decl: ParseNode) -> ParseResult<VarDecl> {
@@ -966,6 +970,7 @@ impl Mcrl2Parser {
     }
 
     fn CommExpr(action: ParseNode) -> ParseResult<Comm> {
+        let span = action.as_span();
         match_nodes!(action.into_children();
             [Id(id), MultActId(multiact), Id(to)] => {
                 let mut actions = vec![id];
@@ -973,7 +978,8 @@ impl Mcrl2Parser {
 
                 Ok(Comm {
                     from: MultiActionLabel { actions },
-                    to
+                    to,
+                    span: span.into(),
                 })
             },
         )
@@ -1018,12 +1024,13 @@ impl Mcrl2Parser {
     }
 
     pub(crate) fn Action(input: ParseNode) -> ParseResult<Action> {
+        let span = input.as_span();
         match_nodes!(input.into_children();
             [Id(id), DataExprList(args)] => {
-                Ok(Action { id, args })
+                Ok(Action { id, args, span: span.into() })
             },
             [Id(id)] => {
-                Ok(Action { id, args: Vec::new() })
+                Ok(Action { id, args: Vec::new(), span: span.into() })
             },
         )
     }
@@ -1045,9 +1052,10 @@ impl Mcrl2Parser {
     }
 
     fn RenExpr(renames: ParseNode) -> ParseResult<Rename> {
+        let span = renames.as_span();
         match_nodes!(renames.into_children();
             [Id(from), Id(to)] => {
-                Ok(Rename { from, to })
+                Ok(Rename { from, to, span: span.into() })
             },
         )
     }
@@ -1306,12 +1314,14 @@ impl Mcrl2Parser {
     }
 
     fn StateVarAssignment(input: ParseNode) -> ParseResult<StateVarAssignment> {
+        let span = input.as_span();
         match_nodes!(input.into_children();
             [Id(identifier), SortExpr(sort), DataExpr(expr)] => {
                 Ok(StateVarAssignment {
                     identifier,
                     sort,
                     expr,
+                    span: span.into(),
                 })
             }
         )
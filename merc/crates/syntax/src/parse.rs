@@ -1,4 +1,7 @@
+use std::fmt;
+
 use pest::Parser;
+use pest::error::InputLocation;
 use pest_derive::Parser;
 
 use merc_pest_consume::Error;
@@ -8,6 +11,8 @@ use crate::DataExpr;
 use crate::DataExprBinaryOp;
 use crate::MultiAction;
 use crate::ParseNode;
+use crate::ParseResult;
+use crate::Span;
 use crate::StateFrmOp;
 use crate::UntypedActionRenameSpec;
 use crate::UntypedDataSpecification;
@@ -19,15 +24,168 @@ use crate::UntypedStateFrmSpec;
 #[grammar = "mcrl2_grammar.pest"]
 pub struct Mcrl2Parser;
 
+/// A syntax error together with its location in the input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// A description of the error.
+    pub message: String,
+
+    /// The byte range of the input at which the error occurs.
+    pub span: Span,
+}
+
+impl From<Error<Rule>> for ParseError {
+    fn from(error: Error<Rule>) -> Self {
+        let span = match error.location {
+            InputLocation::Pos(position) => Span {
+                start: position,
+                end: position,
+            },
+            InputLocation::Span((start, end)) => Span { start, end },
+        };
+
+        ParseError {
+            message: error.variant.message().to_string(),
+            span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {}..{}", self.message, self.span.start, self.span.end)
+    }
+}
+
 /// Parses the given mCRL2 specification into an AST.
 impl UntypedProcessSpecification {
     pub fn parse(spec: &str) -> Result<UntypedProcessSpecification, MercError> {
-        let mut result = Mcrl2Parser::parse(Rule::MCRL2Spec, spec).map_err(extend_parser_error)?;
-        let root = result.next().expect("Could not parse mCRL2 specification");
-        Ok(Mcrl2Parser::MCRL2Spec(ParseNode::new(root))?)
+        Ok(parse_process_specification(spec)?)
+    }
+
+    /// Parses the given mCRL2 specification, but instead of stopping at the
+    /// first syntax error this collects all of them. Returns the specification
+    /// consisting of the statements without errors, together with the errors
+    /// ordered by their location.
+    ///
+    /// # Details
+    ///
+    /// Whenever parsing fails, the statement containing the error, i.e., the
+    /// text between the surrounding semicolons, is blanked out and parsing
+    /// is restarted. Blanking keeps the byte offsets of the remaining input,
+    /// so the reported spans refer to the given specification. This is a
+    /// heuristic, and an error that spans multiple statements can still
+    /// result in follow-up errors.
+    pub fn parse_collect_errors(spec: &str) -> (UntypedProcessSpecification, Vec<ParseError>) {
+        let mut input = spec.to_string();
+        let mut errors: Vec<ParseError> = Vec::new();
+
+        let result = loop {
+            match parse_process_specification(&input) {
+                Ok(result) => break result,
+                Err(error) => {
+                    let error = ParseError::from(error);
+                    let removed = remove_statement(&mut input, error.span.start);
+                    errors.push(error);
+
+                    if !removed {
+                        // Nothing is left to remove, so we cannot recover from this error.
+                        break UntypedProcessSpecification::default();
+                    }
+                }
+            }
+        };
+
+        errors.sort_by_key(|error| error.span.start);
+        (result, errors)
     }
 }
 
+/// Parses the given mCRL2 specification, without converting the error.
+#[allow(clippy::result_large_err)]
+fn parse_process_specification(spec: &str) -> ParseResult<UntypedProcessSpecification> {
+    let mut result = Mcrl2Parser::parse(Rule::MCRL2Spec, spec).map_err(extend_parser_error)?;
+    let root = result.next().expect("Could not parse mCRL2 specification");
+    Mcrl2Parser::MCRL2Spec(ParseNode::new(root))
+}
+
+/// The keywords that start a section of an mCRL2 specification.
+const SECTION_KEYWORDS: [&str; 9] = ["sort", "cons", "map", "var", "eqn", "act", "proc", "glob", "init"];
+
+/// Replaces the statement containing the given position by whitespace, where
+/// the keyword that starts a section is kept when the section contains other
+/// statements. Returns false iff the statement only consisted of whitespace.
+fn remove_statement(input: &mut String, position: usize) -> bool {
+    let separators = statement_separators(input);
+    let mut start = separators
+        .iter()
+        .rev()
+        .find(|separator| **separator < position)
+        .map_or(0, |separator| separator + 1);
+    let end = separators
+        .iter()
+        .find(|separator| **separator >= position)
+        .map_or(input.len(), |separator| separator + 1);
+
+    if let Some((word_start, word)) = first_word(input, start) {
+        let section_continues = first_word(input, end).is_some_and(|(_, next)| !SECTION_KEYWORDS.contains(&next));
+        if word_start < end && SECTION_KEYWORDS.contains(&word) && section_continues {
+            start = word_start + word.len();
+        }
+    }
+
+    let removed = input[start..end].chars().any(|c| !c.is_whitespace());
+    let blank: String = input[start..end]
+        .chars()
+        .map(|c| {
+            if c == '\n' {
+                "\n".to_string()
+            } else {
+                " ".repeat(c.len_utf8())
+            }
+        })
+        .collect();
+    input.replace_range(start..end, &blank);
+    removed
+}
+
+/// Returns the positions of the semicolons that end statements, ignoring those in comments.
+fn statement_separators(input: &str) -> Vec<usize> {
+    let mut result = Vec::new();
+    let mut in_comment = false;
+    for (index, c) in input.char_indices() {
+        match c {
+            '%' => in_comment = true,
+            '\n' => in_comment = false,
+            ';' if !in_comment => result.push(index),
+            _ => {}
+        }
+    }
+
+    result
+}
+
+/// Returns the first word after the given position, skipping whitespace and comments, together with its position.
+fn first_word(input: &str, position: usize) -> Option<(usize, &str)> {
+    let mut in_comment = false;
+    for (index, c) in input[position..].char_indices() {
+        match c {
+            '%' => in_comment = true,
+            '\n' => in_comment = false,
+            _ if in_comment || c.is_whitespace() => {}
+            _ => {
+                let start = position + index;
+                let length = input[start..]
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(input.len() - start);
+                return Some((start, &input[start..start + length]));
+            }
+        }
+    }
+
+    None
+}
+
 /// Parses the given mCRL2 specification into an AST.
 impl UntypedDataSpecification {
     pub fn parse(spec: &str) -> Result<UntypedDataSpecification, MercError> {
@@ -533,18 +533,22 @@ pub fn parse_statefrm(pairs: Pairs<Rule>) -> ParseResult<StateFrm> {
                 op: StateFrmUnaryOp::Negation,
                 expr: Box::new(expr?),
             }),
+            Rule::StateFrmUnaryMinus => Ok(StateFrm::Unary {
+                op: StateFrmUnaryOp::Minus,
+                expr: Box::new(expr?),
+            }),
             Rule::StateFrmSup => Ok(StateFrm::Bound {
                 bound: Bound::Sup,
                 variables: Mcrl2Parser::StateFrmSup(Node::new(prefix))?,
                 body: Box::new(expr?),
             }),
             Rule::StateFrmSum => Ok(StateFrm::Bound {
-                bound: Bound::Sup,
+                bound: Bound::Sum,
                 variables: Mcrl2Parser::StateFrmSum(Node::new(prefix))?,
                 body: Box::new(expr?),
             }),
             Rule::StateFrmInf => Ok(StateFrm::Bound {
-                bound: Bound::Sup,
+                bound: Bound::Inf,
                 variables: Mcrl2Parser::StateFrmInf(Node::new(prefix))?,
                 body: Box::new(expr?),
             }),
@@ -615,6 +619,16 @@ pub fn parse_pbesexpr(pairs: Pairs<Rule>) -> ParseResult<PbesExpr> {
         })
         .map_prefix(|op, expr| match op.as_rule() {
             Rule::PbesExprNegation => Ok(PbesExpr::Negation(Box::new(expr?))),
+            Rule::PbesExprExists => Ok(PbesExpr::Quantifier {
+                quantifier: Quantifier::Exists,
+                variables: Mcrl2Parser::PbesExprExists(Node::new(op))?,
+                body: Box::new(expr?),
+            }),
+            Rule::PbesExprForall => Ok(PbesExpr::Quantifier {
+                quantifier: Quantifier::Forall,
+                variables: Mcrl2Parser::PbesExprForall(Node::new(op))?,
+                body: Box::new(expr?),
+            }),
             _ => unimplemented!("Unexpected prefix operator: {:?}", op.as_rule()),
         })
         .map_infix(|lhs, op, rhs| match op.as_rule() {
@@ -635,19 +649,6 @@ pub fn parse_pbesexpr(pairs: Pairs<Rule>) -> ParseResult<PbesExpr> {
             }),
             _ => unimplemented!("Unexpected binary operator: {:?}", op.as_rule()),
         })
-        .map_postfix(|expr, postfix| match postfix.as_rule() {
-            Rule::PbesExprExists => Ok(PbesExpr::Quantifier {
-                quantifier: Quantifier::Exists,
-                variables: Mcrl2Parser::PbesExprExists(Node::new(postfix))?,
-                body: Box::new(expr?),
-            }),
-            Rule::PbesExprForall => Ok(PbesExpr::Quantifier {
-                quantifier: Quantifier::Forall,
-                variables: Mcrl2Parser::PbesExprForall(Node::new(postfix))?,
-                body: Box::new(expr?),
-            }),
-            _ => unimplemented!("Unexpected postfix operator: {:?}", postfix.as_rule()),
-        })
         .parse(pairs)
 }
 
@@ -662,3 +663,156 @@ static _PRESEXPR_PRATT_PARSER: LazyLock<PrattParser<Rule>> = LazyLock::new(|| {
         .op(Op::prefix(Rule::PresExprLeftConstantMultiply) | Op::postfix(Rule::PresExprRightConstMultiply)) // $right 6
         .op(Op::prefix(Rule::PbesExprNegation)) // $right 7
 });
+
+/// The position of the outermost operator of an expression with respect to its operands.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Fixity {
+    /// An expression without operands, or one that is delimited, such as a bracketed expression.
+    Primary,
+    Prefix,
+    Postfix,
+    Infix(Assoc),
+}
+
+/// The precedence level and fixity of the outermost operator of an
+/// expression, following the Pratt parsers in this module. These determine
+/// when a sub-expression must be parenthesized to be parsed back into the same
+/// tree.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Precedence {
+    level: u8,
+    fixity: Fixity,
+}
+
+impl Precedence {
+    /// The precedence of expressions that bind stronger than all operators.
+    pub const PRIMARY: Precedence = Precedence {
+        level: u8::MAX,
+        fixity: Fixity::Primary,
+    };
+
+    /// Creates a precedence for an operator at the given level, where higher levels bind stronger.
+    pub const fn new(level: u8, fixity: Fixity) -> Self {
+        Self { level, fixity }
+    }
+
+    /// Returns true iff the given operand must be parenthesized when it
+    /// occurs to the left of this operator, i.e., as the left hand side of an
+    /// infix operator or as the operand of a postfix operator.
+    pub fn parenthesize_left(&self, operand: Precedence) -> bool {
+        if operand.level != self.level {
+            return operand.level < self.level;
+        }
+
+        // The operand of a prefix operator extends over all operators of the same level.
+        matches!(
+            (self.fixity, operand.fixity),
+            (_, Fixity::Prefix)
+                | (Fixity::Infix(Assoc::Right), Fixity::Infix(_))
+                | (Fixity::Postfix, Fixity::Infix(Assoc::Right))
+        )
+    }
+
+    /// Returns true iff the given operand must be parenthesized when it
+    /// occurs to the right of this operator, i.e., as the right hand side of
+    /// an infix operator or as the operand of a prefix operator.
+    pub fn parenthesize_right(&self, operand: Precedence) -> bool {
+        if operand.level != self.level {
+            return operand.level < self.level;
+        }
+
+        // A prefix operator at the end of a right hand side would extend over
+        // the operators that follow this expression, so we keep it parenthesized.
+        matches!(
+            (self.fixity, operand.fixity),
+            (Fixity::Infix(Assoc::Left), Fixity::Infix(_) | Fixity::Postfix) | (Fixity::Infix(_), Fixity::Prefix)
+        )
+    }
+}
+
+/// Returns the precedence of the given data expression as defined by [DATAEXPR_PRATT_PARSER].
+pub fn dataexpr_precedence(expr: &DataExpr) -> Precedence {
+    match expr {
+        DataExpr::Whr { .. } => Precedence::new(0, Fixity::Postfix),
+        DataExpr::Quantifier { .. } | DataExpr::Lambda { .. } => Precedence::new(1, Fixity::Prefix),
+        DataExpr::Binary { op, .. } => match op {
+            DataExprBinaryOp::Implies => Precedence::new(2, Fixity::Infix(Right)),
+            DataExprBinaryOp::Disj => Precedence::new(3, Fixity::Infix(Right)),
+            DataExprBinaryOp::Conj => Precedence::new(4, Fixity::Infix(Right)),
+            DataExprBinaryOp::Equal | DataExprBinaryOp::NotEqual => Precedence::new(5, Fixity::Infix(Left)),
+            DataExprBinaryOp::LessThan
+            | DataExprBinaryOp::LessEqual
+            | DataExprBinaryOp::GreaterEqual
+            | DataExprBinaryOp::GreaterThan
+            | DataExprBinaryOp::In => Precedence::new(6, Fixity::Infix(Left)),
+            DataExprBinaryOp::Cons => Precedence::new(7, Fixity::Infix(Right)),
+            DataExprBinaryOp::Snoc => Precedence::new(8, Fixity::Infix(Left)),
+            DataExprBinaryOp::Concat => Precedence::new(9, Fixity::Infix(Left)),
+            DataExprBinaryOp::Add | DataExprBinaryOp::Subtract => Precedence::new(10, Fixity::Infix(Left)),
+            DataExprBinaryOp::Div | DataExprBinaryOp::IntDiv | DataExprBinaryOp::Mod => {
+                Precedence::new(11, Fixity::Infix(Left))
+            }
+            DataExprBinaryOp::Multiply | DataExprBinaryOp::At => Precedence::new(12, Fixity::Infix(Left)),
+        },
+        DataExpr::Unary { .. } => Precedence::new(12, Fixity::Prefix),
+        DataExpr::FunctionUpdate { .. } | DataExpr::Application { .. } => Precedence::new(13, Fixity::Postfix),
+        _ => Precedence::PRIMARY,
+    }
+}
+
+/// Returns the precedence of the given action formula as defined by [ACTFRM_PRATT_PARSER].
+pub fn actfrm_precedence(formula: &ActFrm) -> Precedence {
+    match formula {
+        ActFrm::Quantifier { .. } => Precedence::new(0, Fixity::Prefix),
+        ActFrm::Binary { op, .. } => match op {
+            ActFrmBinaryOp::Implies => Precedence::new(2, Fixity::Infix(Right)),
+            ActFrmBinaryOp::Union => Precedence::new(3, Fixity::Infix(Right)),
+            ActFrmBinaryOp::Intersect => Precedence::new(4, Fixity::Infix(Right)),
+        },
+        ActFrm::Negation(_) => Precedence::new(6, Fixity::Prefix),
+        _ => Precedence::PRIMARY,
+    }
+}
+
+/// Returns the precedence of the given regular formula as defined by [REGFRM_PRATT_PARSER].
+pub fn regfrm_precedence(formula: &RegFrm) -> Precedence {
+    match formula {
+        RegFrm::Choice { .. } => Precedence::new(1, Fixity::Infix(Left)),
+        RegFrm::Sequence { .. } => Precedence::new(2, Fixity::Infix(Right)),
+        RegFrm::Iteration(_) | RegFrm::Plus(_) => Precedence::new(3, Fixity::Postfix),
+        RegFrm::Action(_) => Precedence::PRIMARY,
+    }
+}
+
+/// Returns the precedence of the given state formula as defined by the Pratt parser of [parse_statefrm].
+pub fn statefrm_precedence(formula: &StateFrm) -> Precedence {
+    match formula {
+        StateFrm::FixedPoint { .. } => Precedence::new(1, Fixity::Prefix),
+        StateFrm::Quantifier { .. } | StateFrm::Bound { .. } => Precedence::new(2, Fixity::Prefix),
+        StateFrm::Binary { op, .. } => match op {
+            StateFrmOp::Addition => Precedence::new(3, Fixity::Infix(Left)),
+            StateFrmOp::Implies => Precedence::new(4, Fixity::Infix(Right)),
+            StateFrmOp::Disjunction => Precedence::new(5, Fixity::Infix(Right)),
+            StateFrmOp::Conjunction => Precedence::new(6, Fixity::Infix(Right)),
+        },
+        StateFrm::DataValExprLeftMult(..) => Precedence::new(7, Fixity::Prefix),
+        StateFrm::DataValExprRightMult(..) => Precedence::new(7, Fixity::Postfix),
+        StateFrm::Modality { .. } => Precedence::new(8, Fixity::Prefix),
+        StateFrm::Unary { .. } => Precedence::new(9, Fixity::Prefix),
+        _ => Precedence::PRIMARY,
+    }
+}
+
+/// Returns the precedence of the given PBES expression as defined by the Pratt parser of [parse_pbesexpr].
+pub fn pbesexpr_precedence(expr: &PbesExpr) -> Precedence {
+    match expr {
+        PbesExpr::Quantifier { .. } => Precedence::new(0, Fixity::Prefix),
+        PbesExpr::Binary { op, .. } => match op {
+            PbesExprBinaryOp::Implies => Precedence::new(2, Fixity::Infix(Right)),
+            PbesExprBinaryOp::Disjunction => Precedence::new(3, Fixity::Infix(Right)),
+            PbesExprBinaryOp::Conjunction => Precedence::new(4, Fixity::Infix(Right)),
+        },
+        PbesExpr::Negation(_) => Precedence::new(5, Fixity::Prefix),
+        _ => Precedence::PRIMARY,
+    }
+}
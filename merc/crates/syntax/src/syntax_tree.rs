@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::hash::Hash;
 
 /// A complete mCRL2 process specification.
@@ -45,10 +46,25 @@ pub struct PropVarDecl {
     pub span: Span,
 }
 
-#[derive(Debug, Default, Eq, PartialEq, Hash)]
+#[derive(Debug, Default, Eq)]
 pub struct PropVarInst {
     pub identifier: String,
     pub arguments: Vec<DataExpr>,
+    pub span: Span,
+}
+
+impl PartialEq for PropVarInst {
+    fn eq(&self, other: &Self) -> bool {
+        // The span is ignored, since instances occur in expressions.
+        self.identifier == other.identifier && self.arguments == other.arguments
+    }
+}
+
+impl Hash for PropVarInst {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.identifier.hash(state);
+        self.arguments.hash(state);
+    }
 }
 
 /// A declaration of an identifier with its sort.
@@ -365,11 +381,27 @@ pub struct StateVarDecl {
     pub span: Span,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct StateVarAssignment {
     pub identifier: String,
     pub sort: SortExpression,
     pub expr: DataExpr,
+    pub span: Span,
+}
+
+impl PartialEq for StateVarAssignment {
+    fn eq(&self, other: &Self) -> bool {
+        // The span is ignored, since assignments occur in state formulas.
+        self.identifier == other.identifier && self.sort == other.sort && self.expr == other.expr
+    }
+}
+
+impl Hash for StateVarAssignment {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.identifier.hash(state);
+        self.sort.hash(state);
+        self.expr.hash(state);
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
@@ -425,10 +457,37 @@ pub struct MultiActionLabel {
     pub actions: Vec<String>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[derive(Clone, Debug, Eq)]
 pub struct Action {
     pub id: String,
     pub args: Vec<DataExpr>,
+    pub span: Span,
+}
+
+impl PartialEq for Action {
+    fn eq(&self, other: &Self) -> bool {
+        // The span is ignored, since actions are compared structurally in multi-actions.
+        self.id == other.id && self.args == other.args
+    }
+}
+
+impl Hash for Action {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.args.hash(state);
+    }
+}
+
+impl PartialOrd for Action {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Action {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id).then_with(|| self.args.cmp(&other.args))
+    }
 }
 
 #[derive(Clone, Debug, Eq)]
@@ -593,16 +652,46 @@ pub enum RegFrm {
     Choice { lhs: Box<RegFrm>, rhs: Box<RegFrm> },
 }
 
-#[derive(Debug, Eq, PartialEq, Hash)]
+#[derive(Debug, Eq)]
 pub struct Rename {
     pub from: String,
     pub to: String,
+    pub span: Span,
 }
 
-#[derive(Debug, Eq, PartialEq, Hash)]
+impl PartialEq for Rename {
+    fn eq(&self, other: &Self) -> bool {
+        // The span is ignored, since renamings occur in process expressions.
+        self.from == other.from && self.to == other.to
+    }
+}
+
+impl Hash for Rename {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.from.hash(state);
+        self.to.hash(state);
+    }
+}
+
+#[derive(Debug, Eq)]
 pub struct Comm {
     pub from: MultiActionLabel,
     pub to: String,
+    pub span: Span,
+}
+
+impl PartialEq for Comm {
+    fn eq(&self, other: &Self) -> bool {
+        // The span is ignored, since communications occur in process expressions.
+        self.from == other.from && self.to == other.to
+    }
+}
+
+impl Hash for Comm {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.from.hash(state);
+        self.to.hash(state);
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Hash)]
@@ -633,7 +722,16 @@ pub enum ActionRHS {
 }
 
 /// Source location information, spanning from start to end in the source text.
-#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, Hash)]
+///
+/// # Details
+///
+/// Declarations, equations, instances of named variables, actions,
+/// communications and renamings record the byte range they were parsed from.
+/// The nodes that occur inside expressions ignore their span when compared or
+/// hashed, since expressions are compared structurally, for example to
+/// identify equal multi-actions. The operators of expressions themselves do not
+/// record a span.
+#[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, Hash)]
 pub struct Span {
     pub start: usize,
     pub end: usize,
@@ -47,6 +47,11 @@ use crate::UntypedPbes;
 use crate::UntypedProcessSpecification;
 use crate::UntypedStateFrmSpec;
 use crate::VarDecl;
+use crate::actfrm_precedence;
+use crate::dataexpr_precedence;
+use crate::pbesexpr_precedence;
+use crate::regfrm_precedence;
+use crate::statefrm_precedence;
 
 /// Prints location information for a span in the source.
 pub fn print_location(input: &str, span: &Span) {
@@ -58,6 +63,19 @@ pub fn print_location(input: &str, span: &Span) {
     });
 }
 
+/// Displays an expression, surrounded by parentheses when the boolean is true.
+struct Parens<'a>(&'a dyn fmt::Display, bool);
+
+impl fmt::Display for Parens<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.1 {
+            write!(f, "({})", self.0)
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
 // Display implementations
 impl fmt::Display for Sort {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -204,17 +222,33 @@ impl fmt::Display for PropVarDecl {
 
 impl fmt::Display for PbesExpr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precedence = pbesexpr_precedence(self);
         match self {
             PbesExpr::True => write!(f, "true"),
             PbesExpr::False => write!(f, "false"),
             PbesExpr::PropVarInst(instance) => write!(f, "{instance}"),
-            PbesExpr::Negation(expr) => write!(f, "(! {expr})"),
-            PbesExpr::Binary { op, lhs, rhs } => write!(f, "({lhs} {op} {rhs})"),
+            PbesExpr::Negation(expr) => write!(
+                f,
+                "!{}",
+                Parens(expr, precedence.parenthesize_right(pbesexpr_precedence(expr)))
+            ),
+            PbesExpr::Binary { op, lhs, rhs } => write!(
+                f,
+                "{} {op} {}",
+                Parens(lhs, precedence.parenthesize_left(pbesexpr_precedence(lhs))),
+                Parens(rhs, precedence.parenthesize_right(pbesexpr_precedence(rhs)))
+            ),
             PbesExpr::Quantifier {
                 quantifier,
                 variables,
                 body,
-            } => write!(f, "({} {} . {})", quantifier, variables.iter().format(", "), body),
+            } => write!(
+                f,
+                "{} {} . {}",
+                quantifier,
+                variables.iter().format(", "),
+                Parens(body, precedence.parenthesize_right(pbesexpr_precedence(body)))
+            ),
             PbesExpr::DataValExpr(data_expr) => write!(f, "val({data_expr})"),
         }
     }
@@ -294,6 +328,7 @@ impl fmt::Display for DataExprUnaryOp {
 
 impl fmt::Display for DataExpr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precedence = dataexpr_precedence(self);
         match self {
             DataExpr::EmptyList => write!(f, "[]"),
             DataExpr::EmptyBag => write!(f, "{{:}}"),
@@ -308,14 +343,33 @@ impl fmt::Display for DataExpr {
             ),
             DataExpr::Set(expressions) => write!(f, "{{ {} }}", expressions.iter().format(", ")),
             DataExpr::Id(identifier) => write!(f, "{identifier}"),
-            DataExpr::Binary { op, lhs, rhs } => write!(f, "({lhs} {op} {rhs})"),
-            DataExpr::Unary { op, expr } => write!(f, "({op} {expr})"),
+            DataExpr::Binary { op, lhs, rhs } => write!(
+                f,
+                "{} {op} {}",
+                Parens(lhs, precedence.parenthesize_left(dataexpr_precedence(lhs))),
+                Parens(rhs, precedence.parenthesize_right(dataexpr_precedence(rhs)))
+            ),
+            DataExpr::Unary { op, expr } => write!(
+                f,
+                "{op}{}",
+                Parens(expr, precedence.parenthesize_right(dataexpr_precedence(expr)))
+            ),
             DataExpr::Bool(value) => write!(f, "{value}"),
-            DataExpr::Quantifier { op, variables, body } => {
-                write!(f, "({} {} . {})", op, variables.iter().format(", "), body)
-            }
-            DataExpr::Lambda { variables, body } => write!(f, "(lambda {} . {})", variables.iter().format(", "), body),
+            DataExpr::Quantifier { op, variables, body } => write!(
+                f,
+                "{} {} . {}",
+                op,
+                variables.iter().format(", "),
+                Parens(body, precedence.parenthesize_right(dataexpr_precedence(body)))
+            ),
+            DataExpr::Lambda { variables, body } => write!(
+                f,
+                "lambda {} . {}",
+                variables.iter().format(", "),
+                Parens(body, precedence.parenthesize_right(dataexpr_precedence(body)))
+            ),
             DataExpr::Application { function, arguments } => {
+                let function = Parens(function, precedence.parenthesize_left(dataexpr_precedence(function)));
                 if arguments.is_empty() {
                     write!(f, "{function}")
                 } else {
@@ -323,9 +377,18 @@ impl fmt::Display for DataExpr {
                 }
             }
             DataExpr::Number(value) => write!(f, "{value}"),
-            DataExpr::FunctionUpdate { expr, update } => write!(f, "{expr}[{update}]"),
+            DataExpr::FunctionUpdate { expr, update } => write!(
+                f,
+                "{}[{update}]",
+                Parens(expr, precedence.parenthesize_left(dataexpr_precedence(expr)))
+            ),
             DataExpr::SetBagComp { variable, predicate } => write!(f, "{{ {variable} | {predicate} }}"),
-            DataExpr::Whr { expr, assignments } => write!(f, "{} whr {} end", expr, assignments.iter().format(", ")),
+            DataExpr::Whr { expr, assignments } => write!(
+                f,
+                "{} whr {} end",
+                Parens(expr, precedence.parenthesize_left(dataexpr_precedence(expr))),
+                assignments.iter().format(", ")
+            ),
         }
     }
 }
@@ -386,6 +449,7 @@ impl fmt::Display for FixedPointOperator {
 
 impl fmt::Display for StateFrm {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precedence = statefrm_precedence(self);
         match self {
             StateFrm::True => write!(f, "true"),
             StateFrm::False => write!(f, "false"),
@@ -397,43 +461,71 @@ impl fmt::Display for StateFrm {
                     write!(f, "{}({})", identifier, args.iter().format(", "))
                 }
             }
-            StateFrm::Unary { op, expr } => write!(f, "({op} {expr})"),
+            StateFrm::Unary { op, expr } => write!(
+                f,
+                "{op}{}",
+                Parens(expr, precedence.parenthesize_right(statefrm_precedence(expr)))
+            ),
             StateFrm::Modality {
                 operator,
                 formula,
                 expr,
-            } => match operator {
-                ModalityOperator::Box => write!(f, "[{formula}]{expr}"),
-                ModalityOperator::Diamond => write!(f, "<{formula}>{expr}"),
-            },
+            } => {
+                let expr = Parens(expr, precedence.parenthesize_right(statefrm_precedence(expr)));
+                match operator {
+                    ModalityOperator::Box => write!(f, "[{formula}]{expr}"),
+                    ModalityOperator::Diamond => write!(f, "<{formula}>{expr}"),
+                }
+            }
             StateFrm::Quantifier {
                 quantifier,
                 variables,
                 body,
-            } => {
-                write!(f, "({} {} . {})", quantifier, variables.iter().format(", "), body)
-            }
+            } => write!(
+                f,
+                "{} {} . {}",
+                quantifier,
+                variables.iter().format(", "),
+                Parens(body, precedence.parenthesize_right(statefrm_precedence(body)))
+            ),
             StateFrm::Bound {
                 bound: quantifier,
                 variables,
                 body,
-            } => {
-                write!(f, "({} {} . {})", quantifier, variables.iter().format(", "), body)
-            }
-            StateFrm::Binary { op, lhs, rhs } => {
-                write!(f, "({lhs} {op} {rhs})")
-            }
+            } => write!(
+                f,
+                "{} {} . {}",
+                quantifier,
+                variables.iter().format(", "),
+                Parens(body, precedence.parenthesize_right(statefrm_precedence(body)))
+            ),
+            StateFrm::Binary { op, lhs, rhs } => write!(
+                f,
+                "{} {op} {}",
+                Parens(lhs, precedence.parenthesize_left(statefrm_precedence(lhs))),
+                Parens(rhs, precedence.parenthesize_right(statefrm_precedence(rhs)))
+            ),
             StateFrm::FixedPoint {
                 operator,
                 variable,
                 body,
-            } => {
-                write!(f, "({operator} {variable} . {body})")
-            }
+            } => write!(
+                f,
+                "{operator} {variable} . {}",
+                Parens(body, precedence.parenthesize_right(statefrm_precedence(body)))
+            ),
             StateFrm::Delay(expr) => write!(f, "delay@({expr})"),
             StateFrm::Yaled(expr) => write!(f, "yaled@({expr})"),
-            StateFrm::DataValExprLeftMult(value, expr) => write!(f, "({value} * {expr})"),
-            StateFrm::DataValExprRightMult(expr, value) => write!(f, "({expr} * {value})"),
+            StateFrm::DataValExprLeftMult(value, expr) => write!(
+                f,
+                "val({value}) * {}",
+                Parens(expr, precedence.parenthesize_right(statefrm_precedence(expr)))
+            ),
+            StateFrm::DataValExprRightMult(expr, value) => write!(
+                f,
+                "{} * val({value})",
+                Parens(expr, precedence.parenthesize_left(statefrm_precedence(expr)))
+            ),
         }
     }
 }
@@ -467,12 +559,34 @@ impl fmt::Display for StateFrmOp {
 
 impl fmt::Display for RegFrm {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precedence = regfrm_precedence(self);
         match self {
             RegFrm::Action(action) => write!(f, "{action}"),
-            RegFrm::Iteration(body) => write!(f, "({body})*"),
-            RegFrm::Plus(body) => write!(f, "({body})+"),
-            RegFrm::Choice { lhs, rhs } => write!(f, "({lhs} + {rhs})"),
-            RegFrm::Sequence { lhs, rhs } => write!(f, "({lhs} . {rhs})"),
+            RegFrm::Iteration(body) | RegFrm::Plus(body) => {
+                // The grammar allows at most one postfix operator per operand.
+                let body = Parens(
+                    body,
+                    precedence.parenthesize_left(regfrm_precedence(body))
+                        || matches!(**body, RegFrm::Iteration(_) | RegFrm::Plus(_)),
+                );
+                if matches!(self, RegFrm::Iteration(_)) {
+                    write!(f, "{body}*")
+                } else {
+                    write!(f, "{body}+")
+                }
+            }
+            RegFrm::Choice { lhs, rhs } => write!(
+                f,
+                "{} + {}",
+                Parens(lhs, precedence.parenthesize_left(regfrm_precedence(lhs))),
+                Parens(rhs, precedence.parenthesize_right(regfrm_precedence(rhs)))
+            ),
+            RegFrm::Sequence { lhs, rhs } => write!(
+                f,
+                "{} . {}",
+                Parens(lhs, precedence.parenthesize_left(regfrm_precedence(lhs))),
+                Parens(rhs, precedence.parenthesize_right(regfrm_precedence(rhs)))
+            ),
         }
     }
 }
@@ -506,20 +620,34 @@ impl fmt::Display for DataExprBinaryOp {
 
 impl fmt::Display for ActFrm {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precedence = actfrm_precedence(self);
         match self {
             ActFrm::False => write!(f, "false"),
             ActFrm::True => write!(f, "true"),
             ActFrm::MultAct(action) => write!(f, "{action}"),
-            ActFrm::Binary { op, lhs, rhs } => {
-                write!(f, "({lhs}) {op} ({rhs})")
-            }
+            ActFrm::Binary { op, lhs, rhs } => write!(
+                f,
+                "{} {op} {}",
+                Parens(lhs, precedence.parenthesize_left(actfrm_precedence(lhs))),
+                Parens(rhs, precedence.parenthesize_right(actfrm_precedence(rhs)))
+            ),
             ActFrm::DataExprVal(expr) => write!(f, "val({expr})"),
             ActFrm::Quantifier {
                 quantifier,
                 variables,
                 body,
-            } => write!(f, "({} {} . {})", quantifier, variables.iter().format(", "), body),
-            ActFrm::Negation(expr) => write!(f, "(!{expr})"),
+            } => write!(
+                f,
+                "{} {} . {}",
+                quantifier,
+                variables.iter().format(", "),
+                Parens(body, precedence.parenthesize_right(actfrm_precedence(body)))
+            ),
+            ActFrm::Negation(expr) => write!(
+                f,
+                "!{}",
+                Parens(expr, precedence.parenthesize_right(actfrm_precedence(expr)))
+            ),
         }
     }
 }
@@ -723,3 +851,125 @@ impl fmt::Display for MultiActionLabel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use merc_utilities::MercError;
+
+    use crate::DataExpr;
+    use crate::UntypedPbes;
+    use crate::UntypedStateFrmSpec;
+
+    use super::*;
+
+    /// Removes the source locations from the debug representation, since these
+    /// change when an expression is displayed.
+    fn without_spans(debug: String) -> String {
+        let mut result = String::new();
+        let mut rest = debug.as_str();
+        while let Some(start) = rest.find("span: Span {") {
+            result.push_str(&rest[..start]);
+            let end = start + rest[start..].find('}').expect("The span should be closed");
+            rest = &rest[end + 1..];
+        }
+        result.push_str(rest);
+        result
+    }
+
+    /// Checks that displaying the given expression and parsing the result yields the same expression.
+    fn assert_round_trip<T: fmt::Debug + fmt::Display>(expr: T, parse: impl Fn(&str) -> Result<T, MercError>) {
+        let printed = expr.to_string();
+        let reparsed = parse(&printed).unwrap_or_else(|e| panic!("Failed to parse \"{printed}\": {e}"));
+
+        assert_eq!(
+            without_spans(format!("{reparsed:?}")),
+            without_spans(format!("{expr:?}")),
+            "Parsing \"{printed}\" results in a different expression"
+        );
+    }
+
+    #[test]
+    fn test_dataexpr_round_trip() {
+        let corpus = [
+            "a + b * c",
+            "(a + b) * c",
+            "a - (b - c)",
+            "a - b - c",
+            "a => b => c",
+            "(a => b) => c",
+            "!(a && b) || c",
+            "-(a * b)",
+            "(-a) * b",
+            "a - -b",
+            "#(l ++ m)",
+            "x |> y |> l",
+            "(x |> l) ++ m",
+            "l <| x <| y",
+            "a == b != c",
+            "a == (b != c)",
+            "n div 2 mod 3",
+            "x in { 1, 2 }",
+            "l . (n + 1)",
+            "f(x + 1, g(y))[x -> 2](z)",
+            "(lambda x: Nat . x + 1)(2)",
+            "forall x: Nat . x < 2 && exists y: Nat . y > x",
+            "(forall x: Nat . x < 2) && b",
+            "x + y whr x = 1, y = 2 end",
+            "(x whr x = 1 end) + 2",
+        ];
+
+        for input in corpus {
+            assert_round_trip(DataExpr::parse(input).unwrap(), DataExpr::parse);
+        }
+    }
+
+    #[test]
+    fn test_statefrm_round_trip() {
+        let corpus = [
+            "mu X . [a]X && <b>true",
+            "nu X(n: Nat = 0) . [a(n)]X(n + 1)",
+            "[true*]<a>true",
+            "[(a . b)*]false",
+            "[a + b . c]false",
+            "[(a*)+]false",
+            "<a || b && !c>true",
+            "[exists d: D . r(d)]false",
+            "forall x: Nat . val(x < 2) => X",
+            "!(X && Y)",
+            "(X || Y) && Z",
+            "X => Y => Z",
+            "(X => Y) => Z",
+            "-X + Y",
+            "val(2) * X + Y",
+            "val(2) * (X + Y)",
+            "(val(2) * X) * val(3)",
+            "sup x: Nat . val(x) * X",
+            "inf x: Nat . val(x) * X",
+        ];
+
+        let parse = |input: &str| UntypedStateFrmSpec::parse(input).map(|spec| spec.formula);
+        for input in corpus {
+            assert_round_trip(parse(input).unwrap(), parse);
+        }
+    }
+
+    #[test]
+    fn test_pbesexpr_round_trip() {
+        let corpus = [
+            "X && (Y || Z)",
+            "!(X && Y)",
+            "!X || Y",
+            "(X => Y) => Z",
+            "forall n: Nat . val(n < 2) && X(n)",
+            "(exists n: Nat . X(n)) || Y",
+        ];
+
+        let parse = |input: &str| {
+            UntypedPbes::parse(&format!("pbes nu X = {input}; init X;"))
+                .map(|mut pbes| pbes.equations.remove(0).formula)
+        };
+        for input in corpus {
+            assert_round_trip(parse(input).unwrap(), parse);
+        }
+    }
+}
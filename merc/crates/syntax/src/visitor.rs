@@ -1,3 +1,5 @@
+use std::ops::ControlFlow;
+
 use merc_utilities::MercError;
 
 use crate::StateFrm;
@@ -28,6 +30,19 @@ pub fn visit_statefrm(
     visit_statefrm_rec(formula, &mut visitor)
 }
 
+/// Visits the state formula and calls the given function on each subformula,
+/// until it returns [ControlFlow::Break].
+///
+/// The subformulas are visited in the same order as [visit_statefrm]. Returns
+/// the value of the first break, which stops the traversal, or
+/// [ControlFlow::Continue] when all subformulas have been visited.
+pub fn try_visit_statefrm<B>(
+    formula: &StateFrm,
+    mut visitor: impl FnMut(&StateFrm) -> ControlFlow<B>,
+) -> ControlFlow<B> {
+    try_visit_statefrm_rec(formula, &mut visitor)
+}
+
 /// See [`apply`].
 fn apply_statefrm_rec(
     formula: StateFrm,
@@ -160,6 +175,34 @@ fn visit_statefrm_rec(
     Ok(())
 }
 
+/// See [`try_visit_statefrm`].
+fn try_visit_statefrm_rec<B>(
+    formula: &StateFrm,
+    function: &mut impl FnMut(&StateFrm) -> ControlFlow<B>,
+) -> ControlFlow<B> {
+    function(formula)?;
+
+    match formula {
+        StateFrm::Binary { lhs, rhs, .. } => {
+            try_visit_statefrm_rec(lhs, function)?;
+            try_visit_statefrm_rec(rhs, function)
+        }
+        StateFrm::FixedPoint { body, .. } => try_visit_statefrm_rec(body, function),
+        StateFrm::Bound { body, .. } => try_visit_statefrm_rec(body, function),
+        StateFrm::Modality { expr, .. } => try_visit_statefrm_rec(expr, function),
+        StateFrm::Quantifier { body, .. } => try_visit_statefrm_rec(body, function),
+        StateFrm::DataValExprRightMult(expr, _data_val) => try_visit_statefrm_rec(expr, function),
+        StateFrm::DataValExprLeftMult(_data_val, expr) => try_visit_statefrm_rec(expr, function),
+        StateFrm::Unary { expr, .. } => try_visit_statefrm_rec(expr, function),
+        StateFrm::Id(_, _)
+        | StateFrm::True
+        | StateFrm::False
+        | StateFrm::Delay(_)
+        | StateFrm::Yaled(_)
+        | StateFrm::DataValExpr(_) => ControlFlow::Continue(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::vec;
@@ -184,4 +227,38 @@ mod tests {
 
         assert_eq!(variables, vec!["X", "X", "Y"]);
     }
+
+    #[test]
+    fn test_try_visit_state_frm_early_exit() {
+        let input = UntypedStateFrmSpec::parse("mu X. [a]X && mu Y. Y && Z").unwrap();
+
+        // Stop at the first variable, and return its name.
+        let mut visited = 0;
+        let result = try_visit_statefrm(&input.formula, |frm| {
+            visited += 1;
+            match frm {
+                StateFrm::Id(name, _) => ControlFlow::Break(name.clone()),
+                _ => ControlFlow::Continue(()),
+            }
+        });
+
+        assert_eq!(result, ControlFlow::Break("X".to_string()));
+
+        let mut all = 0;
+        visit_statefrm(&input.formula, |_| {
+            all += 1;
+            Ok(())
+        })
+        .unwrap();
+        assert!(visited < all, "The traversal should stop at the first variable");
+
+        // Without a break all subformulas are visited.
+        let mut count = 0;
+        let result: ControlFlow<()> = try_visit_statefrm(&input.formula, |_| {
+            count += 1;
+            ControlFlow::Continue(())
+        });
+        assert_eq!(result, ControlFlow::Continue(()));
+        assert_eq!(count, all);
+    }
 }
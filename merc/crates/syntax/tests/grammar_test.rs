@@ -1,8 +1,12 @@
+use std::collections::HashSet;
+
 use indoc::indoc;
 use pest::Parser;
 
 use merc_syntax::Mcrl2Parser;
+use merc_syntax::PbesExpr;
 use merc_syntax::Rule;
+use merc_syntax::UntypedPbes;
 use merc_syntax::UntypedProcessSpecification;
 use merc_syntax::UntypedStateFrmSpec;
 use merc_syntax::parse_sortexpr;
@@ -91,6 +95,65 @@ fn test_parse_procexpr() {
     }
 }
 
+#[test]
+fn test_parse_collect_errors() {
+    let spec = indoc! {"
+        act
+            a, b: Nat;
+
+        proc
+            P(n: Nat) = a(n + ) . P(n);
+            Q = b(1) . Q;
+
+        init
+            Q + ;
+    "};
+
+    assert!(UntypedProcessSpecification::parse(spec).is_err());
+
+    let (result, errors) = UntypedProcessSpecification::parse_collect_errors(spec);
+    assert_eq!(errors.len(), 2, "Expected two errors, found {errors:?}");
+
+    // Both errors are located in the statement that contains them.
+    let first = spec.find("P(n: Nat)").unwrap()..spec.find("Q = b(1)").unwrap();
+    assert!(first.contains(&errors[0].span.start), "Unexpected error {}", errors[0]);
+    let second = spec.find("Q + ;").unwrap()..spec.len();
+    assert!(second.contains(&errors[1].span.start), "Unexpected error {}", errors[1]);
+
+    // The statements without errors are still parsed.
+    assert_eq!(result.action_declarations.len(), 2);
+    assert_eq!(result.process_declarations.len(), 1);
+    assert_eq!(result.process_declarations[0].identifier, "Q");
+    assert!(result.init.is_none());
+}
+
+#[test]
+fn test_parse_spans() {
+    let spec = "pbes nu X(n: Nat) = Y(n + 1) && X(n);\ninit X(0);";
+
+    let pbes = UntypedPbes::parse(spec).unwrap();
+    let PbesExpr::Binary { lhs, .. } = &pbes.equations[0].formula else {
+        panic!("Expected a conjunction, found {}", pbes.equations[0].formula);
+    };
+    let PbesExpr::PropVarInst(instance) = &**lhs else {
+        panic!("Expected a variable instance, found {lhs}");
+    };
+
+    assert_eq!(&spec[instance.span.start..instance.span.end], "Y(n + 1)");
+    assert_eq!(&spec[pbes.init.span.start..pbes.init.span.end], "X(0)");
+
+    // Textually identical instances are equal, regardless of their position.
+    let pbes = UntypedPbes::parse("pbes nu X(n: Nat) = X(n) && X(n);\ninit X(0);").unwrap();
+    let PbesExpr::Binary { lhs, rhs, .. } = &pbes.equations[0].formula else {
+        panic!("Expected a conjunction, found {}", pbes.equations[0].formula);
+    };
+    assert_eq!(lhs, rhs);
+
+    let mut hashes = HashSet::new();
+    hashes.insert(lhs);
+    assert!(hashes.contains(rhs));
+}
+
 #[test]
 fn test_parse_statefrm() {
     test_logger();
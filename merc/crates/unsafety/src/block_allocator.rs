@@ -45,7 +45,7 @@ impl<T, const N: usize> BlockAllocator<T, N> {
         if let Some(free) = self.free {
             unsafe {
                 // Safety: By invariant of the freelist the next must point to the next free element.
-                self.free = Some(free.as_ref().next);
+                self.free = free.as_ref().next;
             }
             return Ok(free.cast::<T>());
         }
@@ -80,17 +80,111 @@ impl<T, const N: usize> BlockAllocator<T, N> {
 
     /// Deallocate the given pointer.
     pub fn deallocate_object(&mut self, ptr: NonNull<T>) {
-        if let Some(free) = self.free {
-            unsafe { (ptr.cast::<Entry<_>>()).as_mut().next = free }
-        }
+        unsafe { (ptr.cast::<Entry<_>>()).as_mut().next = self.free }
 
         self.free = Some(ptr.cast());
     }
 
+    /// Returns the blocks that have been allocated, and the memory that is used by them.
+    pub fn stats(&self) -> BlockAllocatorStats {
+        let mut blocks = 0;
+        let mut entries = 0;
+        for block in self.iter_blocks() {
+            blocks += 1;
+            entries += block.length;
+        }
+
+        BlockAllocatorStats {
+            blocks,
+            bytes_reserved: blocks * std::mem::size_of::<Block<T, N>>(),
+            bytes_in_use: (entries - self.iter_free().count()) * std::mem::size_of::<Entry<T>>(),
+        }
+    }
+
+    /// Returns the blocks in which all entries have been deallocated to the
+    /// underlying allocator.
+    ///
+    /// # Safety
+    ///
+    /// The memory of deallocated objects is released, so there must be no
+    /// pointers to deallocated objects that are used afterwards. Pointers to
+    /// objects that are still allocated remain valid.
+    pub unsafe fn trim(&mut self) {
+        // Unlink all blocks, which does not move the entries since they are boxed.
+        let mut blocks = Vec::new();
+        let mut current = self.head_block.take();
+        while let Some(mut block) = current {
+            current = block.next.take();
+            blocks.push(block);
+        }
+
+        // Determine the number of free entries in every block, using the start addresses to find the block of an entry.
+        let mut starts: Vec<(usize, usize)> = blocks
+            .iter()
+            .enumerate()
+            .map(|(index, block)| (block.data.as_ptr() as usize, index))
+            .collect();
+        starts.sort_unstable();
+
+        let block_of = |entry: NonNull<Entry<T>>| {
+            let address = entry.as_ptr() as usize;
+            let position = starts.partition_point(|(start, _)| *start <= address);
+            debug_assert!(position > 0, "Every free entry belongs to a block");
+            starts[position - 1].1
+        };
+
+        let mut free_entries = vec![0; blocks.len()];
+        for entry in self.iter_free() {
+            free_entries[block_of(entry)] += 1;
+        }
+
+        // Rebuild the freelist without the entries of the empty blocks, preserving the order.
+        let free: Vec<NonNull<Entry<T>>> = self
+            .iter_free()
+            .filter(|entry| {
+                let index = block_of(*entry);
+                free_entries[index] < blocks[index].length
+            })
+            .collect();
+
+        self.free = None;
+        for mut entry in free.into_iter().rev() {
+            // Safety: The entry is free, so it is not used by any object.
+            unsafe { entry.as_mut().next = self.free }
+            self.free = Some(entry);
+        }
+
+        // Relink the remaining blocks in their original order, dropping the empty ones.
+        for (index, mut block) in blocks.into_iter().enumerate().rev() {
+            if free_entries[index] < block.length {
+                block.next = self.head_block.take();
+                self.head_block = Some(block);
+            }
+        }
+    }
+
     /// Returns an iterator over the free list entries.
     fn iter_free(&self) -> FreeListIterator<T> {
         FreeListIterator { current: self.free }
     }
+
+    /// Returns an iterator over the blocks, starting at the head block.
+    fn iter_blocks(&self) -> impl Iterator<Item = &Block<T, N>> {
+        std::iter::successors(self.head_block.as_deref(), |block| block.next.as_deref())
+    }
+}
+
+/// The number of blocks of a [BlockAllocator] and the memory they use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockAllocatorStats {
+    /// The number of allocated blocks.
+    pub blocks: usize,
+
+    /// The number of bytes allocated for the blocks.
+    pub bytes_reserved: usize,
+
+    /// The number of bytes used by allocated objects.
+    pub bytes_in_use: usize,
 }
 
 /// A type that can implement `Allocator` using the underlying `BlockAllocator`.
@@ -145,7 +239,7 @@ union Entry<T> {
     data: ManuallyDrop<T>,
 
     /// If the element is free, this points to the next entry in the freelist.
-    next: NonNull<Entry<T>>,
+    next: Option<NonNull<Entry<T>>>,
 }
 
 /// We maintain a list of a blocks that store N elements each.
@@ -162,9 +256,7 @@ struct Block<T, const N: usize> {
 impl<T, const N: usize> Block<T, N> {
     fn new() -> Self {
         Self {
-            data: array::from_fn(|_i| Entry {
-                next: NonNull::dangling(),
-            }),
+            data: array::from_fn(|_i| Entry { next: None }),
             length: 0,
             next: None,
         }
@@ -188,7 +280,7 @@ impl<T> Iterator for FreeListIterator<T> {
         if let Some(current) = self.current {
             // Safety: We assume the free list is properly constructed and current points to a valid Entry
             unsafe {
-                self.current = Some(current.as_ref().next);
+                self.current = current.as_ref().next;
             }
             Some(current)
         } else {
@@ -229,4 +321,60 @@ mod tests {
             // Remove various elements and check whether all the remaining elements are valid
         })
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_block_allocator_trim() {
+        random_test(10, |rng| {
+            let mut allocator: BlockAllocator<u64, 256> = BlockAllocator::new();
+
+            let mut allocated = Vec::new();
+            for _ in 0..1000 {
+                let ptr = allocator.allocate_object().unwrap();
+                let value: u64 = rng.random();
+                unsafe {
+                    ptr.as_ptr().write(value);
+                }
+                allocated.push((ptr, value));
+            }
+
+            let stats = allocator.stats();
+            assert_eq!(stats.blocks, 4);
+            assert_eq!(stats.bytes_in_use, 1000 * std::mem::size_of::<u64>());
+
+            // Deallocate the first two blocks completely, and part of the third block.
+            for (ptr, _) in allocated.drain(0..600) {
+                allocator.deallocate_object(ptr);
+            }
+
+            unsafe {
+                allocator.trim();
+            }
+
+            let trimmed = allocator.stats();
+            assert_eq!(trimmed.blocks, 2);
+            assert!(trimmed.bytes_reserved < stats.bytes_reserved);
+            assert_eq!(trimmed.bytes_in_use, 400 * std::mem::size_of::<u64>());
+
+            // The remaining objects are unchanged, and the free entries of the third block are reused.
+            for (ptr, value) in &allocated {
+                assert_eq!(unsafe { ptr.as_ptr().read() }, *value);
+            }
+
+            for _ in 0..88 {
+                allocated.push((allocator.allocate_object().unwrap(), 0));
+            }
+            assert_eq!(allocator.stats().blocks, 2);
+
+            for (ptr, _) in allocated.drain(..) {
+                allocator.deallocate_object(ptr);
+            }
+
+            unsafe {
+                allocator.trim();
+            }
+            assert_eq!(allocator.stats().blocks, 0);
+            assert_eq!(allocator.stats().bytes_reserved, 0);
+        })
+    }
 }
@@ -1,6 +1,7 @@
 use std::alloc::GlobalAlloc;
 use std::alloc::Layout;
 use std::alloc::System;
+use std::cell::Cell;
 use std::fmt;
 use std::ptr::NonNull;
 use std::sync::atomic::AtomicUsize;
@@ -11,8 +12,20 @@ use allocator_api2::alloc::Allocator;
 
 use merc_io::BytesFormatter;
 
+use crate::global_allocator::log_large_allocation;
+
+thread_local! {
+    /// The number of bytes allocated minus the number of bytes deallocated by the current thread.
+    static THREAD_BYTES: Cell<isize> = const { Cell::new(0) };
+}
+
 /// An allocator that can be used to count performance metrics
 /// on the allocations performed.
+///
+/// # Details
+///
+/// Besides the counters that are shared by all threads, every thread keeps
+/// track of the bytes that it allocated and deallocated, see [AllocCounter::thread_bytes].
 pub struct AllocCounter {
     number_of_allocations: AtomicUsize,
     size_of_allocations: AtomicUsize,
@@ -92,6 +105,18 @@ impl AllocCounter {
         }
     }
 
+    /// Returns the largest number of bytes that were allocated at the same time.
+    pub fn peak_bytes(&self) -> usize {
+        self.max_size_of_allocations.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of bytes allocated minus the number of bytes
+    /// deallocated by the current thread, for all counters combined. This is
+    /// negative when the thread deallocated memory allocated by other threads.
+    pub fn thread_bytes() -> isize {
+        THREAD_BYTES.try_with(|bytes| bytes.get()).unwrap_or(0)
+    }
+
     /// Resets all current allocation metrics (but preserves total and max metrics)
     pub fn reset(&self) {
         self.number_of_allocations.store(0, Ordering::Relaxed);
@@ -102,42 +127,28 @@ impl AllocCounter {
         let ret = unsafe { System.alloc(layout) };
 
         if !ret.is_null() {
+            log_large_allocation(layout);
+
             // Update allocation counters atomically
-            self.number_of_allocations.fetch_add(1, Ordering::Relaxed);
-            self.size_of_allocations.fetch_add(layout.size(), Ordering::Relaxed);
+            let current_allocs = self.number_of_allocations.fetch_add(1, Ordering::Relaxed) + 1;
+            let current_size = self.size_of_allocations.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
 
             self.total_number_of_allocations.fetch_add(1, Ordering::Relaxed);
             self.total_size_of_allocations
                 .fetch_add(layout.size(), Ordering::Relaxed);
 
-            // Update max counters using compare-and-swap loops
-            let current_allocs = self.number_of_allocations.load(Ordering::Relaxed);
-            let mut max_allocs = self.max_number_of_allocations.load(Ordering::Relaxed);
-            while current_allocs > max_allocs {
-                match self.max_number_of_allocations.compare_exchange_weak(
-                    max_allocs,
-                    current_allocs,
-                    Ordering::Relaxed,
-                    Ordering::Relaxed,
-                ) {
-                    Ok(_) => break,
-                    Err(val) => max_allocs = val,
-                }
+            // Only update the max counters when they are exceeded, to avoid contention on every allocation.
+            if current_allocs > self.max_number_of_allocations.load(Ordering::Relaxed) {
+                self.max_number_of_allocations
+                    .fetch_max(current_allocs, Ordering::Relaxed);
             }
 
-            let current_size = self.size_of_allocations.load(Ordering::Relaxed);
-            let mut max_size = self.max_size_of_allocations.load(Ordering::Relaxed);
-            while current_size > max_size {
-                match self.max_size_of_allocations.compare_exchange_weak(
-                    max_size,
-                    current_size,
-                    Ordering::Relaxed,
-                    Ordering::Relaxed,
-                ) {
-                    Ok(_) => break,
-                    Err(val) => max_size = val,
-                }
+            if current_size > self.max_size_of_allocations.load(Ordering::Relaxed) {
+                self.max_size_of_allocations.fetch_max(current_size, Ordering::Relaxed);
             }
+
+            // The thread local might not be available during thread destruction.
+            let _ = THREAD_BYTES.try_with(|bytes| bytes.set(bytes.get().wrapping_add(layout.size() as isize)));
         }
 
         ret
@@ -151,6 +162,8 @@ impl AllocCounter {
         // Update allocation counters atomically
         self.number_of_allocations.fetch_sub(1, Ordering::Relaxed);
         self.size_of_allocations.fetch_sub(layout.size(), Ordering::Relaxed);
+
+        let _ = THREAD_BYTES.try_with(|bytes| bytes.set(bytes.get().wrapping_sub(layout.size() as isize)));
     }
 }
 
@@ -276,4 +289,37 @@ mod tests {
             counter.dealloc(ptr, layout);
         }
     }
+
+    #[test]
+    #[cfg(not(feature = "merc_metrics"))] // The global allocator also changes the bytes of the threads.
+    fn test_peak_and_thread_bytes() {
+        let counter = Arc::new(AllocCounter::new());
+        let layout = Layout::from_size_align(128, 8).unwrap();
+
+        let first = counter.alloc(layout);
+        let second = counter.alloc(layout);
+        counter.dealloc(first, layout);
+        counter.dealloc(second, layout);
+
+        // The peak is preserved after the memory is deallocated.
+        assert_eq!(counter.get_metrics().size_of_allocations, 0);
+        assert_eq!(counter.peak_bytes(), 256);
+
+        // Memory allocated by another thread is attributed to that thread.
+        let before = AllocCounter::thread_bytes();
+        let ptr = {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                let ptr = counter.alloc(layout);
+                assert_eq!(AllocCounter::thread_bytes(), 128);
+                ptr as usize
+            })
+            .join()
+            .unwrap()
+        };
+        assert_eq!(AllocCounter::thread_bytes(), before);
+
+        counter.dealloc(ptr as *mut u8, layout);
+        assert_eq!(AllocCounter::thread_bytes(), before - 128);
+    }
 }
@@ -1,4 +1,10 @@
-#[cfg(feature = "merc_metrics")]
+use std::alloc::Layout;
+use std::backtrace::Backtrace;
+use std::backtrace::BacktraceStatus;
+use std::cell::Cell;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
 use log::info;
 
 #[cfg(feature = "merc_metrics")]
@@ -24,3 +30,97 @@ pub fn print_allocator_metrics() {
 
 #[cfg(not(feature = "merc_metrics"))]
 pub fn print_allocator_metrics() {}
+
+/// The size in bytes above which allocations are logged, where zero disables logging.
+static ALLOCATION_LOG_THRESHOLD: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// True iff the current thread is logging an allocation.
+    static LOGGING_ALLOCATION: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Logs every allocation of more than the given number of bytes, which is
+/// disabled when it is zero. This is only a debugging aid to find the source of
+/// large allocations.
+///
+/// # Details
+///
+/// The allocations are counted by the [crate::AllocCounter], so this only has
+/// an effect for the global allocator when the `merc_metrics` feature is
+/// enabled. Allocations performed by the logger itself are never logged. When
+/// backtraces are enabled, by `RUST_LIB_BACKTRACE=1` or `RUST_BACKTRACE=1`, the
+/// message includes a backtrace.
+pub fn set_allocation_log_threshold(bytes: usize) {
+    ALLOCATION_LOG_THRESHOLD.store(bytes, Ordering::Relaxed);
+}
+
+/// Logs the allocation of the given layout when it exceeds the threshold set by [set_allocation_log_threshold].
+#[inline]
+pub(crate) fn log_large_allocation(layout: Layout) {
+    if should_log(layout.size(), ALLOCATION_LOG_THRESHOLD.load(Ordering::Relaxed)) {
+        log_allocation(layout, |message| info!("{message}"));
+    }
+}
+
+/// Returns true iff an allocation of `size` bytes exceeds the given threshold,
+/// where a threshold of zero disables logging.
+fn should_log(size: usize, threshold: usize) -> bool {
+    threshold != 0 && size > threshold
+}
+
+/// Passes a message describing the allocation of the given layout to `sink`,
+/// unless the current thread is already logging an allocation.
+#[cold]
+fn log_allocation(layout: Layout, sink: impl FnOnce(&str)) {
+    // The thread local might not be available during thread destruction.
+    let _ = LOGGING_ALLOCATION.try_with(|logging| {
+        // Logging can allocate itself, which should not be logged again.
+        if !logging.replace(true) {
+            let thread = std::thread::current();
+            let mut message = format!(
+                "Allocated {} bytes (alignment {}) on thread {}",
+                layout.size(),
+                layout.align(),
+                thread.name().unwrap_or("<unnamed>")
+            );
+
+            let backtrace = Backtrace::capture();
+            if backtrace.status() == BacktraceStatus::Captured {
+                message.push_str(&format!("\n{backtrace}"));
+            }
+
+            sink(&message);
+            logging.set(false);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_log() {
+        assert!(!should_log(4096, 0), "A threshold of zero disables logging");
+        assert!(!should_log(100, 1000));
+        assert!(!should_log(1000, 1000));
+        assert!(should_log(1001, 1000));
+        assert!(should_log(4096, 1000));
+    }
+
+    #[test]
+    fn test_log_allocation() {
+        let mut messages = Vec::new();
+        log_allocation(Layout::from_size_align(4096, 8).unwrap(), |message| {
+            messages.push(message.to_string());
+
+            // Allocations while logging are not logged again.
+            log_allocation(Layout::from_size_align(8192, 8).unwrap(), |_| {
+                panic!("Allocations while logging should not be logged")
+            });
+        });
+
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].starts_with("Allocated 4096 bytes (alignment 8) on thread"));
+    }
+}
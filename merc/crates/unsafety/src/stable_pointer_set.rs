@@ -257,7 +257,7 @@ where
         }
     }
 
-    /// Returns the number of elements in the set.
+    /// Returns the number of elements in the set, where removed elements are not counted.
     pub fn len(&self) -> usize {
         self.index.len()
     }
@@ -346,7 +346,15 @@ where
         Some(ptr)
     }
 
-    /// Returns an iterator over the elements of the set.
+    /// Returns an iterator over the elements of the set, in an arbitrary order.
+    ///
+    /// # Details
+    ///
+    /// Removed elements are not stored in the set, so only elements that are
+    /// present are returned and the stable pointers to them remain valid. The
+    /// iterator locks the parts of the set that it visits, so the set must not
+    /// be modified during the iteration, since that can deadlock when it is
+    /// done by the same thread.
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         self.index.iter().map(|boxed| unsafe { boxed.ptr.as_ref() })
     }
@@ -713,6 +721,28 @@ mod tests {
         assert_eq!(values, vec![1, 2, 3]);
     }
 
+    #[test]
+    fn test_iteration_after_remove() {
+        let set = StablePointerSet::new();
+        let pointers: Vec<_> = (0..5).map(|value| set.insert(value).0).collect();
+
+        // Remove the element in the middle.
+        let mut pointers = pointers.into_iter();
+        let removed = pointers.nth(2).unwrap();
+        assert!(set.remove(removed));
+
+        let mut values: Vec<i32> = set.iter().copied().collect();
+        values.sort();
+
+        assert_eq!(values, vec![0, 1, 3, 4]);
+        assert_eq!(set.len(), 4);
+
+        // The pointers to the other elements are still valid.
+        let mut remaining: Vec<i32> = pointers.map(|pointer| *pointer).collect();
+        remaining.sort();
+        assert_eq!(remaining, vec![3, 4]);
+    }
+
     #[test]
     fn test_stable_pointer_set_insert_equiv_ref() {
         #[derive(PartialEq, Eq, Debug)]
@@ -4,6 +4,9 @@ use core::fmt::Display;
 
 /// The Merc error type. This has a blanket [`From`] impl for any type that implements Rust's [`Error`],
 /// meaning it can be used as a "catch all" error. Captures a backtrace that can be printed from this object.
+///
+/// An error can be wrapped with additional messages using [`MercError::context`], or [`ErrorContext`] for
+/// results, which are printed as a chain from the outermost message to the original error.
 pub struct MercError {
     inner: Box<InnerMercError>,
 }
@@ -13,6 +16,52 @@ impl MercError {
     pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
         self.inner.error.downcast_ref::<E>()
     }
+
+    /// Wraps the error with an additional message that describes where or why it occurred.
+    #[cold]
+    pub fn context(mut self, message: impl Display) -> Self {
+        self.inner.context.push(message.to_string());
+        self
+    }
+
+    /// Writes the context messages, from the outermost to the innermost one, followed by the error.
+    fn fmt_chain(&self, f: &mut core::fmt::Formatter<'_>, error: impl Display) -> core::fmt::Result {
+        for (index, message) in self.inner.context.iter().rev().enumerate() {
+            if index == 0 {
+                writeln!(f, "{message}")?;
+            } else {
+                writeln!(f, "Caused by: {message}")?;
+            }
+        }
+
+        if self.inner.context.is_empty() {
+            writeln!(f, "{error}")
+        } else {
+            writeln!(f, "Caused by: {error}")
+        }
+    }
+}
+
+/// Adds context messages to the error of a [`Result`], similar to [`MercError::context`].
+pub trait ErrorContext<T> {
+    /// Wraps the error, if any, with the given message.
+    fn context(self, message: impl Display) -> Result<T, MercError>;
+
+    /// Wraps the error, if any, with the message returned by the given function, which is only called on an error.
+    fn with_context<D: Display>(self, message: impl FnOnce() -> D) -> Result<T, MercError>;
+}
+
+impl<T, E> ErrorContext<T> for Result<T, E>
+where
+    MercError: From<E>,
+{
+    fn context(self, message: impl Display) -> Result<T, MercError> {
+        self.map_err(|error| MercError::from(error).context(message))
+    }
+
+    fn with_context<D: Display>(self, message: impl FnOnce() -> D) -> Result<T, MercError> {
+        self.map_err(|error| MercError::from(error).context(message()))
+    }
 }
 
 /// This type exists to make [`MercError`] use a "thin pointer" instead of a
@@ -22,6 +71,8 @@ impl MercError {
 struct InnerMercError {
     /// The underlying error
     error: Box<dyn Error + Send + Sync + 'static>,
+    /// Additional messages, from the innermost to the outermost one
+    context: Vec<String>,
     /// A backtrace captured at creation
     backtrace: std::backtrace::Backtrace,
 }
@@ -36,6 +87,7 @@ where
         MercError {
             inner: Box::new(InnerMercError {
                 error: error.into(),
+                context: Vec::new(),
                 backtrace: std::backtrace::Backtrace::capture(),
             }),
         }
@@ -44,14 +96,13 @@ where
 
 impl Display for MercError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        writeln!(f, "{}", self.inner.error)?;
-        Ok(())
+        self.fmt_chain(f, &self.inner.error)
     }
 }
 
 impl Debug for MercError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        writeln!(f, "{:?}", self.inner.error)?;
+        self.fmt_chain(f, format_args!("{:?}", self.inner.error))?;
         {
             let backtrace = &self.inner.backtrace;
             if let std::backtrace::BacktraceStatus::Captured = backtrace.status() {
@@ -62,3 +113,24 @@ impl Debug for MercError {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_context() {
+        let error = MercError::from("Unknown LTS file format.");
+        assert_eq!(error.to_string(), "Unknown LTS file format.\n");
+
+        let result: Result<(), &str> = Err("Unknown LTS file format.");
+        let error = result
+            .context("Could not read \"model.aut\"")
+            .with_context(|| "Failed to reduce the LTS")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Failed to reduce the LTS\nCaused by: Could not read \"model.aut\"\nCaused by: Unknown LTS file format.\n"
+        );
+    }
+}
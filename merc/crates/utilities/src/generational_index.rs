@@ -1,6 +1,7 @@
 //! Provides a generational index implementation that offers generation checking
 //! in debug builds while having zero runtime cost in release builds.
 
+use std::error::Error;
 use std::fmt;
 use std::hash::Hash;
 use std::hash::Hasher;
@@ -55,6 +56,32 @@ impl<I: Copy + Into<usize>> GenerationalIndex<I> {
     }
 }
 
+/// The reason that a generational index does not refer to an element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GenerationError {
+    /// The slot has been reused since the index was created, which can only be
+    /// detected in debug builds.
+    Stale,
+
+    /// The index is outside of the container.
+    OutOfBounds,
+
+    /// The slot of the index does not contain an element.
+    Vacant,
+}
+
+impl fmt::Display for GenerationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerationError::Stale => write!(f, "The index refers to a slot that has been reused"),
+            GenerationError::OutOfBounds => write!(f, "The index is out of bounds"),
+            GenerationError::Vacant => write!(f, "The index refers to an empty slot"),
+        }
+    }
+}
+
+impl Error for GenerationError {}
+
 /// A counter that keeps track of generational indices.
 /// This helps manage generations of indices to detect use-after-free and similar issues.
 #[derive(Clone, Debug, Default)]
@@ -134,6 +161,30 @@ impl GenerationCounter {
 
         index.index
     }
+
+    /// Returns the underlying index, or an error when the generation does not
+    /// match or the index was never created by this counter.
+    ///
+    /// # Details
+    ///
+    /// The generations are only tracked in debug builds, so in release builds
+    /// this always returns the underlying index. The container should then
+    /// check whether it is out of bounds, or vacant, itself.
+    pub fn try_get_index<I>(&self, index: GenerationalIndex<I>) -> Result<I, GenerationError>
+    where
+        I: Copy + Into<usize>,
+    {
+        #[cfg(debug_assertions)]
+        {
+            match self.current_generation.get(index.index.into()) {
+                None => return Err(GenerationError::OutOfBounds),
+                Some(generation) if *generation != index.generation => return Err(GenerationError::Stale),
+                Some(_) => {}
+            }
+        }
+
+        Ok(index.index)
+    }
 }
 
 // Standard trait implementations for GenerationalIndex
@@ -248,4 +299,21 @@ mod tests {
         // This panics since idx1 and idx2 are from different generations
         assert_eq!(idx1, idx2);
     }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_generational_index_try_get_index() {
+        let mut counter = GenerationCounter::new();
+        let idx1 = counter.create_index(1usize);
+        assert_eq!(counter.try_get_index(idx1), Ok(1));
+
+        // Reusing the slot makes the old index stale.
+        let idx2 = counter.create_index(1usize);
+        assert_eq!(counter.try_get_index(idx1), Err(GenerationError::Stale));
+        assert_eq!(counter.try_get_index(idx2), Ok(1));
+
+        // An index of another counter can be out of bounds.
+        let other = GenerationCounter::new();
+        assert_eq!(other.try_get_index(idx2), Err(GenerationError::OutOfBounds));
+    }
 }
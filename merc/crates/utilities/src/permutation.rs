@@ -1,3 +1,361 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::MercError;
+
+/// A permutation on the natural numbers that is the identity on all but
+/// finitely many elements.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Permutation {
+    /// We represent a permutation as an explicit list of (domain -> image) pairs,
+    /// sorted by domain.
+    mapping: Vec<(usize, usize)>,
+}
+
+impl Permutation {
+    /// Create a permutation from a given mapping of (domain -> image) pairs. Internally
+    /// sorts the mapping by domain for a unique representation. The input must be
+    /// a valid permutation (so a bijection).
+    pub fn from_mapping(mut mapping: Vec<(usize, usize)>) -> Self {
+        debug_assert!(
+            is_valid_mapping(&mapping),
+            "Input mapping is not a valid permutation: {:?}",
+            mapping
+        );
+
+        // Sort by domain for deterministic representation.
+        mapping.sort_unstable_by_key(|(d, _)| *d);
+        debug_assert!(mapping.iter().is_sorted(), "Mapping should be sorted by domain.");
+        debug_assert!(
+            mapping.iter().all(|(from, to)| from != to),
+            "Mapping should not contain identity mappings."
+        );
+        debug_assert!(
+            mapping.windows(2).all(|pair| pair[0].0 != pair[1].0),
+            "Mapping should not contain duplicate domain entries."
+        );
+
+        Permutation { mapping }
+    }
+
+    /// Parse a permutation from a string input of the form "[0->2, 1->0, 2->1]".
+    pub fn from_mapping_notation(line: &str) -> Result<Self, MercError> {
+        // Remove the surrounding brackets if present.
+        let trimmed_input = line.trim();
+        let input_no_brackets =
+            if !trimmed_input.is_empty() && trimmed_input.starts_with('[') && trimmed_input.ends_with(']') {
+                &trimmed_input[1..trimmed_input.len() - 1]
+            } else {
+                return Err("Permutation must be enclosed in brackets []".into());
+            };
+
+        // Parse all the comma-separated tokens into (from, to) pairs.
+        let mut pairs: Vec<(usize, usize)> = Vec::new();
+        for token in input_no_brackets.split(',') {
+            let token = token.trim();
+            if token.is_empty() {
+                continue;
+            }
+
+            let arrow_pos = token
+                .find("->")
+                .ok_or_else(|| MercError::from(format!("Invalid permutation format: {}", token)))?;
+
+            let from_str = token[..arrow_pos].trim();
+            let to_str = token[arrow_pos + 2..].trim();
+
+            let from: usize = from_str
+                .parse()
+                .map_err(|_| MercError::from(format!("Invalid number: {}", from_str)))?;
+            let to: usize = to_str
+                .parse()
+                .map_err(|_| MercError::from(format!("Invalid number: {}", to_str)))?;
+
+            if pairs.iter().any(|(f, _)| *f == from) {
+                return Err(MercError::from(format!(
+                    "Invalid permutation: multiple mappings for {}",
+                    from
+                )));
+            }
+
+            pairs.push((from, to));
+        }
+
+        if !is_valid_mapping(&pairs) {
+            return Err(MercError::from("Input mapping is not a valid permutation."));
+        }
+
+        // Fixed points are the identity, like cycles of length one in cycle notation.
+        pairs.retain(|(from, to)| from != to);
+        Ok(Permutation::from_mapping(pairs))
+    }
+
+    /// Parse a permutation in either mapping notation, e.g., "[0->2, 1->0, 2->1]",
+    /// or cycle notation, e.g., "(0 2 1)". The notation is determined by the
+    /// leading '[' or '(' respectively.
+    pub fn parse(input: &str) -> Result<Self, MercError> {
+        let trimmed_input = input.trim_start();
+        if trimmed_input.starts_with('[') {
+            Permutation::from_mapping_notation(input)
+        } else if trimmed_input.starts_with('(') {
+            Permutation::from_cycle_notation(input)
+        } else {
+            Err(format!("Permutation \"{}\" must start with '[' or '('", input.trim()).into())
+        }
+    }
+
+    /// Parse a permutation in cycle notation, e.g., (0 2 1)(3 4).
+    ///
+    /// The cycles must be disjoint, so an index may occur at most once.
+    pub fn from_cycle_notation(cycle_notation: &str) -> Result<Self, MercError> {
+        parse_cycle_notation(cycle_notation, None)
+    }
+
+    /// Parse a permutation in cycle notation, like [Permutation::from_cycle_notation],
+    /// where additionally every index must be smaller than the given domain size.
+    pub fn from_cycle_notation_with_domain(cycle_notation: &str, domain_size: usize) -> Result<Self, MercError> {
+        parse_cycle_notation(cycle_notation, Some(domain_size))
+    }
+
+    /// Construct a new permutation by concatenating two (disjoint) permutations.
+    pub fn concat(self, other: &Permutation) -> Permutation {
+        debug_assert!(
+            self.mapping
+                .iter()
+                .all(|(left, _)| !other.mapping.iter().any(|(right, _)| right == left)),
+            "There should be no overlap between the two permutations being concatenated."
+        );
+
+        let mut mapping = self.mapping;
+        mapping.extend_from_slice(&other.mapping);
+
+        Permutation::from_mapping(mapping)
+    }
+
+    /// Returns the value of the permutation at the given key.
+    pub fn value(&self, key: usize) -> usize {
+        for (d, v) in &self.mapping {
+            if *d == key {
+                return *v;
+            }
+        }
+
+        key // It is the identity on unspecified elements.
+    }
+
+    /// Returns an iterator over the domain of this permutation.
+    pub fn domain(&self) -> impl Iterator<Item = usize> + '_ {
+        self.mapping.iter().map(|(d, _)| *d)
+    }
+
+    /// Check whether this permutation is the identity permutation.
+    pub fn is_identity(&self) -> bool {
+        self.mapping.iter().all(|(d, v)| d == v)
+    }
+
+    /// Returns the inverse of this permutation, i.e., the permutation that maps
+    /// every image back to its domain element.
+    pub fn inverse(&self) -> Permutation {
+        Permutation::from_mapping(self.mapping.iter().map(|(d, v)| (*v, *d)).collect())
+    }
+
+    /// Returns the composition of this permutation with the other permutation,
+    /// i.e., the permutation that maps `x` to `self.value(other.value(x))`.
+    pub fn compose(&self, other: &Permutation) -> Permutation {
+        let mut domain: Vec<usize> = self.domain().chain(other.domain()).collect();
+        domain.sort_unstable();
+        domain.dedup();
+
+        Permutation::from_mapping(
+            domain
+                .into_iter()
+                .map(|x| (x, self.value(other.value(x))))
+                .filter(|(x, y)| x != y)
+                .collect(),
+        )
+    }
+
+    /// Returns the order of this permutation, which is the smallest positive
+    /// number of times that it must be composed with itself to obtain the
+    /// identity. This is the least common multiple of the lengths of its cycles.
+    pub fn order(&self) -> usize {
+        let mut visited = HashSet::with_capacity(self.mapping.len());
+        let mut order = 1;
+
+        for start in self.domain() {
+            if !visited.insert(start) {
+                continue;
+            }
+
+            // Determine the length of the cycle containing start.
+            let mut length = 1;
+            let mut current = self.value(start);
+            while current != start {
+                visited.insert(current);
+                current = self.value(current);
+                length += 1;
+            }
+
+            order = order / gcd(order, length) * length;
+        }
+
+        order
+    }
+}
+
+/// Checks whether the mapping represents a valid permutation
+fn is_valid_mapping(mapping: &[(usize, usize)]) -> bool {
+    let mut domain = HashSet::with_capacity(mapping.len());
+    let mut image = HashSet::with_capacity(mapping.len());
+
+    for (d, v) in mapping {
+        if !domain.insert(*d) || !image.insert(*v) {
+            // Duplicate found in domain or image
+            return false;
+        }
+    }
+
+    domain == image
+}
+
+/// Display the permutation in cycle notation.
+///
+/// Cycle notation is a standard way to present permutations, where each cycle
+/// is represented by parentheses. For example, the permutation that maps 0->2,
+/// 1->0, 2->1 would be represented as (0 2 1). Cycles containing a single
+/// element (fixed points) are omitted for brevity.
+impl fmt::Display for Permutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Determine the maximum value in the permutation mapping.
+        let max_value = self.mapping.iter().map(|(d, e)| *d.max(e)).max().unwrap_or(0);
+
+        let mut visited = vec![false; max_value + 1];
+        let mut identity = true;
+
+        // The mapping is sorted by domain, so we can iterate over it directly.
+        for (start, value) in &self.mapping {
+            if visited[*value] || self.value(*start) == *start {
+                // We have already visited this element, or it is a fixed point.
+                visited[*value] = true;
+                continue;
+            }
+
+            write!(f, "(")?;
+            let mut current = *start;
+            let mut first_in_cycle = true;
+            identity = false; // At least one non-trivial cycle found.
+
+            loop {
+                if !first_in_cycle {
+                    // Print space between elements in the cycle.
+                    write!(f, " ")?;
+                }
+                first_in_cycle = false;
+
+                write!(f, "{}", current)?;
+                visited[current] = true;
+                current = self.value(current);
+
+                if current == *start {
+                    break;
+                }
+                assert!(!visited[current], "This is not a valid permutation!");
+            }
+            write!(f, ")")?;
+        }
+
+        if identity {
+            write!(f, "()")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Permutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, (d, v)) in self.mapping.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} -> {}", d, v)?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Parses the disjoint cycles of the given cycle notation, where every index
+/// must be smaller than the domain size when one is given.
+fn parse_cycle_notation(cycle_notation: &str, domain_size: Option<usize>) -> Result<Permutation, MercError> {
+    let mut mapping: Vec<(usize, usize)> = Vec::new();
+    let mut seen: HashSet<usize> = HashSet::new();
+
+    let mut remaining = cycle_notation.trim();
+    while !remaining.is_empty() {
+        // Every cycle is of the shape '(...)', possibly separated by whitespace.
+        let cycle_str = remaining.strip_prefix('(').ok_or_else(|| {
+            MercError::from(format!(
+                "Invalid cycle notation: expected '(' but found \"{}\"",
+                remaining
+            ))
+        })?;
+        let (cycle_content, rest) = cycle_str.split_once(')').ok_or_else(|| {
+            MercError::from(format!(
+                "Invalid cycle notation: missing closing ')' in \"({}\"",
+                cycle_str
+            ))
+        })?;
+        remaining = rest.trim_start();
+
+        // Parse all numbers in this cycle
+        let mut cycle_elements = Vec::new();
+        for token in cycle_content.split_whitespace() {
+            let index = token
+                .parse::<usize>()
+                .map_err(|_| MercError::from(format!("Invalid number in cycle notation: {}", token)))?;
+
+            if let Some(domain_size) = domain_size.filter(|size| index >= *size) {
+                return Err(format!(
+                    "Index {} in cycle notation is out of range, the domain has size {}",
+                    token, domain_size
+                )
+                .into());
+            }
+
+            if !seen.insert(index) {
+                return Err(format!("Index {} occurs in multiple cycles or twice in one cycle", token).into());
+            }
+
+            cycle_elements.push(index);
+        }
+
+        // Create mappings for the current cycle (each element maps to the next),
+        // where cycles of length one are the identity.
+        let len = cycle_elements.len();
+        if len > 1 {
+            for i in 0..len {
+                mapping.push((cycle_elements[i], cycle_elements[(i + 1) % len]));
+            }
+        }
+    }
+
+    if !is_valid_mapping(&mapping) {
+        return Err(MercError::from("Input mapping is not a valid permutation."));
+    }
+
+    Ok(Permutation::from_mapping(mapping))
+}
+
+/// Returns the greatest common divisor of the given numbers.
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
 /// Returns true iff the given permutation is a bijective mapping within the 0..max range.
 pub fn is_valid_permutation<P>(permutation: P, max: usize) -> bool
 where
@@ -25,9 +383,12 @@ mod tests {
 
     use super::*;
 
-    use crate::random_test;
+    use rand::Rng;
+    use rand::seq::IteratorRandom;
     use rand::seq::SliceRandom;
 
+    use crate::random_test;
+
     #[test]
     fn test_random_is_valid_permutation() {
         random_test(100, |rng| {
@@ -55,4 +416,151 @@ mod tests {
             ));
         });
     }
+
+    #[test]
+    fn test_permutation_from_input() {
+        let permutation = Permutation::from_mapping_notation("[0->   2, 1   ->0, 2->1]").unwrap();
+
+        assert!(permutation.mapping == vec![(0, 2), (1, 0), (2, 1)]);
+    }
+
+    #[test]
+    fn test_cycle_notation() {
+        let permutation = Permutation::from_mapping_notation("[0->2, 1->0, 2->1, 3->4, 4->3]").unwrap();
+        println!("{:?}", permutation.mapping);
+
+        assert_eq!(permutation.to_string(), "(0 2 1)(3 4)");
+    }
+
+    #[test]
+    fn test_cycle_notation_parsing() {
+        let permutation = Permutation::from_cycle_notation("(0 2 1)(3 4)").unwrap();
+        println!("{:?}", permutation.mapping);
+
+        assert_eq!(permutation.mapping, vec![(0, 2), (1, 0), (2, 1), (3, 4), (4, 3)]);
+    }
+
+    #[test]
+    fn test_permutation_parse() {
+        assert_eq!(
+            Permutation::parse("[0->2, 1->0, 2->1]").unwrap(),
+            Permutation::parse(" (0 2 1)").unwrap()
+        );
+        assert!(Permutation::parse("0 2 1").is_err());
+
+        // Fixed points and empty mappings are the identity.
+        assert!(Permutation::parse("[0->0]").unwrap().is_identity());
+        assert!(Permutation::parse("[]").unwrap().is_identity());
+        assert_eq!(
+            Permutation::parse("[0->1, 1->0, 2->2]").unwrap(),
+            Permutation::parse("(0 1)(2)").unwrap()
+        );
+        assert!(Permutation::parse("[0->0, 1->0]").is_err());
+    }
+
+    #[test]
+    fn test_cycle_notation_errors() {
+        let error = Permutation::from_cycle_notation("(0 1)(1 2)").unwrap_err();
+        assert!(error.to_string().contains("Index 1"), "{error}");
+
+        let error = Permutation::from_cycle_notation("(0 x)").unwrap_err();
+        assert!(error.to_string().contains(": x"), "{error}");
+
+        assert!(Permutation::from_cycle_notation("(0 1").is_err());
+        assert!(Permutation::from_cycle_notation("(0 1) 2").is_err());
+
+        assert!(Permutation::from_cycle_notation_with_domain("(0 2)", 3).is_ok());
+        let error = Permutation::from_cycle_notation_with_domain("(0 3)", 3).unwrap_err();
+        assert!(error.to_string().contains("Index 3"), "{error}");
+    }
+
+    #[test]
+    fn test_random_cycle_notation() {
+        random_test(100, |rng| {
+            // Pick a random subset size >= 2 to allow a derangement.
+            let m = rng.random_range(2..10);
+
+            // Choose a random subset of distinct domain elements.
+            let domain: Vec<usize> = (0..10).choose_multiple(rng, m);
+
+            // Create a random derangement of the chosen domain.
+            let mut image = domain.clone();
+            image.shuffle(rng);
+
+            let mapping: Vec<(usize, usize)> = domain.into_iter().zip(image).filter(|(x, y)| x != y).collect();
+            println!("Mapping: {:?}", mapping);
+
+            let permutation = Permutation::from_mapping(mapping.clone());
+
+            let cycle_notation = permutation.to_string();
+            let parsed_permutation = Permutation::from_cycle_notation(&cycle_notation).unwrap();
+
+            assert_eq!(
+                permutation, parsed_permutation,
+                "Failed on permutation {:?}",
+                permutation
+            );
+        })
+    }
+
+    #[test]
+    fn test_random_mapping_notation() {
+        random_test(100, |rng| {
+            // Pick a random subset size >= 2 to allow a derangement.
+            let m = rng.random_range(2..10);
+
+            // Choose a random subset of distinct domain elements.
+            let domain: Vec<usize> = (0..10).choose_multiple(rng, m);
+
+            // Create a random derangement of the chosen domain.
+            let mut image = domain.clone();
+            image.shuffle(rng);
+
+            let mapping: Vec<(usize, usize)> = domain.into_iter().zip(image).filter(|(x, y)| x != y).collect();
+            println!("Mapping: {:?}", mapping);
+
+            let permutation = Permutation::from_mapping(mapping.clone());
+
+            let mapping_notation = format!("{:?}", permutation);
+            let parsed_permutation = Permutation::from_mapping_notation(&mapping_notation).unwrap();
+
+            assert_eq!(
+                permutation, parsed_permutation,
+                "Failed on permutation {:?}",
+                permutation
+            );
+        })
+    }
+
+    #[test]
+    fn test_random_inverse_compose() {
+        random_test(100, |rng| {
+            let m = rng.random_range(2..10);
+            let domain: Vec<usize> = (0..10).choose_multiple(rng, m);
+            let mut image = domain.clone();
+            image.shuffle(rng);
+
+            let mapping: Vec<(usize, usize)> = domain.into_iter().zip(image).filter(|(x, y)| x != y).collect();
+            let permutation = Permutation::from_mapping(mapping);
+
+            assert!(permutation.compose(&permutation.inverse()).is_identity());
+            assert!(permutation.inverse().compose(&permutation).is_identity());
+
+            // Composing the permutation order many times yields the identity, and no fewer times.
+            let mut power = permutation.clone();
+            for _ in 1..permutation.order() {
+                assert!(!power.is_identity());
+                power = power.compose(&permutation);
+            }
+            assert!(power.is_identity());
+        })
+    }
+
+    #[test]
+    fn test_permutation_order() {
+        assert_eq!(Permutation::from_cycle_notation("()").unwrap().order(), 1);
+        assert_eq!(Permutation::from_cycle_notation("(0 1)").unwrap().order(), 2);
+        assert_eq!(Permutation::from_cycle_notation("(0 2 1)(3 4)").unwrap().order(), 6);
+        assert_eq!(Permutation::from_cycle_notation("(0 1)(2 3 4 5)").unwrap().order(), 4);
+    }
 }
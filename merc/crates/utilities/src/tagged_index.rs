@@ -95,6 +95,40 @@ impl<T: Copy, Tag> TagIndex<T, Tag> {
     }
 }
 
+impl<Tag> TagIndex<usize, Tag> {
+    /// Returns the index that is `offset` larger, or None on overflow.
+    ///
+    /// The result has the same tag, so indices with different tags can still not be mixed:
+    ///
+    /// ```compile_fail
+    /// use merc_utilities::TagIndex;
+    ///
+    /// struct StateTag;
+    /// struct LabelTag;
+    ///
+    /// let state: TagIndex<usize, StateTag> = TagIndex::new(0);
+    /// let label: TagIndex<usize, LabelTag> = state.checked_add(1).unwrap();
+    /// ```
+    pub fn checked_add(self, offset: usize) -> Option<Self> {
+        self.index.checked_add(offset).map(Self::new)
+    }
+
+    /// Returns the next index, or None on overflow.
+    pub fn next(self) -> Option<Self> {
+        self.checked_add(1)
+    }
+
+    /// Returns the previous index, or None when this is the zero index.
+    pub fn prev(self) -> Option<Self> {
+        self.index.checked_sub(1).map(Self::new)
+    }
+
+    /// Returns an iterator over the indices from `start` (inclusive) to `end` (exclusive).
+    pub fn range(start: Self, end: Self) -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator {
+        (start.index..end.index).map(Self::new)
+    }
+}
+
 impl<T: fmt::Debug, Tag> fmt::Debug for TagIndex<T, Tag> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.index.fmt(f)
@@ -143,3 +177,37 @@ impl<T, Tag> Deref for TagIndex<T, Tag> {
         &self.index
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StateTag;
+    struct LabelTag;
+
+    type StateIndex = TagIndex<usize, StateTag>;
+    type LabelIndex = TagIndex<usize, LabelTag>;
+
+    #[test]
+    fn test_tagged_index_arithmetic() {
+        let index = StateIndex::new(5);
+        assert_eq!(index.checked_add(3), Some(StateIndex::new(8)));
+        assert_eq!(index.next(), Some(StateIndex::new(6)));
+        assert_eq!(index.prev(), Some(StateIndex::new(4)));
+
+        assert_eq!(StateIndex::new(usize::MAX).checked_add(1), None);
+        assert_eq!(StateIndex::new(usize::MAX).next(), None);
+        assert_eq!(StateIndex::new(0).prev(), None);
+
+        let indices: Vec<StateIndex> = StateIndex::range(StateIndex::new(2), index).collect();
+        assert_eq!(
+            indices,
+            vec![StateIndex::new(2), StateIndex::new(3), StateIndex::new(4)]
+        );
+        assert_eq!(StateIndex::range(index, StateIndex::new(2)).len(), 0);
+
+        // The tag is preserved, so the label indices are a different type.
+        let labels: Vec<LabelIndex> = LabelIndex::range(LabelIndex::new(0), LabelIndex::new(2)).collect();
+        assert_eq!(labels.last().and_then(|label| label.next()), Some(LabelIndex::new(2)));
+    }
+}
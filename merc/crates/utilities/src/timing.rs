@@ -6,12 +6,19 @@ use std::time::Instant;
 
 use log::info;
 use log::warn;
+use serde::Serialize;
 
 /// A timing object to measure the time of different parts of the program. This
 /// is useful for debugging and profiling.
+///
+/// # Details
+///
+/// A timer that is started while another timer is running becomes a child of
+/// the innermost running timer, so nested timers form a tree. This tree is shown
+/// by [Timing::print] and [Timing::to_json].
 #[derive(Default)]
 pub struct Timing {
-    results: Rc<RefCell<Vec<(String, f32)>>>,
+    state: Rc<RefCell<TimingState>>,
 }
 
 /// A timer object that measures the time between its creation and the call to
@@ -19,34 +26,74 @@ pub struct Timing {
 /// otherwise we get zero values since the timer object is unused and can be
 /// immediately dropped.
 pub struct Timer {
-    name: String,
+    /// The index of this timer in the timers of the timing state.
+    index: usize,
     start: Instant,
-    results: Rc<RefCell<Vec<(String, f32)>>>,
+    state: Rc<RefCell<TimingState>>,
     registered: bool,
 }
 
+/// The timers that are shared between the timing object and its timers.
+#[derive(Default)]
+struct TimingState {
+    /// All timers in the order in which they were started.
+    timers: Vec<TimerResult>,
+
+    /// The indices of the running timers, where the innermost timer is last.
+    running: Vec<usize>,
+}
+
+/// The result of a single timer.
+struct TimerResult {
+    name: String,
+
+    /// The timer that was running when this timer was started.
+    parent: Option<usize>,
+
+    /// The number of ancestors of this timer.
+    depth: usize,
+
+    /// The measured time in seconds, or None when the timer has not finished.
+    time: Option<f32>,
+}
+
 impl Timing {
     /// Creates a new timing object to track timers.
     pub fn new() -> Self {
         Self {
-            results: Rc::new(RefCell::new(Vec::new())),
+            state: Rc::new(RefCell::new(TimingState::default())),
         }
     }
 
-    /// Starts a new timer with the given name.
+    /// Starts a new timer with the given name, which is nested in the innermost running timer.
     pub fn start(&mut self, name: &str) -> Timer {
-        Timer {
+        let mut state = self.state.borrow_mut();
+
+        let parent = state.running.last().cloned();
+        let depth = parent.map_or(0, |parent| state.timers[parent].depth + 1);
+        let index = state.timers.len();
+        state.timers.push(TimerResult {
             name: name.to_string(),
+            parent,
+            depth,
+            time: None,
+        });
+        state.running.push(index);
+
+        Timer {
+            index,
             start: Instant::now(),
-            results: self.results.clone(),
+            state: self.state.clone(),
             registered: false,
         }
     }
 
-    /// Prints all the finished timers.
+    /// Prints all the finished timers, where nested timers are indented.
     pub fn print(&self) {
-        for (name, time) in self.results.borrow().iter() {
-            eprintln!("Time {name}: {time:.3}s");
+        for timer in self.state.borrow().timers.iter() {
+            if let Some(time) = timer.time {
+                eprintln!("{}Time {}: {time:.3}s", "  ".repeat(timer.depth), timer.name);
+            }
         }
     }
 
@@ -55,29 +102,137 @@ impl Timing {
         writeln!(writer, "- tool: {tool_name}")?;
         writeln!(writer, "  timing:")?;
 
-        for (name, time) in self.results.borrow().iter() {
-            writeln!(writer, "    {name}: {time:.3}s")?;
+        for timer in self.state.borrow().timers.iter() {
+            if let Some(time) = timer.time {
+                writeln!(writer, "    {}: {time:.3}s", timer.name)?;
+            }
         }
         Ok(())
     }
+
+    /// Returns the tree of timers as a JSON array, where every timer is an
+    /// object with a `name`, the `time` in seconds (null when it has not
+    /// finished) and its `children`.
+    pub fn to_json(&self) -> String {
+        let state = self.state.borrow();
+
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); state.timers.len()];
+        let mut roots = Vec::new();
+        for (index, timer) in state.timers.iter().enumerate() {
+            match timer.parent {
+                Some(parent) => children[parent].push(index),
+                None => roots.push(index),
+            }
+        }
+
+        serde_json::to_string(&json_tree(&state.timers, &children, &roots)).expect("Serializing timers cannot fail")
+    }
+}
+
+/// A timer in the JSON report of [Timing::to_json].
+#[derive(Serialize)]
+struct TimerJson<'a> {
+    name: &'a str,
+    time: Option<f32>,
+    children: Vec<TimerJson<'a>>,
+}
+
+/// Returns the given timers, and recursively their children, as JSON objects.
+fn json_tree<'a>(timers: &'a [TimerResult], children: &[Vec<usize>], indices: &[usize]) -> Vec<TimerJson<'a>> {
+    indices
+        .iter()
+        .map(|index| TimerJson {
+            name: &timers[*index].name,
+            time: timers[*index].time,
+            children: json_tree(timers, children, &children[*index]),
+        })
+        .collect()
 }
 
 impl Timer {
     /// Finishes the timer and registers the result.
     pub fn finish(&mut self) {
         let time = self.start.elapsed().as_secs_f64();
-        info!("Time {}: {:.3}s", self.name, time);
+
+        let mut state = self.state.borrow_mut();
+        info!("Time {}: {:.3}s", state.timers[self.index].name, time);
 
         // Register the result.
-        self.results.borrow_mut().push((self.name.clone(), time as f32));
+        state.timers[self.index].time = Some(time as f32);
+        state.stop(self.index);
         self.registered = true
     }
 }
 
+impl TimingState {
+    /// Removes the given timer from the running timers, which is typically the
+    /// innermost one, unless the lifetimes of the timers are not nested.
+    fn stop(&mut self, index: usize) {
+        if let Some(position) = self.running.iter().rposition(|running| *running == index) {
+            self.running.remove(position);
+        }
+    }
+}
+
 impl Drop for Timer {
     fn drop(&mut self) {
         if !self.registered {
-            warn!("Timer {} was dropped before 'finish()'", self.name);
+            let mut state = self.state.borrow_mut();
+            warn!("Timer {} was dropped before 'finish()'", state.timers[self.index].name);
+            state.stop(self.index);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timing_nested() {
+        let mut timing = Timing::new();
+
+        let mut read = timing.start("read");
+        let mut parse = timing.start("parse");
+        parse.finish();
+        let mut build = timing.start("build \"lts\"");
+        build.finish();
+        read.finish();
+
+        let mut reduce = timing.start("reduce");
+        reduce.finish();
+
+        let depths: Vec<usize> = timing.state.borrow().timers.iter().map(|timer| timer.depth).collect();
+        assert_eq!(depths, vec![0, 1, 1, 0]);
+
+        // The times are not deterministic, so only the names and the structure are compared.
+        let json: serde_json::Value = serde_json::from_str(&timing.to_json()).unwrap();
+        let structure = |timer: &serde_json::Value| {
+            assert!(timer["time"].is_f64());
+            (
+                timer["name"].as_str().unwrap().to_string(),
+                timer["children"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|child| child["name"].as_str().unwrap().to_string())
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        let roots: Vec<_> = json.as_array().unwrap().iter().map(structure).collect();
+        assert_eq!(
+            roots,
+            vec![
+                (
+                    "read".to_string(),
+                    vec!["parse".to_string(), "build \"lts\"".to_string()]
+                ),
+                ("reduce".to_string(), vec![]),
+            ]
+        );
+
+        let read_children: Vec<_> = json[0]["children"].as_array().unwrap().iter().map(structure).collect();
+        assert!(read_children.iter().all(|(_, children)| children.is_empty()));
+    }
+}
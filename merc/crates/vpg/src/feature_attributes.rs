@@ -0,0 +1,376 @@
+use oxidd::BooleanFunction;
+use oxidd::Manager;
+use oxidd::ManagerRef;
+use oxidd::bdd::BDDFunction;
+use oxidd::bdd::BDDManagerRef;
+
+use merc_utilities::MercError;
+
+/// A bounded integer attribute of a feature diagram, encoded by a number of
+/// boolean variables.
+pub struct FeatureAttribute {
+    /// The name of the attribute.
+    name: String,
+
+    /// The smallest value of the attribute.
+    lower: i64,
+
+    /// The largest value of the attribute.
+    upper: i64,
+
+    /// The variables that encode the value minus the lower bound, where the
+    /// first variable is the least significant bit.
+    bits: Vec<BDDFunction>,
+}
+
+impl FeatureAttribute {
+    /// Parses an attribute declaration of the shape `<name> : <lower>..<upper>`
+    /// and adds the variables that encode its value to the manager.
+    pub(crate) fn parse(manager_ref: &BDDManagerRef, declaration: &str) -> Result<Self, MercError> {
+        let (name, range) = declaration.split_once(':').ok_or(format!(
+            "Expected \"<name> : <lower>..<upper>\", found \"{declaration}\""
+        ))?;
+        let (lower, upper) = range
+            .split_once("..")
+            .ok_or(format!("Expected a range \"<lower>..<upper>\", found \"{range}\""))?;
+
+        let name = name.trim().to_string();
+        let lower: i64 = lower.trim().parse()?;
+        let upper: i64 = upper.trim().parse()?;
+        if lower > upper {
+            return Err(format!("The range of attribute \"{name}\" is empty").into());
+        }
+
+        // The number of bits required to represent upper - lower, which is at most 63.
+        let size = upper
+            .checked_sub(lower)
+            .ok_or(format!("The range of attribute \"{name}\" is too large"))?;
+        let num_of_bits = (u64::BITS - (size as u64).leading_zeros()) as usize;
+        let bit_names: Vec<String> = (0..num_of_bits).map(|i| format!("{name}[{i}]")).collect();
+        let bits = manager_ref.with_manager_exclusive(|manager| -> Result<Vec<BDDFunction>, MercError> {
+            Ok(manager
+                .add_named_vars(bit_names.iter())
+                .map_err(|e| format!("{}", e))?
+                .map(|i| BDDFunction::var(manager, i))
+                .collect::<Result<Vec<_>, _>>()?)
+        })?;
+
+        Ok(Self {
+            name,
+            lower,
+            upper,
+            bits,
+        })
+    }
+
+    /// Returns the name of the attribute.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the variables that encode the attribute, least significant bit first.
+    pub fn bits(&self) -> &[BDDFunction] {
+        &self.bits
+    }
+
+    /// Returns the set of assignments that encode a value within the bounds.
+    pub(crate) fn domain(&self, manager_ref: &BDDManagerRef) -> Result<BDDFunction, MercError> {
+        // The encoded offset from the lower bound may not exceed upper - lower.
+        let constraint = LinearConstraint {
+            constant: i128::from(self.lower) - i128::from(self.upper),
+            terms: vec![(1, &self.bits)],
+        };
+
+        constraint.encode(manager_ref, "<=")
+    }
+
+    /// Returns the value of the attribute in the given assignment of its bits,
+    /// or None when one of the bits is not assigned.
+    pub(crate) fn decode(&self, bits: impl Iterator<Item = Option<bool>>) -> Option<i64> {
+        let mut value: u64 = 0;
+        for (index, bit) in bits.enumerate() {
+            if bit? {
+                value |= 1u64.checked_shl(index as u32)?;
+            }
+        }
+
+        self.lower.checked_add_unsigned(value)
+    }
+}
+
+/// Parses a linear constraint `<expr> <op> <expr>` over the given attributes
+/// and encodes it as a BDD, where `<op>` is one of `<=`, `>=`, `==`, `!=`, `<`
+/// and `>`.
+///
+/// # Details
+///
+/// An expression is a sum of terms, separated by `+` or `-`, where every term
+/// is either an integer, an attribute name, or `<integer>*<attribute name>`.
+/// The constraint is encoded bitwise, by computing the two's complement
+/// representation of the difference between both sides with ripple-carry
+/// adders, such that the size of the encoding is polynomial in the number of
+/// bits of the attributes.
+pub(crate) fn parse_constraint(
+    manager_ref: &BDDManagerRef,
+    attributes: &[FeatureAttribute],
+    constraint: &str,
+) -> Result<BDDFunction, MercError> {
+    let (lhs, operator, rhs) = ["<=", ">=", "==", "!=", "<", ">"]
+        .iter()
+        .find_map(|operator| constraint.split_once(operator).map(|(lhs, rhs)| (lhs, *operator, rhs)))
+        .ok_or(format!("Expected a comparison in constraint \"{constraint}\""))?;
+
+    // Move the right hand side to the left, such that we compare with zero.
+    let mut terms = parse_linear_expression(lhs)?;
+    terms.extend(
+        parse_linear_expression(rhs)?
+            .into_iter()
+            .map(|(coefficient, name)| (-coefficient, name)),
+    );
+
+    // Every attribute is the sum of its lower bound and the encoded offset, so
+    // the lower bounds are moved into the constant.
+    let mut linear = LinearConstraint {
+        constant: 0,
+        terms: Vec::new(),
+    };
+    for (coefficient, name) in terms {
+        let coefficient = i128::from(coefficient);
+        match name {
+            Some(name) => {
+                let attribute = attributes
+                    .iter()
+                    .find(|attribute| attribute.name == name)
+                    .ok_or(format!("Unknown attribute \"{name}\" in constraint \"{constraint}\""))?;
+
+                linear.constant += coefficient * i128::from(attribute.lower);
+                linear.terms.push((coefficient, &attribute.bits));
+            }
+            None => linear.constant += coefficient,
+        }
+    }
+
+    linear.encode(manager_ref, operator)
+}
+
+/// The linear constraint `constant + sum(coefficient * offset) <op> 0`, where
+/// every offset is the unsigned value encoded by the given bits.
+struct LinearConstraint<'a> {
+    constant: i128,
+    terms: Vec<(i128, &'a Vec<BDDFunction>)>,
+}
+
+impl LinearConstraint<'_> {
+    /// Encodes the comparison of the linear expression with zero.
+    fn encode(&self, manager_ref: &BDDManagerRef, operator: &str) -> Result<BDDFunction, MercError> {
+        // Every (partial) sum is bounded in absolute value by the constant plus
+        // the largest value of every term, which determines the width of the
+        // two's complement representation, including a sign bit.
+        let mut bound = self.constant.unsigned_abs();
+        for (coefficient, bits) in &self.terms {
+            let largest = 1u128
+                .checked_shl(bits.len() as u32)
+                .map(|power| power - 1)
+                .and_then(|largest| coefficient.unsigned_abs().checked_mul(largest))
+                .ok_or("The constraint is too large to encode")?;
+            bound = bound
+                .checked_add(largest)
+                .ok_or("The constraint is too large to encode")?;
+        }
+        let width = (u128::BITS - bound.leading_zeros()) as usize + 1;
+        if width >= i128::BITS as usize {
+            return Err("The constraint is too large to encode".into());
+        }
+
+        let (t, f) = manager_ref.with_manager_shared(|manager| (BDDFunction::t(manager), BDDFunction::f(manager)));
+        let constant = |value: i128| -> Vec<BDDFunction> {
+            (0..width)
+                .map(|index| {
+                    if (value >> index) & 1 == 1 {
+                        t.clone()
+                    } else {
+                        f.clone()
+                    }
+                })
+                .collect()
+        };
+
+        let mut sum = constant(self.constant);
+        for (coefficient, bits) in &self.terms {
+            // The offset zero extended to the width.
+            let offset: Vec<BDDFunction> = (0..width).map(|index| bits.get(index).unwrap_or(&f).clone()).collect();
+
+            // Multiply the offset by the absolute value of the coefficient using shift and add.
+            let magnitude = coefficient.unsigned_abs();
+            let mut product = constant(0);
+            for shift in 0..width {
+                if (magnitude >> shift) & 1 == 1 {
+                    let shifted: Vec<BDDFunction> = (0..width)
+                        .map(|index| {
+                            if index < shift {
+                                f.clone()
+                            } else {
+                                offset[index - shift].clone()
+                            }
+                        })
+                        .collect();
+                    product = add(&product, &shifted, &f)?;
+                }
+            }
+
+            if *coefficient < 0 {
+                // Two's complement negation, by inverting the bits and adding one.
+                let inverted = product.iter().map(|bit| bit.not()).collect::<Result<Vec<_>, _>>()?;
+                product = add(&inverted, &constant(1), &f)?;
+            }
+
+            sum = add(&sum, &product, &f)?;
+        }
+
+        let negative = sum[width - 1].clone();
+        let mut zero = t.clone();
+        for bit in &sum {
+            zero = zero.and(&bit.not()?)?;
+        }
+
+        Ok(match operator {
+            "<=" => negative.or(&zero)?,
+            ">=" => negative.not()?,
+            "==" => zero,
+            "!=" => zero.not()?,
+            "<" => negative,
+            ">" => negative.or(&zero)?.not()?,
+            _ => unreachable!("Unknown comparison operator"),
+        })
+    }
+}
+
+/// Adds the given bit vectors of the same width with a ripple-carry adder,
+/// where the carry out of the most significant bit is discarded.
+fn add(lhs: &[BDDFunction], rhs: &[BDDFunction], f: &BDDFunction) -> Result<Vec<BDDFunction>, MercError> {
+    let mut carry = f.clone();
+    let mut result = Vec::with_capacity(lhs.len());
+    for (left, right) in lhs.iter().zip(rhs) {
+        let partial = left.xor(right)?;
+        result.push(partial.xor(&carry)?);
+        carry = left.and(right)?.or(&carry.and(&partial)?)?;
+    }
+
+    Ok(result)
+}
+
+/// Parses a linear expression into a list of terms, where constants are
+/// represented by a term without an attribute name.
+fn parse_linear_expression(expression: &str) -> Result<Vec<(i64, Option<String>)>, MercError> {
+    let expression: String = expression.chars().filter(|c| !c.is_whitespace()).collect();
+    if expression.is_empty() {
+        return Err("Expected a non-empty expression".into());
+    }
+
+    // Split the expression into signed terms.
+    let mut terms = Vec::new();
+    let mut start = 0;
+    for (index, c) in expression.char_indices() {
+        if (c == '+' || c == '-') && index > 0 {
+            terms.push(&expression[start..index]);
+            start = index;
+        }
+    }
+    terms.push(&expression[start..]);
+
+    terms
+        .into_iter()
+        .map(|term| {
+            let (sign, term) = match term.strip_prefix('-') {
+                Some(term) => (-1, term),
+                None => (1, term.strip_prefix('+').unwrap_or(term)),
+            };
+
+            if let Some((coefficient, name)) = term.split_once('*') {
+                Ok((sign * coefficient.parse::<i64>()?, Some(name.to_string())))
+            } else if let Ok(constant) = term.parse::<i64>() {
+                Ok((sign * constant, None))
+            } else {
+                Ok((sign, Some(term.to_string())))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_linear_expression() {
+        assert_eq!(
+            parse_linear_expression("cores + 2*memory - 3").unwrap(),
+            vec![
+                (1, Some("cores".to_string())),
+                (2, Some("memory".to_string())),
+                (-3, None)
+            ]
+        );
+        assert_eq!(
+            parse_linear_expression("-cores").unwrap(),
+            vec![(-1, Some("cores".to_string()))]
+        );
+        assert!(parse_linear_expression(" ").is_err());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Oxidd does not work with miri
+    fn test_parse_constraint() {
+        let manager_ref = oxidd::bdd::new_manager(2048, 1024, 1);
+
+        let attributes = vec![
+            FeatureAttribute::parse(&manager_ref, "x : -3..4").unwrap(),
+            FeatureAttribute::parse(&manager_ref, "y : 0..5").unwrap(),
+        ];
+        let domain = attributes[0]
+            .domain(&manager_ref)
+            .unwrap()
+            .and(&attributes[1].domain(&manager_ref).unwrap())
+            .unwrap();
+
+        // Evaluates the given BDD for the values of x and y.
+        let evaluate = |bdd: &BDDFunction, x: i64, y: i64| {
+            let mut assignment = Vec::new();
+            for (attribute, value) in attributes.iter().zip([x, y]) {
+                let offset = value - attribute.lower;
+                for (index, bit) in attribute.bits.iter().enumerate() {
+                    assignment.push((bit.clone(), offset & (1 << index) != 0));
+                }
+            }
+
+            let mut result = bdd.clone();
+            for (bit, value) in assignment {
+                result = result.and(&if value { bit } else { bit.not().unwrap() }).unwrap();
+            }
+            result.satisfiable()
+        };
+
+        for (constraint, holds) in [
+            ("2*x - y <= 1", (|x, y| 2 * x - y <= 1) as fn(i64, i64) -> bool),
+            ("x + y >= 3", |x, y| x + y >= 3),
+            ("x == y - 2", |x, y| x == y - 2),
+            ("3*y != 6", |_, y| 3 * y != 6),
+            ("-x < y", |x, y| -x < y),
+            ("x > 0", |x, _| x > 0),
+        ] {
+            let bdd = parse_constraint(&manager_ref, &attributes, constraint)
+                .unwrap()
+                .and(&domain)
+                .unwrap();
+            for x in -3..=4 {
+                for y in 0..=5 {
+                    assert_eq!(evaluate(&bdd, x, y), holds(x, y), "{constraint} for x={x}, y={y}");
+                }
+            }
+        }
+
+        // The values outside of the range are not in the domain.
+        assert!(!evaluate(&domain, 4, 6));
+        assert!(FeatureAttribute::parse(&manager_ref, "z : -9223372036854775808..9223372036854775807").is_err());
+    }
+}
@@ -6,6 +6,7 @@ use std::io::BufRead;
 use std::io::BufReader;
 use std::io::Read;
 
+use itertools::Itertools;
 use log::debug;
 use merc_lts::LabelIndex;
 use merc_lts::StateIndex;
@@ -15,6 +16,7 @@ use oxidd::Manager;
 use oxidd::ManagerRef;
 use oxidd::bdd::BDDFunction;
 use oxidd::bdd::BDDManagerRef;
+use oxidd::util::OptBool;
 
 use merc_lts::LTS;
 use merc_lts::LabelledTransitionSystem;
@@ -23,6 +25,9 @@ use merc_syntax::DataExpr;
 use merc_syntax::MultiAction;
 use merc_utilities::MercError;
 
+use crate::FeatureAttribute;
+use crate::parse_constraint;
+
 /// Reads a .aut file as feature transition system by using the associated feature diagram.
 ///
 /// # Details
@@ -108,6 +113,15 @@ pub struct FeatureDiagram {
     /// The mapping from variable names to their BDD variable.
     features: HashMap<String, BDDFunction>,
 
+    /// The names of the boolean features, in the order of declaration.
+    feature_names: Vec<String>,
+
+    /// The bounded integer attributes.
+    attributes: Vec<FeatureAttribute>,
+
+    /// All variables, first the boolean features followed by the bits of the attributes.
+    variables: Vec<BDDFunction>,
+
     /// Stores the set of products as a BDD function.
     configuration: BDDFunction,
 }
@@ -122,6 +136,14 @@ impl FeatureDiagram {
     /// expression. This function will initialize the BDD manager with the
     /// variables read from the first line, and assumes that the manager has no
     /// variables yet defined.
+    ///
+    /// Optionally, these lines are followed by an `attributes` section. Every
+    /// line in this section either declares a bounded integer attribute as
+    /// `<name> : <lower>..<upper>`, or is a linear constraint over the
+    /// attributes, for example `cores + 2*memory <= 10`. Every attribute is
+    /// encoded by boolean variables named `<name>[<bit>]`, which are also added
+    /// to the features, and the configuration is restricted to the products
+    /// that satisfy all constraints.
     pub fn from_reader(manager_ref: &BDDManagerRef, input: impl Read) -> Result<Self, MercError> {
         manager_ref.with_manager_exclusive(|manager| {
             debug_assert_eq!(
@@ -144,14 +166,50 @@ impl FeatureDiagram {
                 .collect::<Result<Vec<_>, _>>()?)
         })?;
 
-        let variables = HashMap::from_iter(variable_names.into_iter().zip(variables));
+        let mut features = HashMap::from_iter(variable_names.iter().cloned().zip(variables.iter().cloned()));
 
         let second_line = line_iter.next().ok_or("Expected initial configuration line")??;
-        let initial_configuration = data_expr_to_bdd(manager_ref, &variables, &DataExpr::parse(&second_line)?)?;
+        let mut configuration = data_expr_to_bdd(manager_ref, &features, &DataExpr::parse(&second_line)?)?;
+
+        // Parse the optional attributes section.
+        let mut attributes: Vec<FeatureAttribute> = Vec::new();
+        let mut in_attributes = false;
+        for line in line_iter {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if !in_attributes {
+                if line != "attributes" {
+                    return Err(format!("Expected the attributes section, found \"{line}\"").into());
+                }
+
+                in_attributes = true;
+            } else if line.contains(':') {
+                let attribute = FeatureAttribute::parse(manager_ref, line)?;
+                configuration = configuration.and(&attribute.domain(manager_ref)?)?;
+                attributes.push(attribute);
+            } else {
+                configuration = configuration.and(&parse_constraint(manager_ref, &attributes, line)?)?;
+            }
+        }
+
+        let mut all_variables = variables;
+        for attribute in &attributes {
+            for (index, bit) in attribute.bits().iter().enumerate() {
+                features.insert(format!("{}[{index}]", attribute.name()), bit.clone());
+                all_variables.push(bit.clone());
+            }
+        }
 
         Ok(Self {
-            features: variables,
-            configuration: initial_configuration,
+            features,
+            feature_names: variable_names,
+            attributes,
+            variables: all_variables,
+            configuration,
         })
     }
 
@@ -164,6 +222,76 @@ impl FeatureDiagram {
     pub fn features(&self) -> &HashMap<String, BDDFunction> {
         &self.features
     }
+
+    /// Returns the bounded integer attributes of the feature diagram.
+    pub fn attributes(&self) -> &[FeatureAttribute] {
+        &self.attributes
+    }
+
+    /// Returns the variables of the boolean features, in the order of declaration.
+    pub fn feature_variables(&self) -> &[BDDFunction] {
+        &self.variables[..self.feature_names.len()]
+    }
+
+    /// Returns all variables of the feature diagram, the boolean features in
+    /// the order of declaration followed by the bits of the attributes.
+    pub fn variables(&self) -> &Vec<BDDFunction> {
+        &self.variables
+    }
+
+    /// Decodes the given cube over the given variables, for example obtained
+    /// from [merc_symbolic::CubeIterAll], into the values of the boolean
+    /// features (zero or one) followed by the values of the attributes. A value
+    /// is None when it is not determined by the cube.
+    pub fn decode<'a>(&'a self, variables: &[BDDFunction], cube: &[OptBool]) -> Vec<(&'a str, Option<i64>)> {
+        // Returns the value of the given variable in the cube.
+        let value_of = |variable: &BDDFunction| -> Option<bool> {
+            let index = variables.iter().position(|v| v == variable)?;
+            match cube[index] {
+                OptBool::True => Some(true),
+                OptBool::False => Some(false),
+                OptBool::None => None,
+            }
+        };
+
+        let mut result = Vec::new();
+        for name in &self.feature_names {
+            result.push((name.as_str(), value_of(&self.features[name]).map(i64::from)));
+        }
+
+        for attribute in &self.attributes {
+            result.push((
+                attribute.name(),
+                attribute.decode(attribute.bits().iter().map(value_of)),
+            ));
+        }
+
+        result
+    }
+}
+
+/// A helper structure to format a configuration of a feature diagram, showing
+/// the values of the attributes instead of their bits.
+pub struct FormatFeatureConfig<'a> {
+    pub diagram: &'a FeatureDiagram,
+    pub variables: &'a [BDDFunction],
+    pub cube: &'a [OptBool],
+}
+
+impl fmt::Display for FormatFeatureConfig<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.diagram
+                .decode(self.variables, self.cube)
+                .iter()
+                .format_with(", ", |(name, value), fmt| match value {
+                    Some(value) => fmt(&format_args!("{name}={value}")),
+                    None => fmt(&format_args!("{name}=-")),
+                })
+        )
+    }
 }
 
 impl fmt::Debug for FeatureDiagram {
@@ -234,6 +362,7 @@ impl LTS for FeatureTransitionSystem {
 #[cfg(test)]
 mod tests {
     use merc_macros::merc_test;
+    use merc_symbolic::CubeIterAll;
 
     use super::*;
 
@@ -255,4 +384,30 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[merc_test]
+    #[cfg_attr(miri, ignore)] // Oxidd does not support miri (specifically the crossbeam-epoch dependency)
+    fn test_read_feature_diagram_attributes() {
+        let manager_ref = oxidd::bdd::new_manager(2048, 1024, 1);
+
+        let feature_diagram = FeatureDiagram::from_reader(
+            &manager_ref,
+            include_bytes!("../../../examples/vpg/attributes.fd") as &[u8],
+        )
+        .unwrap();
+
+        let mut products = Vec::new();
+        for cube in CubeIterAll::new(feature_diagram.variables(), feature_diagram.configuration()) {
+            let (cube, _bdd) = cube.unwrap();
+            let values = feature_diagram.decode(feature_diagram.variables(), &cube);
+
+            let cores = values[2].1.unwrap();
+            let memory = values[3].1.unwrap();
+            assert!((2..=4).contains(&cores) && (0..=3).contains(&memory) && cores + memory <= 5);
+            products.push(values);
+        }
+
+        // Both boolean features are free, and there are nine attribute values that satisfy the constraints.
+        assert_eq!(products.len(), 4 * 9);
+    }
 }
@@ -1,6 +1,7 @@
 #![doc = include_str!("../README.md")]
 #![forbid(unsafe_code)]
 
+mod feature_attributes;
 mod feature_transition_system;
 mod modal_equation_system;
 mod parity_games;
@@ -10,6 +11,7 @@ mod translate;
 mod variability_zielonka;
 mod zielonka;
 
+pub use feature_attributes::*;
 pub use feature_transition_system::*;
 pub use modal_equation_system::*;
 pub use parity_games::*;
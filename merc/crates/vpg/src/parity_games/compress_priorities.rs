@@ -0,0 +1,75 @@
+use log::debug;
+
+use crate::ParityGame;
+use crate::Priority;
+
+/// Remaps the priorities of the given parity game to the smallest contiguous
+/// range, such that the winner of every vertex is unchanged.
+///
+/// # Details
+///
+/// The distinct priorities are considered in increasing order, and consecutive
+/// priorities with the same parity are merged into one. The smallest priority
+/// is mapped to zero if it is even, and to one otherwise. Since the relative
+/// order of the priorities, and their parity, is preserved the winning regions
+/// of the game stay the same.
+pub fn compress_priorities(game: &mut ParityGame) {
+    let mut priorities: Vec<usize> = game.priorities().iter().map(|priority| **priority).collect();
+    priorities.sort_unstable();
+    priorities.dedup();
+
+    // Maps every distinct priority, in increasing order, to its new value.
+    let mut mapping = Vec::with_capacity(priorities.len());
+    let mut new_priority = priorities.first().map_or(0, |priority| priority % 2);
+    for (index, priority) in priorities.iter().enumerate() {
+        if index > 0 && priority % 2 != priorities[index - 1] % 2 {
+            new_priority += 1;
+        }
+
+        mapping.push(new_priority);
+    }
+
+    debug!(
+        "Compressed {} priorities into {} priorities",
+        priorities.len(),
+        mapping.last().map_or(0, |last| last + 1 - mapping[0])
+    );
+
+    for priority in game.priorities_mut() {
+        let index = priorities
+            .binary_search(&**priority)
+            .expect("Every priority occurs in the sorted priorities");
+        *priority = Priority::new(mapping[index]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use merc_utilities::random_test;
+
+    use crate::PG;
+    use crate::random_parity_game;
+    use crate::solve_zielonka;
+
+    use super::*;
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Very slow under Miri
+    fn test_random_compress_priorities() {
+        random_test(100, |rng| {
+            let mut game = random_parity_game(rng, true, 50, 50, 3);
+            let expected = solve_zielonka(&game);
+
+            compress_priorities(&mut game);
+
+            // The priorities form a contiguous range starting from zero or one.
+            let mut priorities: Vec<usize> = game.iter_vertices().map(|v| *game.priority(v)).collect();
+            priorities.sort_unstable();
+            priorities.dedup();
+            assert!(priorities[0] <= 1);
+            assert!(priorities.windows(2).all(|pair| pair[0] + 1 == pair[1]));
+
+            assert_eq!(solve_zielonka(&game), expected);
+        })
+    }
+}
@@ -132,8 +132,13 @@ pub fn read_vpg(manager: &BDDManagerRef, reader: impl Read) -> Result<Variabilit
                 edges_to.push(VertexIndex::new(successor_index));
 
                 if parts.len() > 1 {
-                    let config = parse_configuration_set(manager, &variables, parts[1].trim())?;
-                    edges_configuration.push(config);
+                    if parts[1].trim().is_empty() {
+                        // The empty configuration set is written as an empty disjunction, see [write_vpg].
+                        edges_configuration.push(manager.with_manager_shared(|m| BDDFunction::f(m)));
+                    } else {
+                        let config = parse_configuration_set(manager, &variables, parts[1].trim())?;
+                        edges_configuration.push(config);
+                    }
                 } else {
                     // No configuration specified, use true (all configurations)
                     edges_configuration.push(manager.with_manager_shared(|m| BDDFunction::t(m)));
@@ -219,6 +224,11 @@ pub fn parse_configuration_set(
 
 /// Writes the given parity game to the given writer in .vpg format.
 /// Note that the writer is buffered internally using a `BufWriter`.
+///
+/// # Details
+///
+/// Edges with an unsatisfiable configuration are written as `<to>|`, which is
+/// read back as the empty configuration set by [read_vpg].
 pub fn write_vpg(writer: &mut impl Write, game: &VariabilityParityGame) -> Result<(), MercError> {
     info!("Writing variability parity game to .vpg format...");
     let mut writer = BufWriter::new(writer);
@@ -252,6 +262,8 @@ pub fn write_vpg(writer: &mut impl Write, game: &VariabilityParityGame) -> Resul
 
 #[cfg(test)]
 mod tests {
+    use crate::make_vpg_total;
+
     use super::*;
 
     #[test]
@@ -268,4 +280,27 @@ mod tests {
         assert_eq!(parity_game.num_of_vertices(), 3002);
         assert_eq!(parity_game.num_of_edges(), 4409);
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Oxidd does not work with miri
+    fn test_write_total_vpg() {
+        let manager = oxidd::bdd::new_manager(2048, 1024, 8);
+        let parity_game = read_vpg(
+            &manager,
+            include_bytes!("../../../../examples/vpg/example.vpg") as &[u8],
+        )
+        .unwrap();
+        let total_game = make_vpg_total(&manager, &parity_game).unwrap();
+
+        let mut buffer = Vec::new();
+        write_vpg(&mut buffer, &total_game).unwrap();
+
+        // A BDD manager can only hold the variables of a single game.
+        let other_manager = oxidd::bdd::new_manager(2048, 1024, 8);
+        let result = read_vpg(&other_manager, &buffer[..]).unwrap();
+
+        assert_eq!(result.num_of_vertices(), total_game.num_of_vertices());
+        assert_eq!(result.num_of_edges(), total_game.num_of_edges());
+        assert!(result.is_total(&other_manager).unwrap());
+    }
 }
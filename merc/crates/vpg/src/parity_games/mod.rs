@@ -6,6 +6,7 @@
 
 #![forbid(unsafe_code)]
 
+mod compress_priorities;
 mod display_dot;
 mod io;
 mod io_pg;
@@ -18,6 +19,7 @@ mod random_game;
 mod variability_parity_game;
 mod variability_predecessors;
 
+pub use compress_priorities::*;
 pub use display_dot::*;
 pub use io::*;
 pub use io_pg::*;
@@ -211,6 +211,11 @@ impl ParityGame {
     pub(crate) fn priorities(&self) -> &Vec<Priority> {
         &self.priority
     }
+
+    /// Returns a mutable reference to the priorities array.
+    pub(crate) fn priorities_mut(&mut self) -> &mut Vec<Priority> {
+        &mut self.priority
+    }
 }
 
 impl PG for ParityGame {
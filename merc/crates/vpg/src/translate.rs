@@ -19,6 +19,7 @@ use merc_syntax::MultiAction;
 use merc_syntax::RegFrm;
 use merc_syntax::StateFrm;
 use merc_syntax::StateFrmOp;
+use merc_syntax::UntypedStateFrmSpec;
 use merc_utilities::MercError;
 
 use crate::FeatureTransitionSystem;
@@ -30,6 +31,52 @@ use crate::VertexIndex;
 use crate::compute_reachable;
 use crate::make_vpg_total;
 
+/// Translates a feature transition system into a variability parity game for
+/// the given formula specification.
+///
+/// # Details
+///
+/// The actions declared in the specification are resolved against the labels
+/// of the feature transition system, where the feature configuration argument
+/// of every label is ignored. Declared actions must therefore be parameterless
+/// and occur in the feature transition system. Specifications without action
+/// declarations are translated exactly as by [translate].
+pub fn translate_spec(
+    manager_ref: &BDDManagerRef,
+    fts: &FeatureTransitionSystem,
+    configuration: BDDFunction,
+    spec: &UntypedStateFrmSpec,
+) -> Result<VariabilityParityGame, MercError> {
+    if !spec.data_specification.is_empty() {
+        return Err(MercError::from("The formula must not contain a data specification."));
+    }
+
+    let mut fts_actions = Vec::new();
+    for label in fts.labels() {
+        for action in MultiAction::parse(label)?.actions {
+            fts_actions.push(action.id);
+        }
+    }
+
+    for declaration in &spec.action_declarations {
+        if !declaration.args.is_empty() {
+            return Err(MercError::from(format!(
+                "Action \"{}\" has parameters, which are not supported in formulas",
+                declaration.identifier
+            )));
+        }
+
+        if !fts_actions.contains(&declaration.identifier) {
+            return Err(MercError::from(format!(
+                "Action \"{}\" does not occur in the feature transition system",
+                declaration.identifier
+            )));
+        }
+    }
+
+    translate(manager_ref, fts, configuration, &spec.formula)
+}
+
 /// Translates a feature transition system into a variability parity game.
 pub fn translate(
     manager_ref: &BDDManagerRef,
@@ -340,6 +387,7 @@ fn strip_feature_configuration_from_multi_action(multi_action: &MultiAction) ->
             .map(|action| Action {
                 id: action.id.clone(),
                 args: Vec::new(),
+                span: action.span.clone(),
             })
             .collect(),
     }
@@ -379,10 +427,11 @@ fn match_action_formula(formula: &ActFrm, action: &MultiAction) -> bool {
 #[cfg(test)]
 mod tests {
     use merc_macros::merc_test;
-    use merc_syntax::UntypedStateFrmSpec;
 
     use crate::FeatureDiagram;
+    use crate::ZielonkaVariant;
     use crate::read_fts;
+    use crate::solve_variability_zielonka;
 
     use super::*;
 
@@ -407,4 +456,30 @@ mod tests {
 
         let _vpg = translate(&manager_ref, &fts, fd.configuration().clone(), &formula.formula).unwrap();
     }
+
+    #[merc_test]
+    #[cfg_attr(miri, ignore)] // Oxidd does not work with miri
+    fn test_translate_action_formula() {
+        let manager_ref = oxidd::bdd::new_manager(2048, 1024, 1);
+
+        let fd = FeatureDiagram::from_reader(&manager_ref, b"A\ntt" as &[u8]).unwrap();
+        let fts = read_fts(
+            &manager_ref,
+            b"des (0, 2, 2)
+            (0, \"a(node(A, tt, ff))\", 1)
+            (1, \"b(tt)\", 1)" as &[u8],
+            fd.features().clone(),
+        )
+        .unwrap();
+
+        let formula = UntypedStateFrmSpec::parse("act a, b; form <a>true;").unwrap();
+        let vpg = translate_spec(&manager_ref, &fts, fd.configuration().clone(), &formula).unwrap();
+
+        // The initial vertex is won by even exactly for the products with feature A.
+        let solution = solve_variability_zielonka(&manager_ref, &vpg, ZielonkaVariant::Family, false).unwrap();
+        assert!(solution[0][VertexIndex::new(0)] == fd.features()["A"]);
+
+        let undeclared = UntypedStateFrmSpec::parse("act c; form <c>true;").unwrap();
+        assert!(translate_spec(&manager_ref, &fts, fd.configuration().clone(), &undeclared).is_err());
+    }
 }
@@ -4,6 +4,7 @@
 #![allow(unused)]
 
 use std::fmt;
+use std::hash::RandomState;
 use std::ops::Index;
 
 use bitvec::order::Lsb0;
@@ -12,10 +13,16 @@ use clap::ValueEnum;
 use log::debug;
 use log::trace;
 use oxidd::BooleanFunction;
+use oxidd::BooleanFunctionQuant;
+use oxidd::Function;
+use oxidd::LevelNo;
+use oxidd::Manager;
 use oxidd::ManagerRef;
 use oxidd::bdd::BDDFunction;
 use oxidd::bdd::BDDManagerRef;
 use oxidd::util::AllocResult;
+use oxidd::util::SatCountCache;
+use oxidd::util::num::F64;
 
 use merc_symbolic::FormatConfigSet;
 use merc_utilities::MercError;
@@ -487,6 +494,45 @@ pub fn minus(lhs: &BDDFunction, rhs: &BDDFunction) -> AllocResult<BDDFunction> {
     lhs.and(&rhs.not()?)
 }
 
+/// Returns the number of products for which the initial vertex is won by even
+/// and odd respectively, given the winning sets of the variability parity game.
+///
+/// # Details
+///
+/// A product is an assignment to the given `features`, which must be variables
+/// of the game. The remaining variables of the game, for example the bits of
+/// the attributes of a feature diagram, are existentially quantified first, so
+/// that every product is counted once. The products are then counted using the
+/// satisfiability count over all variables of the BDD manager, divided by the
+/// number of assignments to the variables that are not features.
+pub fn count_winning_products(
+    game: &VariabilityParityGame,
+    solution: &[Submap; 2],
+    features: &[BDDFunction],
+) -> Result<[f64; 2], MercError> {
+    let num_of_levels = game
+        .configuration()
+        .manager_ref()
+        .with_manager_shared(|manager| manager.num_levels());
+    let num_of_others = num_of_levels - features.len() as LevelNo;
+    let mut cache: SatCountCache<F64, RandomState> = SatCountCache::default();
+
+    let mut result = [0.0; 2];
+    for (index, winning_set) in solution.iter().enumerate() {
+        let mut won = winning_set[game.initial_vertex()].and(game.configuration())?;
+        for variable in game.variables().iter().filter(|variable| !features.contains(variable)) {
+            won = won.exists(variable)?;
+        }
+
+        // Every product is counted once for every assignment to the other variables.
+        let mut count = won.sat_count(num_of_levels, &mut cache);
+        count >>= num_of_others;
+        result[index] = count.0;
+    }
+
+    Ok(result)
+}
+
 /// A mapping from vertices to configurations.
 #[derive(Clone, PartialEq, Eq)]
 pub struct Submap {
@@ -668,6 +714,7 @@ mod tests {
     use crate::Submap;
     use crate::VertexIndex;
     use crate::ZielonkaVariant;
+    use crate::count_winning_products;
     use crate::project_variability_parity_games_iter;
     use crate::random_variability_parity_game;
     use crate::solve_variability_product_zielonka;
@@ -728,4 +775,33 @@ mod tests {
             debug_assert_eq!(solution[1], solution_expected[1]);
         })
     }
+
+    #[merc_test]
+    #[cfg_attr(miri, ignore)] // Oxidd does not work with miri
+    fn test_count_winning_products() {
+        random_test(100, |rng| {
+            let manager_ref = oxidd::bdd::new_manager(2048, 1024, 1);
+            let vpg = random_variability_parity_game(&manager_ref, rng, true, 20, 3, 3, 3).unwrap();
+
+            // A variable of the manager that is not a feature should not be counted.
+            manager_ref.with_manager_exclusive(|manager| manager.add_vars(1));
+
+            let solution = solve_variability_zielonka(&manager_ref, &vpg, ZielonkaVariant::Family, false).unwrap();
+
+            // Every projection corresponds to exactly one product.
+            let mut expected = [0.0; 2];
+            for (_cube, _bdd, pg_solution) in solve_variability_product_zielonka(&vpg) {
+                if pg_solution[0][*vpg.initial_vertex()] {
+                    expected[0] += 1.0;
+                } else {
+                    expected[1] += 1.0;
+                }
+            }
+
+            assert_eq!(
+                count_winning_products(&vpg, &solution, vpg.variables()).unwrap(),
+                expected
+            );
+        })
+    }
 }
@@ -7,6 +7,9 @@
 
 use core::fmt;
 use std::ops::BitAnd;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::thread;
 
 use bitvec::bitvec;
 use bitvec::order::Lsb0;
@@ -19,6 +22,7 @@ use oxidd::BooleanFunction;
 use oxidd::bdd::BDDFunction;
 use oxidd::util::OptBool;
 
+use merc_symbolic::CubeIterAll;
 use merc_symbolic::FormatConfig;
 use merc_utilities::MercError;
 
@@ -32,6 +36,7 @@ use crate::Submap;
 use crate::VariabilityParityGame;
 use crate::VertexIndex;
 use crate::compute_reachable;
+use crate::project_variability_parity_game;
 use crate::project_variability_parity_games_iter;
 
 type Set = BitVec<usize, Lsb0>;
@@ -119,6 +124,177 @@ pub fn verify_variability_product_zielonka_solution(
     Ok(())
 }
 
+/// Solves all projections of the given variability parity game on `threads`
+/// worker threads, and returns for every configuration the player that wins
+/// the initial vertex.
+///
+/// # Details
+///
+/// The configurations are enumerated up front, after which every worker
+/// repeatedly claims the next unsolved configuration. This way the workers that
+/// finish early take over the remaining work. The result is ordered in the same
+/// way as [project_variability_parity_games_iter], regardless of the order in
+/// which the projections were solved. Returns an error when `threads` is zero.
+pub fn solve_all_projections_parallel(
+    vpg: &VariabilityParityGame,
+    threads: usize,
+) -> Result<Vec<(Vec<OptBool>, Player)>, MercError> {
+    if threads == 0 {
+        return Err("At least one thread is required to solve the projections".into());
+    }
+
+    let configurations = CubeIterAll::new(vpg.variables(), vpg.configuration()).collect::<Result<Vec<_>, _>>()?;
+    let next_configuration = AtomicUsize::new(0);
+
+    let mut results = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next_configuration.fetch_add(1, Ordering::Relaxed);
+                        let Some((cube, bdd)) = configurations.get(index) else {
+                            break;
+                        };
+
+                        debug!("Solving projection on {}...", FormatConfig(cube));
+                        results.push((index, solve_projection(vpg, bdd)));
+                    }
+                    results
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("Worker thread panicked"))
+            .collect::<Vec<_>>()
+    });
+
+    // Restore the order of the configurations.
+    results.sort_unstable_by_key(|(index, _)| *index);
+    configurations
+        .into_iter()
+        .zip(results)
+        .map(|((cube, _bdd), (_index, winner))| Ok((cube, winner?)))
+        .collect()
+}
+
+/// Returns the player that wins the initial vertex of the projection of the
+/// given variability parity game onto the given configuration.
+fn solve_projection(vpg: &VariabilityParityGame, configuration: &BDDFunction) -> Result<Player, MercError> {
+    let pg = project_variability_parity_game(vpg, configuration)?;
+    let (reachable_pg, projection) = compute_reachable(&pg);
+
+    let solution = solve_zielonka(&reachable_pg);
+    let initial = projection[*pg.initial_vertex()].expect("The initial vertex is always reachable");
+    if solution[0][initial] {
+        Ok(Player::Even)
+    } else {
+        Ok(Player::Odd)
+    }
+}
+
+/// Solves the projection of the given variability parity game onto `cube`,
+/// reusing the solution of a previously solved projection when given.
+///
+/// # Details
+///
+/// A vertex whose outgoing edges are the same in both projections is
+/// unchanged. Any vertex that cannot reach a changed vertex has the same
+/// reachable subgame in both projections, and therefore the same winner. Only
+/// the remaining vertices are solved again, where edges to the unaffected
+/// vertices are redirected to a sink that is won by the same player. The
+/// returned winning sets range over all vertices of the projection.
+pub fn solve_variability_zielonka_incremental(
+    vpg: &VariabilityParityGame,
+    previous: Option<(&BDDFunction, &[Set; 2])>,
+    cube: &BDDFunction,
+) -> Result<[Set; 2], MercError> {
+    let pg = project_variability_parity_game(vpg, cube)?;
+    let Some((previous_cube, previous_solution)) = previous else {
+        return Ok(solve_zielonka(&pg));
+    };
+
+    // Find the vertices whose outgoing edges differ between both projections.
+    let mut affected = bitvec![usize, Lsb0; 0; pg.num_of_vertices()];
+    let mut queue = Vec::new();
+    for v in vpg.iter_vertices() {
+        for edge in vpg.outgoing_conf_edges(v) {
+            if previous_cube.and(edge.configuration())?.satisfiable() != cube.and(edge.configuration())?.satisfiable() {
+                affected.set(*v, true);
+                queue.push(v);
+                break;
+            }
+        }
+    }
+
+    // All vertices that can reach a changed vertex are affected as well.
+    let predecessors = Predecessors::new(&pg);
+    while let Some(v) = queue.pop() {
+        for u in predecessors.predecessors(v) {
+            if !affected[*u] {
+                affected.set(*u, true);
+                queue.push(u);
+            }
+        }
+    }
+
+    debug!(
+        "Solving {} out of {} vertices again",
+        affected.count_ones(),
+        pg.num_of_vertices()
+    );
+
+    let mut solution = previous_solution.clone();
+    if affected.not_any() {
+        return Ok(solution);
+    }
+
+    // Construct the subgame of the affected vertices, followed by a sink won by even and a sink won by odd.
+    let mut mapping = vec![None; pg.num_of_vertices()];
+    let mut owners = Vec::new();
+    let mut priorities = Vec::new();
+    for v in affected.iter_ones() {
+        mapping[v] = Some(VertexIndex::new(owners.len()));
+        owners.push(pg.owner(VertexIndex::new(v)));
+        priorities.push(pg.priority(VertexIndex::new(v)));
+    }
+
+    let even_sink = VertexIndex::new(owners.len());
+    owners.push(Player::Even);
+    priorities.push(Priority::new(0));
+
+    let odd_sink = VertexIndex::new(owners.len());
+    owners.push(Player::Even);
+    priorities.push(Priority::new(1));
+
+    let mut edges = vec![(even_sink, even_sink), (odd_sink, odd_sink)];
+    for v in affected.iter_ones() {
+        let from = mapping[v].expect("Affected vertices are mapped");
+        for w in pg.outgoing_edges(VertexIndex::new(v)) {
+            let to = match mapping[*w] {
+                Some(to) => to,
+                None if previous_solution[0][*w] => even_sink,
+                None => odd_sink,
+            };
+
+            edges.push((from, to));
+        }
+    }
+
+    let subgame = ParityGame::from_edges(VertexIndex::new(0), owners, priorities, false, || edges.iter().cloned());
+    let subgame_solution = solve_zielonka(&subgame);
+
+    for v in affected.iter_ones() {
+        let subgame_v = *mapping[v].expect("Affected vertices are mapped");
+        solution[0].set(v, subgame_solution[0][subgame_v]);
+        solution[1].set(v, subgame_solution[1][subgame_v]);
+    }
+
+    Ok(solution)
+}
+
 struct ZielonkaSolver<'a> {
     game: &'a ParityGame,
 
@@ -314,9 +490,17 @@ impl fmt::Display for DisplaySet<'_> {
 
 #[cfg(test)]
 mod tests {
+    use merc_symbolic::CubeIterAll;
     use merc_utilities::random_test;
 
+    use crate::PG;
+    use crate::Player;
+    use crate::project_variability_parity_game;
     use crate::random_parity_game;
+    use crate::random_variability_parity_game;
+    use crate::solve_all_projections_parallel;
+    use crate::solve_variability_product_zielonka;
+    use crate::solve_variability_zielonka_incremental;
     use crate::solve_zielonka;
 
     #[test]
@@ -329,4 +513,54 @@ mod tests {
             solve_zielonka(&pg);
         })
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Oxidd does not work with miri
+    fn test_solve_all_projections_parallel() {
+        random_test(20, |rng| {
+            let manager_ref = oxidd::bdd::new_manager(2048, 1024, 1);
+            let vpg = random_variability_parity_game(&manager_ref, rng, true, 20, 3, 3, 3).unwrap();
+
+            let expected: Vec<_> = solve_variability_product_zielonka(&vpg)
+                .map(|(cube, _bdd, solution)| {
+                    if solution[0][*vpg.initial_vertex()] {
+                        (cube, Player::Even)
+                    } else {
+                        (cube, Player::Odd)
+                    }
+                })
+                .collect();
+
+            assert_eq!(solve_all_projections_parallel(&vpg, 4).unwrap(), expected);
+            assert!(solve_all_projections_parallel(&vpg, 0).is_err());
+        })
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Oxidd does not work with miri
+    fn test_solve_variability_zielonka_incremental() {
+        random_test(20, |rng| {
+            let manager_ref = oxidd::bdd::new_manager(2048, 1024, 1);
+            let vpg = random_variability_parity_game(&manager_ref, rng, true, 20, 3, 3, 3).unwrap();
+
+            let mut previous = None;
+            for cube in CubeIterAll::new(vpg.variables(), vpg.configuration()) {
+                let (_cube, bdd) = cube.unwrap();
+
+                let expected = solve_zielonka(&project_variability_parity_game(&vpg, &bdd).unwrap());
+                let solution = solve_variability_zielonka_incremental(
+                    &vpg,
+                    previous.as_ref().map(|(bdd, solution)| (bdd, solution)),
+                    &bdd,
+                )
+                .unwrap();
+                assert_eq!(
+                    solution, expected,
+                    "The incremental solution differs from the solution from scratch"
+                );
+
+                previous = Some((bdd, solution));
+            }
+        })
+    }
 }
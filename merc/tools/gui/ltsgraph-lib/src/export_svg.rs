@@ -0,0 +1,234 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+
+use glam::Vec2;
+use glam::Vec3Swizzles;
+
+use merc_lts::LTS;
+
+use crate::Viewer;
+
+/// The view related settings that determine how the graph is exported.
+#[derive(Clone)]
+pub struct SvgSettings {
+    pub draw_action_labels: bool,
+    pub state_radius: f32,
+    pub label_text_size: f32,
+
+    /// The size of the exported image.
+    pub width: u32,
+    pub height: u32,
+
+    pub zoom_level: f32,
+    pub view_x: f32,
+    pub view_y: f32,
+}
+
+/// Exports the graph as shown by the given viewer to an SVG file at the given path.
+pub fn export_svg(viewer: &Viewer, settings: &SvgSettings, path: &Path) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_svg(&mut writer, viewer, settings)?;
+    writer.flush()
+}
+
+/// Writes the graph as shown by the given viewer as SVG to the given writer.
+///
+/// # Details
+///
+/// The same states, edges, handles and labels are drawn as by the
+/// [crate::SkiaRenderer], but as vector graphics.
+pub fn write_svg<W: Write>(writer: &mut W, viewer: &Viewer, settings: &SvgSettings) -> std::io::Result<()> {
+    let lts = viewer.lts();
+    let state_radius = settings.state_radius;
+
+    writeln!(
+        writer,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+        settings.width, settings.height, settings.width, settings.height
+    )?;
+    writeln!(writer, r#"<rect width="100%" height="100%" fill="white"/>"#)?;
+
+    // The view transform, which is the same as the one used by the renderers.
+    writeln!(
+        writer,
+        r#"<g transform="translate({} {}) scale({}) translate({} {})">"#,
+        settings.width as f32 / 2.0,
+        settings.height as f32 / 2.0,
+        settings.zoom_level,
+        settings.view_x,
+        settings.view_y
+    )?;
+
+    // Draw the edges, the arrows on them and their handles
+    writeln!(writer, r#"<g stroke="black" fill="none">"#)?;
+    let mut labels = Vec::new();
+
+    for state_index in lts.iter_states() {
+        let state_view = &viewer.state_view()[state_index];
+
+        for (transition_index, transition) in lts.outgoing_transitions(state_index).enumerate() {
+            let to_state_view = &viewer.state_view()[transition.to];
+            let transition_view = &state_view.outgoing[transition_index];
+
+            let label_position = if transition.to != state_index {
+                // The transition is curved when there are parallel transitions
+                let control = transition_view.control_point(state_view.position, to_state_view.position);
+                writeln!(
+                    writer,
+                    r#"<path d="M {} {} Q {} {} {} {}"/>"#,
+                    state_view.position.x,
+                    state_view.position.y,
+                    control.x,
+                    control.y,
+                    to_state_view.position.x,
+                    to_state_view.position.y
+                )?;
+
+                let direction = (control - to_state_view.position).normalize();
+                let angle = -direction.xy().angle_to(Vec2::new(0.0, -1.0)).to_degrees();
+
+                writeln!(
+                    writer,
+                    r#"<polygon points="0,0 2,-5 -2,-5" fill="black" transform="translate({} {}) rotate({angle}) translate(0 {})"/>"#,
+                    to_state_view.position.x,
+                    to_state_view.position.y,
+                    -state_radius - 0.5
+                )?;
+
+                // Draw the edge handle
+                writeln!(writer, r#"<circle cx="{}" cy="{}" r="1"/>"#, control.x, control.y)?;
+
+                transition_view.curve_middle(state_view.position, to_state_view.position)
+            } else {
+                // This is a self loop so draw a circle around the middle of the position and the handle
+                let middle = (2.0 * state_view.position + transition_view.handle_offset) / 2.0;
+                writeln!(
+                    writer,
+                    r#"<circle cx="{}" cy="{}" r="{}"/>"#,
+                    middle.x,
+                    middle.y,
+                    transition_view.handle_offset.length() / 2.0
+                )?;
+
+                // Draw the edge handle
+                let handle = state_view.position + transition_view.handle_offset;
+                writeln!(writer, r#"<circle cx="{}" cy="{}" r="1"/>"#, handle.x, handle.y)?;
+
+                handle
+            };
+
+            if settings.draw_action_labels {
+                labels.push((label_position, transition.label));
+            }
+        }
+    }
+    writeln!(writer, "</g>")?;
+
+    // Draw the labels on top of the edges
+    if !labels.is_empty() {
+        writeln!(
+            writer,
+            r#"<g font-family="sans-serif" font-size="{}" dominant-baseline="hanging">"#,
+            settings.label_text_size
+        )?;
+        for (position, label) in labels {
+            writeln!(
+                writer,
+                r#"<text x="{}" y="{}">{}</text>"#,
+                position.x,
+                position.y,
+                escape(&lts.labels()[label])
+            )?;
+        }
+        writeln!(writer, "</g>")?;
+    }
+
+    // Draw the states on top
+    writeln!(writer, r#"<g stroke="black" fill="white">"#)?;
+    for (index, state_view) in viewer.state_view().iter().enumerate() {
+        let fill = if index == *lts.initial_state_index() {
+            r#" fill="rgb(100,255,100)""#
+        } else {
+            ""
+        };
+
+        writeln!(
+            writer,
+            r#"<circle cx="{}" cy="{}" r="{state_radius}"{fill}/>"#,
+            state_view.position.x, state_view.position.y
+        )?;
+    }
+    writeln!(writer, "</g>")?;
+
+    writeln!(writer, "</g>")?;
+    writeln!(writer, "</svg>")
+}
+
+/// Escapes the characters that have a special meaning in XML text.
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use merc_lts::read_aut;
+
+    use super::*;
+
+    #[test]
+    fn test_write_svg() {
+        let file = include_str!("../../../../examples/lts/abp.aut");
+        let lts = Arc::new(read_aut(file.as_bytes(), vec![]).unwrap());
+
+        let viewer = Viewer::new(lts.clone());
+        let mut settings = SvgSettings {
+            draw_action_labels: true,
+            state_radius: 5.0,
+            label_text_size: 14.0,
+            width: 800,
+            height: 600,
+            zoom_level: 2.0,
+            view_x: 10.0,
+            view_y: -10.0,
+        };
+
+        let mut output = Vec::new();
+        write_svg(&mut output, &viewer, &settings).unwrap();
+        let svg = String::from_utf8(output).unwrap();
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains(r#"translate(400 300) scale(2) translate(10 -10)"#));
+        assert_eq!(
+            svg.matches(&format!(r#"r="{}""#, settings.state_radius)).count(),
+            lts.num_of_states()
+        );
+        assert_eq!(svg.matches("<text").count(), lts.num_of_transitions());
+
+        // The labels are omitted when they should not be drawn.
+        settings.draw_action_labels = false;
+        let mut output = Vec::new();
+        write_svg(&mut output, &viewer, &settings).unwrap();
+        assert!(!String::from_utf8(output).unwrap().contains("<text"));
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("r1(d<1>) & \"x\""), "r1(d&lt;1&gt;) &amp; &quot;x&quot;");
+    }
+}
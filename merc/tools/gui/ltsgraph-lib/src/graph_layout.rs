@@ -4,8 +4,11 @@ use glam::Vec3;
 use log::debug;
 use merc_lts::LTS;
 use rand::Rng;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 
 use merc_lts::LabelledTransitionSystem;
+use merc_lts::StateIndex;
 use merc_unsafety::Edge;
 use merc_unsafety::index_edge;
 
@@ -21,16 +24,31 @@ pub struct GraphLayout {
 pub struct StateLayout {
     pub position: Vec3,
     pub force: Vec3,
+
+    /// A pinned state keeps its position, but still exerts forces on the other states.
+    pub pinned: bool,
 }
 
 impl GraphLayout {
     /// Construct a new layout for the given LTS.
     pub fn new(lts: Arc<LabelledTransitionSystem<String>>) -> GraphLayout {
+        Self::with_rng(lts, &mut rand::rng())
+    }
+
+    /// Construct a new layout for the given LTS, where the initial placement
+    /// of the states is determined by the given seed. Since the simulation
+    /// itself is deterministic, the same seed and settings result in the same
+    /// layout.
+    pub fn with_seed(lts: Arc<LabelledTransitionSystem<String>>, seed: u64) -> GraphLayout {
+        Self::with_rng(lts, &mut StdRng::seed_from_u64(seed))
+    }
+
+    /// Construct a new layout for the given LTS, using the given random number generator for the initial placement.
+    fn with_rng(lts: Arc<LabelledTransitionSystem<String>>, rng: &mut impl Rng) -> GraphLayout {
         // Keep track of state layout information.
         let mut states_simulation = vec![StateLayout::default(); lts.num_of_states()];
 
         // Place the states at a random position within some bound based on the number of states.
-        let mut rng = rand::rng();
         let bound = (lts.num_of_states() as f32).sqrt().ceil();
 
         debug!("Placing states within bound {bound}");
@@ -45,6 +63,11 @@ impl GraphLayout {
         }
     }
 
+    /// Pins the given state to its current position, or releases it when `pinned` is false.
+    pub fn set_pinned(&mut self, state_index: StateIndex, pinned: bool) {
+        self.layout_states[state_index.value()].pinned = pinned;
+    }
+
     /// Update the layout one step using spring forces for transitions and repulsion between states.
     ///
     /// Returns true iff the layout is stable.
@@ -90,9 +113,11 @@ impl GraphLayout {
         let mut displacement = 0.0;
 
         for state_layout in &mut self.layout_states {
-            // Integrate the forces.
-            state_layout.position += state_layout.force * delta;
-            displacement += (state_layout.force * delta).length_squared();
+            // Integrate the forces, pinned states are not moved.
+            if !state_layout.pinned {
+                state_layout.position += state_layout.force * delta;
+                displacement += (state_layout.force * delta).length_squared();
+            }
 
             // Reset the force.
             state_layout.force = Vec3::default();
@@ -138,6 +163,7 @@ fn compute_repulsion_force(p1: &Vec3, p2: &Vec3, repulsion_strength: f32) -> Vec
 mod tests {
     use std::sync::Arc;
 
+    use merc_lts::LTS;
     use merc_lts::read_aut;
 
     use super::GraphLayout;
@@ -156,4 +182,41 @@ mod tests {
         layout.update(5.0, 1.0, 0.01);
         layout.update(5.0, 1.0, 0.01);
     }
+
+    #[test]
+    fn test_graph_layout_pinned() {
+        let file = include_str!("../../../../examples/lts/abp.aut");
+        let lts = Arc::new(read_aut(file.as_bytes(), vec![]).unwrap());
+        let initial_state = lts.initial_state_index();
+
+        let mut layout = GraphLayout::new(lts);
+        layout.set_pinned(initial_state, true);
+        let position = layout.layout_states[initial_state.value()].position;
+
+        layout.update(5.0, 1.0, 0.01);
+        assert_eq!(layout.layout_states[initial_state.value()].position, position);
+
+        // A released state moves again.
+        layout.set_pinned(initial_state, false);
+        layout.update(5.0, 1.0, 0.01);
+        assert_ne!(layout.layout_states[initial_state.value()].position, position);
+    }
+
+    #[test]
+    fn test_graph_layout_with_seed() {
+        let file = include_str!("../../../../examples/lts/abp.aut");
+        let lts = Arc::new(read_aut(file.as_bytes(), vec![]).unwrap());
+
+        let mut left = GraphLayout::with_seed(lts.clone(), 42);
+        let mut right = GraphLayout::with_seed(lts, 42);
+
+        for _ in 0..10 {
+            left.update(5.0, 1.0, 0.01);
+            right.update(5.0, 1.0, 0.01);
+        }
+
+        for (left, right) in left.layout_states.iter().zip(&right.layout_states) {
+            assert_eq!(left.position, right.position);
+        }
+    }
 }
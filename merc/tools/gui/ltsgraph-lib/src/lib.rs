@@ -3,12 +3,14 @@
 //!
 //!
 
+mod export_svg;
 mod graph_layout;
 mod renderer_femtovg;
 mod renderer_skia;
 mod text_cache;
 mod viewer;
 
+pub use export_svg::*;
 pub use graph_layout::GraphLayout;
 pub use renderer_femtovg::*;
 pub use renderer_skia::*;
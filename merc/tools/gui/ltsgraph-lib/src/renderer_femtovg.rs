@@ -74,23 +74,20 @@ impl FemtovgRenderer {
                 let transition_view = &state_view.outgoing[transition_index];
 
                 let label_position = if transition.to != state_index {
-                    // Draw the transition line
+                    // Draw the transition, which is curved when there are parallel transitions
+                    let control = transition_view.control_point(state_view.position, to_state_view.position);
                     let mut path = Path::new();
                     path.move_to(state_view.position.x, state_view.position.y);
-                    path.line_to(to_state_view.position.x, to_state_view.position.y);
+                    path.quad_to(control.x, control.y, to_state_view.position.x, to_state_view.position.y);
                     canvas.stroke_path(&path, &edge_paint);
 
-                    let direction = (state_view.position - to_state_view.position).normalize();
+                    let direction = (control - to_state_view.position).normalize();
                     let _angle = -direction.xy().angle_to(Vec2::new(0.0, -1.0)).to_degrees();
 
                     // Draw the edge handle
-                    let middle = (to_state_view.position + state_view.position) / 2.0;
-                    let handle_x = middle.x + transition_view.handle_offset.x;
-                    let handle_y = middle.y + transition_view.handle_offset.y;
+                    path.circle(control.x, control.y, 1.0);
 
-                    path.circle(handle_x, handle_y, 1.0);
-
-                    middle
+                    transition_view.curve_middle(state_view.position, to_state_view.position)
                 } else {
                     // This is a self loop
                     let middle = (2.0 * state_view.position + transition_view.handle_offset) / 2.0;
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use cosmic_text::Metrics;
@@ -6,6 +7,7 @@ use glam::Vec3Swizzles;
 use merc_lts::LTS;
 use merc_lts::LabelledTransitionSystem;
 use tiny_skia::PixmapMut;
+use tiny_skia::Rect;
 use tiny_skia::Shader;
 use tiny_skia::Stroke;
 use tiny_skia::Transform;
@@ -110,6 +112,7 @@ impl SkiaRenderer {
         // Draw the edges and the arrows on them
         let mut edge_builder = tiny_skia::PathBuilder::new();
         let mut arrow_builder = tiny_skia::PathBuilder::new();
+        let mut label_area = OccupiedArea::default();
 
         for state_index in self.lts.iter_states() {
             let state_view = &viewer.state_view()[state_index];
@@ -122,11 +125,12 @@ impl SkiaRenderer {
                 let transition_view = &state_view.outgoing[transition_index];
 
                 let label_position = if transition.to != state_index {
-                    // Draw the transition
+                    // Draw the transition, which is curved when there are parallel transitions
+                    let control = transition_view.control_point(state_view.position, to_state_view.position);
                     edge_builder.move_to(state_view.position.x, state_view.position.y);
-                    edge_builder.line_to(to_state_view.position.x, to_state_view.position.y);
+                    edge_builder.quad_to(control.x, control.y, to_state_view.position.x, to_state_view.position.y);
 
-                    let direction = (state_view.position - to_state_view.position).normalize();
+                    let direction = (control - to_state_view.position).normalize();
                     let angle = -direction.xy().angle_to(Vec2::new(0.0, -1.0)).to_degrees();
 
                     // Draw the arrow of the transition
@@ -139,14 +143,9 @@ impl SkiaRenderer {
                     };
 
                     // Draw the edge handle
-                    let middle = (to_state_view.position + state_view.position) / 2.0;
-                    edge_builder.push_circle(
-                        middle.x + transition_view.handle_offset.x,
-                        middle.y + transition_view.handle_offset.y,
-                        1.0,
-                    );
+                    edge_builder.push_circle(control.x, control.y, 1.0);
 
-                    middle
+                    transition_view.curve_middle(state_view.position, to_state_view.position)
                 } else {
                     // This is a self loop so draw a circle around the middle of the position and the handle
                     let middle = (2.0 * state_view.position + transition_view.handle_offset) / 2.0;
@@ -161,14 +160,22 @@ impl SkiaRenderer {
                     state_view.position + transition_view.handle_offset
                 };
 
-                // Draw the text label
+                // Draw the text label, unless it overlaps with a label that has already been drawn
                 if draw_actions {
-                    let buffer = &self.labels_cache[transition.label];
-                    self.text_cache.draw(
-                        buffer,
-                        pixmap,
-                        Transform::from_translate(label_position.x, label_position.y).post_concat(view_transform),
-                    );
+                    let (width, height) = self
+                        .text_cache
+                        .measure(&self.lts.labels()[transition.label], label_text_size);
+
+                    if Rect::from_xywh(label_position.x, label_position.y, width, height)
+                        .is_none_or(|rect| label_area.occupy(rect))
+                    {
+                        let buffer = &self.labels_cache[transition.label];
+                        self.text_cache.draw(
+                            buffer,
+                            pixmap,
+                            Transform::from_translate(label_position.x, label_position.y).post_concat(view_transform),
+                        );
+                    }
                 }
             }
         }
@@ -220,6 +227,53 @@ impl SkiaRenderer {
     }
 }
 
+/// Keeps track of the area that is occupied by labels, partitioned into cells
+/// to avoid comparing every pair of labels.
+#[derive(Default)]
+struct OccupiedArea {
+    cells: HashMap<(i32, i32), Vec<Rect>>,
+}
+
+impl OccupiedArea {
+    /// The size of the cells in which the area is partitioned.
+    const CELL_SIZE: f32 = 64.0;
+
+    /// Occupies the given rectangle, unless it overlaps with the occupied area. Returns true iff it was occupied.
+    fn occupy(&mut self, rect: Rect) -> bool {
+        let cell = |value: f32| (value / Self::CELL_SIZE).floor() as i32;
+        let columns = cell(rect.left())..=cell(rect.right());
+        let rows = cell(rect.top())..=cell(rect.bottom());
+
+        for x in columns.clone() {
+            for y in rows.clone() {
+                if self
+                    .cells
+                    .get(&(x, y))
+                    .is_some_and(|occupied| occupied.iter().any(|other| overlaps(other, &rect)))
+                {
+                    return false;
+                }
+            }
+        }
+
+        for x in columns {
+            for y in rows.clone() {
+                self.cells.entry((x, y)).or_default().push(rect);
+            }
+        }
+
+        true
+    }
+}
+
+/// Returns true iff the interiors of the given rectangles overlap.
+fn overlaps(left: &Rect, right: &Rect) -> bool {
+    left.left() < right.right()
+        && right.left() < left.right()
+        && left.top() < right.bottom()
+        && right.top() < left.bottom()
+}
+
 #[cfg(test)]
 mod tests {
     use merc_lts::read_aut;
@@ -228,6 +282,7 @@ mod tests {
     use tiny_skia::PixmapMut;
 
     use super::*;
+    use crate::GraphLayout;
     use crate::viewer::Viewer;
 
     #[test]
@@ -254,4 +309,53 @@ mod tests {
             14.0,
         );
     }
+
+    #[test]
+    fn test_skia_renderer_parallel_edges() {
+        // Two states with two parallel transitions between them
+        let file = "des (0,2,2)\n(0,\"a\",1)\n(0,\"b\",1)\n";
+        let lts = Arc::new(read_aut(file.as_bytes(), vec![]).unwrap());
+
+        let mut layout = GraphLayout::new(lts.clone());
+        layout.layout_states[0].position = glam::Vec3::new(-50.0, 0.0, 0.0);
+        layout.layout_states[1].position = glam::Vec3::new(50.0, 0.0, 0.0);
+
+        let mut viewer = Viewer::new(lts.clone());
+        viewer.update(&layout);
+        let mut renderer = SkiaRenderer::new(lts);
+
+        let mut pixel_buffer = Pixmap::new(200, 200).unwrap();
+        renderer.render(
+            &mut PixmapMut::from_bytes(pixel_buffer.data_mut(), 200, 200).unwrap(),
+            &viewer,
+            false,
+            5.0,
+            0.0,
+            0.0,
+            200,
+            200,
+            1.0,
+            14.0,
+        );
+
+        // Returns true iff some pixel in the given column between the given rows has been drawn.
+        let is_drawn = |x: u32, first: u32, last: u32| {
+            (first..=last).any(|y| pixel_buffer.pixel(x, y).is_some_and(|pixel| pixel.red() < 255))
+        };
+
+        // The straight line between the states is empty, and the transitions are bent to either side
+        assert!(!is_drawn(100, 99, 101));
+        assert!(is_drawn(100, 93, 97));
+        assert!(is_drawn(100, 103, 107));
+    }
+
+    #[test]
+    fn test_occupied_area() {
+        let mut area = OccupiedArea::default();
+
+        assert!(area.occupy(Rect::from_xywh(0.0, 0.0, 100.0, 20.0).unwrap()));
+        assert!(!area.occupy(Rect::from_xywh(90.0, 10.0, 100.0, 20.0).unwrap()));
+        assert!(area.occupy(Rect::from_xywh(0.0, 20.0, 100.0, 20.0).unwrap()));
+        assert!(area.occupy(Rect::from_xywh(-200.0, -200.0, 10.0, 10.0).unwrap()));
+    }
 }
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use cosmic_text::Attrs;
 use cosmic_text::Buffer;
 use cosmic_text::FontSystem;
@@ -15,6 +17,10 @@ pub struct TextCache {
 
     /// A SwashCache stores rasterized glyphs, create one per application
     swash_cache: SwashCache,
+
+    /// The measured sizes of texts, indexed by the bits of the font size and then by the text. The nesting allows
+    /// lookups with a borrowed text, which avoids an allocation per measurement.
+    measurements: HashMap<u32, HashMap<String, (f32, f32)>>,
 }
 
 impl TextCache {
@@ -25,7 +31,34 @@ impl TextCache {
         TextCache {
             font_system,
             swash_cache,
+            measurements: HashMap::new(),
+        }
+    }
+
+    /// Returns the width and height of the bounding box of the given text at the given font size, without drawing it.
+    pub fn measure(&mut self, text: &str, size: f32) -> (f32, f32) {
+        if let Some(measurement) = self
+            .measurements
+            .get(&size.to_bits())
+            .and_then(|measurements| measurements.get(text))
+        {
+            return *measurement;
+        }
+
+        let buffer = self.create_buffer(text, Metrics::new(size, size));
+
+        let mut width: f32 = 0.0;
+        let mut height = 0.0;
+        for run in buffer.layout_runs() {
+            width = width.max(run.line_w);
+            height += run.line_height;
         }
+
+        self.measurements
+            .entry(size.to_bits())
+            .or_default()
+            .insert(text.to_string(), (width, height));
+        (width, height)
     }
 
     /// Font metrics indicate the font size and line height of a buffer
@@ -152,4 +185,24 @@ mod tests {
             Transform::default(),
         );
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_textcache_measure() {
+        let mut cache = TextCache::new();
+
+        let (width, height) = cache.measure("A test label", 14.0);
+        assert_eq!(cache.measure("A test label", 14.0), (width, height));
+        assert_eq!(
+            cache.measurements[&14.0f32.to_bits()].len(),
+            1,
+            "The second measurement should hit the cache"
+        );
+
+        // A larger font size results in a larger bounding box.
+        let (larger_width, larger_height) = cache.measure("A test label", 28.0);
+        assert_eq!(cache.measurements.len(), 2);
+        assert_eq!(cache.measurements[&28.0f32.to_bits()].len(), 1);
+        assert!(larger_width >= width && larger_height > height);
+    }
 }
@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
 use glam::Mat3;
+use glam::Vec2;
 use glam::Vec3;
+use glam::Vec3Swizzles;
 
 use merc_lts::LTS;
 use merc_lts::LabelledTransitionSystem;
@@ -27,8 +29,32 @@ pub struct StateView {
 pub struct TransitionView {
     /// The offset of the handle w.r.t. the 'from' state
     pub handle_offset: Vec3,
+
+    /// The distance by which the edge is bent away from the straight line,
+    /// used to fan out parallel transitions.
+    pub bend: f32,
+}
+
+impl TransitionView {
+    /// Returns the control point of the (quadratic) curve that is drawn for
+    /// this transition between the given positions.
+    pub fn control_point(&self, from: Vec3, to: Vec3) -> Vec3 {
+        let direction = (to - from).normalize_or_zero();
+        let normal = Vec3::new(-direction.y, direction.x, 0.0);
+
+        (from + to) / 2.0 + self.handle_offset + normal * self.bend
+    }
+
+    /// Returns the middle of the curve that is drawn for this transition
+    /// between the given positions, which is where its label is placed.
+    pub fn curve_middle(&self, from: Vec3, to: Vec3) -> Vec3 {
+        (from + 2.0 * self.control_point(from, to) + to) / 4.0
+    }
 }
 
+/// The distance between parallel transitions.
+const PARALLEL_EDGE_SPACING: f32 = 20.0;
+
 impl Viewer {
     /// Creates a new viewer for the given LTS
     pub fn new(lts: Arc<LabelledTransitionSystem<String>>) -> Viewer {
@@ -50,9 +76,6 @@ impl Viewer {
             // Keep track of the current self loop index
             let mut index_selfloop = 0;
 
-            // Keep track of the current transition index
-            let mut index_transition = 0;
-
             for (transition_index, transition) in lts.outgoing_transitions(state_index).enumerate() {
                 let transition_view = &mut state_view.outgoing[transition_index];
 
@@ -71,25 +94,26 @@ impl Viewer {
                     // Determine whether any of the outgoing edges from the reached state point back
                     let has_backtransition = lts
                         .outgoing_transitions(transition.to)
-                        .filter(|transition| transition.to == state_index)
-                        .count()
-                        > 0;
+                        .any(|back| back.to == state_index);
 
-                    // Compute the number of transitions going to the same state
-                    let num_transitions = lts
+                    // Compute the number of transitions going to the same state, and the index of this one among them
+                    let num_parallel = lts
+                        .outgoing_transitions(state_index)
+                        .filter(|parallel| parallel.to == transition.to)
+                        .count();
+                    let index_parallel = lts
                         .outgoing_transitions(state_index)
-                        .filter(|transition| transition.to == state_index)
+                        .take(transition_index)
+                        .filter(|parallel| parallel.to == transition.to)
                         .count();
 
-                    if has_backtransition {
-                        // Offset the outgoing transitions towards that state to the right
-                        transition_view.handle_offset =
-                            Vec3::new(0.0, index_transition as f32 / num_transitions as f32, 0.0);
+                    transition_view.bend = if has_backtransition {
+                        // Bend the transitions to the right, such that they do not overlap with the ones pointing back
+                        (index_parallel + 1) as f32 * PARALLEL_EDGE_SPACING
                     } else {
-                        // Balance transitions around the midpoint
-                    }
-
-                    index_transition += 1;
+                        // Balance transitions around the straight line
+                        (index_parallel as f32 - (num_parallel - 1) as f32 / 2.0) * PARALLEL_EDGE_SPACING
+                    };
                 }
             }
         }
@@ -109,6 +133,44 @@ impl Viewer {
         self.view_states.iter().map(|x| x.position).sum::<Vec3>() / self.view_states.len() as f32
     }
 
+    /// Returns the state under the given screen coordinate, for the view
+    /// transform used by the renderers, or None if there is no such state.
+    ///
+    /// # Details
+    ///
+    /// Returns the closest state within `state_radius` of the coordinate,
+    /// where states that are drawn later (and thus on top) are preferred.
+    #[allow(clippy::too_many_arguments)]
+    pub fn pick(
+        &self,
+        x: f32,
+        y: f32,
+        zoom_level: f32,
+        view_x: f32,
+        view_y: f32,
+        screen_x: u32,
+        screen_y: u32,
+        state_radius: f32,
+    ) -> Option<StateIndex> {
+        // Apply the inverse of the view transform to obtain the position in the graph
+        let position = Vec2::new(
+            (x - screen_x as f32 / 2.0) / zoom_level - view_x,
+            (y - screen_y as f32 / 2.0) / zoom_level - view_y,
+        );
+
+        let mut result = None;
+        let mut closest = state_radius;
+        for (index, state_view) in self.view_states.iter().enumerate() {
+            let distance = state_view.position.xy().distance(position);
+            if distance <= closest {
+                result = Some(StateIndex::new(index));
+                closest = distance;
+            }
+        }
+
+        result
+    }
+
     /// Gets a reference to the state views for testing and rendering
     pub fn state_view(&self) -> &[StateView] {
         &self.view_states
@@ -119,3 +181,39 @@ impl Viewer {
         &self.lts
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use merc_lts::read_aut;
+
+    use super::*;
+
+    #[test]
+    fn test_viewer_pick() {
+        let file = include_str!("../../../../examples/lts/abp.aut");
+        let lts = Arc::new(read_aut(file.as_bytes(), vec![]).unwrap());
+
+        let mut viewer = Viewer::new(lts);
+        for (index, state_view) in viewer.view_states.iter_mut().enumerate() {
+            state_view.position = Vec3::new(index as f32 * 20.0, 0.0, 0.0);
+        }
+
+        // The state at (40, 0) is shown at (800 / 2 + (40 + 10) * 2, 600 / 2 + (0 - 5) * 2)
+        assert_eq!(
+            viewer.pick(500.0, 290.0, 2.0, 10.0, -5.0, 800, 600, 5.0),
+            Some(StateIndex::new(2))
+        );
+        assert_eq!(
+            viewer.pick(506.0, 290.0, 2.0, 10.0, -5.0, 800, 600, 5.0),
+            Some(StateIndex::new(2))
+        );
+        assert_eq!(viewer.pick(520.0, 290.0, 2.0, 10.0, -5.0, 800, 600, 5.0), None);
+
+        // Overlapping states prefer the one that is drawn on top.
+        viewer.view_states[3].position = viewer.view_states[2].position;
+        assert_eq!(
+            viewer.pick(500.0, 290.0, 2.0, 10.0, -5.0, 800, 600, 5.0),
+            Some(StateIndex::new(3))
+        );
+    }
+}
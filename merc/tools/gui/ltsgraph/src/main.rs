@@ -35,9 +35,11 @@ use merc_lts::read_explicit_lts;
 use merc_lts::LabelledTransitionSystem;
 use merc_lts::LtsFormat;
 use merc_lts::LTS;
+use merc_ltsgraph_lib::export_svg;
 use merc_ltsgraph_lib::FemtovgRenderer;
 use merc_ltsgraph_lib::GraphLayout;
 use merc_ltsgraph_lib::SkiaRenderer;
+use merc_ltsgraph_lib::SvgSettings;
 use merc_ltsgraph_lib::Viewer;
 use merc_tools::console;
 use merc_tools::verbosity::VerbosityFlag;
@@ -599,6 +601,48 @@ async fn main() -> Result<ExitCode, MercError> {
         });
     }
 
+    // Open the file dialog and export the current view to SVG.
+    {
+        let state = state.clone();
+        let settings = settings.clone();
+        app.on_export_svg_filedialog(move || {
+            let state = state.clone();
+            let settings = settings.clone();
+
+            invoke_from_event_loop(move || {
+                slint::spawn_local(async move {
+                    if let Some(handle) = rfd::AsyncFileDialog::new()
+                        .add_filter("", &["svg"])
+                        .set_file_name("lts.svg")
+                        .save_file()
+                        .await
+                    {
+                        let settings = settings.lock().unwrap().clone();
+                        let svg_settings = SvgSettings {
+                            draw_action_labels: settings.draw_action_labels,
+                            state_radius: settings.state_radius,
+                            label_text_size: settings.label_text_size,
+                            width: settings.width,
+                            height: settings.height,
+                            zoom_level: settings.zoom_level,
+                            view_x: settings.view_x,
+                            view_y: settings.view_y,
+                        };
+
+                        if let Some(viewer) = state.viewer.lock().unwrap().as_ref() {
+                            debug!("Exporting view to {}", handle.path().to_string_lossy());
+                            if let Err(x) = export_svg(viewer, &svg_settings, handle.path()) {
+                                show_error_dialog("Failed to export SVG!", &format!("{x}"));
+                            }
+                        }
+                    }
+                })
+                .unwrap();
+            })
+            .unwrap();
+        });
+    }
+
     // Focus on the graph
     {
         let settings = settings.clone();
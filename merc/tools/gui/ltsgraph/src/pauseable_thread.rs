@@ -84,6 +84,17 @@ impl PauseableThread {
         self.shared.cond_var.notify_one();
     }
 
+    /// Returns true iff the thread has not been stopped and is not paused, either
+    /// by [Self::pause] or by the loop function.
+    pub fn is_running(&self) -> bool {
+        self.shared.running.load(Ordering::Relaxed) && !self.is_paused()
+    }
+
+    /// Returns true iff the thread is paused.
+    pub fn is_paused(&self) -> bool {
+        *self.shared.paused.lock().expect("No lock poisoning allowed")
+    }
+
     /// Joins the thread and returns its result
     pub fn join(&mut self) -> Result<(), MercError> {
         if let Some(handle) = self.handle.take() {
@@ -117,6 +128,9 @@ impl Drop for PauseableThread {
 
 #[cfg(test)]
 mod tests {
+    use std::thread::sleep;
+    use std::time::Duration;
+
     use super::*;
 
     #[test]
@@ -133,4 +147,36 @@ mod tests {
 
         thread.stop();
     }
+
+    #[test]
+    fn test_pausablethread_state() {
+        let thread = PauseableThread::new(
+            "test",
+            || Ok(0),
+            move |iteration| {
+                // Pause itself after a number of iterations.
+                *iteration += 1;
+                sleep(Duration::from_millis(1));
+                Ok(*iteration % 100 != 0)
+            },
+        )
+        .unwrap();
+
+        // Wait until the loop function has paused the thread.
+        while !thread.is_paused() {
+            sleep(Duration::from_millis(1));
+        }
+        assert!(!thread.is_running());
+
+        thread.resume();
+        assert!(thread.is_running());
+        assert!(!thread.is_paused());
+
+        thread.pause();
+        assert!(!thread.is_running());
+        assert!(thread.is_paused());
+
+        thread.stop();
+        assert!(!thread.is_running());
+    }
 }
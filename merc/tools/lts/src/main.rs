@@ -1,4 +1,5 @@
 use std::fs::File;
+use std::io::Write;
 use std::io::stdout;
 use std::path::Path;
 use std::path::PathBuf;
@@ -6,21 +7,31 @@ use std::process::ExitCode;
 
 use clap::Parser;
 use clap::Subcommand;
+use duct::cmd;
+use itertools::Itertools;
 use log::info;
+use regex::Regex;
 
 use merc_io::LargeFormatter;
 use merc_lts::GenericLts;
 use merc_lts::LTS;
+use merc_lts::LtsDot;
 use merc_lts::LtsFormat;
+use merc_lts::StateIndex;
 use merc_lts::apply_lts;
 use merc_lts::apply_lts_pair;
 use merc_lts::guess_lts_format_from_extension;
 use merc_lts::read_explicit_lts;
+use merc_lts::shortest_trace;
 use merc_lts::write_aut;
 use merc_lts::write_bcg;
+use merc_lts::write_graphml;
 use merc_preorder::RefinementType;
 use merc_preorder::refines;
+use merc_preorder::refines_with_witness;
 use merc_reduction::Equivalence;
+use merc_reduction::compare_lts_with_witness;
+use merc_reduction::detect;
 use merc_reduction::reduce_lts;
 use merc_tools::Version;
 use merc_tools::VersionFlag;
@@ -56,6 +67,9 @@ enum Commands {
     Compare(CompareArgs),
     Refines(RefinesArgs),
     Convert(ConvertArgs),
+    Display(DisplayArgs),
+    Detect(DetectArgs),
+    Trace(TraceArgs),
 }
 
 #[derive(clap::Args, Debug)]
@@ -108,6 +122,12 @@ struct CompareArgs {
         value_delimiter = ','
     )]
     tau: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        help = "Prints a trace that distinguishes the LTSs when they are not equivalent, if their traces differ"
+    )]
+    counterexample: bool,
 }
 
 #[derive(clap::Args, Debug)]
@@ -132,6 +152,78 @@ struct ConvertArgs {
         value_delimiter = ','
     )]
     tau: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        help = "Renames actions by a rule 'pattern/replacement', where the pattern is a regular expression and the replacement can refer to capture groups, e.g., $1. Can be given multiple times, the first matching rule is applied"
+    )]
+    rename: Vec<String>,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(about = "Writes the given LTS in the Graphviz DOT format")]
+struct DisplayArgs {
+    /// Specify the input LTS.
+    filename: PathBuf,
+
+    /// The .dot file output filename
+    output: PathBuf,
+
+    #[arg(long, help = "Explicitly specify the LTS file format")]
+    filetype: Option<LtsFormat>,
+
+    #[arg(long, help = "Only write the first given number of states")]
+    max_states: Option<usize>,
+
+    #[arg(
+        short,
+        long,
+        help = "List of actions that should be considered tau actions",
+        value_delimiter = ','
+    )]
+    tau: Option<Vec<String>>,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(about = "Detects the deadlock states and the states on a tau-cycle (divergences) of the given LTS")]
+struct DetectArgs {
+    /// Specify the input LTS.
+    filename: PathBuf,
+
+    #[arg(long, help = "Explicitly specify the LTS file format")]
+    filetype: Option<LtsFormat>,
+
+    #[arg(long, help = "Also print the indices of the detected states")]
+    list: bool,
+
+    #[arg(
+        short,
+        long,
+        help = "List of actions that should be considered tau actions",
+        value_delimiter = ','
+    )]
+    tau: Option<Vec<String>>,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(about = "Prints a shortest trace from the initial state to the given state of the LTS")]
+struct TraceArgs {
+    /// Specify the input LTS.
+    filename: PathBuf,
+
+    /// The index of the target state.
+    state: usize,
+
+    #[arg(long, help = "Explicitly specify the LTS file format")]
+    filetype: Option<LtsFormat>,
+
+    #[arg(
+        short,
+        long,
+        help = "List of actions that should be considered tau actions",
+        value_delimiter = ','
+    )]
+    tau: Option<Vec<String>>,
 }
 
 #[derive(clap::Args, Debug)]
@@ -150,6 +242,12 @@ struct RefinesArgs {
 
     #[arg(long, help = "Explicitly specify the LTS file format")]
     filetype: Option<LtsFormat>,
+
+    #[arg(
+        long,
+        help = "Prints a trace and refusal set of the implementation that is not allowed by the specification"
+    )]
+    counterexample: bool,
 }
 
 fn main() -> Result<ExitCode, MercError> {
@@ -184,6 +282,15 @@ fn main() -> Result<ExitCode, MercError> {
             Commands::Convert(args) => {
                 handle_convert(args, &mut timing)?;
             }
+            Commands::Display(args) => {
+                handle_display(args, &mut timing)?;
+            }
+            Commands::Detect(args) => {
+                handle_detect(args, &mut timing)?;
+            }
+            Commands::Trace(args) => {
+                handle_trace(args, &mut timing)?;
+            }
         }
     }
 
@@ -271,6 +378,31 @@ fn handle_refinement(args: &RefinesArgs, timing: &mut Timing) -> Result<(), Merc
         LargeFormatter(spec_lts.num_of_transitions())
     );
 
+    if args.counterexample {
+        // The labels are converted to strings, since the label types differ per format.
+        let witness = apply_lts_pair!(impl_lts, spec_lts, timing, |left, right, timing| {
+            refines_with_witness(left, right, args.refinement, timing).map(|(trace, refusal)| {
+                (
+                    trace.iter().map(|label| label.to_string()).collect::<Vec<_>>(),
+                    refusal.iter().map(|label| label.to_string()).collect::<Vec<_>>(),
+                )
+            })
+        });
+
+        if let Some((trace, refusal)) = witness {
+            println!("false");
+            println!("Counterexample trace: {}", trace.iter().format(" "));
+            println!(
+                "Refused by the implementation after the trace: {{{}}}",
+                refusal.iter().format(", ")
+            );
+        } else {
+            println!("true");
+        }
+
+        return Ok(());
+    }
+
     let refines = apply_lts_pair!(impl_lts, spec_lts, timing, |left, right, timing| {
         refines(left, right, args.refinement, timing)
     });
@@ -314,6 +446,28 @@ fn handle_compare(args: &CompareArgs, timing: &mut Timing) -> Result<(), MercErr
         LargeFormatter(right_lts.num_of_transitions())
     );
 
+    if args.counterexample {
+        // The labels are converted to strings, since the label types differ per format.
+        let witness = apply_lts_pair!(left_lts, right_lts, timing, |left, right, timing| {
+            compare_lts_with_witness(args.equivalence, left, right, timing)
+                .map(|witness| witness.map(|trace| trace.iter().map(|label| label.to_string()).collect::<Vec<_>>()))
+        })?;
+
+        match witness {
+            None => println!("true"),
+            Some(trace) => {
+                println!("false");
+                if trace.is_empty() {
+                    println!("There is no distinguishing trace, since the LTSs have the same traces.");
+                } else {
+                    println!("Distinguishing trace: {}", trace.iter().format(" "));
+                }
+            }
+        }
+
+        return Ok(());
+    }
+
     let equivalent = apply_lts_pair!(left_lts, right_lts, timing, |left, right, timing| {
         merc_reduction::compare_lts(args.equivalence, left, right, timing)
     });
@@ -333,6 +487,14 @@ fn handle_convert(args: &ConvertArgs, timing: &mut Timing) -> Result<(), MercErr
         guess_lts_format_from_extension(&args.filename, args.input_filetype).ok_or("Unknown LTS file format.")?;
     let input_lts = read_explicit_lts(&args.filename, format, args.tau.clone().unwrap_or_default(), timing)?;
 
+    // Renaming yields string labels, so the result is treated as an Aldebaran LTS.
+    let input_lts = if args.rename.is_empty() {
+        input_lts
+    } else {
+        let rules = parse_rename_rules(&args.rename)?;
+        GenericLts::Aut(apply_lts!(input_lts, &rules, |lts, rules| lts.relabel_regex(rules)))
+    };
+
     let output_format = if let Some(output) = &args.output {
         guess_lts_format_from_extension(output, args.output_filetype).ok_or("Unknown LTS file format.")?
     } else if let Some(format) = args.output_filetype {
@@ -345,13 +507,28 @@ fn handle_convert(args: &ConvertArgs, timing: &mut Timing) -> Result<(), MercErr
         GenericLts::Aut(lts) => match output_format {
             LtsFormat::Bcg => {
                 if let Some(path) = &args.output {
-                    write_bcg(&lts, path)?;
+                    write_bcg(&lts, path, |label| label.into())?;
                 } else {
                     return Err("Output path must be specified when writing BCG files.".into());
                 }
             }
             LtsFormat::Aut => {
-                return Err("Conversion from AUT to AUT is not useful.".into());
+                if args.rename.is_empty() {
+                    return Err("Conversion from AUT to AUT is not useful.".into());
+                }
+
+                if let Some(path) = &args.output {
+                    write_aut(&mut File::create(path)?, &lts)?;
+                } else {
+                    write_aut(&mut stdout(), &lts)?;
+                }
+            }
+            LtsFormat::GraphML => {
+                if let Some(path) = &args.output {
+                    write_graphml(&mut File::create(path)?, &lts)?;
+                } else {
+                    write_graphml(&mut stdout(), &lts)?;
+                }
             }
             _ => {
                 return Err(format!("Conversion to {output_format:?}LTS format is not yet implemented.").into());
@@ -367,7 +544,7 @@ fn handle_convert(args: &ConvertArgs, timing: &mut Timing) -> Result<(), MercErr
             }
             LtsFormat::Bcg => {
                 if let Some(path) = &args.output {
-                    write_bcg(&lts.relabel(|label| label.to_string()), path)?;
+                    write_bcg(&lts, path, |label| label.to_string().into())?;
                 } else {
                     return Err("Output path must be specified when writing BCG files.".into());
                 }
@@ -375,6 +552,13 @@ fn handle_convert(args: &ConvertArgs, timing: &mut Timing) -> Result<(), MercErr
             LtsFormat::Lts => {
                 return Err("Conversion from LTS to LTS is not useful.".into());
             }
+            LtsFormat::GraphML => {
+                if let Some(path) = &args.output {
+                    write_graphml(&mut File::create(path)?, &lts)?;
+                } else {
+                    write_graphml(&mut stdout(), &lts)?;
+                }
+            }
         },
         GenericLts::Bcg(lts) => match output_format {
             LtsFormat::Aut => {
@@ -392,3 +576,109 @@ fn handle_convert(args: &ConvertArgs, timing: &mut Timing) -> Result<(), MercErr
 
     Ok(())
 }
+
+/// Parses the rename rules of the shape 'pattern/replacement', split at the first '/'.
+fn parse_rename_rules(rules: &[String]) -> Result<Vec<(Regex, String)>, MercError> {
+    rules
+        .iter()
+        .map(|rule| {
+            let (pattern, replacement) = rule
+                .split_once('/')
+                .ok_or_else(|| format!("Invalid rename rule '{rule}', expected 'pattern/replacement'."))?;
+            let regex = Regex::new(pattern)
+                .map_err(|err| format!("Invalid regular expression '{pattern}' in rename rule '{rule}': {err}"))?;
+
+            Ok((regex, replacement.to_string()))
+        })
+        .collect()
+}
+
+/// Writes a Graphviz `.dot` representation of the given LTS to `output`. If the
+/// `dot` tool is available, also generates a PDF (`output.pdf`).
+fn handle_display(args: &DisplayArgs, timing: &mut Timing) -> Result<(), MercError> {
+    let format = guess_lts_format_from_extension(&args.filename, args.filetype).ok_or("Unknown LTS file format.")?;
+    let lts = read_explicit_lts(&args.filename, format, args.tau.clone().unwrap_or_default(), timing)?;
+
+    apply_lts!(lts, (), |lts, _| -> Result<(), MercError> {
+        let mut dot = LtsDot::new(&lts);
+        if let Some(max_states) = args.max_states {
+            dot = dot.with_max_states(max_states);
+        }
+
+        let mut output_file = File::create(&args.output)?;
+        write!(&mut output_file, "{}", dot)?;
+        Ok(())
+    })?;
+
+    if let Ok(dot_path) = which::which("dot") {
+        info!("Generating PDF using dot...");
+        cmd!(dot_path, "-Tpdf", &args.output, "-O").run()?;
+    }
+
+    Ok(())
+}
+
+/// Prints the number of deadlock and divergent states of the given LTS, and
+/// optionally their indices.
+fn handle_detect(args: &DetectArgs, timing: &mut Timing) -> Result<(), MercError> {
+    let format = guess_lts_format_from_extension(&args.filename, args.filetype).ok_or("Unknown LTS file format.")?;
+    let lts = read_explicit_lts(&args.filename, format, args.tau.clone().unwrap_or_default(), timing)?;
+
+    let mut time_detect = timing.start("detect");
+    let result = apply_lts!(lts, (), |lts, _| detect(&lts));
+    time_detect.finish();
+
+    println!("Found {} deadlock states.", LargeFormatter(result.deadlocks.len()));
+    if args.list {
+        println!("  {}", result.deadlocks.iter().format(", "));
+    }
+
+    println!("Found {} divergent states.", LargeFormatter(result.divergent.len()));
+    if args.list {
+        println!("  {}", result.divergent.iter().format(", "));
+    }
+
+    Ok(())
+}
+
+/// Prints a shortest trace from the initial state to the given state, with the
+/// states that are visited along the way.
+fn handle_trace(args: &TraceArgs, timing: &mut Timing) -> Result<(), MercError> {
+    let format = guess_lts_format_from_extension(&args.filename, args.filetype).ok_or("Unknown LTS file format.")?;
+    let lts = read_explicit_lts(&args.filename, format, args.tau.clone().unwrap_or_default(), timing)?;
+
+    if args.state >= lts.num_of_states() {
+        return Err(format!(
+            "State {} does not exist, the LTS has {} states.",
+            args.state,
+            lts.num_of_states()
+        )
+        .into());
+    }
+
+    let mut time_trace = timing.start("trace");
+    let trace = apply_lts!(lts, (), |lts, _| {
+        shortest_trace(&lts, StateIndex::new(args.state)).map(|trace| {
+            (
+                lts.initial_state_index(),
+                trace
+                    .into_iter()
+                    .map(|(label, to)| (lts.labels()[label].to_string(), to))
+                    .collect::<Vec<_>>(),
+            )
+        })
+    });
+    time_trace.finish();
+
+    if let Some((initial_state, trace)) = trace {
+        println!("Found a trace of length {} to state {}:", trace.len(), args.state);
+        println!("  {initial_state}");
+        for (label, to) in trace {
+            println!("  --{label}--> {to}");
+        }
+    } else {
+        println!("State {} is not reachable from the initial state.", args.state);
+    }
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use mcrl2_macros::mcrl2_derive_terms;
 use mcrl2_sys::data::ffi::assignment_pair;
 use mcrl2_sys::data::ffi::mcrl2_data_expression_is_abstraction;
@@ -363,6 +365,59 @@ pub fn substitute_variables(data_expression: &DataExpressionRef, sigma: Vec<(Dat
     )))
 }
 
+/// A substitution that replaces data variables by data expressions, which can
+/// be applied to many expressions without rebuilding the underlying mapping.
+pub struct Substitution {
+    mapping: HashMap<DataVariable, DataExpression>,
+
+    /// The assignments passed to the mCRL2 toolset, which point to the terms
+    /// that are kept alive by `mapping`.
+    sigma: Vec<assignment_pair>,
+}
+
+impl Substitution {
+    /// Creates a new substitution from the given mapping of variables to expressions.
+    pub fn new(mapping: HashMap<DataVariable, DataExpression>) -> Self {
+        let sigma = mapping
+            .iter()
+            .map(|(lhs, rhs)| assignment_pair {
+                lhs: lhs.address(),
+                rhs: rhs.address(),
+            })
+            .collect();
+
+        Self { mapping, sigma }
+    }
+
+    /// Applies the substitution to the given data expression.
+    pub fn apply(&self, data_expression: &DataExpressionRef<'_>) -> DataExpression {
+        DataExpression::new(ATerm::from_unique_ptr(mcrl2_data_expression_replace_variables(
+            data_expression.get(),
+            &self.sigma,
+        )))
+    }
+
+    /// Returns the expression that the given variable is replaced by, if any.
+    pub fn get(&self, variable: &DataVariable) -> Option<&DataExpression> {
+        self.mapping.get(variable)
+    }
+
+    /// Returns the number of variables that are replaced.
+    pub fn len(&self) -> usize {
+        self.mapping.len()
+    }
+
+    /// Returns true iff the substitution does not replace any variable.
+    pub fn is_empty(&self) -> bool {
+        self.mapping.is_empty()
+    }
+}
+
+impl FromIterator<(DataVariable, DataExpression)> for Substitution {
+    fn from_iter<T: IntoIterator<Item = (DataVariable, DataExpression)>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
 
 // Allowed conversions     
 impl From<DataVariable> for DataExpression {
@@ -534,4 +589,27 @@ impl<'a> From<DataExpressionRef<'a>> for DataUntypedIdentifierRef<'a> {
     fn from(var: DataExpressionRef<'a>) -> Self {
         Self::new(var.into())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substitution_apply() {
+        let x = DataVariable::new(ATerm::from_string("DataVarId(x,SortId(Bool))").unwrap());
+        let y = DataVariable::new(ATerm::from_string("DataVarId(y,SortId(Bool))").unwrap());
+        let z = DataVariable::new(ATerm::from_string("DataVarId(z,SortId(Bool))").unwrap());
+
+        let sigma: Substitution = [(x.clone(), DataExpression::from(y.clone()))].into_iter().collect();
+        assert_eq!(sigma.len(), 1);
+        assert_eq!(sigma.get(&x), Some(&DataExpression::from(y.clone())));
+        assert_eq!(sigma.get(&z), None);
+
+        // The substituted variable is replaced, and all other variables remain unchanged.
+        let x: DataExpression = x.into();
+        let z: DataExpression = z.into();
+        assert_eq!(sigma.apply(&x.copy()), DataExpression::from(y));
+        assert_eq!(sigma.apply(&z.copy()), z);
+    }
+}
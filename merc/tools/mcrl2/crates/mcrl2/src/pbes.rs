@@ -349,6 +349,44 @@ impl SrfPbes {
     pub fn equations(&self) -> &Vec<SrfEquation> {
         &self.equations
     }
+
+    /// Returns the number of equations, summands and parameters of the SRF pbes.
+    pub fn summary(&self) -> SrfPbesSummary {
+        SrfPbesSummary {
+            num_of_equations: self.equations.len(),
+            num_of_summands: self.equations.iter().map(|equation| equation.summands().len()).sum(),
+            num_of_parameters: self
+                .equations
+                .iter()
+                .map(|equation| equation.variable().parameters().len())
+                .max()
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// The size of an [SrfPbes], see [SrfPbes::summary].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SrfPbesSummary {
+    /// The number of equations.
+    pub num_of_equations: usize,
+
+    /// The total number of summands over all equations.
+    pub num_of_summands: usize,
+
+    /// The largest number of parameters of an equation, which after unifying
+    /// the parameters is the number of parameters of every equation.
+    pub num_of_parameters: usize,
+}
+
+impl fmt::Display for SrfPbesSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} equations, {} summands and {} parameters",
+            self.num_of_equations, self.num_of_summands, self.num_of_parameters
+        )
+    }
 }
 
 /// mcrl2::pbes_system::srf_equation
@@ -1,9 +1,13 @@
+use std::fs::File;
+use std::io::Write;
 use std::process::ExitCode;
+use std::time::Duration;
 
 use clap::Parser;
 use clap::Subcommand;
 use log::debug;
 use log::info;
+use log::warn;
 
 use mcrl2::Pbes;
 use mcrl2::set_reporting_level;
@@ -15,6 +19,7 @@ use merc_utilities::MercError;
 use merc_utilities::Timing;
 
 use crate::permutation::Permutation;
+use crate::permutation::generating_set;
 use crate::symmetry::SymmetryAlgorithm;
 
 mod clone_iterator;
@@ -67,6 +72,10 @@ struct SymmetryArgs {
     #[arg(long, default_value_t = false)]
     all_symmetries: bool,
 
+    /// Stop searching for symmetries after the given number of seconds.
+    #[arg(long)]
+    budget: Option<u64>,
+
     /// Partition data parameters into their sorts before considering their permutation groups.
     #[arg(long, default_value_t = false)]
     partition_data_sorts: bool,
@@ -75,6 +84,18 @@ struct SymmetryArgs {
     #[arg(long, default_value_t = false)]
     partition_data_updates: bool,
 
+    /// Partition data parameters whose names only differ in a trailing number, e.g., x_1 and x_2.
+    #[arg(long, default_value_t = false)]
+    partition_data_names: bool,
+
+    /// Write all found symmetries in the mapping notation to the given file, one per line.
+    #[arg(long)]
+    output: Option<String>,
+
+    /// Only write a generating set of the found symmetries to the output file.
+    #[arg(long, default_value_t = false, requires = "output")]
+    generators_only: bool,
+
     /// Print the symmetry in the mapping notation instead of the cycle notation.
     #[arg(long, default_value_t = false)]
     mapping_notation: bool,
@@ -112,11 +133,7 @@ fn main() -> Result<ExitCode, MercError> {
 
         let algorithm = SymmetryAlgorithm::new(&pbes, args.print_srf)?;
         if let Some(permutation) = &args.permutation {
-            let pi = if permutation.trim_start().starts_with("[") {
-                Permutation::from_mapping_notation(permutation)?
-            } else {
-                Permutation::from_cycle_notation(permutation)?
-            };
+            let pi = Permutation::parse(permutation)?;
 
             if let Err(x) = algorithm.is_valid_permutation(&pi) {
                 info!("The given permutation is not valid: {x}");
@@ -130,7 +147,13 @@ fn main() -> Result<ExitCode, MercError> {
                 println!("false");
             }
         } else {
-            for candidate in algorithm.candidates(args.partition_data_sorts, args.partition_data_updates) {
+            let mut symmetries: Vec<Permutation> = Vec::new();
+            for candidate in algorithm.candidates(
+                args.partition_data_sorts,
+                args.partition_data_updates,
+                args.partition_data_names,
+                args.budget.map(Duration::from_secs),
+            ) {
                 debug!("Found candidate: {}", candidate);
 
                 if candidate.is_identity() {
@@ -145,6 +168,10 @@ fn main() -> Result<ExitCode, MercError> {
                         info!("Found symmetry: {}", candidate);
                     }
 
+                    if !symmetries.contains(&candidate) {
+                        symmetries.push(candidate);
+                    }
+
                     if !args.all_symmetries {
                         // Only search for the first symmetry
                         info!("Stopping search after first non-trivial symmetry.");
@@ -152,6 +179,26 @@ fn main() -> Result<ExitCode, MercError> {
                     }
                 }
             }
+
+            if algorithm.budget_exhausted() {
+                warn!(
+                    "Budget exhausted after checking {} candidates, found {} symmetries so far.",
+                    algorithm.num_of_checked_candidates(),
+                    symmetries.len()
+                );
+            }
+
+            if let Some(output) = &args.output {
+                if args.generators_only {
+                    symmetries = generating_set(&symmetries);
+                }
+
+                let mut output_file = File::create(output)?;
+                for symmetry in &symmetries {
+                    writeln!(output_file, "{:?}", symmetry)?;
+                }
+                info!("Wrote {} symmetries to {}", symmetries.len(), output);
+            }
         }
     }
 
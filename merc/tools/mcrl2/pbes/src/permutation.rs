@@ -1,256 +1,9 @@
 /// Authors: Menno Bartels and Maurice Laveaux
-use itertools::Itertools;
 use std::collections::HashSet;
-use std::fmt;
-
-use merc_utilities::MercError;
-
-#[derive(Clone, PartialEq, Eq)]
-pub struct Permutation {
-    /// We represent a permutation as an explicit list of (domain -> image) pairs,
-    /// sorted by domain.
-    mapping: Vec<(usize, usize)>,
-}
-
-impl Permutation {
-    /// Create a permutation from a given mapping of (domain -> image) pairs. Internally
-    /// sorts the mapping by domain for a unique representation. The input must be
-    /// a valid permutation (so a bijection).
-    pub fn from_mapping(mut mapping: Vec<(usize, usize)>) -> Self {
-        debug_assert!(
-            is_valid_permutation(&mapping),
-            "Input mapping is not a valid permutation: {:?}",
-            mapping
-        );
-
-        // Sort by domain for deterministic representation.
-        mapping.sort_unstable_by_key(|(d, _)| *d);
-        debug_assert!(mapping.iter().is_sorted(), "Mapping should be sorted by domain.");
-        debug_assert!(
-            mapping.iter().all(|(from, to)| from != to),
-            "Mapping should not contain identity mappings."
-        );
-        debug_assert!(
-            mapping.iter().duplicates().count() == 0,
-            "Mapping should not contain duplicate domain entries."
-        );
-
-        Permutation { mapping }
-    }
-
-    /// Parse a permutation from a string input of the form "[0->2, 1->0, 2->1]".
-    pub fn from_mapping_notation(line: &str) -> Result<Self, MercError> {
-        // Remove the surrounding brackets if present.
-        let trimmed_input = line.trim();
-        let input_no_brackets =
-            if !trimmed_input.is_empty() && trimmed_input.starts_with('[') && trimmed_input.ends_with(']') {
-                &trimmed_input[1..trimmed_input.len() - 1]
-            } else {
-                return Err("Permutation must be enclosed in brackets []".into());
-            };
-
-        // Parse all the comma-separated tokens into (from, to) pairs.
-        let mut pairs: Vec<(usize, usize)> = Vec::new();
-        for token in input_no_brackets.split(',') {
-            let token = token.trim();
-            if token.is_empty() {
-                continue;
-            }
-
-            let arrow_pos = token
-                .find("->")
-                .ok_or_else(|| MercError::from(format!("Invalid permutation format: {}", token)))?;
-
-            let from_str = token[..arrow_pos].trim();
-            let to_str = token[arrow_pos + 2..].trim();
-
-            let from: usize = from_str
-                .parse()
-                .map_err(|_| MercError::from(format!("Invalid number: {}", from_str)))?;
-            let to: usize = to_str
-                .parse()
-                .map_err(|_| MercError::from(format!("Invalid number: {}", to_str)))?;
-
-            if pairs.iter().any(|(f, _)| *f == from) {
-                return Err(MercError::from(format!(
-                    "Invalid permutation: multiple mappings for {}",
-                    from
-                )));
-            }
-
-            pairs.push((from, to));
-        }
-
-        if !is_valid_permutation(&pairs) {
-            return Err(MercError::from("Input mapping is not a valid permutation."));
-        }
-
-        Ok(Permutation::from_mapping(pairs))
-    }
-
-    /// Parse a permutation in cycle notation, e.g., (0 2 1)(3 4).
-    pub fn from_cycle_notation(cycle_notation: &str) -> Result<Self, MercError> {
-        let mut mapping: Vec<(usize, usize)> = Vec::new();
-
-        // Split the input into cycles by finding all '(...)' groups
-        for cycle_str in cycle_notation.split('(').skip(1) {
-            // Find the closing parenthesis
-            let cycle_content = cycle_str
-                .split(')')
-                .next()
-                .ok_or_else(|| MercError::from("Invalid cycle notation: missing closing ')'"))?;
-
-            // Skip empty cycles
-            if cycle_content.trim().is_empty() {
-                continue;
-            }
-
-            // Parse all numbers in this cycle
-            let cycle_elements: Result<Vec<usize>, MercError> = cycle_content
-                .split_whitespace()
-                .map(|num_str| {
-                    num_str
-                        .parse::<usize>()
-                        .map_err(|_| MercError::from(format!("Invalid number in cycle notation: {}", num_str)))
-                })
-                .collect();
-
-            let cycle_elements = cycle_elements?;
-
-            // Create mappings for the current cycle (each element maps to the next)
-            let len = cycle_elements.len();
-            for i in 0..len {
-                let from = cycle_elements[i];
-                let to = cycle_elements[(i + 1) % len];
-                mapping.push((from, to));
-            }
-        }
-
-        if !is_valid_permutation(&mapping) {
-            return Err(MercError::from("Input mapping is not a valid permutation."));
-        }
-
-        Ok(Permutation::from_mapping(mapping))
-    }
-
-    /// Construct a new permutation by concatenating two (disjoint) permutations.
-    pub fn concat(self, other: &Permutation) -> Permutation {
-        debug_assert!(
-            self.mapping
-                .iter()
-                .all(|(left, _)| !other.mapping.iter().any(|(right, _)| right == left)),
-            "There should be no overlap between the two permutations being concatenated."
-        );
-
-        let mut mapping = self.mapping;
-        mapping.extend_from_slice(&other.mapping);
-
-        Permutation::from_mapping(mapping)
-    }
-
-    /// Returns the value of the permutation at the given key.
-    pub fn value(&self, key: usize) -> usize {
-        for (d, v) in &self.mapping {
-            if *d == key {
-                return *v;
-            }
-        }
-
-        key // It is the identity on unspecified elements.
-    }
-
-    /// Returns an iterator over the domain of this permutation.
-    pub fn domain(&self) -> impl Iterator<Item = usize> + '_ {
-        self.mapping.iter().map(|(d, _)| *d)
-    }
-
-    /// Check whether this permutation is the identity permutation.
-    pub fn is_identity(&self) -> bool {
-        self.mapping.iter().all(|(d, v)| d == v)
-    }
-}
-
-/// Checks whether the mapping represents a valid permutation
-pub fn is_valid_permutation(mapping: &Vec<(usize, usize)>) -> bool {
-    let mut domain = HashSet::with_capacity(mapping.len());
-    let mut image = HashSet::with_capacity(mapping.len());
-
-    for (d, v) in mapping {
-        if !domain.insert(*d) || !image.insert(*v) {
-            // Duplicate found in domain or image
-            return false;
-        }
-    }
-
-    domain == image
-}
-
-/// Display the permutation in cycle notation.
-///
-/// Cycle notation is a standard way to present permutations, where each cycle
-/// is represented by parentheses. For example, the permutation that maps 0->2,
-/// 1->0, 2->1 would be represented as (0 2 1). Cycles containing a single
-/// element (fixed points) are omitted for brevity.
-impl fmt::Display for Permutation {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // Determine the maximum value in the permutation mapping.
-        let max_value = self.mapping.iter().map(|(d, e)| *d.max(e)).max().unwrap_or(0);
 
-        let mut visited = vec![false; max_value + 1];
-        let mut identity = true;
-
-        // The mapping is sorted by domain, so we can iterate over it directly.
-        for (start, value) in &self.mapping {
-            if visited[*value] || self.value(*start) == *start {
-                // We have already visited this element, or it is a fixed point.
-                visited[*value] = true;
-                continue;
-            }
-
-            write!(f, "(")?;
-            let mut current = *start;
-            let mut first_in_cycle = true;
-            identity = false; // At least one non-trivial cycle found.
-
-            loop {
-                if !first_in_cycle {
-                    // Print space between elements in the cycle.
-                    write!(f, " ")?;
-                }
-                first_in_cycle = false;
-
-                write!(f, "{}", current)?;
-                visited[current] = true;
-                current = self.value(current);
-
-                if current == *start {
-                    break;
-                }
-                assert!(!visited[current], "This is not a valid permutation!");
-            }
-            write!(f, ")")?;
-        }
+use itertools::Itertools;
 
-        if identity {
-            write!(f, "()")?;
-        }
-
-        Ok(())
-    }
-}
-
-impl fmt::Debug for Permutation {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "[")?;
-        for (i, (d, v)) in self.mapping.iter().enumerate() {
-            if i > 0 {
-                write!(f, ", ")?;
-            }
-            write!(f, "{} -> {}", d, v)?;
-        }
-        write!(f, "]")
-    }
-}
+pub use merc_utilities::Permutation;
 
 /// Given a set of indices, generate the permutation group on these indices.
 ///
@@ -273,6 +26,42 @@ pub fn permutation_group(indices: Vec<usize>) -> impl Iterator<Item = Permutatio
     })
 }
 
+/// Returns a generating set of the group generated by the given permutations.
+///
+/// # Details
+///
+/// A permutation is only added as a generator when it is not yet an element of
+/// the group generated by the previous generators. Every added generator at
+/// least doubles the size of the generated group, so for a group with n elements
+/// at most log2(n) generators are returned.
+pub fn generating_set<'a>(permutations: impl IntoIterator<Item = &'a Permutation>) -> Vec<Permutation> {
+    let mut generators = Vec::new();
+    let mut group = HashSet::from([Permutation::from_mapping(Vec::new())]);
+
+    for permutation in permutations {
+        if !group.contains(permutation) {
+            generators.push(permutation.clone());
+            close_group(&mut group, &generators);
+        }
+    }
+
+    generators
+}
+
+/// Extends the given set of permutations to the group generated by it and the generators.
+fn close_group(group: &mut HashSet<Permutation>, generators: &[Permutation]) {
+    // Since the group is finite it suffices to close it under composition.
+    let mut queue: Vec<Permutation> = group.iter().cloned().collect();
+    while let Some(element) = queue.pop() {
+        for generator in generators {
+            let next = element.compose(generator);
+            if group.insert(next.clone()) {
+                queue.push(next);
+            }
+        }
+    }
+}
+
 /// Returns the number of permutations in a given group.
 pub fn permutation_group_size(n: usize) -> usize {
     (1..=n).product()
@@ -280,36 +69,8 @@ pub fn permutation_group_size(n: usize) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use merc_utilities::random_test;
-    use rand::Rng;
-    use rand::seq::IteratorRandom;
-    use rand::seq::SliceRandom;
-
     use super::*;
 
-    #[test]
-    fn test_permutation_from_input() {
-        let permutation = Permutation::from_mapping_notation("[0->   2, 1   ->0, 2->1]").unwrap();
-
-        assert!(permutation.mapping == vec![(0, 2), (1, 0), (2, 1)]);
-    }
-
-    #[test]
-    fn test_cycle_notation() {
-        let permutation = Permutation::from_mapping_notation("[0->2, 1->0, 2->1, 3->4, 4->3]").unwrap();
-        println!("{:?}", permutation.mapping);
-
-        assert_eq!(permutation.to_string(), "(0 2 1)(3 4)");
-    }
-
-    #[test]
-    fn test_cycle_notation_parsing() {
-        let permutation = Permutation::from_cycle_notation("(0 2 1)(3 4)").unwrap();
-        println!("{:?}", permutation.mapping);
-
-        assert_eq!(permutation.mapping, vec![(0, 2), (1, 0), (2, 1), (3, 4), (4, 3)]);
-    }
-
     #[test]
     fn test_permutation_group() {
         let indices = vec![0, 3, 5];
@@ -322,60 +83,19 @@ mod tests {
     }
 
     #[test]
-    fn test_random_cycle_notation() {
-        random_test(100, |rng| {
-            // Pick a random subset size >= 2 to allow a derangement.
-            let m = rng.random_range(2..10);
-
-            // Choose a random subset of distinct domain elements.
-            let domain: Vec<usize> = (0..10).choose_multiple(rng, m);
-
-            // Create a random derangement of the chosen domain.
-            let mut image = domain.clone();
-            image.shuffle(rng);
-
-            let mapping: Vec<(usize, usize)> = domain.into_iter().zip(image).filter(|(x, y)| x != y).collect();
-            println!("Mapping: {:?}", mapping);
-
-            let permutation = Permutation::from_mapping(mapping.clone());
-
-            let cycle_notation = permutation.to_string();
-            let parsed_permutation = Permutation::from_cycle_notation(&cycle_notation).unwrap();
-
-            assert_eq!(
-                permutation, parsed_permutation,
-                "Failed on permutation {:?}",
-                permutation
-            );
-        })
-    }
-
-    #[test]
-    fn test_random_mapping_notation() {
-        random_test(100, |rng| {
-            // Pick a random subset size >= 2 to allow a derangement.
-            let m = rng.random_range(2..10);
-
-            // Choose a random subset of distinct domain elements.
-            let domain: Vec<usize> = (0..10).choose_multiple(rng, m);
-
-            // Create a random derangement of the chosen domain.
-            let mut image = domain.clone();
-            image.shuffle(rng);
-
-            let mapping: Vec<(usize, usize)> = domain.into_iter().zip(image).filter(|(x, y)| x != y).collect();
-            println!("Mapping: {:?}", mapping);
-
-            let permutation = Permutation::from_mapping(mapping.clone());
-
-            let mapping_notation = format!("{:?}", permutation);
-            let parsed_permutation = Permutation::from_mapping_notation(&mapping_notation).unwrap();
-
-            assert_eq!(
-                permutation, parsed_permutation,
-                "Failed on permutation {:?}",
-                permutation
-            );
-        })
+    fn test_generating_set() {
+        let permutations: Vec<Permutation> = permutation_group(vec![0, 1, 2, 3]).collect();
+        let generators = generating_set(&permutations);
+        assert!(generators.len() <= 4, "{generators:?}");
+
+        // The generators must generate the whole group.
+        let mut group = HashSet::from([Permutation::from_mapping(Vec::new())]);
+        close_group(&mut group, &generators);
+        assert_eq!(group.len(), permutation_group_size(4));
+
+        // Duplicates and elements of the generated group are not generators.
+        let permutation = Permutation::from_cycle_notation("(0 1 2)").unwrap();
+        let generators = generating_set(&[permutation.clone(), permutation.clone(), permutation.inverse()]);
+        assert_eq!(generators, vec![permutation]);
     }
 }
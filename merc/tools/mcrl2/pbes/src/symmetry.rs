@@ -2,8 +2,11 @@
 /// Authors: Menno Bartels and Maurice Laveaux
 /// To keep consistent with the theory we allow non-snake case names.
 use std::cell::Cell;
+use std::cell::RefCell;
 use std::collections::HashSet;
 use std::iter;
+use std::time::Duration;
+use std::time::Instant;
 
 use itertools::Itertools;
 use log::debug;
@@ -22,12 +25,12 @@ use mcrl2::PbesExpression;
 use mcrl2::PbesStategraph;
 use mcrl2::SrfPbes;
 use mcrl2::StategraphEquation;
+use mcrl2::Substitution;
 use mcrl2::Symbol;
 use mcrl2::data_expression_variables;
 use mcrl2::pbes_expression_pvi;
 use mcrl2::reorder_propositional_variables;
 use mcrl2::substitute_data_expressions;
-use mcrl2::substitute_variables;
 use merc_io::LargeFormatter;
 use merc_io::TimeProgress;
 use merc_utilities::MercError;
@@ -53,7 +56,13 @@ pub struct SymmetryAlgorithm {
 
     /// Keep track of some progress messages.
     num_of_checked_candidates: Cell<usize>,
-    progress: TimeProgress<usize>,
+    progress: RefCell<TimeProgress<usize>>,
+
+    /// The moment after which no more candidates are generated, if any.
+    deadline: Cell<Option<Instant>>,
+
+    /// Whether the generation of candidates was stopped because the deadline passed.
+    cut_short: Cell<bool>,
 }
 
 impl SymmetryAlgorithm {
@@ -62,6 +71,7 @@ impl SymmetryAlgorithm {
         // Apply various preproecessing necessary for symmetry detection
         let mut srf = SrfPbes::from(pbes)?;
         srf.unify_parameters(false, false)?;
+        info!("SRF PBES: {}", srf.summary());
 
         if print_srf {
             info!("==== SRF PBES ====");
@@ -102,19 +112,27 @@ impl SymmetryAlgorithm {
             all_control_flow_parameters,
             parameters,
             srf,
-            progress,
+            progress: RefCell::new(progress),
             num_of_checked_candidates: Cell::new(0),
+            deadline: Cell::new(None),
+            cut_short: Cell::new(false),
         })
     }
 
     /// Returns compliant permutations.
     ///
-    /// See [clique_candidates] for the parameters.
+    /// See [clique_candidates] for the parameters. When a budget is given no
+    /// more candidates are generated once it has elapsed, see [Self::budget_exhausted].
     pub fn candidates(
         &self,
         partition_data_sorts: bool,
         partition_data_updates: bool,
+        partition_data_names: bool,
+        budget: Option<Duration>,
     ) -> impl Iterator<Item = Permutation> + '_ {
+        self.deadline.set(budget.map(|budget| Instant::now() + budget));
+        self.cut_short.set(false);
+
         let cliques = self.cliques();
 
         for clique in &cliques {
@@ -132,8 +150,12 @@ impl SymmetryAlgorithm {
         let mut number_of_candidates = 1usize;
 
         for clique in &cliques {
-            let (number_of_permutations, candidates) =
-                self.clique_candidates(clique.clone(), partition_data_sorts, partition_data_updates);
+            let (number_of_permutations, candidates) = self.clique_candidates(
+                clique.clone(),
+                partition_data_sorts,
+                partition_data_updates,
+                partition_data_names,
+            );
             info!(
                 "Maximum number of permutations for clique {:?}: {}",
                 clique,
@@ -160,9 +182,37 @@ impl SymmetryAlgorithm {
             LargeFormatter(number_of_candidates)
         );
 
+        if cliques.len() == 1 {
+            // Only for a single clique every candidate is checked exactly once, so the total is known.
+            let checked = self.num_of_checked_candidates.get();
+            self.progress.replace(TimeProgress::with_total(
+                checked.saturating_add(number_of_candidates),
+                |count: usize, total: usize, eta: Duration| {
+                    info!(
+                        "Checked {count} of {total} candidates ({:.1}%), about {:.0?} remaining...",
+                        count as f64 / total as f64 * 100.0,
+                        eta
+                    );
+                },
+                1,
+            ));
+        }
+
         combined_candidates.map(|(alpha, beta)| alpha.concat(&beta))
     }
 
+    /// Returns true iff the generation of candidates was cut short, because the
+    /// budget passed to [Self::candidates] elapsed before all candidates were
+    /// generated.
+    pub fn budget_exhausted(&self) -> bool {
+        self.cut_short.get()
+    }
+
+    /// Returns the number of candidates that have been checked for compliance.
+    pub fn num_of_checked_candidates(&self) -> usize {
+        self.num_of_checked_candidates.get()
+    }
+
     /// Checks whether the given permutation is valid, meaning that control flow parameters are mapped to control flow parameters.
     pub fn is_valid_permutation(&self, pi: &Permutation) -> Result<(), MercError> {
         // Check that all control flow parameters are mapped to control flow parameters.
@@ -255,11 +305,16 @@ impl SymmetryAlgorithm {
     }
 
     /// Computes the set of candidates we can derive from a single clique
+    ///
+    /// The data parameters are partitioned by their sort, the expressions they
+    /// are updated with and the prefix of their name up to a trailing number,
+    /// for each of these strategies that is enabled.
     fn clique_candidates(
         &self,
         I: Vec<usize>,
         partition_data_sorts: bool,
         partition_data_updates: bool,
+        partition_data_names: bool,
     ) -> (usize, Box<dyn CloneIterator<Item = (Permutation, Permutation)> + '_>) {
         // Determine the parameter indices involved in the clique
         let control_flow_parameter_indices: Vec<usize> = I
@@ -313,16 +368,27 @@ impl SymmetryAlgorithm {
             let mut parameter_updates = vec![HashSet::new(); self.parameters.len()];
 
             // Figure out all the PVIs in which the parameter is updated.
+            let mut updates = Vec::new();
             for equation in self.srf.equations() {
                 for summand in equation.summands() {
                     for pvi in pbes_expression_pvi(&summand.variable().copy()) {
                         for (index, param) in pvi.arguments().protect().cast::<DataExpression>().iter().enumerate() {
-                            parameter_updates[index].insert(replace_variables_by_omega(&param));
+                            updates.push((index, param));
                         }
                     }
                 }
             }
 
+            // Replace all variables by omega, using a single substitution for all updates.
+            let omega = omega_substitution(
+                updates
+                    .iter()
+                    .flat_map(|(_, param)| data_expression_variables(&param.copy())),
+            );
+            for (index, param) in &updates {
+                parameter_updates[*index].insert(omega.apply(&param.copy()));
+            }
+
             for (index, param) in self.parameters.iter().enumerate() {
                 debug!(
                     "Parameter {} is updated with expressions: {}",
@@ -345,6 +411,20 @@ impl SymmetryAlgorithm {
             data_parameter_partition
         };
 
+        let data_parameter_partition = if partition_data_names {
+            // Groups the data parameters whose names only differ in a trailing number, e.g., x_1 and x_2.
+            let mut name_partition = Vec::new();
+            for group in data_parameter_partition {
+                name_partition.extend(partition(group.iter().cloned(), |lhs, rhs| {
+                    name_prefix(&lhs.name().str()) == name_prefix(&rhs.name().str())
+                }));
+            }
+
+            name_partition
+        } else {
+            data_parameter_partition
+        };
+
         // For progress messages keep track of the number of permutations we need to check.
         let mut number_of_permutations = 1usize;
 
@@ -382,13 +462,22 @@ impl SymmetryAlgorithm {
             Box::new(
                 permutation_group(control_flow_parameter_indices)
                     .cartesian_product(all_data_groups)
+                    .take_while(move |_| {
+                        // Only called when there is another candidate, so a search that completes is never cut short.
+                        if self.deadline.get().is_some_and(|deadline| Instant::now() >= deadline) {
+                            self.cut_short.set(true);
+                            return false;
+                        }
+
+                        true
+                    })
                     .filter(move |(a, b)| {
                         let pi = a.clone().concat(b);
 
                         // Print progress messages.
                         self.num_of_checked_candidates
                             .set(self.num_of_checked_candidates.get() + 1);
-                        self.progress.print(self.num_of_checked_candidates.get());
+                        self.progress.borrow().print(self.num_of_checked_candidates.get());
 
                         if !self.complies(&pi, &I) {
                             debug!("Non compliant permutation {}.", pi);
@@ -718,10 +807,8 @@ where
     result
 }
 
-/// Replaces all variables in the expression by omega.
-fn replace_variables_by_omega(expression: &DataExpression) -> DataExpression {
-    let variables = data_expression_variables(&expression.copy());
-
+/// Returns the substitution that replaces the given variables by omega.
+fn omega_substitution(variables: impl IntoIterator<Item = DataVariable>) -> Substitution {
     // Generate an omega variable.
     let omega = DataExpression::from(ATerm::with_args(
         &Symbol::new("OpId", 3),
@@ -738,12 +825,7 @@ fn replace_variables_by_omega(expression: &DataExpression) -> DataExpression {
         ],
     ));
 
-    let sigma = variables
-        .iter()
-        .map(|var| (var.clone().into(), omega.clone()))
-        .collect::<Vec<(DataExpression, DataExpression)>>();
-
-    substitute_variables(&expression.copy(), sigma)
+    variables.into_iter().map(|var| (var, omega.clone())).collect()
 }
 
 /// A constant representing an undefined vertex.
@@ -791,6 +873,11 @@ fn apply_permutation(expression: &PbesExpression, parameters: &Vec<DataVariable>
     reorder_propositional_variables(&result, &pi)
 }
 
+/// Returns the given name without its trailing number.
+fn name_prefix(name: &str) -> &str {
+    name.trim_end_matches(|c: char| c.is_ascii_digit())
+}
+
 #[cfg(test)]
 mod tests {
     use merc_utilities::test_logger;
@@ -832,7 +919,7 @@ mod tests {
         );
 
         let mut symmetries: Vec<Permutation> = algorithm
-            .candidates(false, false)
+            .candidates(false, false, false, None)
             .filter(|pi| algorithm.check_symmetry(pi))
             .collect();
 
@@ -859,4 +946,38 @@ mod tests {
             "Expected to find the (0 2)(1 3) permutation"
         );
     }
+
+    #[test]
+    fn test_symmetry_budget() {
+        let _ = test_logger();
+        let pbes = Pbes::from_text(include_str!("../../../../examples/pbes/c.text.pbes")).unwrap();
+
+        let algorithm = SymmetryAlgorithm::new(&pbes, false).unwrap();
+
+        // Without any time left no candidates are generated.
+        assert_eq!(
+            algorithm.candidates(false, false, false, Some(Duration::ZERO)).count(),
+            0
+        );
+        assert!(algorithm.budget_exhausted());
+
+        // A search that completes within the budget is not cut short, even when the deadline passes afterwards.
+        let expected = algorithm.candidates(false, false, false, None).count();
+        assert_eq!(
+            algorithm
+                .candidates(false, false, false, Some(Duration::from_secs(3600)))
+                .count(),
+            expected
+        );
+        algorithm.deadline.set(Some(Instant::now()));
+        assert!(!algorithm.budget_exhausted());
+    }
+
+    #[test]
+    fn test_name_prefix() {
+        assert_eq!(name_prefix("x_1"), "x_");
+        assert_eq!(name_prefix("x_12"), name_prefix("x_2"));
+        assert_ne!(name_prefix("x1"), name_prefix("y1"));
+        assert_eq!(name_prefix("x"), "x");
+    }
 }
@@ -11,6 +11,10 @@ use merc_sabre::RewriteEngine;
 use merc_sabre::SabreRewriter;
 use merc_utilities::MercError;
 
+mod trs_format;
+
+pub use trs_format::*;
+
 /// Selects the rewriter to use.
 #[derive(ValueEnum, Debug, Clone)]
 pub enum Rewriter {
@@ -6,6 +6,7 @@ use clap::Parser;
 use clap::Subcommand;
 
 use merc_rec_tests::load_rec_from_file;
+use merc_rec_tests::load_trs_from_file;
 use merc_tools::VerbosityFlag;
 use merc_tools::Version;
 use merc_tools::VersionFlag;
@@ -13,12 +14,9 @@ use merc_unsafety::print_allocator_metrics;
 use merc_utilities::MercError;
 
 use merc_rewrite::Rewriter;
+use merc_rewrite::TrsFormatter;
 use merc_rewrite::rewrite_rec;
 
-mod trs_format;
-
-pub use trs_format::*;
-
 #[derive(clap::Parser, Debug)]
 #[command(about = "A command line rewriting tool", arg_required_else_help = true)]
 struct Cli {
@@ -54,7 +52,7 @@ struct RewriteArgs {
 }
 
 #[derive(clap::Args, Debug)]
-#[command(about = "Convert input rewrite system to the TRS format")]
+#[command(about = "Convert input rewrite system (REC or TRS) to the TRS format")]
 struct ConvertArgs {
     #[arg(value_name = "SPEC")]
     specification: String,
@@ -84,14 +82,18 @@ fn main() -> Result<ExitCode, MercError> {
                 }
             }
             Commands::Convert(args) => {
-                if args.specification.ends_with(".rec") {
-                    // Read the data specification
+                // Read the data specification
+                let spec = if args.specification.ends_with(".rec") {
                     let (spec_text, _) = load_rec_from_file(args.specification.into())?;
-                    let spec = spec_text.to_rewrite_spec();
-
-                    let mut output = File::create(args.output)?;
-                    write!(output, "{}", TrsFormatter::new(&spec))?;
-                }
+                    spec_text.to_rewrite_spec()
+                } else if args.specification.ends_with(".trs") {
+                    load_trs_from_file(args.specification.into())?
+                } else {
+                    return Err(format!("Unsupported input format for {}", args.specification).into());
+                };
+
+                let mut output = File::create(args.output)?;
+                write!(output, "{}", TrsFormatter::new(&spec))?;
             }
         }
     }
@@ -140,3 +140,48 @@ impl fmt::Display for TrsFormatter<'_> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use merc_aterm::ATerm;
+    use merc_data::to_untyped_data_expression;
+    use merc_rec_tests::load_rec_from_strings;
+    use merc_rec_tests::load_trs_from_string;
+    use merc_sabre::RewriteEngine;
+    use merc_sabre::SabreRewriter;
+
+    use super::*;
+
+    #[test]
+    fn test_trs_round_trip() {
+        for rec_spec in [
+            include_str!("../../../examples/REC/rec/order.rec"),
+            include_str!("../../../examples/REC/rec/merge.rec"),
+            include_str!("../../../examples/REC/rec/searchinconditions.rec"),
+        ] {
+            let (syntax_spec, syntax_terms) = load_rec_from_strings(&[rec_spec]).unwrap();
+            let spec = syntax_spec.to_rewrite_spec();
+            let trs_spec = load_trs_from_string(&TrsFormatter::new(&spec).to_string()).unwrap();
+
+            let mut rec_rewriter = SabreRewriter::new(&spec);
+            let mut trs_rewriter = SabreRewriter::new(&trs_spec);
+            for term in syntax_terms {
+                let term = to_untyped_data_expression(term, None);
+                let expected = rec_rewriter.rewrite(&term);
+
+                // The function symbols in the TRS format are suffixed by their operation id.
+                let trs_term = to_untyped_data_expression(
+                    ATerm::from_string(&SimpleTermFormatter::new(&term).to_string()).unwrap(),
+                    None,
+                );
+                let result = trs_rewriter.rewrite(&trs_term);
+
+                assert_eq!(
+                    result.to_string(),
+                    SimpleTermFormatter::new(&expected).to_string(),
+                    "The TRS specification yields a different normal form"
+                );
+            }
+        }
+    }
+}
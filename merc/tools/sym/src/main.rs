@@ -7,10 +7,12 @@ use clap::Subcommand;
 
 use merc_io::LargeFormatter;
 use merc_ldd::Storage;
+use merc_symbolic::ReachabilityStrategy;
 use merc_symbolic::SymFormat;
 use merc_symbolic::SymbolicLTS;
 use merc_symbolic::guess_format_from_extension;
 use merc_symbolic::reachability;
+use merc_symbolic::reachability_saturation;
 use merc_symbolic::read_sylvan;
 use merc_symbolic::read_symbolic_lts;
 use merc_tools::Version;
@@ -50,6 +52,8 @@ enum Commands {
 #[command(about = "Prints information related to the given symbolic LTS")]
 struct InfoArgs {
     filename: PathBuf,
+
+    format: Option<SymFormat>,
 }
 
 #[derive(clap::Args, Debug)]
@@ -58,6 +62,10 @@ struct ExploreArgs {
     filename: PathBuf,
 
     format: Option<SymFormat>,
+
+    /// The order in which the transition groups are applied, defaults to breadth-first search.
+    #[arg(long)]
+    strategy: Option<ReachabilityStrategy>,
 }
 
 fn main() -> Result<ExitCode, MercError> {
@@ -94,43 +102,93 @@ fn main() -> Result<ExitCode, MercError> {
 fn handle_info(args: InfoArgs, timing: &mut Timing) -> Result<(), MercError> {
     let mut storage = Storage::new();
 
-    let mut time_read = timing.start("read_symbolic_lts");
-    let lts = read_symbolic_lts(&mut storage, File::open(&args.filename)?)?;
-    time_read.finish();
+    let format = guess_format_from_extension(&args.filename, args.format).ok_or("Cannot determine input format")?;
 
-    println!("Symbolic LTS information:");
-    println!(
-        "  Number of states: {}",
-        LargeFormatter(merc_ldd::len(&mut storage, lts.states()))
-    );
-    println!("  Number of summand groups: {}", lts.transition_groups().len());
+    match format {
+        SymFormat::Sylvan => {
+            let mut time_read = timing.start("read_lts");
+            let lts = read_sylvan(&mut storage, &mut File::open(&args.filename)?)?;
+            time_read.finish();
+
+            println!("Symbolic LTS information:");
+            if let Some(vector_length) = lts.vector_length() {
+                println!("  Length of state vectors: {vector_length}");
+            }
+            println!("  Number of summand groups: {}", lts.transition_groups().len());
+            for (index, group) in lts.transition_groups().iter().enumerate() {
+                println!(
+                    "  Group {index} reads {:?} and writes {:?}",
+                    group.read_projection(),
+                    group.write_projection()
+                );
+            }
+        }
+        SymFormat::Sym => {
+            let mut time_read = timing.start("read_symbolic_lts");
+            let lts = read_symbolic_lts(&mut storage, File::open(&args.filename)?)?;
+            time_read.finish();
+
+            println!("Symbolic LTS information:");
+            println!(
+                "  Number of states: {}",
+                LargeFormatter(merc_ldd::len(&mut storage, lts.states()))
+            );
+            println!("  Number of summand groups: {}", lts.transition_groups().len());
+        }
+    }
 
     Ok(())
 }
 
 /// Explores the given symbolic LTS.
-fn handle_explore(args: ExploreArgs, _timing: &mut Timing) -> Result<(), MercError> {
+fn handle_explore(args: ExploreArgs, timing: &mut Timing) -> Result<(), MercError> {
     let mut storage = Storage::new();
 
     let format = guess_format_from_extension(&args.filename, args.format).ok_or("Cannot determine input format")?;
 
     let mut file = File::open(&args.filename)?;
-    let mut timing = Timing::new();
 
+    let strategy = args.strategy.unwrap_or(ReachabilityStrategy::Bfs);
     match format {
         SymFormat::Sylvan => {
             let mut time_read = timing.start("read_lts");
             let lts = read_sylvan(&mut storage, &mut file)?;
             time_read.finish();
 
-            let mut time_explore = timing.start("explore");
-            println!("LTS has {} states", reachability(&mut storage, &lts)?);
-            time_explore.finish();
+            explore(&mut storage, &lts, strategy, timing)?;
         }
         SymFormat::Sym => {
-            let _input = read_symbolic_lts(&mut storage, &mut file)?;
+            let mut time_read = timing.start("read_lts");
+            let lts = read_symbolic_lts(&mut storage, &mut file)?;
+            time_read.finish();
+
+            explore(&mut storage, &lts, strategy, timing)?;
         }
     }
 
     Ok(())
 }
+
+/// Computes the reachable states of the given LTS using the given strategy and prints the results.
+fn explore(
+    storage: &mut Storage,
+    lts: &impl SymbolicLTS,
+    strategy: ReachabilityStrategy,
+    timing: &mut Timing,
+) -> Result<(), MercError> {
+    let mut time_explore = timing.start("explore");
+    let (num_of_states, stats) = match strategy {
+        ReachabilityStrategy::Bfs => reachability(storage, lts)?,
+        ReachabilityStrategy::Saturation => reachability_saturation(storage, lts)?,
+    };
+    time_explore.finish();
+
+    println!("LTS has {} states", num_of_states);
+    println!(
+        "Explored in {} iterations with at most {} LDD nodes",
+        stats.iterations,
+        LargeFormatter(stats.peak_nodes)
+    );
+
+    Ok(())
+}
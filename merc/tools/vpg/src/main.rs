@@ -24,21 +24,25 @@ use merc_unsafety::print_allocator_metrics;
 use merc_utilities::MercError;
 use merc_utilities::Timing;
 use merc_vpg::FeatureDiagram;
+use merc_vpg::PG;
 use merc_vpg::ParityGameFormat;
 use merc_vpg::PgDot;
 use merc_vpg::Player;
 use merc_vpg::VpgDot;
 use merc_vpg::ZielonkaVariant;
+use merc_vpg::compress_priorities;
 use merc_vpg::compute_reachable;
+use merc_vpg::count_winning_products;
 use merc_vpg::guess_format_from_extension;
 use merc_vpg::project_variability_parity_games_iter;
 use merc_vpg::read_fts;
 use merc_vpg::read_pg;
 use merc_vpg::read_vpg;
+use merc_vpg::solve_all_projections_parallel;
 use merc_vpg::solve_variability_product_zielonka;
 use merc_vpg::solve_variability_zielonka;
 use merc_vpg::solve_zielonka;
-use merc_vpg::translate;
+use merc_vpg::translate_spec;
 use merc_vpg::write_pg;
 use merc_vpg::write_vpg;
 
@@ -78,6 +82,7 @@ enum Commands {
     Solve(SolveArgs),
     Reachable(ReachableArgs),
     Project(ProjectArgs),
+    Convert(ConvertArgs),
     Translate(TranslateArgs),
     Display(DisplayArgs),
 }
@@ -102,6 +107,20 @@ struct SolveArgs {
     /// Whether to verify the solution after computing it
     #[arg(long, default_value_t = false)]
     verify_solution: bool,
+
+    /// Whether to only print the number of products won by every player in
+    /// the initial vertex, for the family-based variants
+    #[arg(long, default_value_t = false)]
+    summary: bool,
+
+    /// Whether to compress the priorities of a parity game before solving it
+    #[arg(long, default_value_t = false)]
+    compress: bool,
+
+    /// The number of threads used to solve the projections in the product
+    /// variant, only the solution in the initial vertex is computed.
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    threads: Option<u64>,
 }
 
 /// Arguments for computing the reachable part of a parity game
@@ -130,6 +149,21 @@ struct ProjectArgs {
     format: Option<ParityGameFormat>,
 }
 
+/// Arguments for converting a (variability) parity game
+#[derive(clap::Args, Debug)]
+struct ConvertArgs {
+    filename: String,
+
+    output: String,
+
+    /// Whether to make the variability parity game total before writing it
+    #[arg(long, default_value_t = false)]
+    make_total: bool,
+
+    #[arg(long, short)]
+    format: Option<ParityGameFormat>,
+}
+
 /// Arguments for translating a feature transition system and a modal formula into a variability parity game
 #[derive(clap::Args, Debug)]
 struct TranslateArgs {
@@ -179,6 +213,7 @@ fn main() -> Result<ExitCode, MercError> {
             Commands::Solve(args) => handle_solve(&cli, args, &mut timing)?,
             Commands::Reachable(args) => handle_reachable(&cli, args, &mut timing)?,
             Commands::Project(args) => handle_project(&cli, args, &mut timing)?,
+            Commands::Convert(args) => handle_convert(&cli, args, &mut timing)?,
             Commands::Translate(args) => handle_translate(&cli, args)?,
             Commands::Display(args) => handle_display(&cli, args, &mut timing)?,
         }
@@ -208,9 +243,15 @@ fn handle_solve(cli: &Cli, args: &SolveArgs, timing: &mut Timing) -> Result<(),
     if format == ParityGameFormat::PG {
         // Read and solve a standard parity game.
         let mut time_read = timing.start("read_pg");
-        let game = read_pg(&mut file)?;
+        let mut game = read_pg(&mut file)?;
         time_read.finish();
 
+        if args.compress {
+            let mut time_compress = timing.start("compress_priorities");
+            compress_priorities(&mut game);
+            time_compress.finish();
+        }
+
         let mut time_solve = timing.start("solve_zielonka");
         let solution = solve_zielonka(&game);
         if args.full_solution {
@@ -245,8 +286,30 @@ fn handle_solve(cli: &Cli, args: &SolveArgs, timing: &mut Timing) -> Result<(),
             game
         };
 
+        if args.summary && solve_variant == ZielonkaVariant::Product {
+            return Err("The summary is only available for the family-based variants".into());
+        }
+
         let mut time_solve = timing.start("solve_variability_zielonka");
-        if solve_variant == ZielonkaVariant::Product {
+        if let (ZielonkaVariant::Product, Some(threads)) = (solve_variant, args.threads) {
+            if args.full_solution {
+                return Err("Solving in parallel only computes the solution in the initial vertex".into());
+            }
+
+            let results = solve_all_projections_parallel(&game, threads as usize)?;
+            let initial_vertex = game.initial_vertex().to_string();
+            for (index, player) in [Player::Even, Player::Odd].iter().enumerate() {
+                println!("W{index}: ");
+
+                for (cube, winner) in &results {
+                    println!(
+                        "For product {} the following vertices are in: {}",
+                        FormatConfig(cube),
+                        if winner == player { initial_vertex.as_str() } else { "" }
+                    );
+                }
+            }
+        } else if solve_variant == ZielonkaVariant::Product {
             // Since we want to print W0, W1 separately, we need to store the results temporarily.
             let mut results = [Vec::new(), Vec::new()];
             for (cube, _bdd, solution) in solve_variability_product_zielonka(&game) {
@@ -271,21 +334,27 @@ fn handle_solve(cli: &Cli, args: &SolveArgs, timing: &mut Timing) -> Result<(),
             }
         } else {
             let solutions = solve_variability_zielonka(&manager_ref, &game, solve_variant, false)?;
-            for (index, w) in solutions.iter().enumerate() {
-                println!("W{index}: ");
-
-                for entry in CubeIterAll::new(game.variables(), game.configuration()) {
-                    let (config, config_function) = entry?;
-
-                    println!(
-                        "For product {} the following vertices are in: {}",
-                        FormatConfig(&config),
-                        w.iter() // Do not use iter_vertices because the first one is the initial vertex only
-                            .take(if args.full_solution { usize::MAX } else { 1 }) // Take only first if we don't want full solution
-                            .filter(|(_v, config)| { config.and(&config_function).unwrap().satisfiable() })
-                            .map(|(v, _)| v)
-                            .format(", ")
-                    );
+            if args.summary {
+                let [even, odd] = count_winning_products(&game, &solutions, game.variables())?;
+                println!("Products won by even: {even}");
+                println!("Products won by odd: {odd}");
+            } else {
+                for (index, w) in solutions.iter().enumerate() {
+                    println!("W{index}: ");
+
+                    for entry in CubeIterAll::new(game.variables(), game.configuration()) {
+                        let (config, config_function) = entry?;
+
+                        println!(
+                            "For product {} the following vertices are in: {}",
+                            FormatConfig(&config),
+                            w.iter() // Do not use iter_vertices because the first one is the initial vertex only
+                                .take(if args.full_solution { usize::MAX } else { 1 }) // Take only first if we don't want full solution
+                                .filter(|(_v, config)| { config.and(&config_function).unwrap().satisfiable() })
+                                .map(|(v, _)| v)
+                                .format(", ")
+                        );
+                    }
                 }
             }
 
@@ -407,6 +476,55 @@ fn handle_project(cli: &Cli, args: &ProjectArgs, timing: &mut Timing) -> Result<
     Ok(())
 }
 
+/// Handle the `convert` subcommand.
+///
+/// Reads a PG or VPG and writes it to `output` in the same format. A VPG can
+/// optionally be made total before it is written.
+fn handle_convert(cli: &Cli, args: &ConvertArgs, timing: &mut Timing) -> Result<(), MercError> {
+    let path = Path::new(&args.filename);
+    let mut file = File::open(path)?;
+    let format = guess_format_from_extension(path, args.format).ok_or("Unknown parity game file format.")?;
+
+    if format == ParityGameFormat::PG {
+        if args.make_total {
+            return Err(MercError::from(
+                "Making a game total is only supported for variability parity games.",
+            ));
+        }
+
+        let mut time_read = timing.start("read_pg");
+        let game = read_pg(&mut file)?;
+        time_read.finish();
+
+        let mut output_file = File::create(&args.output)?;
+        write_pg(&mut output_file, &game)?;
+    } else {
+        let manager_ref = oxidd::bdd::new_manager(
+            cli.oxidd_node_capacity,
+            cli.oxidd_cache_capacity.unwrap_or(cli.oxidd_node_capacity),
+            cli.oxidd_workers,
+        );
+
+        let mut time_read = timing.start("read_vpg");
+        let game = read_vpg(&manager_ref, &mut file)?;
+        time_read.finish();
+
+        let game = if args.make_total && !game.is_total(&manager_ref)? {
+            let mut time_total = timing.start("make_vpg_total");
+            let total_game = make_vpg_total(&manager_ref, &game)?;
+            time_total.finish();
+            total_game
+        } else {
+            game
+        };
+
+        let mut output_file = File::create(&args.output)?;
+        write_vpg(&mut output_file, &game)?;
+    }
+
+    Ok(())
+}
+
 /// Handle the `translate` subcommand.
 ///
 /// Translates a feature diagram, a feature transition system (FTS), and a modal
@@ -436,27 +554,21 @@ fn handle_translate(cli: &Cli, args: &TranslateArgs) -> Result<(), MercError> {
     })?;
     let fts = read_fts(&manager_ref, &mut fts_file, feature_diagram.features().clone())?;
 
-    // Read and validate formula (no actions/data specs supported here)
+    // Read the formula, whose actions are resolved against the labels of the FTS
     let formula_spec = UntypedStateFrmSpec::parse(&read_to_string(&args.formula_filename).map_err(|e| {
         MercError::from(format!(
             "Could not open formula file '{}': {}",
             &args.formula_filename, e
         ))
     })?)?;
-    if !formula_spec.action_declarations.is_empty() {
-        return Err(MercError::from("We do not support formulas with action declarations."));
-    }
-
-    if !formula_spec.data_specification.is_empty() {
-        return Err(MercError::from("The formula must not contain a data specification."));
-    }
 
-    let vpg = translate(
+    let vpg = translate_spec(
         &manager_ref,
         &fts,
         feature_diagram.configuration().clone(),
-        &formula_spec.formula,
+        &formula_spec,
     )?;
+
     let mut output_file = File::create(&args.output)?;
     write_vpg(&mut output_file, &vpg)?;
 